@@ -0,0 +1,241 @@
+//! Live input level for the overlay while recording.
+//!
+//! When the state watcher sees a recording start, a meter thread opens the
+//! input device the backend records from (`audio_input` in the state file,
+//! else the default) with cpal and emits `audio-level` to the overlay about
+//! 30 times a second. The audio callback only folds samples into RMS and
+//! peak; levels reach the meter thread over a small channel, which drops
+//! them rather than block the audio thread if the thread falls behind.
+//!
+//! The backend keeps its own stream on the same device; sound servers
+//! (PulseAudio, PipeWire, CoreAudio, WASAPI shared mode) mix both. Where the
+//! device can't be shared the meter logs why and the overlay keeps its
+//! static animation. Pausing, muting or stopping the recording closes the
+//! stream and sends one silent level so the meter drops to zero.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, SyncSender};
+use std::sync::Arc;
+use std::time::Duration;
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{Data, InputCallbackInfo, SampleFormat, Stream};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::error::{AppError, AppResult};
+use crate::state::AppState;
+use crate::synthia_state::SynthiaState;
+
+/// Level updates per second.
+const LEVELS_PER_SEC: u32 = 30;
+
+/// Quietest level the meter shows, in dBFS; anything below reads as zero.
+const FLOOR_DB: f32 = -60.0;
+
+/// How long opening the stream may take before giving up.
+const OPEN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How often the meter thread checks its stop flag when no levels arrive.
+const STOP_POLL: Duration = Duration::from_millis(100);
+
+/// Payload of the `audio-level` event. `rms` and `peak` are linear sample
+/// magnitudes (0..1); `level` is the RMS on a -60..0 dBFS scale mapped to
+/// 0..1, ready to size a bar.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct AudioLevel {
+    pub rms: f32,
+    pub peak: f32,
+    pub level: f32,
+}
+
+impl AudioLevel {
+    const SILENT: AudioLevel = AudioLevel { rms: 0.0, peak: 0.0, level: 0.0 };
+}
+
+/// `rms` on the meter's logarithmic 0..1 scale.
+fn display_level(rms: f32) -> f32 {
+    if rms <= 0.0 {
+        return 0.0;
+    }
+    ((20.0 * rms.log10() - FLOOR_DB) / -FLOOR_DB).clamp(0.0, 1.0)
+}
+
+/// Folds samples into one `AudioLevel` per `window` samples.
+struct LevelMeter {
+    window: usize,
+    count: usize,
+    sum_squares: f64,
+    peak: f32,
+}
+
+impl LevelMeter {
+    fn new(window: usize) -> Self {
+        LevelMeter { window: window.max(1), count: 0, sum_squares: 0.0, peak: 0.0 }
+    }
+
+    /// Add `samples` (interleaved channels count alike), returning the
+    /// level of the last window they completed, if any.
+    fn push(&mut self, samples: impl IntoIterator<Item = f32>) -> Option<AudioLevel> {
+        let mut completed = None;
+        for sample in samples {
+            let magnitude = sample.abs().min(1.0);
+            self.sum_squares += f64::from(magnitude) * f64::from(magnitude);
+            self.peak = self.peak.max(magnitude);
+            self.count += 1;
+            if self.count >= self.window {
+                completed = Some(self.take());
+            }
+        }
+        completed
+    }
+
+    fn take(&mut self) -> AudioLevel {
+        let rms = (self.sum_squares / self.count as f64).sqrt() as f32;
+        let level = AudioLevel { rms, peak: self.peak, level: display_level(rms) };
+        *self = LevelMeter::new(self.window);
+        level
+    }
+}
+
+/// Fold `data` into `meter`, scaled to -1..1. Formats the meter doesn't
+/// read are ignored.
+fn push_data(meter: &mut LevelMeter, format: SampleFormat, data: &Data) -> Option<AudioLevel> {
+    match format {
+        SampleFormat::F32 => meter.push(data.as_slice::<f32>()?.iter().copied()),
+        SampleFormat::I16 => {
+            meter.push(data.as_slice::<i16>()?.iter().map(|&s| f32::from(s) / 32768.0))
+        }
+        SampleFormat::U16 => meter.push(
+            data.as_slice::<u16>()?
+                .iter()
+                .map(|&s| (f32::from(s) - 32768.0) / 32768.0),
+        ),
+        SampleFormat::I32 => {
+            meter.push(data.as_slice::<i32>()?.iter().map(|&s| s as f32 / 2_147_483_648.0))
+        }
+        _ => None,
+    }
+}
+
+/// Open `device_name` (or the default input if it is `None` or gone) and
+/// start sending levels to `levels`.
+fn open_stream(device_name: Option<&str>, levels: SyncSender<AudioLevel>) -> AppResult<Stream> {
+    let host = cpal::default_host();
+    let named = device_name.and_then(|name| {
+        host.input_devices()
+            .ok()?
+            .find(|d| d.name().is_ok_and(|n| n == name))
+    });
+    let device = named
+        .or_else(|| host.default_input_device())
+        .ok_or_else(|| AppError::NotFound("no audio input device".to_string()))?;
+    let config = device
+        .default_input_config()
+        .map_err(|e| AppError::Other(format!("Failed to read input config: {}", e)))?;
+    let format = config.sample_format();
+    let window = (config.sample_rate().0 / LEVELS_PER_SEC) as usize * config.channels() as usize;
+    let mut meter = LevelMeter::new(window);
+    let stream = device
+        .build_input_stream_raw(
+            &config.config(),
+            format,
+            move |data: &Data, _: &InputCallbackInfo| {
+                if let Some(level) = push_data(&mut meter, format, data) {
+                    let _ = levels.try_send(level);
+                }
+            },
+            |e| tracing::debug!(error = %e, "audio meter stream error"),
+            Some(OPEN_TIMEOUT),
+        )
+        .map_err(|e| AppError::Other(format!("Failed to open input stream: {}", e)))?;
+    stream
+        .play()
+        .map_err(|e| AppError::Other(format!("Failed to start input stream: {}", e)))?;
+    Ok(stream)
+}
+
+fn emit(app: &AppHandle, level: AudioLevel) {
+    if let Some(window) = app.get_webview_window("overlay") {
+        let _ = window.emit("audio-level", level);
+    }
+}
+
+/// Meter `device_name` until `stop` is set. The stream lives and dies on
+/// this thread, since cpal streams can't be moved between threads on every
+/// platform.
+fn run(app: AppHandle, device_name: Option<String>, stop: Arc<AtomicBool>) {
+    let (tx, rx) = mpsc::sync_channel(4);
+    {
+        let _stream = match open_stream(device_name.as_deref(), tx) {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::info!(error = %e, device = ?device_name, "audio level meter unavailable");
+                return;
+            }
+        };
+        tracing::debug!(device = ?device_name, "audio level meter started");
+        while !stop.load(Ordering::SeqCst) {
+            match rx.recv_timeout(STOP_POLL) {
+                Ok(level) => emit(&app, level),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    }
+    emit(&app, AudioLevel::SILENT);
+}
+
+/// Start or stop the meter to match `synthia`. Called from the state
+/// watcher on every change; meters only while the backend is listening.
+pub fn sync(app: &AppHandle, synthia: &SynthiaState) {
+    let listening = synthia.recording && !synthia.paused && !synthia.muted;
+    let state = app.state::<AppState>();
+    let Ok(mut meter) = state.audio_meter.lock() else { return };
+    match (listening, meter.is_some()) {
+        (true, false) => {
+            let stop = Arc::new(AtomicBool::new(false));
+            *meter = Some(stop.clone());
+            let app = app.clone();
+            let device_name = synthia.audio_input.clone();
+            std::thread::spawn(move || run(app, device_name, stop));
+        }
+        (false, true) => {
+            if let Some(stop) = meter.take() {
+                stop.store(true, Ordering::SeqCst);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meter_reports_once_per_window() {
+        let mut meter = LevelMeter::new(4);
+        assert_eq!(meter.push([0.5, -0.5]), None);
+        let level = meter.push([0.5, -0.5, 0.25]).unwrap();
+        assert_eq!((level.rms, level.peak), (0.5, 0.5));
+        // The fifth sample starts the next window.
+        let level = meter.push([0.25, 0.25, 0.25]).unwrap();
+        assert_eq!((level.rms, level.peak), (0.25, 0.25));
+        assert_eq!(meter.count, 0);
+    }
+
+    #[test]
+    fn meter_clamps_overs() {
+        let level = LevelMeter::new(2).push([2.0, -3.0]).unwrap();
+        assert_eq!((level.rms, level.peak, level.level), (1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn display_level_spans_sixty_db() {
+        assert_eq!(display_level(0.0), 0.0);
+        assert_eq!(display_level(1.0), 1.0);
+        assert_eq!(display_level(0.001), 0.0);
+        assert!((display_level(10f32.powf(-1.5)) - 0.5).abs() < 1e-6);
+    }
+}
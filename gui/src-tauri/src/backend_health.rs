@@ -0,0 +1,132 @@
+//! Telling a live backend from a dead or hung one by its heartbeat.
+//!
+//! The backend rewrites `heartbeat_at` in its state every few seconds. The
+//! state watcher records each one here, and a monitor thread checks its age
+//! every `CHECK_INTERVAL`: a backend that claims to be ready but whose
+//! heartbeat is older than `runtime.heartbeat_timeout_secs` is marked
+//! unresponsive in the tray. Changes are published as `backend-health`
+//! events. Backends that predate the heartbeat are reported as `unknown`
+//! and never flagged.
+
+use std::sync::atomic::Ordering;
+use std::sync::PoisonError;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::gui_config;
+use crate::state::AppState;
+use crate::threads;
+use crate::tray;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    /// No ready backend.
+    Stopped,
+    Healthy,
+    /// Ready, but the heartbeat has stopped.
+    Unresponsive,
+    /// Ready, but it has never sent a heartbeat, or the check is off.
+    Unknown,
+}
+
+/// Returned by `get_backend_health` and emitted as `backend-health`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct BackendHealth {
+    pub status: HealthStatus,
+    pub heartbeat_at: Option<DateTime<Utc>>,
+    pub heartbeat_age_secs: Option<u64>,
+    pub timeout_secs: u64,
+}
+
+fn assess(
+    ready: bool,
+    heartbeat_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    timeout_secs: u64,
+) -> BackendHealth {
+    // A clock step backwards makes the heartbeat look new, never stale.
+    let age = heartbeat_at.map(|at| (now - at).num_seconds().max(0) as u64);
+    let status = match age {
+        _ if !ready => HealthStatus::Stopped,
+        Some(age) if timeout_secs > 0 && age > timeout_secs => HealthStatus::Unresponsive,
+        Some(_) if timeout_secs > 0 => HealthStatus::Healthy,
+        _ => HealthStatus::Unknown,
+    };
+    BackendHealth { status, heartbeat_at, heartbeat_age_secs: age, timeout_secs }
+}
+
+pub fn current(app: &AppHandle) -> BackendHealth {
+    let state = app.state::<AppState>();
+    let heartbeat_at = *state.last_heartbeat.lock().unwrap_or_else(PoisonError::into_inner);
+    assess(
+        state.backend_ready.load(Ordering::SeqCst),
+        heartbeat_at,
+        Utc::now(),
+        gui_config::load().runtime.heartbeat_timeout_secs,
+    )
+}
+
+/// Re-assess and, if the verdict changed, update the tray and publish it.
+fn check(app: &AppHandle) {
+    let health = current(app);
+    let unresponsive = health.status == HealthStatus::Unresponsive;
+    let state = app.state::<AppState>();
+    if state.backend_unresponsive.swap(unresponsive, Ordering::SeqCst) == unresponsive {
+        return;
+    }
+    if unresponsive {
+        tracing::warn!(age_secs = ?health.heartbeat_age_secs, "backend heartbeat stopped");
+    } else {
+        tracing::info!(status = ?health.status, "backend responsive again");
+    }
+    tray::set_unresponsive(app, unresponsive);
+    let _ = app.emit("backend-health", &health);
+}
+
+/// Note the heartbeat in a state update from the watcher or IPC.
+pub(crate) fn record(app: &AppHandle, heartbeat_at: Option<DateTime<Utc>>) {
+    {
+        let state = app.state::<AppState>();
+        let mut last = state.last_heartbeat.lock().unwrap_or_else(PoisonError::into_inner);
+        *last = heartbeat_at;
+    }
+    check(app);
+}
+
+pub(crate) fn spawn_monitor(app: AppHandle) {
+    threads::spawn_supervised("backend health monitor", move || loop {
+        std::thread::sleep(CHECK_INTERVAL);
+        check(&app);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs_ago: i64, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        Some(now - chrono::Duration::seconds(secs_ago))
+    }
+
+    #[test]
+    fn stale_heartbeat_of_a_ready_backend_is_unresponsive() {
+        let now = Utc::now();
+        assert_eq!(assess(true, at(3, now), now, 15).status, HealthStatus::Healthy);
+        assert_eq!(assess(true, at(60, now), now, 15).status, HealthStatus::Unresponsive);
+        assert_eq!(assess(false, at(60, now), now, 15).status, HealthStatus::Stopped);
+        assert_eq!(assess(true, at(-30, now), now, 15).heartbeat_age_secs, Some(0));
+    }
+
+    #[test]
+    fn no_heartbeat_or_no_timeout_is_unknown() {
+        let now = Utc::now();
+        assert_eq!(assess(true, None, now, 15).status, HealthStatus::Unknown);
+        assert_eq!(assess(true, at(600, now), now, 0).status, HealthStatus::Unknown);
+    }
+}
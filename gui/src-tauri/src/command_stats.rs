@@ -0,0 +1,127 @@
+//! Success/failure counters for the commands that drive the backend and the
+//! Telegram bot, for correlating "Start sometimes does nothing" with actual
+//! error rates.
+//!
+//! Counted wherever the command runs (Tauri handler, tray, HTTP control), so
+//! the numbers reflect every attempt. Kept in memory only: they start at
+//! zero with each app launch.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::error::AppResult;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TrackedCommand {
+    Start,
+    Stop,
+    ToggleRecording,
+    StartRemote,
+    StopRemote,
+}
+
+const TRACKED: [TrackedCommand; 5] = [
+    TrackedCommand::Start,
+    TrackedCommand::Stop,
+    TrackedCommand::ToggleRecording,
+    TrackedCommand::StartRemote,
+    TrackedCommand::StopRemote,
+];
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct LastError {
+    pub at: DateTime<Utc>,
+    pub message: String,
+}
+
+#[derive(Default)]
+struct Counter {
+    succeeded: AtomicU64,
+    failed: AtomicU64,
+    last_error: Mutex<Option<LastError>>,
+}
+
+/// One row of `get_command_stats`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CommandStat {
+    pub command: TrackedCommand,
+    pub succeeded: u64,
+    pub failed: u64,
+    pub last_error: Option<LastError>,
+}
+
+#[derive(Default)]
+pub struct CommandStats {
+    counters: [Counter; TRACKED.len()],
+}
+
+impl CommandStats {
+    fn counter(&self, command: TrackedCommand) -> &Counter {
+        &self.counters[command as usize]
+    }
+
+    /// Count `result` against `command`, keeping the message if it failed.
+    pub fn record<T>(&self, command: TrackedCommand, result: &AppResult<T>) {
+        let counter = self.counter(command);
+        match result {
+            Ok(_) => {
+                counter.succeeded.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => {
+                counter.failed.fetch_add(1, Ordering::Relaxed);
+                if let Ok(mut last) = counter.last_error.lock() {
+                    *last = Some(LastError {
+                        at: Utc::now(),
+                        message: e.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    pub fn snapshot(&self) -> Vec<CommandStat> {
+        TRACKED
+            .iter()
+            .map(|&command| {
+                let counter = self.counter(command);
+                CommandStat {
+                    command,
+                    succeeded: counter.succeeded.load(Ordering::Relaxed),
+                    failed: counter.failed.load(Ordering::Relaxed),
+                    last_error: counter.last_error.lock().ok().and_then(|l| l.clone()),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppError;
+
+    #[test]
+    fn record_counts_per_command_and_keeps_last_error() {
+        let stats = CommandStats::default();
+        stats.record(TrackedCommand::Start, &Ok(()));
+        stats.record::<()>(TrackedCommand::Start, &Err(AppError::Process("first".into())));
+        stats.record::<()>(TrackedCommand::Start, &Err(AppError::Process("second".into())));
+        stats.record(TrackedCommand::StopRemote, &Ok(()));
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.len(), TRACKED.len());
+        let start = &snapshot[0];
+        assert_eq!((start.succeeded, start.failed), (1, 2));
+        assert!(start.last_error.as_ref().unwrap().message.contains("second"));
+        let stop_remote = snapshot
+            .iter()
+            .find(|s| s.command == TrackedCommand::StopRemote)
+            .unwrap();
+        assert_eq!((stop_remote.succeeded, stop_remote.failed), (1, 0));
+        assert!(stop_remote.last_error.is_none());
+    }
+}
@@ -1228,15 +1228,8 @@ pub fn list_active_agents() -> Vec<AgentInfo> {
 
 #[tauri::command]
 pub fn kill_agent(pid: u32) -> AppResult<()> {
-    let status = std::process::Command::new("kill")
-        .arg(pid.to_string())
-        .status()
-        .map_err(|e| AppError::Process(format!("Failed to spawn kill: {}", e)))?;
-    if !status.success() {
-        return Err(AppError::Process(format!(
-            "kill exited with status {:?}",
-            status.code()
-        )));
+    if !crate::platform::stop_pid(pid as i32, crate::platform::Stop::Terminate) {
+        return Err(AppError::Process(format!("Failed to stop process {}", pid)));
     }
     Ok(())
 }
@@ -0,0 +1,212 @@
+//! Audio input device Tauri commands.
+
+use std::fs;
+use std::time::Duration;
+
+use cpal::traits::{DeviceTrait, HostTrait};
+use cpal::BuildStreamError;
+use serde::Serialize;
+
+use super::run_blocking;
+use crate::error::{AppError, AppResult};
+use crate::{i18n, notifications};
+use crate::synthia_state::update_synthia_state;
+use crate::{get_config_path, get_runtime_dir};
+
+/// An input device as offered in the settings dropdown. cpal exposes no
+/// stable device id, so `id` is the device name — the same string the
+/// backend's `sounddevice` matches against.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct AudioDevice {
+    pub id: String,
+    pub name: String,
+    pub is_default: bool,
+}
+
+/// Enumerate input devices fresh on every call so hot-plugged mics show up
+/// without restarting the GUI.
+fn enumerate_inputs() -> AppResult<Vec<AudioDevice>> {
+    let host = cpal::default_host();
+    let default_name = host.default_input_device().and_then(|d| d.name().ok());
+    let devices = host
+        .input_devices()
+        .map_err(|e| AppError::Other(format!("Failed to list audio inputs: {}", e)))?;
+
+    let mut inputs: Vec<AudioDevice> = Vec::new();
+    for device in devices {
+        let Ok(name) = device.name() else { continue };
+        // ALSA can list the same card under several hosts; show it once.
+        if inputs.iter().any(|d| d.id == name) {
+            continue;
+        }
+        inputs.push(AudioDevice {
+            id: name.clone(),
+            is_default: default_name.as_deref() == Some(name.as_str()),
+            name,
+        });
+    }
+    Ok(inputs)
+}
+
+/// Whether the default microphone can be recorded from.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "status", content = "detail", rename_all = "snake_case")]
+pub enum AudioStatus {
+    /// A stream opened on this device.
+    Ok(String),
+    NoDevice,
+    PermissionDenied(String),
+    Error(String),
+}
+
+impl AudioStatus {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, AudioStatus::Ok(_))
+    }
+}
+
+/// How long opening the test stream may take before giving up.
+const OPEN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Sort a backend's error text for `device` into a permission problem or
+/// anything else; ALSA and PulseAudio only report `EACCES` as text. A busy
+/// device is already being recorded from, most likely by the backend, so it
+/// counts as usable.
+fn classify_open_error(device: &str, message: String) -> AudioStatus {
+    let lower = message.to_ascii_lowercase();
+    if lower.contains("busy") {
+        AudioStatus::Ok(device.to_string())
+    } else if ["permission denied", "not permitted", "eacces", "access denied"]
+        .iter()
+        .any(|needle| lower.contains(needle))
+    {
+        AudioStatus::PermissionDenied(message)
+    } else {
+        AudioStatus::Error(message)
+    }
+}
+
+/// Find the default input device and briefly open a stream on it. The
+/// stream is never started and is closed straight away.
+fn probe_default_input() -> AudioStatus {
+    let host = cpal::default_host();
+    let Some(device) = host.default_input_device() else {
+        let any = host.input_devices().map(|mut d| d.next().is_some()).unwrap_or(false);
+        return if any {
+            AudioStatus::Error("No default input device is selected".to_string())
+        } else {
+            AudioStatus::NoDevice
+        };
+    };
+    let name = device.name().unwrap_or_else(|_| "default".to_string());
+    let config = match device.default_input_config() {
+        Ok(config) => config,
+        Err(e) => return classify_open_error(&name, e.to_string()),
+    };
+    let stream = device.build_input_stream_raw(
+        &config.config(),
+        config.sample_format(),
+        |_, _| {},
+        |_| {},
+        Some(OPEN_TIMEOUT),
+    );
+    match stream {
+        Ok(_) => AudioStatus::Ok(name),
+        Err(BuildStreamError::DeviceNotAvailable) => AudioStatus::NoDevice,
+        Err(e) => classify_open_error(&name, e.to_string()),
+    }
+}
+
+pub(crate) fn audio_status() -> AudioStatus {
+    let status = probe_default_input();
+    if !status.is_ok() {
+        tracing::warn!(?status, "audio input unavailable");
+    }
+    status
+}
+
+/// Check the microphone off the caller's thread and raise a desktop
+/// notification if recording is about to capture nothing.
+pub(crate) fn warn_if_unavailable() {
+    std::thread::spawn(|| {
+        let message = match audio_status() {
+            AudioStatus::Ok(_) => return,
+            AudioStatus::NoDevice => i18n::t("notify.audio_no_device"),
+            AudioStatus::PermissionDenied(_) => i18n::t("notify.audio_permission_denied"),
+            AudioStatus::Error(detail) => detail,
+        };
+        notifications::notify_error(&i18n::t("notify.audio_unavailable"), &message);
+    });
+}
+
+#[tauri::command]
+pub async fn check_audio_input() -> AppResult<AudioStatus> {
+    run_blocking(|| Ok(audio_status())).await
+}
+
+#[tauri::command]
+pub async fn list_audio_inputs() -> AppResult<Vec<AudioDevice>> {
+    run_blocking(enumerate_inputs).await
+}
+
+/// Select the backend's input device. Persisted as `audio_device` in
+/// `config.yaml` for the next start, and mirrored into the state file for a
+/// backend that is already running. An empty `id` returns to auto-detection.
+#[tauri::command]
+pub async fn set_audio_input(id: String) -> AppResult<String> {
+    run_blocking(move || {
+        if !id.is_empty() && !enumerate_inputs()?.iter().any(|d| d.id == id) {
+            return Err(AppError::NotFound(format!("Audio input not found: {}", id)));
+        }
+
+        let config_path = get_config_path();
+        let content = fs::read_to_string(&config_path)
+            .map_err(|e| AppError::Io(format!("Failed to read config: {}", e)))?;
+        let value = serde_json::to_string(&id)?;
+        let new_content =
+            crate::yaml_writer::upsert_synthia_config_key(&content, "audio_device", &value);
+        fs::write(&config_path, new_content)
+            .map_err(|e| AppError::Io(format!("Failed to write config: {}", e)))?;
+
+        update_synthia_state(|s| {
+            s.audio_input = if id.is_empty() { None } else { Some(id.clone()) };
+        })?;
+
+        // Signal Synthia to reload config
+        let signal_file = get_runtime_dir().join("synthia-reload-config");
+        fs::write(&signal_file, "reload").ok();
+
+        Ok("Audio input set".to_string())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_errors_are_classified_by_text() {
+        let denied = "snd_pcm_open failed: EACCES: Permission denied";
+        assert_eq!(
+            classify_open_error("mic", denied.into()),
+            AudioStatus::PermissionDenied(denied.into())
+        );
+        assert_eq!(
+            classify_open_error("mic", "Device or resource busy".into()),
+            AudioStatus::Ok("mic".into())
+        );
+        assert_eq!(
+            classify_open_error("mic", "Invalid argument".into()),
+            AudioStatus::Error("Invalid argument".into())
+        );
+    }
+
+    #[test]
+    fn status_serializes_with_tag() {
+        let json = serde_json::to_value(AudioStatus::NoDevice).unwrap();
+        assert_eq!(json, serde_json::json!({ "status": "no_device" }));
+        let json = serde_json::to_value(AudioStatus::Ok("USB Mic".into())).unwrap();
+        assert_eq!(json, serde_json::json!({ "status": "ok", "detail": "USB Mic" }));
+    }
+}
@@ -0,0 +1,226 @@
+//! Backend speech settings Tauri commands: STT model, language, wake word
+//! and its sensitivity, VAD.
+
+use std::fs;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::lifecycle::reload_signal_file;
+use crate::config::SynthiaConfigYaml;
+use crate::error::{AppError, AppResult};
+use crate::get_config_path;
+use crate::paths::write_atomic_private;
+use crate::synthia_state::{read_synthia_state, WakeDetections};
+use crate::yaml_writer::upsert_synthia_config_key;
+
+/// Whisper model sizes the backend's `local_stt_model` accepts.
+const STT_MODELS: &[&str] = &["tiny", "base", "small", "medium", "large"];
+
+const WAKE_WORD_MAX_CHARS: usize = 32;
+
+/// The subset of `config.yaml` the GUI edits on the backend's behalf. The
+/// backend reads these at start and reapplies all of them when it takes the
+/// reload signal written on save.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BackendSettings {
+    /// Local Whisper model size (`local_stt_model`).
+    pub stt_model: String,
+    /// BCP 47 language tag for speech recognition, e.g. `en-US`.
+    pub language: String,
+    /// Hands-free trigger phrase; empty disables it. The backend then asks
+    /// the assistant whatever is said in the few seconds after it.
+    pub wake_word: String,
+    /// Voice activity detection sensitivity, 0.0 to 1.0: sets the speech
+    /// threshold of local Whisper's VAD filter. Google STT has none.
+    pub vad_sensitivity: f64,
+}
+
+impl From<SynthiaConfigYaml> for BackendSettings {
+    fn from(y: SynthiaConfigYaml) -> Self {
+        BackendSettings {
+            stt_model: if y.local_stt_model.is_empty() {
+                "small".to_string()
+            } else {
+                y.local_stt_model
+            },
+            language: y.language,
+            wake_word: y.wake_word,
+            vad_sensitivity: y.vad_sensitivity,
+        }
+    }
+}
+
+impl BackendSettings {
+    pub fn validate(&self) -> AppResult<()> {
+        if !STT_MODELS.contains(&self.stt_model.as_str()) {
+            return Err(AppError::Validation(format!(
+                "unknown STT model {:?}; expected one of {}",
+                self.stt_model,
+                STT_MODELS.join(", ")
+            )));
+        }
+        let language = Regex::new(r"^[a-z]{2,3}(-[A-Z]{2})?$").expect("valid regex");
+        if !language.is_match(&self.language) {
+            return Err(AppError::Validation(format!(
+                "language must be a tag like en or en-US, got {:?}",
+                self.language
+            )));
+        }
+        if self.wake_word.chars().count() > WAKE_WORD_MAX_CHARS
+            || !self
+                .wake_word
+                .chars()
+                .all(|c| c.is_alphabetic() || c == ' ' || c == '-' || c == '\'')
+        {
+            return Err(AppError::Validation(format!(
+                "wake word must be at most {WAKE_WORD_MAX_CHARS} letters, spaces or hyphens"
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.vad_sensitivity) {
+            return Err(AppError::Validation(format!(
+                "VAD sensitivity must be between 0.0 and 1.0, got {}",
+                self.vad_sensitivity
+            )));
+        }
+        Ok(())
+    }
+
+    /// `config.yaml` updates, quoted the way `save_synthia_config` quotes them.
+    fn yaml_updates(&self) -> AppResult<Vec<(&'static str, String)>> {
+        Ok(vec![
+            ("local_stt_model", format!("\"{}\"", self.stt_model)),
+            ("language", format!("\"{}\"", self.language)),
+            ("wake_word", serde_json::to_string(self.wake_word.trim())?),
+            ("vad_sensitivity", self.vad_sensitivity.to_string()),
+        ])
+    }
+}
+
+pub(crate) fn read_config_yaml() -> AppResult<SynthiaConfigYaml> {
+    let content = fs::read_to_string(get_config_path()).unwrap_or_default();
+    Ok(if content.trim().is_empty() {
+        serde_yaml::from_str("{}")?
+    } else {
+        serde_yaml::from_str(&content)?
+    })
+}
+
+#[tauri::command]
+pub fn get_backend_settings() -> AppResult<BackendSettings> {
+    Ok(read_config_yaml()?.into())
+}
+
+#[tauri::command]
+pub fn set_backend_settings(settings: BackendSettings) -> AppResult<String> {
+    save(&settings)?;
+    Ok("Backend settings saved".to_string())
+}
+
+/// Validate `settings`, write them into `config.yaml` and signal the
+/// backend to reload.
+pub(crate) fn save(settings: &BackendSettings) -> AppResult<()> {
+    settings.validate()?;
+
+    let config_path = get_config_path();
+    let mut content = fs::read_to_string(&config_path)
+        .map_err(|e| AppError::Io(format!("Failed to read config: {}", e)))?;
+    for (key, value) in settings.yaml_updates()? {
+        content = upsert_synthia_config_key(&content, key, &value);
+    }
+    write_atomic_private(&config_path, content)?;
+    tracing::info!(
+        stt_model = %settings.stt_model,
+        language = %settings.language,
+        vad_sensitivity = settings.vad_sensitivity,
+        "backend settings saved"
+    );
+
+    // Signal Synthia to reload config
+    fs::write(reload_signal_file(), "reload").ok();
+    Ok(())
+}
+
+/// Clamp to 0.0–1.0 and two decimals, the slider's resolution. NaN falls
+/// back to the default rather than poisoning the config.
+fn clamp_sensitivity(value: f32) -> f32 {
+    if value.is_nan() {
+        return 0.5;
+    }
+    (value.clamp(0.0, 1.0) * 100.0).round() / 100.0
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct WakeSensitivity {
+    pub sensitivity: f32,
+    /// Wake word outcomes since the backend started, if it reports them.
+    pub detections: Option<WakeDetections>,
+}
+
+#[tauri::command]
+pub fn get_wake_sensitivity() -> AppResult<WakeSensitivity> {
+    Ok(WakeSensitivity {
+        sensitivity: read_config_yaml()?.wake_sensitivity as f32,
+        detections: read_synthia_state().wake_detections,
+    })
+}
+
+/// Save `wake_sensitivity` and signal the backend to reload, so the slider
+/// takes effect on the next wake word check. Returns the value stored.
+#[tauri::command]
+pub fn set_wake_sensitivity(sensitivity: f32) -> AppResult<f32> {
+    let sensitivity = clamp_sensitivity(sensitivity);
+    let config_path = get_config_path();
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| AppError::Io(format!("Failed to read config: {}", e)))?;
+    let content =
+        upsert_synthia_config_key(&content, "wake_sensitivity", &sensitivity.to_string());
+    write_atomic_private(&config_path, content)?;
+    tracing::info!(sensitivity, "wake sensitivity saved");
+
+    fs::write(reload_signal_file(), "reload").ok();
+    Ok(sensitivity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid() -> BackendSettings {
+        BackendSettings {
+            stt_model: "small".to_string(),
+            language: "en-US".to_string(),
+            wake_word: "hey synthia".to_string(),
+            vad_sensitivity: 0.5,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_defaults_and_rejects_out_of_range() {
+        assert!(valid().validate().is_ok());
+        assert!(BackendSettings { wake_word: String::new(), ..valid() }.validate().is_ok());
+        assert!(BackendSettings { stt_model: "huge".into(), ..valid() }.validate().is_err());
+        assert!(BackendSettings { language: "english".into(), ..valid() }.validate().is_err());
+        assert!(BackendSettings { wake_word: "rm -rf /".into(), ..valid() }.validate().is_err());
+        assert!(BackendSettings { vad_sensitivity: 1.5, ..valid() }.validate().is_err());
+        assert!(BackendSettings { vad_sensitivity: f64::NAN, ..valid() }.validate().is_err());
+    }
+
+    #[test]
+    fn sensitivity_is_clamped_and_rounded() {
+        assert_eq!(clamp_sensitivity(0.5), 0.5);
+        assert_eq!(clamp_sensitivity(-0.2), 0.0);
+        assert_eq!(clamp_sensitivity(3.0), 1.0);
+        assert_eq!(clamp_sensitivity(0.333), 0.33);
+        assert_eq!(clamp_sensitivity(f32::NAN), 0.5);
+    }
+
+    #[test]
+    fn missing_keys_read_as_backend_defaults() {
+        let parsed: SynthiaConfigYaml = serde_yaml::from_str("tts_speed: 1.0\n").unwrap();
+        let settings = BackendSettings::from(parsed);
+        assert_eq!(settings.stt_model, "small");
+        assert_eq!(settings.language, "en-US");
+        assert_eq!(settings.vad_sensitivity, 0.5);
+    }
+}
@@ -1,4 +1,5 @@
-//! Clipboard history Tauri commands.
+//! Clipboard Tauri commands: the backend's clipboard history, and copying
+//! transcriptions from the tray, a hotkey or the history view.
 
 use std::fs;
 use std::io::Write;
@@ -6,8 +7,10 @@ use std::process::{Command, Stdio};
 
 use serde::{Deserialize, Serialize};
 
+use super::history::get_last_transcript;
+use super::run_blocking;
 use crate::error::{AppError, AppResult};
-use crate::{get_clipboard_file, is_wayland_env};
+use crate::{get_clipboard_file, history_store, i18n, is_wayland_env, notifications, platform};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ClipboardEntry {
@@ -29,38 +32,110 @@ pub fn get_clipboard_history() -> Vec<ClipboardEntry> {
 
 #[tauri::command]
 pub fn copy_from_clipboard_history(content: String) -> AppResult<String> {
-    if is_wayland_env() {
-        let mut child = Command::new("wl-copy")
-            .stdin(Stdio::piped())
-            .spawn()
-            .map_err(|e| AppError::Process(format!("Failed to spawn wl-copy: {}", e)))?;
-
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin
-                .write_all(content.as_bytes())
-                .map_err(|e| AppError::Io(format!("Failed to write to wl-copy: {}", e)))?;
-        }
-
-        child
-            .wait()
-            .map_err(|e| AppError::Process(format!("wl-copy failed: {}", e)))?;
+    copy_to_clipboard(&content)?;
+    Ok("Copied to clipboard".to_string())
+}
+
+/// The latest transcription: the backend's last-transcript file, which is
+/// written first, else the newest history entry, which outlives a reboot.
+fn last_transcription() -> AppResult<String> {
+    if let Some(text) = get_last_transcript() {
+        return Ok(text);
+    }
+    let conn = history_store::open()?;
+    history_store::latest(&conn)?
+        .map(|entry| entry.text)
+        .ok_or_else(|| AppError::NotFound("no transcription to copy yet".to_string()))
+}
+
+/// Copy the latest transcription and return it.
+#[tauri::command]
+pub async fn copy_last_transcription() -> AppResult<String> {
+    run_blocking(copy_last).await
+}
+
+fn copy_last() -> AppResult<String> {
+    let text = last_transcription()?;
+    copy_to_clipboard(&text)?;
+    Ok(text)
+}
+
+/// Copy the text of history entry `id` and return it.
+#[tauri::command]
+pub async fn copy_history_entry(id: i64) -> AppResult<String> {
+    run_blocking(move || {
+        let conn = history_store::open()?;
+        let entry = history_store::get(&conn, id)?
+            .ok_or_else(|| AppError::NotFound(format!("no history entry {id}")))?;
+        copy_to_clipboard(&entry.text)?;
+        Ok(entry.text)
+    })
+    .await
+}
+
+/// `copy_last_transcription` for the tray item and hotkey, where a failure
+/// has nowhere to go but a notification.
+pub(crate) fn copy_last_transcription_or_notify() {
+    if let Err(e) = copy_last() {
+        tracing::warn!(error = %e, "copy last transcription failed");
+        notifications::notify_error(&i18n::t("notify.error_title"), &e.to_string());
+    }
+}
+
+/// Put `content` on the system clipboard via `clip.exe` on Windows,
+/// `pbcopy` on macOS, else `wl-copy` or `xclip`.
+pub(crate) fn copy_to_clipboard(content: &str) -> AppResult<()> {
+    if cfg!(windows) {
+        pipe_to("clip", &[], &utf16_with_bom(content))
+    } else if cfg!(target_os = "macos") {
+        pipe_to("pbcopy", &[], content.as_bytes())
+    } else if is_wayland_env() {
+        pipe_to("wl-copy", &[], content.as_bytes())
     } else {
-        let mut child = Command::new("xclip")
-            .args(["-selection", "clipboard"])
-            .stdin(Stdio::piped())
-            .spawn()
-            .map_err(|e| AppError::Process(format!("Failed to spawn xclip: {}", e)))?;
-
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin
-                .write_all(content.as_bytes())
-                .map_err(|e| AppError::Io(format!("Failed to write to xclip: {}", e)))?;
-        }
-
-        child
-            .wait()
-            .map_err(|e| AppError::Process(format!("xclip failed: {}", e)))?;
+        pipe_to("xclip", &["-selection", "clipboard"], content.as_bytes())
     }
+}
 
-    Ok("Copied to clipboard".to_string())
+/// `clip.exe` reads its input in the console code page unless it starts
+/// with a UTF-16LE byte order mark.
+fn utf16_with_bom(content: &str) -> Vec<u8> {
+    std::iter::once('\u{feff}')
+        .chain(content.chars())
+        .collect::<String>()
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect()
+}
+
+/// Run `program` with `content` on its stdin and wait for it to succeed.
+fn pipe_to(program: &str, args: &[&str], content: &[u8]) -> AppResult<()> {
+    let mut child = platform::no_window(&mut Command::new(program))
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::Process(format!("Failed to spawn {}: {}", program, e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(content)
+            .map_err(|e| AppError::Io(format!("Failed to write to {}: {}", program, e)))?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| AppError::Process(format!("{} failed: {}", program, e)))?;
+    if !status.success() {
+        return Err(AppError::Process(format!("{} exited with {}", program, status)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clip_input_is_utf16_with_a_bom() {
+        assert_eq!(utf16_with_bom("hé"), vec![0xff, 0xfe, b'h', 0, 0xe9, 0]);
+    }
 }
@@ -0,0 +1,20 @@
+//! Compute device Tauri commands: which GPUs the backend could use, and
+//! which one it does. See `compute_devices`.
+
+use crate::compute_devices::{self, DeviceReport};
+use crate::error::AppResult;
+use crate::gui_config::{self, ComputeDevice};
+
+#[tauri::command]
+pub async fn detect_devices() -> AppResult<DeviceReport> {
+    super::run_blocking(|| Ok(compute_devices::detect())).await
+}
+
+/// Save `backend.compute_device`. Takes effect the next time the backend
+/// or remote bot starts.
+#[tauri::command]
+pub fn set_compute_device(device: ComputeDevice) -> AppResult<ComputeDevice> {
+    gui_config::update(|cfg| cfg.backend.compute_device = device)?;
+    tracing::info!(?device, "compute device set");
+    Ok(device)
+}
@@ -0,0 +1,207 @@
+//! Diagnostics Tauri commands: one zip to attach to a bug report, the log
+//! viewer's queries, plus shortcuts to the GUI's log files for a quick look.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+
+use regex::Regex;
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+use tauri_plugin_opener::OpenerExt;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::health::health;
+use super::lifecycle::last_lines;
+use super::run_blocking;
+use super::version::backend_version;
+use crate::error::{AppError, AppResult};
+use crate::state::AppState;
+use crate::{get_config_path, get_runtime_state_path, get_state_file};
+use crate::{gui_config, logging};
+
+/// Lines kept from the end of each log file.
+const LOG_TAIL_LINES: usize = 500;
+
+const REDACTED: &str = "<redacted>";
+
+#[derive(Serialize)]
+struct SystemInfo {
+    gui_version: &'static str,
+    backend_version: Option<String>,
+    os: &'static str,
+    arch: &'static str,
+    kernel: Option<String>,
+    desktop: Option<String>,
+    session_type: Option<String>,
+}
+
+/// Blank out remote-mode bot tokens wherever they appear: as config keys
+/// (`*_bot_token`, `*_access_token`), and a Telegram token in bare
+/// `<bot id>:<secret>` form (e.g. inside an API URL in a log). `*_api_key`
+/// config values get the same treatment, as does the HTTP control `token`
+/// in `gui.toml`.
+fn scrub_secrets(text: &str) -> String {
+    let key = Regex::new(r#"(?m)^(\s*[a-z_]*(?:bot_token|access_token|api_key)\s*:).*$"#)
+        .expect("valid regex");
+    let toml_key = Regex::new(r#"(?m)^(\s*token\s*=).*$"#).expect("valid regex");
+    let token = Regex::new(r"\d{6,}:[A-Za-z0-9_-]{30,}").expect("valid regex");
+    let text = key.replace_all(text, format!("$1 \"{REDACTED}\""));
+    let text = toml_key.replace_all(&text, format!("$1 \"{REDACTED}\""));
+    token.replace_all(&text, REDACTED).into_owned()
+}
+
+fn system_info(state: &AppState) -> SystemInfo {
+    let kernel = Command::new("uname")
+        .arg("-a")
+        .output()
+        .ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+    SystemInfo {
+        gui_version: env!("CARGO_PKG_VERSION"),
+        backend_version: backend_version(state).ok(),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        kernel,
+        desktop: std::env::var("XDG_CURRENT_DESKTOP").ok(),
+        session_type: std::env::var("XDG_SESSION_TYPE").ok(),
+    }
+}
+
+/// Bundle entries as `(name in zip, contents)`. Unreadable sources are
+/// recorded as a note rather than failing the whole export.
+fn collect_entries(state: &AppState) -> AppResult<Vec<(&'static str, String)>> {
+    let read = |path: PathBuf| {
+        fs::read_to_string(&path)
+            .unwrap_or_else(|e| format!("<could not read {}: {}>", path.display(), e))
+    };
+    let tail = |path: PathBuf| last_lines(&read(path), LOG_TAIL_LINES);
+
+    Ok(vec![
+        ("system.json", serde_json::to_string_pretty(&system_info(state))?),
+        ("health.json", serde_json::to_string_pretty(&health(state))?),
+        ("config.yaml", read(get_config_path())),
+        ("gui.toml", read(gui_config::gui_config_path())),
+        ("runtime.json", read(get_runtime_state_path())),
+        ("synthia-state.json", read(get_state_file())),
+        ("gui.log", tail(logging::log_file_path())),
+        ("backend.log", tail(logging::backend_log_path())),
+    ])
+}
+
+fn write_bundle(state: &AppState) -> AppResult<PathBuf> {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let path = std::env::temp_dir().join(format!("synthia-diagnostics-{stamp}.zip"));
+    let zip_err = |e: zip::result::ZipError| AppError::Other(format!("Failed to write zip: {}", e));
+
+    let mut zip = ZipWriter::new(File::create(&path)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, contents) in collect_entries(state)? {
+        zip.start_file(name, options).map_err(zip_err)?;
+        zip.write_all(scrub_secrets(&contents).as_bytes())?;
+    }
+    zip.finish().map_err(zip_err)?;
+    tracing::info!(path = %path.display(), "exported diagnostics bundle");
+    Ok(path)
+}
+
+/// Write a diagnostics zip to the temp dir and return its path, so the UI
+/// can reveal it in the file manager.
+#[tauri::command]
+pub async fn export_diagnostics(app: tauri::AppHandle) -> AppResult<PathBuf> {
+    run_blocking(move || write_bundle(&app.state::<AppState>())).await
+}
+
+/// Hand `path` to the system's default handler (file manager for a dir).
+fn open_with_default(app: &tauri::AppHandle, path: &std::path::Path) -> AppResult<()> {
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| AppError::Other(format!("Failed to open {}: {}", path.display(), e)))
+}
+
+/// Open the GUI log directory in the file manager, creating it if needed.
+#[tauri::command]
+pub fn open_log_dir(app: tauri::AppHandle) -> AppResult<PathBuf> {
+    let dir = logging::log_dir();
+    fs::create_dir_all(&dir)?;
+    open_with_default(&app, &dir)?;
+    Ok(dir)
+}
+
+/// Open the current GUI log in the default text viewer. The file is created
+/// empty if logging couldn't write it yet, so the viewer has something to show.
+#[tauri::command]
+pub fn open_log_file(app: tauri::AppHandle) -> AppResult<PathBuf> {
+    let path = logging::log_file_path();
+    fs::create_dir_all(logging::log_dir())?;
+    fs::OpenOptions::new().create(true).append(true).open(&path)?;
+    open_with_default(&app, &path)?;
+    Ok(path)
+}
+
+/// Most records `get_recent_logs` returns, whatever was asked for.
+const MAX_RECENT_LOGS: usize = 5000;
+
+/// The last `lines` records of `gui.log` (backend output included) at
+/// `level` or above (default `info`), oldest first.
+#[tauri::command]
+pub async fn get_recent_logs(
+    lines: usize,
+    level: Option<String>,
+) -> AppResult<Vec<logging::LogEntry>> {
+    let level = level.unwrap_or_else(|| "info".to_string());
+    run_blocking(move || Ok(logging::recent_entries(lines.min(MAX_RECENT_LOGS), &level))).await
+}
+
+/// Start or stop streaming new `gui.log` records to the frontend as
+/// `log-line` events. Needs the log file to be writable.
+#[tauri::command]
+pub fn tail_logs(app: tauri::AppHandle, enabled: bool) {
+    if !enabled {
+        logging::stop_tail();
+        return;
+    }
+    let rx = logging::start_tail();
+    // Ends once the sender is dropped by `stop_tail` or a newer tail.
+    std::thread::spawn(move || {
+        for chunk in rx {
+            for entry in logging::parse_entries(&chunk) {
+                let _ = app.emit("log-line", entry);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrub_redacts_config_key_and_bare_tokens() {
+        let token = "123456789:AAHdqTcvCH1vGWJxfSeofSAs0K5PALDsaw";
+        let config = format!(
+            "tts_speed: 1.0\ntelegram_bot_token: \"{token}\"\nanthropic_api_key: sk-ant-123\n"
+        );
+        let scrubbed = scrub_secrets(&config);
+        assert!(!scrubbed.contains(token));
+        assert!(!scrubbed.contains("sk-ant-123"));
+        assert!(scrubbed.contains("telegram_bot_token: \"<redacted>\""));
+        assert!(scrubbed.contains("tts_speed: 1.0"));
+        let scrubbed = scrub_secrets("matrix_access_token: syt_c2VjcmV0_abc\n");
+        assert_eq!(scrubbed, "matrix_access_token: \"<redacted>\"\n");
+
+        let gui_toml = "[http_control]\nenabled = true\ntoken = \"0f3c9a\"\n";
+        assert_eq!(
+            scrub_secrets(gui_toml),
+            "[http_control]\nenabled = true\ntoken = \"<redacted>\"\n"
+        );
+
+        let log = format!("POST https://api.telegram.org/bot{token}/sendMessage");
+        assert_eq!(
+            scrub_secrets(&log),
+            "POST https://api.telegram.org/bot<redacted>/sendMessage"
+        );
+    }
+}
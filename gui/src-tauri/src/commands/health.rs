@@ -0,0 +1,158 @@
+//! Diagnostics Tauri commands: one call that aggregates every health check,
+//! plus command counts, backend heartbeat health and resource usage.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+use tauri::Manager;
+
+use super::audio::{audio_status, AudioStatus};
+use super::run_blocking;
+use crate::backend_health::{self, BackendHealth};
+use crate::command_stats::CommandStat;
+use crate::error::AppResult;
+use crate::process::{count_running, is_running, ShutdownOutcome, BACKEND_PATTERN, BOT_PATTERN};
+use crate::remote_channel;
+use crate::resource_monitor::{self, ResourceUsage};
+use crate::state::AppState;
+use crate::sidecar;
+use crate::synthia_state::{read_synthia_state, OverlayState};
+use crate::tray::IconStatus;
+use crate::{
+    get_config_path, get_python_path, get_run_script, get_runtime_dir, get_state_file,
+    get_synthia_root,
+};
+
+/// A state file untouched for longer than this is reported as stale. A hung
+/// backend stops writing but leaves its last (valid-looking) state behind.
+const STATE_FRESH_WITHIN: Duration = Duration::from_secs(120);
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PathStatus {
+    pub name: String,
+    pub path: String,
+    pub exists: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Health {
+    pub backend_running: bool,
+    /// More than one means a stale backend is competing for the mic.
+    pub backend_instances: usize,
+    pub remote_running: bool,
+    pub state_file_exists: bool,
+    pub state_file_age_secs: Option<u64>,
+    pub state_file_fresh: bool,
+    pub status: String,
+    pub last_error: Option<String>,
+    /// Outcome of the last backend stop issued by this GUI session.
+    pub last_shutdown: Option<ShutdownOutcome>,
+    /// How each tray icon was resolved at startup.
+    pub tray_icons: Vec<IconStatus>,
+    /// Whether the default microphone can be opened.
+    pub audio_input: AudioStatus,
+    pub paths: Vec<PathStatus>,
+}
+
+fn path_status(name: &str, path: &Path) -> PathStatus {
+    PathStatus {
+        name: name.to_string(),
+        path: path.display().to_string(),
+        exists: path.exists(),
+    }
+}
+
+/// Seconds since `path` was last modified, or `None` if it can't be stat'd.
+fn file_age(path: &Path) -> Option<Duration> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(SystemTime::now().duration_since(modified).unwrap_or_default())
+}
+
+fn resolved_paths() -> Vec<(&'static str, PathBuf)> {
+    let root = get_synthia_root();
+    let channel = remote_channel::active();
+    vec![
+        ("synthia_root", root.clone()),
+        ("run_script", get_run_script()),
+        ("python", get_python_path()),
+        ("remote_bot", root.join(channel.script())),
+        ("backend_sidecar", sidecar::expected_path(sidecar::BACKEND)),
+        ("remote_bot_sidecar", sidecar::expected_path(channel.sidecar())),
+        ("config", get_config_path()),
+        ("gui_config", crate::gui_config::gui_config_path()),
+        ("runtime_dir", get_runtime_dir()),
+        ("state_file", get_state_file()),
+    ]
+}
+
+pub(crate) fn health(app_state: &AppState) -> Health {
+    let backend_instances = count_running(BACKEND_PATTERN);
+    let state_file = get_state_file();
+    let age = file_age(&state_file);
+    let state = read_synthia_state();
+    let last_error = match state.overlay_state() {
+        OverlayState::Error => Some(
+            state
+                .detail
+                .clone()
+                .or_else(|| state.error.clone())
+                .unwrap_or_else(|| state.status.clone()),
+        ),
+        _ => None,
+    };
+
+    Health {
+        backend_running: backend_instances > 0,
+        backend_instances,
+        remote_running: is_running(BOT_PATTERN),
+        state_file_exists: state_file.exists(),
+        state_file_age_secs: age.map(|a| a.as_secs()),
+        state_file_fresh: age.map(|a| a <= STATE_FRESH_WITHIN).unwrap_or(false),
+        status: state.status,
+        last_error,
+        last_shutdown: app_state.last_shutdown.lock().ok().and_then(|s| s.clone()),
+        tray_icons: app_state
+            .tray
+            .lock()
+            .map(|t| t.icons.statuses.clone())
+            .unwrap_or_default(),
+        audio_input: audio_status(),
+        paths: resolved_paths()
+            .into_iter()
+            .map(|(name, path)| path_status(name, &path))
+            .collect(),
+    }
+}
+
+#[tauri::command]
+pub async fn get_health(app: tauri::AppHandle) -> AppResult<Health> {
+    run_blocking(move || {
+        let health = health(&app.state::<AppState>());
+        super::lifecycle::warn_if_multiple(&app, health.backend_instances);
+        Ok(health)
+    })
+    .await
+}
+
+/// Success/failure counts for start, stop, toggle and remote mode since the
+/// app launched.
+#[tauri::command]
+pub fn get_command_stats(app: tauri::AppHandle) -> Vec<CommandStat> {
+    app.state::<AppState>().command_stats.snapshot()
+}
+
+/// Whether the backend's heartbeat is current; changes arrive as
+/// `backend-health` events.
+#[tauri::command]
+pub fn get_backend_health(app: tauri::AppHandle) -> BackendHealth {
+    backend_health::current(&app)
+}
+
+/// The backend's recent CPU and memory samples, oldest first; new ones
+/// arrive as `resource-usage` events.
+#[tauri::command]
+pub fn get_resource_usage() -> Vec<ResourceUsage> {
+    resource_monitor::history()
+}
@@ -1,36 +1,112 @@
 //! History Tauri commands.
 
 use std::fs;
+use std::path::PathBuf;
 use std::process::Command;
 
-use serde::{Deserialize, Serialize};
+use tauri_plugin_dialog::DialogExt;
 
+use super::run_blocking;
 use crate::error::{AppError, AppResult};
-use crate::{get_history_file, get_runtime_dir};
-
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct HistoryEntry {
-    id: u32,
-    text: String,
-    mode: String,
-    timestamp: String,
-    response: Option<String>,
+use crate::history_export::{self, ExportFormat};
+use crate::history_store::{
+    self, DateRange, HistoryFilter, HistoryPage, RemoteActivity, SearchHit,
+};
+use crate::paths::write_atomic;
+use crate::text_injection;
+use crate::{get_last_transcript_file, get_runtime_dir};
+
+/// Page `page` (default 0) of the transcription history, newest first.
+#[tauri::command]
+pub fn get_history(page: Option<u32>, filter: Option<HistoryFilter>) -> AppResult<HistoryPage> {
+    let conn = history_store::open()?;
+    history_store::page(&conn, page.unwrap_or(0), &filter.unwrap_or_default())
+}
+
+/// Full-text search over the history, best match first. Every word of
+/// `query` must appear; the last may be a prefix.
+#[tauri::command]
+pub fn search_history(query: String, date_range: Option<DateRange>) -> AppResult<Vec<SearchHit>> {
+    let conn = history_store::open()?;
+    history_store::search(&conn, &query, date_range.unwrap_or_default())
+}
+
+/// What went to and from remote mode within `range` (default: all of it),
+/// newest first. New exchanges are emitted as `remote-activity`.
+#[tauri::command]
+pub fn get_remote_log(range: Option<DateRange>) -> AppResult<Vec<RemoteActivity>> {
+    let conn = history_store::open()?;
+    history_store::remote_log(&conn, range.unwrap_or_default())
 }
 
+/// Write the history within `range` (default: all of it) to `path` as
+/// `format`. Without a path, asks where with a save dialog. Returns the
+/// file written, or `None` if the dialog was cancelled.
 #[tauri::command]
-pub fn get_history() -> Vec<HistoryEntry> {
-    let history_file = get_history_file();
-    if let Ok(content) = fs::read_to_string(&history_file) {
-        serde_json::from_str(&content).unwrap_or_default()
-    } else {
-        Vec::new()
+pub async fn export_history(
+    app: tauri::AppHandle,
+    format: ExportFormat,
+    range: Option<DateRange>,
+    path: Option<PathBuf>,
+) -> AppResult<Option<PathBuf>> {
+    run_blocking(move || {
+        let path = match path {
+            Some(path) if path.is_absolute() => path,
+            Some(path) => {
+                return Err(AppError::Validation(format!(
+                    "export path must be absolute: {}",
+                    path.display()
+                )))
+            }
+            None => {
+                let name = format!(
+                    "synthia-history-{}.{}",
+                    chrono::Local::now().format("%Y-%m-%d"),
+                    format.extension()
+                );
+                let chosen = app
+                    .dialog()
+                    .file()
+                    .set_title("Export transcriptions")
+                    .set_file_name(name)
+                    .add_filter(format.label(), &[format.extension()])
+                    .blocking_save_file();
+                let Some(chosen) = chosen else { return Ok(None) };
+                chosen.into_path().map_err(|e| AppError::Path(e.to_string()))?
+            }
+        };
+        let conn = history_store::open()?;
+        let entries = history_store::entries_in(&conn, range.unwrap_or_default())?;
+        write_atomic(&path, history_export::render(format, &entries)?)?;
+        tracing::info!(path = %path.display(), count = entries.len(), ?format, "history exported");
+        Ok(Some(path))
+    })
+    .await
+}
+
+#[tauri::command]
+pub fn delete_history_entry(id: i64) -> AppResult<()> {
+    let conn = history_store::open()?;
+    if !history_store::delete(&conn, id)? {
+        return Err(AppError::NotFound(format!("no history entry {id}")));
     }
+    Ok(())
+}
+
+/// The most recent transcript, or `None` if nothing has been transcribed
+/// since the backend's runtime dir was created.
+#[tauri::command]
+pub fn get_last_transcript() -> Option<String> {
+    let text = fs::read_to_string(get_last_transcript_file()).ok()?;
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
 }
 
 #[tauri::command]
 pub fn clear_history() -> AppResult<String> {
-    let history_file = get_history_file();
-    fs::write(&history_file, "[]")?;
+    let conn = history_store::open()?;
+    let removed = history_store::clear(&conn)?;
+    tracing::info!(removed, "history cleared");
     Ok("History cleared".to_string())
 }
 
@@ -47,10 +123,7 @@ pub fn resend_to_assistant(text: String) -> AppResult<String> {
         .output();
 
     // Type the text
-    Command::new("xdotool")
-        .args(["type", "--clearmodifiers", &text])
-        .output()
-        .map_err(|e| AppError::Process(format!("Failed to type text: {}", e)))?;
+    text_injection::type_text(&text)?;
 
     // Press Enter to submit
     Command::new("xdotool")
@@ -1,11 +1,21 @@
 //! Hotkeys + word replacement Tauri commands.
 
 use std::fs;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
+use tauri::Manager;
 
 use crate::error::{AppError, AppResult};
-use crate::{get_config_path, get_runtime_dir};
+use crate::evdev_input::{self, CapturedKey, InputDevice};
+use crate::global_hotkeys::{self, HotkeyAction, ACTIONS};
+use crate::gui_config::{self, DoubleTapConfig, EvdevBinding, PushToTalkInput};
+use crate::state::AppState;
+use crate::synthia_state::read_synthia_state;
+use crate::{get_config_path, get_runtime_dir, push_to_talk};
+
+/// How long `capture_input_key` waits by default.
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct WordReplacement {
@@ -105,6 +115,115 @@ pub fn save_hotkeys(dictation_key: String, assistant_key: String) -> AppResult<S
     Ok("Hotkeys saved".to_string())
 }
 
+/// A global shortcut the GUI registers, as shown in settings.
+#[derive(Serialize, Debug, Clone)]
+pub struct HotkeyBinding {
+    pub action: HotkeyAction,
+    pub shortcut: Option<String>,
+    /// Whether the shortcut is registered right now. Push-to-talk's is only
+    /// registered while in push-to-talk mode, and counts as registered while
+    /// its evdev key is listened for instead.
+    pub registered: bool,
+}
+
+fn global_bindings(app: &tauri::AppHandle) -> Vec<HotkeyBinding> {
+    let config = gui_config::load();
+    let state = app.state::<AppState>();
+    let ptt_registered = state.ptt_shortcut.lock().is_ok_and(|s| s.is_some())
+        || state.ptt_evdev.lock().is_ok_and(|l| l.is_some());
+    let registered: Vec<HotkeyAction> = state
+        .global_hotkeys
+        .lock()
+        .map(|hotkeys| hotkeys.iter().map(|h| h.action).collect())
+        .unwrap_or_default();
+    ACTIONS
+        .into_iter()
+        .map(|action| HotkeyBinding {
+            action,
+            shortcut: action.shortcut(&config),
+            registered: match action {
+                HotkeyAction::PushToTalk => ptt_registered,
+                _ => registered.contains(&action),
+            },
+        })
+        .collect()
+}
+
+/// Global shortcuts handled by the GUI itself, unlike `get_hotkeys`, which
+/// reads the backend's own key bindings from `config.yaml`.
+#[tauri::command]
+pub fn get_global_hotkeys(app: tauri::AppHandle) -> Vec<HotkeyBinding> {
+    global_bindings(&app)
+}
+
+/// Bind a global shortcut to `action`, or unbind it with `None`.
+#[tauri::command]
+pub fn set_hotkey(
+    app: tauri::AppHandle,
+    action: HotkeyAction,
+    shortcut: Option<String>,
+) -> AppResult<Vec<HotkeyBinding>> {
+    global_hotkeys::set(&app, action, shortcut)?;
+    Ok(global_bindings(&app))
+}
+
+#[tauri::command]
+pub fn get_double_tap() -> Option<DoubleTapConfig> {
+    gui_config::load().hotkeys.double_tap
+}
+
+/// Toggle recording on a double tap of `double_tap.key`, or turn that off
+/// with `None`.
+#[tauri::command]
+pub fn set_double_tap(
+    app: tauri::AppHandle,
+    double_tap: Option<DoubleTapConfig>,
+) -> AppResult<Option<DoubleTapConfig>> {
+    global_hotkeys::set_double_tap(&app, double_tap)?;
+    Ok(double_tap)
+}
+
+/// Keyboards push-to-talk could read from `/dev/input`, and whether this
+/// user may.
+#[tauri::command]
+pub async fn list_input_devices() -> AppResult<Vec<InputDevice>> {
+    super::run_blocking(evdev_input::list_devices).await
+}
+
+/// Wait for the next key pressed on `device` (any keyboard if `None`), for
+/// picking the push-to-talk key. Gives up after `timeout_secs` (default 10).
+#[tauri::command]
+pub async fn capture_input_key(
+    device: Option<String>,
+    timeout_secs: Option<u64>,
+) -> AppResult<CapturedKey> {
+    let timeout = timeout_secs.map_or(CAPTURE_TIMEOUT, Duration::from_secs);
+    super::run_blocking(move || evdev_input::capture_key(device.as_deref(), timeout)).await
+}
+
+/// Switch push-to-talk between the global shortcut and an evdev key,
+/// optionally with a new key. An evdev key must be readable before it is
+/// saved, so a missing `input` group membership shows up here rather than
+/// as a dead key.
+#[tauri::command]
+pub fn set_push_to_talk_input(
+    app: tauri::AppHandle,
+    input: PushToTalkInput,
+    evdev: Option<EvdevBinding>,
+) -> AppResult<Vec<HotkeyBinding>> {
+    let binding = evdev.unwrap_or_else(|| gui_config::load().push_to_talk.evdev);
+    if input == PushToTalkInput::Evdev {
+        evdev_input::check(&binding)?;
+    }
+    gui_config::update(|cfg| {
+        cfg.push_to_talk.input = input;
+        cfg.push_to_talk.evdev = binding.clone();
+    })?;
+    tracing::info!(?input, key = %evdev_input::key_name(binding.key), "push-to-talk input set");
+    push_to_talk::sync(&app, &read_synthia_state());
+    Ok(global_bindings(&app))
+}
+
 #[tauri::command]
 pub fn get_word_replacements() -> Vec<WordReplacement> {
     let config_path = get_config_path();
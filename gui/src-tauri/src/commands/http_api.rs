@@ -0,0 +1,25 @@
+//! HTTP API and WebSocket control Tauri commands: the tokens scripts
+//! authenticate with.
+
+use super::run_blocking;
+use crate::error::AppResult;
+use crate::{http_control, ws_control};
+
+/// The HTTP API token, generating one on first use. Kept in the keyring.
+#[tauri::command]
+pub async fn get_http_api_token() -> AppResult<String> {
+    run_blocking(http_control::ensure_token).await
+}
+
+/// Replace the HTTP API token, for when the old one may have leaked.
+#[tauri::command]
+pub async fn regenerate_http_api_token() -> AppResult<String> {
+    run_blocking(http_control::regenerate_token).await
+}
+
+/// The WebSocket control token, generating one on first use. Kept in the
+/// keyring.
+#[tauri::command]
+pub async fn get_ws_control_token() -> AppResult<String> {
+    run_blocking(ws_control::ensure_token).await
+}
@@ -1,65 +1,744 @@
-//! Lifecycle Tauri commands: status, start/stop synthia, set mode, voice muted.
+//! Lifecycle Tauri commands: status, start/stop/reload synthia, set mode, voice muted.
 
 use std::fs;
-use std::path::PathBuf;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+use super::run_blocking;
+use crate::command_stats::TrackedCommand;
 use crate::error::{AppError, AppResult};
+use crate::process::{
+    count_running, describe_exit, find_processes, terminate_foreign, terminate_process_group,
+    BACKEND_PATTERN,
+};
+use crate::platform::{self, Stop};
+use crate::{compute_devices, dry_run, gui_config, logging, sidecar};
 use crate::state::AppState;
-use crate::{get_runtime_state_path, get_synthia_root};
+use crate::supervisor::{self, record_exit, BackendExit};
+use crate::synthia_state::{
+    read_synthia_state, update_synthia_state, write_synthia_state, Mode,
+    OverlayState, SynthiaState,
+};
+use crate::power::{self, PowerMode};
+use crate::{push_to_talk, tray};
+use crate::{get_runtime_dir, get_runtime_state_path, get_synthia_root};
+#[cfg(windows)]
+use crate::get_python_path;
+#[cfg(not(windows))]
+use crate::get_run_script;
 
-#[tauri::command]
-pub fn get_status() -> String {
-    let output = Command::new("pgrep")
-        .args(["-f", "synthia.main"])
-        .output();
+/// How long a freshly spawned backend must stay alive before we call it started.
+const START_GRACE: Duration = Duration::from_millis(750);
+
+/// After a failed start, further starts are refused for this long.
+const START_RETRY_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Lines of `backend.log` included in the error when the backend dies on start.
+const START_FAILURE_TAIL_LINES: usize = 10;
+
+/// How long a running backend gets to consume the reload signal file. It
+/// checks for it twice a second.
+const RELOAD_ACK_TIMEOUT: Duration = Duration::from_secs(2);
 
-    match output {
-        Ok(out) if out.status.success() => "running".to_string(),
-        _ => "stopped".to_string(),
+/// Last `n` non-empty lines of `content`, joined with newlines.
+pub(crate) fn last_lines(content: &str, n: usize) -> String {
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines[lines.len().saturating_sub(n)..].join("\n")
+}
+
+/// Payload of the `backend-instances` warning event.
+#[derive(Serialize, Debug, Clone)]
+pub struct BackendInstances {
+    pub count: usize,
+}
+
+/// Emit `backend-instances` when more than one backend is alive, e.g. after a
+/// suspend/resume cycle or a crashed GUI left one behind. The UI can offer
+/// `restart_synthia_fresh` in response.
+pub(crate) fn warn_if_multiple(app: &tauri::AppHandle, count: usize) {
+    if count > 1 {
+        tracing::warn!(count, "multiple backend instances running");
+        let _ = app.emit("backend-instances", BackendInstances { count });
     }
 }
 
 #[tauri::command]
-pub fn start_synthia(state: tauri::State<'_, AppState>) -> AppResult<String> {
+pub async fn get_status(app: tauri::AppHandle) -> AppResult<String> {
+    run_blocking(move || {
+        if dry_run::enabled() {
+            let running = app.state::<AppState>().simulated.backend();
+            return Ok(if running { "running" } else { "stopped" }.to_string());
+        }
+        let count = count_running(BACKEND_PATTERN);
+        warn_if_multiple(&app, count);
+        Ok(match count {
+            0 => "stopped",
+            _ if app.state::<AppState>().backend_stopping.load(Ordering::SeqCst) => "stopping",
+            _ if read_synthia_state().is_starting() => "starting",
+            _ => "running",
+        }
+        .to_string())
+    })
+    .await
+}
+
+/// Whether any backend is running, simulated in a dry run.
+pub(crate) fn backend_running(state: &AppState) -> bool {
+    if dry_run::enabled() {
+        state.simulated.backend()
+    } else {
+        count_running(BACKEND_PATTERN) > 0
+    }
+}
+
+/// Spawn the backend and wait out `START_GRACE`. Blocking: holds the
+/// `synthia_process` lock for the whole grace window so concurrent starts
+/// can't both spawn a backend.
+///
+/// A stored child that has since exited doesn't count as running: it is
+/// reaped and replaced. Within `START_RETRY_BACKOFF` of a failed start,
+/// another attempt is refused rather than hammering a broken install.
+pub(crate) fn start(state: &AppState) -> AppResult<String> {
+    let result = try_start(state);
+    state.command_stats.record(TrackedCommand::Start, &result);
+    result
+}
+
+#[tracing::instrument(skip_all)]
+fn try_start(state: &AppState) -> AppResult<String> {
+    if dry_run::enabled() {
+        dry_run::skip("start backend", &backend_command(&get_synthia_root()));
+        let was_running = state.simulated.set_backend(true);
+        return Ok(if was_running {
+            "Already running (dry run)"
+        } else {
+            "Synthia started (dry run)"
+        }
+        .to_string());
+    }
     let mut proc = state.synthia_process.lock().unwrap();
-    if proc.is_some() {
+    let mut replacing_dead = false;
+    if let Some(child) = proc.as_mut() {
+        match child.try_wait() {
+            Ok(None) => return Ok("Already running".to_string()),
+            Ok(Some(status)) => {
+                tracing::warn!(
+                    status = %describe_exit(status),
+                    "stored backend had exited; starting a new one"
+                );
+                record_exit(state, BackendExit::now(Some(status.into()), false));
+                *proc = None;
+                if let Ok(mut pgid) = state.backend_pgid.lock() {
+                    *pgid = None;
+                }
+                replacing_dead = true;
+            }
+            Err(e) => {
+                return Err(AppError::Process(format!("Failed to check process: {}", e)));
+            }
+        }
+    }
+    // A backend started outside the GUI (or left by a previous GUI session)
+    // would otherwise get a twin.
+    if count_running(BACKEND_PATTERN) > 0 {
         return Ok("Already running".to_string());
     }
 
+    let last_failure = state.last_start_failure.lock().ok().and_then(|f| *f);
+    if let Some(wait) = last_failure.and_then(|at| START_RETRY_BACKOFF.checked_sub(at.elapsed())) {
+        return Err(AppError::Validation(format!(
+            "Previous start failed moments ago; retry in {:.1}s",
+            wait.as_secs_f32()
+        )));
+    }
+
+    match launch(state) {
+        Ok(child) => {
+            if let Ok(mut failure) = state.last_start_failure.lock() {
+                *failure = None;
+            }
+            *proc = Some(child);
+            Ok(if last_failure.is_some() {
+                "Previous start failed; retried and Synthia started"
+            } else if replacing_dead {
+                "Previous backend had exited; Synthia restarted"
+            } else {
+                "Synthia started"
+            }
+            .to_string())
+        }
+        Err(e) => {
+            if let Ok(mut failure) = state.last_start_failure.lock() {
+                *failure = Some(Instant::now());
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Record in the state file whether a backend is on its way up. Starting
+/// clears `ready` until the new backend sets it; stopping clears it too and
+/// drops a leftover "starting" status.
+pub(crate) fn mark_starting(starting: bool) {
+    let result = update_synthia_state(|s| {
+        s.ready = false;
+        if starting {
+            s.status = OverlayState::Starting.as_status().to_string();
+            s.detail = None;
+        } else if s.status == OverlayState::Starting.as_status() {
+            s.status = OverlayState::Idle.as_status().to_string();
+        }
+    });
+    if let Err(e) = result {
+        tracing::warn!(error = %e, starting, "failed to record backend start in state file");
+    }
+}
+
+/// Spawn the backend and wait out `START_GRACE`, returning the child once
+/// it has survived. The caller holds the `synthia_process` lock.
+fn launch(state: &AppState) -> AppResult<Child> {
+    mark_starting(true);
+    let child = spawn_backend(state);
+    if child.is_err() {
+        mark_starting(false);
+    }
+    child
+}
+
+/// The bundled backend if there is one, else the launch script, run from
+/// the checkout at `root`. Windows can't run `run.sh`, so there the venv's
+/// python is started directly with the `PYTHONPATH` the script would set.
+fn backend_command(root: &Path) -> Command {
+    if let Some(bin) = sidecar::find(sidecar::BACKEND) {
+        return Command::new(bin);
+    }
+    #[cfg(windows)]
+    let mut cmd = {
+        let mut cmd = Command::new(get_python_path());
+        cmd.args(["-m", "synthia.main"])
+            .env("PYTHONPATH", root.join("src"));
+        cmd
+    };
+    #[cfg(not(windows))]
+    let mut cmd = Command::new(get_run_script());
+    cmd.current_dir(root);
+    cmd
+}
+
+fn spawn_backend(state: &AppState) -> AppResult<Child> {
     let root = get_synthia_root();
-    let log_path = root.join("synthia.log");
+    let log_path = logging::backend_log_path();
+    if let Some(dir) = log_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
     let log_file = std::fs::File::create(&log_path)
         .map_err(|e| AppError::Io(format!("Failed to create log file: {}", e)))?;
     let stderr_file = log_file
         .try_clone()
         .map_err(|e| AppError::Io(format!("Failed to clone log file: {}", e)))?;
-    let child = Command::new(root.join("run.sh"))
-        .current_dir(&root)
-        .stdout(std::process::Stdio::from(log_file))
-        .stderr(std::process::Stdio::from(stderr_file))
+    let mut cmd = backend_command(&root);
+    platform::runtime_env(&mut cmd, &get_runtime_dir());
+    compute_devices::apply_env(&mut cmd);
+    cmd.stdout(std::process::Stdio::from(log_file))
+        .stderr(std::process::Stdio::from(stderr_file));
+    // Own process group, so stopping can take down python and anything it
+    // forks rather than just the `run.sh` wrapper.
+    platform::isolate(&mut cmd);
+    let mut child = cmd
         .spawn()
         .map_err(|e| AppError::Process(format!("Failed to start: {}", e)))?;
+    tracing::info!(pid = child.id(), "spawned backend");
 
-    *proc = Some(child);
-    Ok("Synthia started".to_string())
+    // A broken venv or missing model makes run.sh exit almost immediately.
+    // Only report success once the process has survived the grace window.
+    let deadline = Instant::now() + START_GRACE;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                tracing::error!(status = %describe_exit(status), "backend exited during startup");
+                record_exit(state, BackendExit::now(Some(status.into()), false));
+                // run.sh may have left python behind before dying.
+                platform::stop_group(child.id() as i32, Stop::Kill);
+                let output = fs::read_to_string(&log_path).unwrap_or_default();
+                return Err(AppError::Process(format!(
+                    "Synthia exited immediately ({}):\n{}",
+                    describe_exit(status),
+                    last_lines(&output, START_FAILURE_TAIL_LINES)
+                )));
+            }
+            Ok(None) if Instant::now() >= deadline => break,
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => {
+                return Err(AppError::Process(format!("Failed to check process: {}", e)));
+            }
+        }
+    }
+
+    if let Ok(mut pgid) = state.backend_pgid.lock() {
+        *pgid = Some(child.id() as i32);
+    }
+    supervisor::write_pidfile(child.id() as i32);
+    if let Ok(mut version) = state.backend_version.lock() {
+        *version = None;
+    }
+    Ok(child)
 }
 
 #[tauri::command]
-pub fn stop_synthia(state: tauri::State<'_, AppState>) -> AppResult<String> {
-    let _ = Command::new("pkill")
-        .args(["-f", "synthia.main"])
-        .output();
+pub async fn start_synthia(app: tauri::AppHandle) -> AppResult<String> {
+    run_blocking(move || {
+        let result = start(&app.state::<AppState>());
+        if result.is_ok() {
+            tray::set_running(&app, true);
+        }
+        result
+    })
+    .await
+}
+
+/// Phase of a stop, published as `backend-stopping` so the UI can show
+/// "Stopping…" while the backend winds down.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StopPhase {
+    /// Sent the `shutdown` command; waiting for the backend to exit.
+    #[cfg_attr(not(unix), allow(dead_code))]
+    Requested,
+    /// Not connected over IPC, or didn't exit in time: SIGTERM, then
+    /// SIGKILL after `backend.shutdown_grace_ms`.
+    Signalling,
+    Stopped,
+}
+
+/// Payload of the `backend-stopping` event.
+#[derive(Serialize, Debug, Clone)]
+pub struct StopProgress {
+    pub phase: StopPhase,
+    pub elapsed_ms: u64,
+    /// `backend.shutdown_timeout_ms`.
+    pub timeout_ms: u64,
+}
 
+/// Ask connected backends to shut down over IPC and wait up to `timeout`
+/// for every backend process to exit. Returns false if the command couldn't
+/// be delivered or the wait ran out.
+#[cfg(unix)]
+fn request_shutdown(timeout: Duration, progress: impl Fn(StopPhase)) -> bool {
+    if let Err(e) = crate::ipc::send_command("shutdown", &serde_json::json!({})) {
+        tracing::info!(error = %e, "shutdown command not delivered; signalling the backend");
+        return false;
+    }
+    progress(StopPhase::Requested);
+    let deadline = Instant::now() + timeout;
+    loop {
+        if count_running(BACKEND_PATTERN) == 0 {
+            tracing::info!("backend shut down on request");
+            return true;
+        }
+        if Instant::now() >= deadline {
+            tracing::warn!(
+                timeout_ms = timeout.as_millis() as u64,
+                "backend didn't exit after the shutdown command; signalling it"
+            );
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+#[cfg(not(unix))]
+fn request_shutdown(_timeout: Duration, _progress: impl Fn(StopPhase)) -> bool {
+    false
+}
+
+/// Terminate the process group of the backend this GUI spawned or adopted,
+/// if any. Leaves backends started outside the GUI alone.
+pub(crate) fn stop_owned_backend(state: &AppState) {
     let mut proc = state.synthia_process.lock().unwrap();
+    let pgid = state.backend_pgid.lock().ok().and_then(|mut p| p.take());
+    if let Some(pgid) = pgid {
+        tracing::info!(pgid, "terminating backend process group");
+        let grace = gui_config::load().backend.shutdown_grace();
+        let outcome = terminate_process_group(pgid, proc.as_mut(), grace);
+        record_exit(state, BackendExit::now(outcome.exit_status.clone(), true));
+        if let Ok(mut last) = state.last_shutdown.lock() {
+            *last = Some(outcome);
+        }
+        supervisor::remove_pidfile();
+    }
     *proc = None;
+}
+
+pub(crate) fn stop(app: &tauri::AppHandle) -> AppResult<String> {
+    let state = app.state::<AppState>();
+    state.backend_stopping.store(true, Ordering::SeqCst);
+    let result = try_stop(app);
+    state.backend_stopping.store(false, Ordering::SeqCst);
+    state.command_stats.record(TrackedCommand::Stop, &result);
+    result
+}
 
+/// Ask the backend to shut itself down and give it
+/// `backend.shutdown_timeout_ms` to do so, then signal whatever is left.
+#[tracing::instrument(skip_all)]
+fn try_stop(app: &tauri::AppHandle) -> AppResult<String> {
+    tracing::info!("stopping backend");
+    let state = app.state::<AppState>();
+    if dry_run::enabled() {
+        dry_run::skip_signal("stop backend", BACKEND_PATTERN);
+        state.simulated.set_backend(false);
+        return Ok("Synthia stopped (dry run)".to_string());
+    }
+    supervisor::cancel_restart(&state);
+
+    let started = Instant::now();
+    let timeout = gui_config::load().backend.shutdown_timeout();
+    let progress = |phase| {
+        tracing::debug!(?phase, "backend stop progress");
+        let _ = app.emit(
+            "backend-stopping",
+            StopProgress {
+                phase,
+                elapsed_ms: started.elapsed().as_millis() as u64,
+                timeout_ms: timeout.as_millis() as u64,
+            },
+        );
+    };
+    if count_running(BACKEND_PATTERN) > 0
+        && (timeout.is_zero() || !request_shutdown(timeout, progress))
+    {
+        progress(StopPhase::Signalling);
+    }
+    // Reaps a backend that already exited; signals one that didn't.
+    stop_owned_backend(&state);
+    // Also ask backends started outside the GUI (e.g. from a terminal) to
+    // exit. They get SIGTERM by exact pid, never SIGKILL.
+    let foreign = find_processes(BACKEND_PATTERN);
+    if !foreign.is_empty() {
+        terminate_foreign(&foreign, gui_config::load().backend.shutdown_grace());
+    }
+    mark_starting(false);
+
+    if let Ok(mut version) = state.backend_version.lock() {
+        *version = None;
+    }
+
+    progress(StopPhase::Stopped);
     Ok("Synthia stopped".to_string())
 }
 
 #[tauri::command]
-pub fn set_mode(mode: &str) -> AppResult<String> {
-    Ok(format!("Mode set to: {}", mode))
+pub async fn stop_synthia(app: tauri::AppHandle) -> AppResult<String> {
+    run_blocking(move || {
+        let result = stop(&app);
+        if result.is_ok() {
+            tray::set_running(&app, false);
+        }
+        result
+    })
+    .await
+}
+
+/// Stop every backend instance, make sure they have all exited, then start
+/// one. Backends the GUI didn't spawn are never SIGKILLed, so one that
+/// ignores SIGTERM fails the restart instead.
+#[tracing::instrument(skip_all)]
+pub(crate) fn restart_fresh(app: &tauri::AppHandle) -> AppResult<String> {
+    stop(app)?;
+    let state = app.state::<AppState>();
+    if dry_run::enabled() {
+        return start(&state);
+    }
+    let survivors = find_processes(BACKEND_PATTERN);
+    if !survivors.is_empty() {
+        return Err(AppError::Process(format!(
+            "Backend processes started outside Synthia ignored SIGTERM (pids {}); stop them and try again",
+            survivors.iter().map(i32::to_string).collect::<Vec<_>>().join(", ")
+        )));
+    }
+    start(&state)
+}
+
+#[tauri::command]
+pub async fn restart_synthia_fresh(app: tauri::AppHandle) -> AppResult<String> {
+    run_blocking(move || restart_fresh(&app)).await
+}
+
+/// File the backend polls for; it re-reads `config.yaml` and deletes the
+/// file when it appears.
+pub(crate) fn reload_signal_file() -> PathBuf {
+    get_runtime_dir().join("synthia-reload-config")
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ReloadResult {
+    /// The backend consumed the reload signal.
+    pub delivered: bool,
+    /// The signal went unanswered, so the backend was restarted instead.
+    pub restarted: bool,
+}
+
+/// Ask a running backend to re-read its config, restarting it if it doesn't
+/// acknowledge in time (a backend too old to watch for the signal file, or
+/// one that is wedged).
+#[tracing::instrument(skip_all)]
+pub(crate) fn reload(app: &tauri::AppHandle) -> AppResult<ReloadResult> {
+    if count_running(BACKEND_PATTERN) == 0 {
+        tracing::info!("backend not running; nothing to reload");
+        return Ok(ReloadResult {
+            delivered: false,
+            restarted: false,
+        });
+    }
+
+    let signal_file = reload_signal_file();
+    fs::write(&signal_file, "reload")?;
+    let deadline = Instant::now() + RELOAD_ACK_TIMEOUT;
+    while Instant::now() < deadline {
+        if !signal_file.exists() {
+            tracing::info!("backend reloaded its config");
+            return Ok(ReloadResult {
+                delivered: true,
+                restarted: false,
+            });
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    // Don't leave the signal for the next backend to trip over on start.
+    let _ = fs::remove_file(&signal_file);
+    tracing::warn!("backend ignored the reload signal; restarting it");
+    restart_fresh(app)?;
+    Ok(ReloadResult {
+        delivered: false,
+        restarted: true,
+    })
+}
+
+#[tauri::command]
+pub async fn reload_backend_config(app: tauri::AppHandle) -> AppResult<ReloadResult> {
+    run_blocking(move || reload(&app)).await
+}
+
+/// Send a command to the running backend over the IPC socket. Returns how
+/// many connected backends received it.
+#[tauri::command]
+pub async fn send_backend_command(
+    command: String,
+    args: Option<serde_json::Value>,
+) -> AppResult<usize> {
+    let args = args.unwrap_or_else(|| serde_json::json!({}));
+    #[cfg(unix)]
+    return run_blocking(move || crate::ipc::send_command(&command, &args)).await;
+    #[cfg(not(unix))]
+    {
+        let _ = (command, args);
+        Err(AppError::Other("The IPC socket is only available on Unix".into()))
+    }
+}
+
+fn set_paused(paused: bool) -> AppResult<()> {
+    tracing::info!(paused, "setting paused flag");
+    update_synthia_state(|s| s.paused = paused)?;
+    Ok(())
+}
+
+/// Silence the assistant without stopping the backend, so resuming skips
+/// the model load a full restart would pay.
+#[tauri::command]
+pub async fn pause_synthia() -> AppResult<String> {
+    run_blocking(|| {
+        set_paused(true)?;
+        Ok("Synthia paused".to_string())
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn resume_synthia() -> AppResult<String> {
+    run_blocking(|| {
+        set_paused(false)?;
+        Ok("Synthia resumed".to_string())
+    })
+    .await
+}
+
+/// Hard privacy mute. Muting also ends any recording in progress, dropping
+/// its audio, and the flag survives record toggles until it is explicitly
+/// cleared.
+#[tauri::command]
+pub async fn set_muted(app: tauri::AppHandle, muted: bool) -> AppResult<String> {
+    run_blocking(move || mute(&app, muted)).await
+}
+
+fn mute(app: &tauri::AppHandle, muted: bool) -> AppResult<String> {
+    tracing::info!(muted, "setting muted flag");
+    let mut cut_short = false;
+    let state = update_synthia_state(|s| {
+        s.muted = muted;
+        if muted && s.recording {
+            cut_short = true;
+            s.recording = false;
+            s.recording_started_at = None;
+            s.status = OverlayState::Idle.as_status().to_string();
+        }
+    })?;
+    // The backend also checks the flag on release, so the audio is dropped
+    // even if this doesn't reach it.
+    if cut_short {
+        if let Err(e) = send_recording(false, true) {
+            tracing::warn!(error = %e, "could not stop the muted recording");
+        }
+    }
+    // Reflect the mute immediately rather than on the watcher's next event.
+    tray::apply_state(app, &state);
+    Ok(if muted { "Microphone muted" } else { "Microphone unmuted" }.to_string())
+}
+
+#[tauri::command]
+pub fn get_muted() -> bool {
+    read_synthia_state().muted
+}
+
+/// The whole backend state, migrated to the current schema. Keys this GUI
+/// doesn't know are included as written, beside the typed fields.
+#[tauri::command]
+pub fn get_full_state() -> SynthiaState {
+    read_synthia_state()
+}
+
+/// Current power mode; changes arrive as `power-mode` events.
+#[tauri::command]
+pub fn get_power_mode(app: tauri::AppHandle) -> PowerMode {
+    power::mode(&app.state::<AppState>())
+}
+
+/// Start or stop recording from the GUI. Refused while muted or paused.
+#[tauri::command]
+pub async fn toggle_recording(app: tauri::AppHandle) -> AppResult<String> {
+    run_blocking(move || toggle(&app.state::<AppState>())).await
+}
+
+pub(crate) fn toggle(state: &AppState) -> AppResult<String> {
+    let result = try_set_recording(None);
+    state.command_stats.record(TrackedCommand::ToggleRecording, &result);
+    result
+}
+
+/// Start or stop recording, doing nothing if it already is. Refused like
+/// `toggle` otherwise.
+pub(crate) fn set_recording_to(state: &AppState, recording: bool) -> AppResult<String> {
+    let result = try_set_recording(Some(recording));
+    state.command_stats.record(TrackedCommand::ToggleRecording, &result);
+    result
+}
+
+/// Ask the backend to start or stop recording. With `discard`, a stopped
+/// recording's audio is dropped instead of transcribed. The state file
+/// follows once the backend has acted on it.
+#[cfg(unix)]
+pub(crate) fn send_recording(start: bool, discard: bool) -> AppResult<()> {
+    let action = if start { "start" } else { "stop" };
+    crate::ipc::send_command(
+        "recording",
+        &serde_json::json!({ "action": action, "discard": discard }),
+    )?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn send_recording(_start: bool, _discard: bool) -> AppResult<()> {
+    Err(AppError::Other("Recording control needs the IPC socket".to_string()))
+}
+
+/// `None` toggles.
+fn try_set_recording(want: Option<bool>) -> AppResult<String> {
+    let current = read_synthia_state();
+    if want == Some(current.recording) {
+        let message = if current.recording { "Already recording" } else { "Not recording" };
+        return Ok(message.to_string());
+    }
+    if current.muted {
+        return Err(AppError::Validation(
+            "Microphone is muted; unmute to record".to_string(),
+        ));
+    }
+    if current.paused && !current.recording {
+        return Err(AppError::Validation(
+            "Synthia is paused; resume to record".to_string(),
+        ));
+    }
+    let start = !current.recording;
+    send_recording(start, false)?;
+    if start {
+        super::audio::warn_if_unavailable();
+    }
+    Ok(if start { "Recording started" } else { "Recording stopped" }.to_string())
+}
+
+/// Recover from a stuck UI: overwrite the state file with a clean "ready"
+/// state (clearing error and paused flags) and force the tray and overlay
+/// back to normal. The chosen audio input and mode are settings rather than
+/// state, so they are kept, as is the privacy mute: a reset must never turn
+/// the microphone back on.
+#[tauri::command]
+pub async fn reset_state(app: tauri::AppHandle) -> AppResult<String> {
+    run_blocking(move || reset(&app)).await
+}
+
+fn reset(app: &tauri::AppHandle) -> AppResult<String> {
+    tracing::info!("resetting state file");
+    let previous = read_synthia_state();
+    let clean = SynthiaState {
+        status: OverlayState::Idle.as_status().to_string(),
+        audio_input: previous.audio_input,
+        mode: previous.mode,
+        muted: previous.muted,
+        ..Default::default()
+    };
+    write_synthia_state(&clean)?;
+
+    tray::apply_state(app, &clean);
+    tray::refresh(app);
+    super::overlay::emit_overlay_state(app, &clean);
+
+    Ok("State reset".to_string())
+}
+
+/// The last `EXIT_HISTORY_LEN` backend exits, oldest first.
+#[tauri::command]
+pub fn get_crash_history(app: tauri::AppHandle) -> Vec<BackendExit> {
+    let state = app.state::<AppState>();
+    let history = state.exit_history.lock();
+    history.map(|h| h.iter().cloned().collect()).unwrap_or_default()
+}
+
+#[tauri::command]
+pub async fn set_mode(app: tauri::AppHandle, mode: String) -> AppResult<String> {
+    run_blocking(move || change_mode(&app, &mode)).await
+}
+
+pub(crate) fn change_mode(app: &tauri::AppHandle, mode: &str) -> AppResult<String> {
+    let mode: Mode = mode.parse()?;
+    apply_mode(app, mode)?;
+    Ok(format!("Mode set to: {}", mode.as_str()))
+}
+
+/// Switch the recording mode in the state file and remember it in
+/// `gui.toml` so the next launch starts in the same mode.
+pub(crate) fn apply_mode(app: &tauri::AppHandle, mode: Mode) -> AppResult<()> {
+    tracing::info!(?mode, "setting mode");
+    let state = update_synthia_state(|s| s.mode = mode)?;
+    gui_config::update(|cfg| cfg.startup.mode = Some(mode))?;
+    // The watcher would get there too; syncing now makes the shortcut live
+    // before this command returns.
+    push_to_talk::sync(app, &state);
+    Ok(())
 }
 
 #[tauri::command]
@@ -92,3 +771,16 @@ pub fn set_voice_muted(muted: bool) -> AppResult<()> {
     fs::write(&path, serialized)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_lines_keeps_tail_and_skips_blanks() {
+        let log = "one\ntwo\n\nthree\nfour\n";
+        assert_eq!(last_lines(log, 2), "three\nfour");
+        assert_eq!(last_lines(log, 10), "one\ntwo\nthree\nfour");
+        assert_eq!(last_lines("", 3), "");
+    }
+}
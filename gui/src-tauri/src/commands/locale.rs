@@ -0,0 +1,46 @@
+//! Locale Tauri commands: which language the tray and notifications use.
+
+use serde::Serialize;
+
+use crate::error::{AppError, AppResult};
+use crate::{gui_config, i18n, tray};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct LocaleInfo {
+    /// Locale the strings are currently shown in.
+    pub active: &'static str,
+    /// The `gui.toml` override; `None` follows the system locale.
+    pub configured: Option<String>,
+    pub available: Vec<&'static str>,
+}
+
+#[tauri::command]
+pub fn get_locale() -> LocaleInfo {
+    LocaleInfo {
+        active: i18n::current(),
+        configured: gui_config::load().i18n.locale,
+        available: i18n::available(),
+    }
+}
+
+/// Override the locale (`None` to follow the system again), then relabel
+/// the tray menu and tooltip in place.
+#[tauri::command]
+pub fn set_locale(app: tauri::AppHandle, locale: Option<String>) -> AppResult<LocaleInfo> {
+    apply(&app, locale)?;
+    Ok(get_locale())
+}
+
+/// Validate and save the locale override, then relabel the tray.
+pub(crate) fn apply(app: &tauri::AppHandle, locale: Option<String>) -> AppResult<()> {
+    let locale = locale.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+    if let Some(l) = &locale {
+        if !l.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '@')) {
+            return Err(AppError::Validation(format!("not a locale name: {l:?}")));
+        }
+    }
+    gui_config::update(|cfg| cfg.i18n.locale = locale)?;
+    i18n::init();
+    tray::relocalize(app);
+    Ok(())
+}
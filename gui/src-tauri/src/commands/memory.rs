@@ -140,7 +140,7 @@ pub fn get_memory_stats() -> MemoryStats {
 
     // Sort tags by count
     let mut tags: Vec<_> = tag_counts.into_iter().collect();
-    tags.sort_by(|a, b| b.1.cmp(&a.1));
+    tags.sort_by_key(|t| std::cmp::Reverse(t.1));
     tags.truncate(15);
 
     MemoryStats {
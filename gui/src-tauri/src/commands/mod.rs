@@ -1,20 +1,46 @@
 //! Tauri IPC command handlers grouped by domain.
 
 pub mod agents;
+pub mod audio;
+pub mod backend_settings;
 pub mod claude_config;
 pub mod clipboard;
+pub mod devices;
+pub mod diagnostics;
 pub mod github;
+pub mod health;
 pub mod history;
 pub mod hotkeys;
+pub mod http_api;
 pub mod inbox;
 pub mod journal;
 pub mod lifecycle;
+pub mod locale;
 pub mod memory;
+pub mod models;
 pub mod neuralguard;
 pub mod news;
 pub mod notes;
 pub mod overlay;
 pub mod remote;
+pub mod settings;
+pub mod setup;
+pub mod typing;
 pub mod usage;
+pub mod version;
 pub mod weather;
 pub mod worktrees;
+
+use crate::error::{AppError, AppResult};
+
+/// Run blocking work (process spawns and scans, sleeps) on Tauri's blocking
+/// pool so async command handlers don't stall the IPC runtime.
+pub(crate) async fn run_blocking<T, F>(f: F) -> AppResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> AppResult<T> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(f)
+        .await
+        .map_err(|e| AppError::Other(format!("Background task failed: {}", e)))?
+}
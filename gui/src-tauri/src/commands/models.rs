@@ -0,0 +1,109 @@
+//! Whisper model manager Tauri commands: list, download, delete and pick
+//! the model the backend transcribes with. See `whisper_models`.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+#[cfg(unix)]
+use serde_json::json;
+
+use super::backend_settings::read_config_yaml;
+#[cfg(not(unix))]
+use super::lifecycle::reload_signal_file;
+use crate::error::{AppError, AppResult};
+use crate::get_config_path;
+use crate::paths::write_atomic_private;
+use crate::whisper_models;
+use crate::yaml_writer::upsert_synthia_config_key;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ModelInfo {
+    pub name: String,
+    pub installed: bool,
+    pub downloading: bool,
+    /// The backend's `local_stt_model`.
+    pub active: bool,
+    /// Size on disk once installed, else the approximate download size.
+    pub size_bytes: u64,
+    pub path: PathBuf,
+}
+
+fn active_model() -> AppResult<String> {
+    let model = read_config_yaml()?.local_stt_model;
+    Ok(if model.is_empty() { "small".to_string() } else { model })
+}
+
+#[tauri::command]
+pub fn list_models() -> AppResult<Vec<ModelInfo>> {
+    let active = active_model()?;
+    Ok(whisper_models::CATALOG
+        .iter()
+        .map(|spec| {
+            let path = whisper_models::model_dir(spec.name);
+            let installed = whisper_models::is_installed(spec.name);
+            ModelInfo {
+                name: spec.name.to_string(),
+                installed,
+                downloading: whisper_models::is_downloading(spec.name),
+                active: spec.name == active,
+                size_bytes: if installed {
+                    whisper_models::dir_size(&path)
+                } else {
+                    spec.approx_bytes
+                },
+                path,
+            }
+        })
+        .collect())
+}
+
+/// Download model `name`, emitting `model-download-progress` as it goes,
+/// and return where it was installed.
+#[tauri::command]
+pub async fn download_model(app: tauri::AppHandle, name: String) -> AppResult<PathBuf> {
+    whisper_models::download(&app, &name).await
+}
+
+/// Delete model `name`. The active model can't be deleted: the backend
+/// would fetch it again by itself on its next start.
+#[tauri::command]
+pub fn delete_model(name: String) -> AppResult<bool> {
+    if name == active_model()? {
+        return Err(AppError::Validation(format!(
+            "{name} is the active model; switch to another before deleting it"
+        )));
+    }
+    whisper_models::delete(&name)
+}
+
+/// Make installed model `name` the backend's model: saved as
+/// `local_stt_model` and sent to a running backend, which reloads it.
+/// Returns whether a running backend was told; if not, the model is used
+/// from its next start. Without the IPC socket (non-Unix) a running backend
+/// is left to pick the model up from the reload signal instead.
+#[tauri::command]
+pub async fn set_active_model(name: String) -> AppResult<bool> {
+    whisper_models::spec(&name)?;
+    if !whisper_models::is_installed(&name) {
+        return Err(AppError::NotFound(format!("{name} is not downloaded")));
+    }
+
+    let config_path = get_config_path();
+    let content = std::fs::read_to_string(&config_path).unwrap_or_default();
+    let content = upsert_synthia_config_key(&content, "local_stt_model", &format!("\"{name}\""));
+    write_atomic_private(&config_path, content)?;
+    tracing::info!(model = %name, "active model set");
+
+    #[cfg(unix)]
+    {
+        let path = whisper_models::model_dir(&name);
+        let args = json!({ "name": name, "path": path });
+        super::run_blocking(move || Ok(crate::ipc::send_command("set_model", &args).is_ok()))
+            .await
+    }
+    #[cfg(not(unix))]
+    {
+        std::fs::write(reload_signal_file(), "reload").ok();
+        Ok(false)
+    }
+}
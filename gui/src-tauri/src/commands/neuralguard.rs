@@ -6,7 +6,7 @@ use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
 use crate::error::{AppError, AppResult};
-use crate::{egress, get_settings_file, get_synthia_root, security};
+use crate::{egress, get_python_path, get_settings_file, get_synthia_root, security};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PendingPrompt {
@@ -20,13 +20,11 @@ pub struct PendingPrompt {
 }
 
 fn pending_prompts_dir() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(home).join(".config/synthia/security/pending-prompts")
+    crate::platform::home_dir().join(".config/synthia/security/pending-prompts")
 }
 
 fn security_dir() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(home).join(".config/synthia/security")
+    crate::platform::home_dir().join(".config/synthia/security")
 }
 
 /// Validate a hostname for the user allowlist. Rejects empty, slashes,
@@ -58,13 +56,11 @@ pub fn add_to_allowlist(host: String) -> AppResult<()> {
 }
 
 fn prompt_responses_dir() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(home).join(".config/synthia/security/prompt-responses")
+    crate::platform::home_dir().join(".config/synthia/security/prompt-responses")
 }
 
 fn synthia_python_path() -> PathBuf {
-    let root = get_synthia_root();
-    root.join("venv/bin/python")
+    get_python_path()
 }
 
 fn security_gate_path() -> PathBuf {
@@ -12,8 +12,7 @@ pub(crate) fn get_notes_base_path() -> PathBuf {
     if let Ok(path) = std::env::var("SYNTHIA_NOTES_PATH") {
         return PathBuf::from(path);
     }
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(home).join("dev/eventflo/docs")
+    crate::platform::home_dir().join("dev/eventflo/docs")
 }
 
 fn get_pinned_note_path() -> PathBuf {
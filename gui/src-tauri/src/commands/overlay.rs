@@ -1,13 +1,428 @@
 //! Overlay window Tauri commands.
 
-use tauri::{Emitter, Manager};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{Emitter, Manager, Monitor, PhysicalPosition, WebviewWindow};
 
 use crate::error::{AppError, AppResult};
+use crate::gui_config::{
+    self, OverlayAnchor, OverlayBackend, OverlayCorner, OverlayOffset, OverlayTranscriptMode,
+    OverlayVisibility,
+};
+use crate::overlay_layer_shell::DisplaySession;
+use crate::state::AppState;
+use crate::synthia_state::{self, OverlayPayload, OverlayState, SynthiaState};
+use crate::{overlay_layer_shell, overlay_transcript, threads, window_geometry};
+
+/// Layer-shell overlays are placed by the compositor and report no real
+/// position, so there is nothing to save for them.
+fn save_overlay_position(window: &WebviewWindow) -> AppResult<()> {
+    if overlay_layer_shell::is_active(window.app_handle()) {
+        return Ok(());
+    }
+    let position = window_geometry::current_position(window)?;
+    gui_config::update(|cfg| cfg.overlay.position = Some(position))
+}
+
+/// How often the monitor layout is checked for hot-plug and resolution
+/// changes while the overlay is anchored.
+const MONITOR_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// One connected display, in `available_monitors` order.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    pub index: usize,
+    pub name: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    pub x: i32,
+    pub y: i32,
+    pub scale_factor: f64,
+    pub primary: bool,
+}
+
+fn monitor_info(monitors: &[Monitor], primary: Option<&Monitor>) -> Vec<MonitorInfo> {
+    monitors
+        .iter()
+        .enumerate()
+        .map(|(index, m)| MonitorInfo {
+            index,
+            name: m.name().cloned(),
+            width: m.size().width,
+            height: m.size().height,
+            x: m.position().x,
+            y: m.position().y,
+            scale_factor: m.scale_factor(),
+            primary: primary.is_some_and(|p| p.name() == m.name()),
+        })
+        .collect()
+}
+
+/// Pin the overlay to `anchor`'s spot on its monitor's work area, or on
+/// the primary monitor if that one isn't connected.
+fn anchor_overlay(window: &WebviewWindow, anchor: &OverlayAnchor) -> AppResult<()> {
+    let err = |e: tauri::Error| AppError::Other(e.to_string());
+    let monitors = window.available_monitors().map_err(err)?;
+    let target = match monitors.iter().find(|m| m.name() == Some(&anchor.monitor)) {
+        Some(m) => Some(m.clone()),
+        None => window.primary_monitor().map_err(err)?,
+    };
+    let Some(target) = target else {
+        return Ok(());
+    };
+    let area = target.work_area();
+    let size = window.outer_size().map_err(err)?;
+    let (x, y) = window_geometry::anchor_in_area(
+        anchor.corner,
+        anchor.offset,
+        (size.width, size.height),
+        (area.position.x, area.position.y),
+        (area.size.width, area.size.height),
+    );
+    window.set_position(PhysicalPosition::new(x, y)).map_err(err)
+}
+
+/// Move the overlay to its anchor if it has one, else back to its saved
+/// position. If the monitor it was saved on is no longer connected, the
+/// position is clamped onto the primary display. A layer-shell overlay is
+/// re-pinned by the compositor instead.
+fn restore_overlay_position(window: &WebviewWindow) -> AppResult<()> {
+    let config = gui_config::load().overlay;
+    if overlay_layer_shell::is_active(window.app_handle()) {
+        return overlay_layer_shell::place(window, config.anchor.as_ref());
+    }
+    if let Some(anchor) = config.anchor {
+        return anchor_overlay(window, &anchor);
+    }
+    match config.position {
+        Some(saved) => window_geometry::restore(window, &saved, None),
+        None => Ok(()),
+    }
+}
+
+/// Called on every overlay `Moved` event; persists the position once the
+/// window has been still for `window_geometry::SAVE_DEBOUNCE`. Anchored
+/// overlays are placed by their anchor, so their moves aren't saved.
+pub(crate) fn on_overlay_moved(app: &tauri::AppHandle) {
+    if gui_config::load().overlay.anchor.is_some() {
+        return;
+    }
+    window_geometry::debounce(app, |s| &s.overlay_move_seq, |app| {
+        if let Some(window) = app.get_webview_window("overlay") {
+            let _ = save_overlay_position(&window);
+        }
+    });
+}
+
+/// Whether the overlay should let clicks through in `state`, given the
+/// user's `overlay.click_through` setting.
+fn click_through_for(setting: Option<bool>, state: OverlayState) -> bool {
+    setting.unwrap_or(state != OverlayState::Error)
+}
+
+/// Set the overlay's ignore-cursor-events flag for `state`. Works on the
+/// live window; nothing is recreated.
+pub(crate) fn apply_click_through(app: &tauri::AppHandle, state: OverlayState) {
+    let Some(window) = app.get_webview_window("overlay") else { return };
+    let ignore = click_through_for(gui_config::load().overlay.click_through, state);
+    set_click_through(&window, ignore);
+}
+
+/// On Linux, X11 and Wayland alike, ignoring the cursor means giving the
+/// GDK window an empty input shape, and that window only exists once the
+/// overlay has been shown; a hidden overlay is skipped and `show_overlay`
+/// applies the flag after showing it. While clicks pass through, the
+/// overlay also refuses keyboard focus so a window manager that focuses
+/// new windows doesn't hand it the keyboard when it appears.
+fn set_click_through(window: &WebviewWindow, ignore: bool) {
+    #[cfg(target_os = "linux")]
+    if !window.is_visible().unwrap_or(false) {
+        return;
+    }
+    if let Err(e) = window.set_ignore_cursor_events(ignore) {
+        tracing::warn!(error = %e, "failed to set overlay click-through");
+    }
+    if let Err(e) = window.set_focusable(!ignore) {
+        tracing::warn!(error = %e, "failed to set overlay focusability");
+    }
+}
+
+/// State last pushed to the overlay.
+fn current_state(app: &tauri::AppHandle) -> OverlayState {
+    app.state::<AppState>()
+        .last_overlay_payload
+        .lock()
+        .ok()
+        .and_then(|p| p.as_ref().map(|p| p.state))
+        .unwrap_or(OverlayState::Idle)
+}
+
+/// What `overlay.visibility` asks of the window on entering `state`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VisibilityChange {
+    Show,
+    Hide,
+    HideAfter(Duration),
+    Leave,
+}
+
+fn visibility_change(
+    visibility: OverlayVisibility,
+    auto_hide: Duration,
+    state: OverlayState,
+) -> VisibilityChange {
+    match visibility {
+        OverlayVisibility::Manual => VisibilityChange::Leave,
+        OverlayVisibility::Always => VisibilityChange::Show,
+        OverlayVisibility::Never => VisibilityChange::Hide,
+        OverlayVisibility::Auto => match state {
+            OverlayState::Listening
+            | OverlayState::Thinking
+            | OverlayState::Speaking
+            | OverlayState::Error => VisibilityChange::Show,
+            OverlayState::Idle | OverlayState::Starting => VisibilityChange::HideAfter(auto_hide),
+        },
+    }
+}
+
+/// Show or hide the overlay for `state` per `overlay.visibility`. Any
+/// change supersedes a hide still waiting out its delay.
+pub(crate) fn apply_visibility(app: &tauri::AppHandle, state: OverlayState) {
+    let config = gui_config::load().overlay;
+    let auto_hide = Duration::from_secs(config.auto_hide_secs);
+    let change = visibility_change(config.visibility, auto_hide, state);
+    if change == VisibilityChange::Leave {
+        return;
+    }
+    let seq = &app.state::<AppState>().overlay_visibility_seq;
+    let ticket = seq.fetch_add(1, Ordering::SeqCst) + 1;
+    let result = match change {
+        VisibilityChange::Show => show_overlay(app.clone()),
+        VisibilityChange::Hide => hide_overlay(app.clone()),
+        VisibilityChange::HideAfter(delay) => {
+            let app = app.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(delay);
+                if app.state::<AppState>().overlay_visibility_seq.load(Ordering::SeqCst) == ticket {
+                    if let Err(e) = hide_overlay(app.clone()) {
+                        tracing::warn!(error = %e, "failed to auto-hide overlay");
+                    }
+                }
+            });
+            Ok(())
+        }
+        VisibilityChange::Leave => Ok(()),
+    };
+    if let Err(e) = result {
+        tracing::warn!(error = %e, ?change, "failed to change overlay visibility");
+    }
+}
+
+/// Re-apply the overlay's `gui.toml` settings to the live window: its
+/// click-through flag, visibility and, when anchored, its position.
+pub(crate) fn reapply_config(app: &tauri::AppHandle) {
+    let state = current_state(app);
+    apply_click_through(app, state);
+    apply_visibility(app, state);
+    if let Some(window) = app.get_webview_window("overlay") {
+        if let Err(e) = restore_overlay_position(&window) {
+            tracing::warn!(error = %e, "failed to re-place overlay");
+        }
+    }
+}
+
+/// Force click-through on or off, or pass `None` to go back to following
+/// the overlay's state. Persisted in `gui.toml`.
+#[tauri::command]
+pub fn set_overlay_click_through(
+    app: tauri::AppHandle,
+    click_through: Option<bool>,
+) -> AppResult<()> {
+    gui_config::update(|cfg| cfg.overlay.click_through = click_through)?;
+    let state = current_state(&app);
+    apply_click_through(&app, state);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_overlay_click_through() -> Option<bool> {
+    gui_config::load().overlay.click_through
+}
+
+/// Flip whether clicks currently pass through the overlay and pin that
+/// choice in `gui.toml`. Returns the new value.
+#[tauri::command]
+pub fn toggle_overlay_click_through(app: tauri::AppHandle) -> AppResult<bool> {
+    let state = current_state(&app);
+    let click_through = !click_through_for(gui_config::load().overlay.click_through, state);
+    gui_config::update(|cfg| cfg.overlay.click_through = Some(click_through))?;
+    apply_click_through(&app, state);
+    Ok(click_through)
+}
+
+/// Payload of `get_overlay_visibility`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct OverlayVisibilitySettings {
+    pub visibility: OverlayVisibility,
+    pub auto_hide_secs: u64,
+}
+
+/// Choose when the overlay shows itself, optionally with a new auto-hide
+/// delay. Persisted in `gui.toml` and applied to the current state at once.
+#[tauri::command]
+pub fn set_overlay_visibility(
+    app: tauri::AppHandle,
+    visibility: OverlayVisibility,
+    auto_hide_secs: Option<u64>,
+) -> AppResult<()> {
+    gui_config::update(|cfg| {
+        cfg.overlay.visibility = visibility;
+        if let Some(secs) = auto_hide_secs {
+            cfg.overlay.auto_hide_secs = secs;
+        }
+    })?;
+    apply_visibility(&app, current_state(&app));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_overlay_visibility() -> OverlayVisibilitySettings {
+    let overlay = gui_config::load().overlay;
+    OverlayVisibilitySettings {
+        visibility: overlay.visibility,
+        auto_hide_secs: overlay.auto_hide_secs,
+    }
+}
+
+/// Turn the overlay's live transcription text on or off. Persisted in
+/// `gui.toml`; turning it off clears any text showing.
+#[tauri::command]
+pub fn set_overlay_transcript_mode(
+    app: tauri::AppHandle,
+    mode: OverlayTranscriptMode,
+) -> AppResult<()> {
+    gui_config::update(|cfg| cfg.overlay.transcript = mode)?;
+    if mode == OverlayTranscriptMode::Off {
+        overlay_transcript::clear(&app);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_overlay_transcript_mode() -> OverlayTranscriptMode {
+    gui_config::load().overlay.transcript
+}
+
+/// Re-anchor the overlay whenever monitors are added, removed, moved or
+/// change resolution or scale.
+pub(crate) fn spawn_monitor_watch(app: tauri::AppHandle) {
+    threads::spawn_supervised("monitor watch", move || {
+        let mut last = None;
+        loop {
+            std::thread::sleep(MONITOR_POLL_INTERVAL);
+            if gui_config::load().overlay.anchor.is_none() {
+                last = None;
+                continue;
+            }
+            let Ok(monitors) = app.available_monitors() else { continue };
+            let layout = Some(monitor_info(&monitors, None));
+            if layout == last {
+                continue;
+            }
+            // The first pass only records the layout; the overlay was
+            // already placed when it was shown.
+            if last.is_some() {
+                tracing::info!("monitor layout changed; re-anchoring overlay");
+                if let Some(window) = app.get_webview_window("overlay") {
+                    if let Err(e) = restore_overlay_position(&window) {
+                        tracing::warn!(error = %e, "failed to re-anchor overlay");
+                    }
+                }
+            }
+            last = layout;
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_monitors(app: tauri::AppHandle) -> AppResult<Vec<MonitorInfo>> {
+    let err = |e: tauri::Error| AppError::Other(e.to_string());
+    let monitors = app.available_monitors().map_err(err)?;
+    let primary = app.primary_monitor().map_err(err)?;
+    Ok(monitor_info(&monitors, primary.as_ref()))
+}
+
+/// Save `anchor` (or free placement for `None`) and move the overlay to it.
+pub(crate) fn apply_anchor(app: &tauri::AppHandle, anchor: Option<OverlayAnchor>) -> AppResult<()> {
+    gui_config::update(|cfg| cfg.overlay.anchor = anchor.clone())?;
+    tracing::info!(anchor = ?anchor, "overlay anchor changed");
+    if let Some(window) = app.get_webview_window("overlay") {
+        restore_overlay_position(&window)?;
+    }
+    Ok(())
+}
+
+/// Anchor the overlay to `anchor` (default top right) of the monitor at
+/// `monitor` in `get_monitors`, nudged by `offset`, or pass no monitor to go
+/// back to free placement. Persisted in `gui.toml` by monitor name, so
+/// reordering doesn't move it, and re-applied each time the overlay shows.
+#[tauri::command]
+pub fn set_overlay_position(
+    app: tauri::AppHandle,
+    monitor: Option<usize>,
+    anchor: Option<OverlayCorner>,
+    offset: Option<OverlayOffset>,
+) -> AppResult<()> {
+    let anchor = match monitor {
+        None => None,
+        Some(index) => {
+            let monitors = app
+                .available_monitors()
+                .map_err(|e| AppError::Other(e.to_string()))?;
+            let monitor = monitors
+                .get(index)
+                .ok_or_else(|| AppError::Validation(format!("no monitor at index {index}")))?;
+            let name = monitor.name().cloned().ok_or_else(|| {
+                AppError::Validation(format!("monitor {index} has no name to remember it by"))
+            })?;
+            Some(OverlayAnchor {
+                monitor: name,
+                corner: anchor.unwrap_or_default(),
+                offset: offset.unwrap_or_default(),
+            })
+        }
+    };
+    apply_anchor(&app, anchor)
+}
+
+/// Payload of `get_overlay_backend`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct OverlayBackendInfo {
+    pub session: DisplaySession,
+    pub configured: OverlayBackend,
+    /// Whether the overlay is a layer surface right now. A changed
+    /// `overlay.backend` only takes effect on the next start.
+    pub layer_shell: bool,
+}
+
+#[tauri::command]
+pub fn get_overlay_backend(app: tauri::AppHandle) -> OverlayBackendInfo {
+    OverlayBackendInfo {
+        session: DisplaySession::detect(),
+        configured: gui_config::load().overlay.backend,
+        layer_shell: overlay_layer_shell::is_active(&app),
+    }
+}
 
 #[tauri::command]
 pub fn show_overlay(app: tauri::AppHandle) -> AppResult<()> {
     if let Some(window) = app.get_webview_window("overlay") {
+        // A bad saved position shouldn't stop the overlay from appearing.
+        let _ = restore_overlay_position(&window);
         window.show().map_err(|e| AppError::Other(e.to_string()))?;
+        apply_click_through(&app, current_state(&app));
     }
     Ok(())
 }
@@ -15,6 +430,7 @@ pub fn show_overlay(app: tauri::AppHandle) -> AppResult<()> {
 #[tauri::command]
 pub fn hide_overlay(app: tauri::AppHandle) -> AppResult<()> {
     if let Some(window) = app.get_webview_window("overlay") {
+        let _ = save_overlay_position(&window);
         window.hide().map_err(|e| AppError::Other(e.to_string()))?;
     }
     Ok(())
@@ -29,3 +445,68 @@ pub fn set_overlay_recording(app: tauri::AppHandle, active: bool) -> AppResult<(
     }
     Ok(())
 }
+
+/// Push `state` to the overlay as an `overlay-state` event, plus the legacy
+/// `recording` bool for older overlay builds. Skips unchanged payloads since
+/// one backend write can fire several filesystem events.
+pub(crate) fn emit_overlay_state(app: &tauri::AppHandle, state: &SynthiaState) {
+    let payload = OverlayPayload::from(state);
+    {
+        let app_state = app.state::<AppState>();
+        let Ok(mut last) = app_state.last_overlay_payload.lock() else { return };
+        if last.as_ref() == Some(&payload) {
+            return;
+        }
+        *last = Some(payload.clone());
+    }
+    tracing::info!(state = ?payload.state, detail = ?payload.detail, "assistant state changed");
+    apply_click_through(app, payload.state);
+    apply_visibility(app, payload.state);
+    if let Some(window) = app.get_webview_window("overlay") {
+        let _ = window.emit("recording", payload.state == OverlayState::Listening);
+        let _ = window.emit("overlay-state", payload);
+    }
+}
+
+/// Set the assistant's visual state (`idle`, `listening`, `thinking`,
+/// `speaking`, `error`). Written to the state file rather than emitted
+/// directly, so the watcher updates overlay and tray together.
+#[tauri::command]
+pub fn set_overlay_state(state: &str, detail: Option<String>) -> AppResult<()> {
+    let overlay_state: OverlayState = state.parse()?;
+    synthia_state::update_synthia_state(|s| {
+        s.status = overlay_state.as_status().to_string();
+        s.recording = overlay_state == OverlayState::Listening;
+        s.detail = detail;
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn click_through_follows_state_unless_set() {
+        assert!(click_through_for(None, OverlayState::Idle));
+        assert!(click_through_for(None, OverlayState::Listening));
+        assert!(!click_through_for(None, OverlayState::Error));
+        assert!(click_through_for(Some(true), OverlayState::Error));
+        assert!(!click_through_for(Some(false), OverlayState::Idle));
+    }
+
+    #[test]
+    fn auto_visibility_shows_while_active_and_hides_later() {
+        let delay = Duration::from_secs(3);
+        let change = |v, s| visibility_change(v, delay, s);
+        assert_eq!(change(OverlayVisibility::Auto, OverlayState::Listening), VisibilityChange::Show);
+        assert_eq!(change(OverlayVisibility::Auto, OverlayState::Error), VisibilityChange::Show);
+        assert_eq!(
+            change(OverlayVisibility::Auto, OverlayState::Idle),
+            VisibilityChange::HideAfter(delay)
+        );
+        assert_eq!(change(OverlayVisibility::Always, OverlayState::Idle), VisibilityChange::Show);
+        assert_eq!(change(OverlayVisibility::Never, OverlayState::Speaking), VisibilityChange::Hide);
+        assert_eq!(change(OverlayVisibility::Manual, OverlayState::Listening), VisibilityChange::Leave);
+    }
+}
@@ -1,86 +1,951 @@
-//! Telegram remote-mode Tauri commands.
+//! Remote-mode Tauri commands, for whichever `remote_channel` is active.
+//!
+//! Starting and stopping go through `RemoteState`, so only one transition
+//! runs at a time: a start while starting, or a stop while the bot is
+//! already down, is refused instead of racing the one in flight.
+//!
+//! While remote mode is on, a watchdog supervises the bot: it owns the
+//! child, relaunches it when it exits, waiting `remote.restart_backoff_ms`
+//! (doubled per crash in a row), and turns remote mode off after
+//! `remote.max_restarts` crashes. The bot prints `SYNTHIA_REMOTE_STATUS
+//! connected` or `... disconnected` on stdout as it gains or loses its chat
+//! service.
+//! All of it is kept as a `RemoteHealth`, returned by `get_remote_health`
+//! and emitted as `remote-status-changed` whenever it changes.
+//!
+//! The bot's stdin stays piped so it can be told which held messages may
+//! reach Claude Code; see `remote_approval`.
 
 use std::fs;
-use std::process::Command;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdout, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError};
+use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::run_blocking;
+use crate::command_stats::TrackedCommand;
+use crate::config::SynthiaConfigYaml;
 use crate::error::{AppError, AppResult};
-use crate::{get_runtime_dir, get_synthia_root};
+use crate::gui_config::RemoteChannelKind;
+use crate::history_store::{self, NewRemoteActivity};
+use crate::{compute_devices, dry_run, gui_config, notifications, platform, secrets, sidecar};
+use crate::process::{
+    find_processes, is_running, terminate_child, terminate_foreign, ExitInfo, BOT_PATTERN,
+};
+use crate::remote_approval::{self, PendingApproval};
+use crate::remote_channel::{self, BotAccess, RemoteChannel, RemoteNotice};
+use crate::remote_notify::notify_remote;
+use crate::state::AppState;
+use crate::supervisor::CrashRecovery;
+use crate::synthia_state::read_synthia_state;
+use crate::threads;
+use crate::tray;
+use crate::paths::write_atomic_private;
+use crate::yaml_writer::write_synthia_config_list;
+use crate::{get_config_path, get_python_path, get_runtime_dir, get_synthia_root};
 
-#[tauri::command]
-pub fn start_remote_mode() -> AppResult<String> {
-    // Check if already running
-    let check = Command::new("pgrep")
-        .args(["-f", "telegram_bot.py"])
-        .output();
+/// How often the watchdog checks that the bot is still alive.
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long the bot gets to exit after SIGTERM before it is SIGKILLed (or,
+/// for a bot started outside the GUI, left alone).
+const BOT_STOP_GRACE: Duration = Duration::from_secs(3);
+
+/// How often the status monitor checks whether the bot is running.
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Minimum gap between two "reconnected" notifications, so a bot stuck in a
+/// crash loop doesn't spam the phone on every relaunch.
+const RECONNECT_NOTIFY_COOLDOWN: Duration = Duration::from_secs(600);
+
+/// Prefix of the bot's stdout lines reporting its connection; matches
+/// `STATUS_MARKER` in remote/common.py.
+const STATUS_MARKER: &str = "SYNTHIA_REMOTE_STATUS";
+
+/// Prefix of the bot's stdout lines reporting an exchange for the activity
+/// log; matches `ACTIVITY_MARKER` in remote/common.py.
+const ACTIVITY_MARKER: &str = "SYNTHIA_REMOTE_ACTIVITY";
+
+/// Where the bot stands, as far as the watchdog knows.
+#[derive(Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BotStatus {
+    #[default]
+    Stopped,
+    /// Spawned, and hasn't reported reaching its chat service yet.
+    Starting,
+    Connected,
+    /// Running, but it lost its chat service; it keeps retrying.
+    Disconnected,
+    /// Running, but not started by this GUI, so its connection is unknown.
+    External,
+    /// Exited unexpectedly; the relaunch is waiting out its backoff.
+    Restarting,
+    /// Crashed `max_restarts` times in a row, so remote mode was turned off.
+    Failed,
+}
+
+/// Payload of `remote-status-changed` and `get_remote_health`.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct RemoteHealth {
+    pub status: BotStatus,
+    /// Whether any bot process is running, by the process table.
+    pub running: bool,
+    /// Pid of the bot this GUI spawned.
+    pub pid: Option<u32>,
+    /// When `status` last changed.
+    pub since: Option<DateTime<Utc>>,
+    /// Crashes in the current run, from 1; 0 once the bot is stopped.
+    pub restarts: u32,
+    pub max_restarts: u32,
+    /// How the last unexpected exit ended, when it could be collected.
+    pub last_exit: Option<ExitInfo>,
+    /// When the pending relaunch happens; set only while `Restarting`.
+    pub restart_in_ms: Option<u64>,
+}
+
+/// The recorded health plus what only a fresh look can tell.
+fn current_health(state: &AppState) -> RemoteHealth {
+    let health = state.remote_health.lock().unwrap_or_else(PoisonError::into_inner).clone();
+    RemoteHealth {
+        running: bot_running(state),
+        max_restarts: gui_config::load().remote.max_restarts,
+        ..health
+    }
+}
+
+/// Apply `change` to the recorded health and emit `remote-status-changed`
+/// if it changed anything.
+fn update_health(app: &AppHandle, change: impl FnOnce(&mut RemoteHealth)) {
+    let state = app.state::<AppState>();
+    {
+        let mut health = state.remote_health.lock().unwrap_or_else(PoisonError::into_inner);
+        let before = health.clone();
+        change(&mut health);
+        if *health == before {
+            return;
+        }
+        if health.status != before.status {
+            tracing::info!(status = ?health.status, "remote bot status changed");
+            health.since = Some(Utc::now());
+        }
+    }
+    let _ = app.emit("remote-status-changed", current_health(&state));
+}
+
+/// The connection state a line of the bot's stdout reports, if it is a
+/// status marker.
+fn parse_marker(line: &str) -> Option<bool> {
+    match line.trim().split_once(' ')? {
+        (STATUS_MARKER, "connected") => Some(true),
+        (STATUS_MARKER, "disconnected") => Some(false),
+        _ => None,
+    }
+}
+
+/// Everything needed to run a channel's bot, checked before anything spawns.
+struct BotLaunch {
+    /// Name of the channel, as the activity log records it.
+    channel: &'static str,
+    root: PathBuf,
+    /// The bundled bot binary, or the venv's python.
+    program: PathBuf,
+    /// Script for `program` to run; `None` for the bundled binary.
+    script: Option<PathBuf>,
+    /// The token, and whatever else the channel passes in the environment.
+    env: Vec<(&'static str, String)>,
+}
+
+impl BotLaunch {
+    /// Where `channel`'s bot would be launched from, without checking any
+    /// of it exists or reading the token. A `remote.bot_script` override
+    /// wins over the bundled binary.
+    fn locate(channel: &dyn RemoteChannel) -> Self {
+        let root = get_synthia_root();
+        let bot_script = gui_config::load().remote.bot_script;
+        if let (None, Some(program)) = (&bot_script, sidecar::find(channel.sidecar())) {
+            return BotLaunch {
+                channel: channel.name(),
+                root,
+                program,
+                script: None,
+                env: Vec::new(),
+            };
+        }
+        let program = get_python_path();
+        let script = bot_script.unwrap_or_else(|| root.join(channel.script()));
+        BotLaunch { channel: channel.name(), root, program, script: Some(script), env: Vec::new() }
+    }
+
+    fn resolve(channel: &dyn RemoteChannel) -> AppResult<Self> {
+        let launch = Self::locate(channel);
+        let mut paths = vec![("program", &launch.program)];
+        paths.extend(launch.script.as_ref().map(|s| ("script", s)));
+        for (what, path) in paths {
+            if !path.is_file() {
+                return Err(AppError::NotFound(format!(
+                    "{} bot {what} {}",
+                    channel.name(),
+                    path.display()
+                )));
+            }
+        }
+        let mut env = vec![(channel.token_env_var(), channel.token()?)];
+        env.extend(channel.extra_env(&remote_channel::synthia_config())?);
+        Ok(BotLaunch { env, ..launch })
+    }
+
+    fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.args(&self.script).current_dir(&self.root).envs(self.env.iter().cloned());
+        platform::runtime_env(&mut cmd, &get_runtime_dir());
+        cmd
+    }
+}
+
+/// The exchange a line of the bot's stdout reports, if it is an activity
+/// marker.
+fn parse_activity(line: &str) -> Option<NewRemoteActivity> {
+    let payload = line.strip_prefix(ACTIVITY_MARKER)?.strip_prefix(' ')?;
+    match serde_json::from_str(payload) {
+        Ok(activity) => Some(activity),
+        Err(e) => {
+            tracing::warn!(error = %e, "malformed activity report from remote bot");
+            None
+        }
+    }
+}
+
+/// Log the bot's stdout and turn its status markers into health updates,
+/// its approval requests over to `remote_approval` and its activity reports
+/// into the history database, until it exits. Markers from a bot since
+/// replaced are ignored.
+fn follow_output(app: AppHandle, stdout: ChildStdout, pid: u32, channel: &'static str) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if let Some(request) = remote_approval::parse_request(&line) {
+                remote_approval::request(&app, pid, request);
+                continue;
+            }
+            if let Some(activity) = parse_activity(&line) {
+                history_store::on_remote_activity(&app, channel, activity);
+                continue;
+            }
+            let Some(connected) = parse_marker(&line) else {
+                tracing::info!(target: "remote_bot", "{line}");
+                continue;
+            };
+            update_health(&app, |health| {
+                if health.pid == Some(pid) {
+                    health.status =
+                        if connected { BotStatus::Connected } else { BotStatus::Disconnected };
+                }
+            });
+        }
+    });
+}
+
+fn spawn_bot(app: &AppHandle, launch: &BotLaunch, access: &BotAccess) -> std::io::Result<Child> {
+    let mut cmd = launch.command();
+    cmd.args(access.args())
+        .args(remote_approval::bot_args(&gui_config::load().remote))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped());
+    compute_devices::apply_env(&mut cmd);
+    let mut child = cmd.spawn()?;
+    let pid = child.id();
+    if let Some(stdin) = child.stdin.take() {
+        remote_approval::attach(app, pid, stdin);
+    }
+    if let Some(stdout) = child.stdout.take() {
+        follow_output(app.clone(), stdout, pid, launch.channel);
+    }
+    update_health(app, |health| {
+        health.status = BotStatus::Starting;
+        health.pid = Some(pid);
+        health.restart_in_ms = None;
+    });
+    Ok(child)
+}
 
-    if check.map(|o| o.status.success()).unwrap_or(false) {
+/// Sleep for `duration` in short slices, so a stop request is honoured
+/// promptly. Returns false if `stop` was set meanwhile.
+fn sleep_unless_stopped(stop: &AtomicBool, duration: Duration) -> bool {
+    let started = Instant::now();
+    while started.elapsed() < duration {
+        if stop.load(Ordering::SeqCst) {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(250).min(duration));
+    }
+    !stop.load(Ordering::SeqCst)
+}
+
+/// Whether the bot is still up, or how it ended.
+enum BotCheck {
+    Alive,
+    /// The exit status, if it was this GUI's child.
+    Exited(Option<ExitInfo>),
+}
+
+/// Check on the bot this GUI spawned, reaping it if it exited, or on any
+/// bot in the process table if this GUI didn't spawn one.
+fn check_bot(state: &AppState) -> BotCheck {
+    let mut slot = state.remote_bot.lock().unwrap_or_else(PoisonError::into_inner);
+    match slot.as_mut().map(Child::try_wait) {
+        Some(Ok(Some(status))) => {
+            *slot = None;
+            BotCheck::Exited(Some(status.into()))
+        }
+        Some(_) => BotCheck::Alive,
+        None if is_running(BOT_PATTERN) => BotCheck::Alive,
+        None => BotCheck::Exited(None),
+    }
+}
+
+/// Turn remote mode off after the bot crashed once too often.
+fn give_up(app: &AppHandle, stop: &Arc<AtomicBool>) {
+    tracing::error!("remote bot keeps crashing; turning remote mode off");
+    let state = app.state::<AppState>();
+    let _ = fs::remove_file(get_runtime_dir().join("synthia-remote-mode"));
+    // This watchdog is exiting, so a later start must spawn a new one.
+    if let Ok(mut guard) = state.remote_watchdog.lock() {
+        if guard.as_ref().is_some_and(|s| Arc::ptr_eq(s, stop)) {
+            *guard = None;
+        }
+    }
+    {
+        let mut current = state.remote_state.lock().unwrap_or_else(PoisonError::into_inner);
+        if *current == RemoteState::Running {
+            *current = RemoteState::Stopped;
+        }
+    }
+    notify_remote(RemoteNotice::GaveUp);
+    publish_status(app, bot_running(&state));
+}
+
+/// Supervise the bot while remote mode is on; see the module docs. Exits
+/// once `stop` is set (by `stop_remote_mode`) or the remote-mode flag file
+/// is gone.
+fn spawn_watchdog(app: AppHandle, stop: Arc<AtomicBool>) {
+    std::thread::spawn(move || {
+        let state = app.state::<AppState>();
+        let remote_mode_file = get_runtime_dir().join("synthia-remote-mode");
+        let mut recovery = CrashRecovery::default();
+        let mut awaiting_confirm = false;
+        let mut last_notified: Option<Instant> = None;
+
+        loop {
+            if !sleep_unless_stopped(&stop, WATCHDOG_INTERVAL) {
+                return;
+            }
+            if !remote_mode_file.exists() {
+                tracing::debug!("remote watchdog exiting");
+                return;
+            }
+
+            let BotCheck::Exited(exit) = check_bot(&state) else {
+                let status = state.remote_health.lock().map(|h| h.status).ok();
+                // Only announce a relaunch once the new bot is connected.
+                if awaiting_confirm && status == Some(BotStatus::Connected) {
+                    awaiting_confirm = false;
+                    let cooled_down = last_notified
+                        .map(|t| t.elapsed() >= RECONNECT_NOTIFY_COOLDOWN)
+                        .unwrap_or(true);
+                    if cooled_down {
+                        notify_remote(RemoteNotice::Reconnected);
+                        notifications::notify_remote_connected(true);
+                        last_notified = Some(Instant::now());
+                    }
+                }
+                continue;
+            };
+            // `stop_remote_mode` has just taken the bot down.
+            if stop.load(Ordering::SeqCst) {
+                return;
+            }
+
+            let config = gui_config::load().remote;
+            let attempt = recovery.next_attempt(Instant::now());
+            let delay = config.restart_delay(attempt);
+            tracing::warn!(
+                status = ?exit.as_ref().map(|e| &e.description),
+                attempt = attempt + 1,
+                "remote bot exited unexpectedly"
+            );
+            remote_approval::detach(&app);
+            update_health(&app, |health| {
+                health.status =
+                    if delay.is_some() { BotStatus::Restarting } else { BotStatus::Failed };
+                health.pid = None;
+                health.restarts = attempt + 1;
+                health.last_exit = exit;
+                health.restart_in_ms = delay.map(|d| d.as_millis() as u64);
+            });
+            let Some(delay) = delay else {
+                give_up(&app, &stop);
+                return;
+            };
+            if !sleep_unless_stopped(&stop, delay) {
+                return;
+            }
+            if is_running(BOT_PATTERN) {
+                tracing::info!("another remote bot took over; not relaunching");
+                update_health(&app, |health| health.status = BotStatus::External);
+                continue;
+            }
+
+            tracing::warn!(attempt = attempt + 1, "relaunching remote bot");
+            // Re-read access and token each time so config edits apply on relaunch.
+            let channel = remote_channel::active();
+            let resolved = channel.access().and_then(|a| Ok((a, BotLaunch::resolve(channel)?)));
+            let (access, launch) = match resolved {
+                Ok(resolved) => resolved,
+                Err(e) => {
+                    tracing::error!(error = %e, "not relaunching remote bot");
+                    continue;
+                }
+            };
+            match spawn_bot(&app, &launch, &access) {
+                Ok(mut c) => {
+                    let mut slot = state.remote_bot.lock().unwrap_or_else(PoisonError::into_inner);
+                    // A stop that raced the relaunch has already emptied the
+                    // slot; don't leave this bot behind.
+                    if stop.load(Ordering::SeqCst) {
+                        terminate_child(&mut c, BOT_STOP_GRACE);
+                        update_health(&app, |health| *health = RemoteHealth::default());
+                        return;
+                    }
+                    *slot = Some(c);
+                    awaiting_confirm = true;
+                }
+                Err(e) => tracing::error!(error = %e, "failed to relaunch remote bot"),
+            }
+        }
+    });
+}
+
+/// Start the watchdog unless one is already running.
+fn start_watchdog(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let Ok(mut guard) = state.remote_watchdog.lock() else { return };
+    if guard.is_some() {
+        return;
+    }
+    let stop = Arc::new(AtomicBool::new(false));
+    spawn_watchdog(app.clone(), stop.clone());
+    *guard = Some(stop);
+}
+
+fn stop_watchdog(state: &AppState) {
+    if let Ok(mut guard) = state.remote_watchdog.lock() {
+        if let Some(stop) = guard.take() {
+            stop.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Where remote mode is, as far as this GUI's own transitions know.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteState {
+    #[default]
+    Stopped,
+    Starting,
+    Running,
+    Stopping,
+}
+
+/// The state to enter for a start (`start = true`) or stop request, or why
+/// the request is redundant. `bot_running` is what the process table says,
+/// which wins over a stale `Running`/`Stopped`.
+fn begin_transition(
+    current: RemoteState,
+    start: bool,
+    bot_running: bool,
+) -> Result<RemoteState, &'static str> {
+    match current {
+        RemoteState::Starting => Err("Remote mode is already starting"),
+        RemoteState::Stopping => Err("Remote mode is already stopping"),
+        _ if start && current == RemoteState::Running && bot_running => {
+            Err("Remote mode is already running")
+        }
+        _ if !start && current == RemoteState::Stopped && !bot_running => {
+            Err("Remote mode is not running")
+        }
+        _ if start => Ok(RemoteState::Starting),
+        _ => Ok(RemoteState::Stopping),
+    }
+}
+
+/// Run `work` as a start or stop transition, refusing it if another is in
+/// flight or it would change nothing, and settle on what actually resulted.
+fn transition(
+    state: &AppState,
+    start: bool,
+    work: impl FnOnce() -> AppResult<String>,
+) -> AppResult<String> {
+    let running = bot_running(state);
+    {
+        let mut current = state.remote_state.lock().unwrap_or_else(PoisonError::into_inner);
+        *current = begin_transition(*current, start, running)
+            .map_err(|reason| AppError::Validation(reason.to_string()))?;
+    }
+    let result = work();
+    let settled = match (&result, start) {
+        (Ok(_), true) => RemoteState::Running,
+        (Ok(_), false) => RemoteState::Stopped,
+        (Err(_), _) if bot_running(state) => RemoteState::Running,
+        (Err(_), _) => RemoteState::Stopped,
+    };
+    tracing::debug!(?settled, "remote mode transition finished");
+    *state.remote_state.lock().unwrap_or_else(PoisonError::into_inner) = settled;
+    result
+}
+
+pub(crate) fn start(app: &AppHandle) -> AppResult<String> {
+    let state = app.state::<AppState>();
+    let result = transition(&state, true, || try_start(app));
+    state.command_stats.record(TrackedCommand::StartRemote, &result);
+    result
+}
+
+#[tracing::instrument(skip_all)]
+fn try_start(app: &AppHandle) -> AppResult<String> {
+    let state = app.state::<AppState>();
+    let channel = remote_channel::active();
+    if dry_run::enabled() {
+        let mut cmd = BotLaunch::locate(channel).command();
+        if let Ok(access) = channel.access() {
+            cmd.args(access.args());
+        }
+        cmd.args(remote_approval::bot_args(&gui_config::load().remote));
+        dry_run::skip(&format!("start {} bot", channel.name()), &cmd);
+        let was_running = state.simulated.set_remote(true);
+        return Ok(if was_running {
+            "Remote mode already running (dry run)"
+        } else {
+            "Remote mode started (dry run)"
+        }
+        .to_string());
+    }
+    // Check if already running
+    if is_running(BOT_PATTERN) {
+        // Only one bot runs at a time, so a switch of channel has to wait.
+        if let Some(other) = remote_channel::ALL.iter().find(|c| c.running()) {
+            if other.kind() != channel.kind() {
+                return Err(AppError::Validation(format!(
+                    "The {} bot is still running; stop remote mode before switching to {}",
+                    other.name(),
+                    channel.name()
+                )));
+            }
+        }
+        if state.remote_bot.lock().unwrap_or_else(PoisonError::into_inner).is_none() {
+            update_health(app, |health| health.status = BotStatus::External);
+        }
+        start_watchdog(app);
         return Ok("Remote mode already running".to_string());
     }
 
-    let root = get_synthia_root();
+    let access = channel.access()?;
+    if access == BotAccess::AllowAll {
+        tracing::warn!(channel = channel.name(), "starting remote bot with no user allowlist");
+    }
+    let launch = BotLaunch::resolve(channel)?;
+
     let runtime_dir = get_runtime_dir();
     let remote_mode_file = runtime_dir.join("synthia-remote-mode");
 
-    // Create the remote mode flag file (chat ID is read from config by telegram_bot.py)
+    // Create the remote mode flag file (the bot reads it to forward messages to Claude Code)
     let _ = fs::write(&remote_mode_file, "remote");
 
-    // Start the telegram bot with CUDA disabled
-    let python = root.join("venv/bin/python");
-    let bot_script = root.join("src/synthia/remote/telegram_bot.py");
-    Command::new(&python)
-        .args([bot_script.to_str().unwrap_or("")])
-        .current_dir(&root)
-        .env("CUDA_VISIBLE_DEVICES", "")
-        .spawn()
+    let bot = spawn_bot(app, &launch, &access)
         .map_err(|e| AppError::Process(format!("Failed to start remote mode: {}", e)))?;
+    tracing::info!(pid = bot.id(), channel = channel.name(), "spawned remote bot");
+    *state.remote_bot.lock().unwrap_or_else(PoisonError::into_inner) = Some(bot);
 
-    // Send notification in background (don't block UI)
-    let _ = Command::new(&python)
-        .args([
-            bot_script.to_str().unwrap_or(""),
-            "--notify",
-            "🟢 *Remote Mode ENABLED*\n\nYou can now control Claude Code via Telegram."
-        ])
-        .current_dir(&root)
-        .spawn();
+    notify_remote(RemoteNotice::Enabled);
+    notifications::notify_remote_connected(false);
+
+    start_watchdog(app);
 
     Ok("Remote mode started".to_string())
 }
 
+/// Whether the bot is up, or pretends to be in dry-run mode.
+pub(crate) fn bot_running(state: &AppState) -> bool {
+    if dry_run::enabled() {
+        state.simulated.remote()
+    } else {
+        is_running(BOT_PATTERN)
+    }
+}
+
+/// Emit `remote-status` and update the tray badge if the bot's running
+/// state differs from what was last published.
+fn publish_status(app: &tauri::AppHandle, running: bool) {
+    let changed = app
+        .state::<AppState>()
+        .remote_running
+        .swap(running, Ordering::SeqCst)
+        != running;
+    if changed {
+        tracing::info!(running, "remote status changed");
+        let _ = app.emit("remote-status", running);
+        tray::set_remote(app, running);
+    }
+}
+
+/// Whether remote mode has sat idle for `timeout_mins` (`0` disables),
+/// counting from the bot's last activity or, failing that, from `since`.
+fn remote_idle(
+    now: DateTime<Utc>,
+    since: DateTime<Utc>,
+    last_activity: Option<DateTime<Utc>>,
+    timeout_mins: u64,
+) -> bool {
+    let from = last_activity.map_or(since, |a| a.max(since));
+    timeout_mins > 0 && now - from >= chrono::Duration::minutes(timeout_mins as i64)
+}
+
+fn stop_idle(app: &tauri::AppHandle, timeout_mins: u64) {
+    tracing::info!(timeout_mins, "remote mode idle; stopping the remote bot");
+    match stop(app) {
+        // Supersedes the generic "disabled" message `stop` queued.
+        Ok(_) => notify_remote(RemoteNotice::IdleStopped(timeout_mins)),
+        Err(e) => tracing::warn!(error = %e, "failed to stop idle remote mode"),
+    }
+    publish_status(app, bot_running(&app.state::<AppState>()));
+}
+
+/// Poll the bot's process for the app's lifetime so the UI and tray learn
+/// about it going up or down without polling `get_remote_status`. Also
+/// stops remote mode once it has been idle for `remote.idle_timeout_mins`.
+pub(crate) fn spawn_status_monitor(app: tauri::AppHandle) {
+    threads::spawn_supervised("remote status monitor", move || {
+        let mut running_since: Option<DateTime<Utc>> = None;
+        loop {
+            let running = bot_running(&app.state::<AppState>());
+            publish_status(&app, running);
+            running_since = running.then(|| running_since.unwrap_or_else(Utc::now));
+            if let Some(since) = running_since {
+                let timeout_mins = gui_config::load().remote.idle_timeout_mins;
+                let last_activity = read_synthia_state().last_remote_activity;
+                if remote_idle(Utc::now(), since, last_activity, timeout_mins) {
+                    stop_idle(&app, timeout_mins);
+                    running_since = None;
+                }
+            }
+            std::thread::sleep(STATUS_POLL_INTERVAL);
+        }
+    });
+}
+
+/// Start or stop remote mode and publish the bot's resulting state.
+/// Blocking.
+pub(crate) fn set_enabled(app: &tauri::AppHandle, enabled: bool) -> AppResult<String> {
+    let result = if enabled { start(app) } else { stop(app) };
+    publish_status(app, bot_running(&app.state::<AppState>()));
+    result
+}
+
 #[tauri::command]
-pub fn stop_remote_mode() -> AppResult<String> {
-    let root = get_synthia_root();
+pub async fn start_remote_mode(app: tauri::AppHandle) -> AppResult<String> {
+    run_blocking(move || set_enabled(&app, true)).await
+}
+
+pub(crate) fn stop(app: &AppHandle) -> AppResult<String> {
+    let state = app.state::<AppState>();
+    let result = transition(&state, false, || try_stop(app));
+    state.command_stats.record(TrackedCommand::StopRemote, &result);
+    result
+}
+
+#[tracing::instrument(skip_all)]
+fn try_stop(app: &AppHandle) -> AppResult<String> {
+    let state = app.state::<AppState>();
+    tracing::info!("stopping remote mode");
+    if dry_run::enabled() {
+        dry_run::skip_signal("stop remote bot", BOT_PATTERN);
+        state.simulated.set_remote(false);
+        return Ok("Remote mode stopped (dry run)".to_string());
+    }
+    // Stop the watchdog first so it doesn't relaunch the bot we're about to kill
+    stop_watchdog(&state);
+
     let runtime_dir = get_runtime_dir();
     let remote_mode_file = runtime_dir.join("synthia-remote-mode");
 
-    // Remove the remote mode flag file (stops response forwarding to Telegram)
+    // Remove the remote mode flag file (stops forwarding messages to Claude Code)
     let _ = fs::remove_file(&remote_mode_file);
 
-    // Kill the bot immediately for instant UI response
-    let _ = Command::new("pkill")
-        .args(["-f", "telegram_bot.py"])
-        .output();
-
-    // Send notification in background (after bot is killed, uses --notify which is standalone)
-    let python = root.join("venv/bin/python");
-    let bot_script = root.join("src/synthia/remote/telegram_bot.py");
-    let _ = Command::new(&python)
-        .args([
-            bot_script.to_str().unwrap_or(""),
-            "--notify",
-            "🔴 *Remote Mode DISABLED*\n\nTelegram bot stopped."
-        ])
-        .current_dir(&root)
-        .spawn();
+    // The bot this GUI spawned is stopped by pid; one left over from an
+    // earlier session or started by hand only gets SIGTERM.
+    let bot = state.remote_bot.lock().unwrap_or_else(PoisonError::into_inner).take();
+    if let Some(mut bot) = bot {
+        terminate_child(&mut bot, BOT_STOP_GRACE);
+    }
+    remote_approval::detach(app);
+    let foreign = find_processes(BOT_PATTERN);
+    if !foreign.is_empty() {
+        terminate_foreign(&foreign, BOT_STOP_GRACE);
+    }
+    // The last crash, if any, stays on record.
+    update_health(app, |health| {
+        *health = RemoteHealth { last_exit: health.last_exit.take(), ..Default::default() };
+    });
+
+    notify_remote(RemoteNotice::Disabled);
 
     Ok("Remote mode stopped".to_string())
 }
 
 #[tauri::command]
-pub fn get_remote_status() -> bool {
-    let check = Command::new("pgrep")
-        .args(["-f", "telegram_bot.py"])
-        .output();
+pub async fn stop_remote_mode(app: tauri::AppHandle) -> AppResult<String> {
+    run_blocking(move || set_enabled(&app, false)).await
+}
+
+#[tauri::command]
+pub async fn get_remote_status(app: tauri::AppHandle) -> AppResult<bool> {
+    run_blocking(move || Ok(bot_running(&app.state::<AppState>()))).await
+}
+
+#[tauri::command]
+pub async fn get_remote_health(app: tauri::AppHandle) -> AppResult<RemoteHealth> {
+    run_blocking(move || Ok(current_health(&app.state::<AppState>()))).await
+}
+
+/// Switch remote mode to `channel` from its next start. Refused while a
+/// bot is running, since only one runs at a time. Blocking.
+pub(crate) fn set_channel(app: &AppHandle, channel: RemoteChannelKind) -> AppResult<()> {
+    if gui_config::load().remote.channel == channel {
+        return Ok(());
+    }
+    if bot_running(&app.state::<AppState>()) {
+        return Err(AppError::Validation(
+            "Stop remote mode before switching its channel".to_string(),
+        ));
+    }
+    gui_config::update(|cfg| cfg.remote.channel = channel)?;
+    tracing::info!(?channel, "remote channel switched");
+    // The new channel may have no token yet.
+    tray::sync_mode_items(app);
+    Ok(())
+}
+
+/// Save the active channel's bot token in the OS keyring, where
+/// `RemoteChannel::token` looks first. Takes effect the next time the bot
+/// starts; the tray's remote entries become available straight away.
+#[tauri::command]
+pub async fn set_remote_token(app: tauri::AppHandle, token: String) -> AppResult<()> {
+    let token = token.trim().to_string();
+    run_blocking(move || {
+        let channel = remote_channel::active();
+        channel.validate_token(&token)?;
+        secrets::set_remote_token(channel.kind(), &token)?;
+        tracing::info!(channel = channel.name(), "bot token saved to keyring");
+        tray::sync_mode_items(&app);
+        Ok(())
+    })
+    .await
+}
 
-    check.map(|o| o.status.success()).unwrap_or(false)
+#[tauri::command]
+pub async fn has_remote_token() -> AppResult<bool> {
+    run_blocking(|| Ok(secrets::remote_token(remote_channel::active().kind())?.is_some())).await
+}
+
+/// Remove the active channel's bot token from the keyring. Returns whether
+/// one was stored.
+#[tauri::command]
+pub async fn clear_remote_token(app: tauri::AppHandle) -> AppResult<bool> {
+    run_blocking(move || {
+        let channel = remote_channel::active();
+        let removed = secrets::clear_remote_token(channel.kind())?;
+        if removed {
+            tracing::info!(channel = channel.name(), "bot token removed from keyring");
+            tray::sync_mode_items(&app);
+        }
+        Ok(removed)
+    })
+    .await
+}
+
+/// Apply `change` to the active channel's allowlist and, if it reports a
+/// change, save it to config.yaml; returns the new list. The bot gets the
+/// list as `--allowed-users` when it starts, so a running one is restarted
+/// to pick up a changed list.
+fn update_allowlist(
+    app: &AppHandle,
+    change: impl FnOnce(&dyn RemoteChannel, &mut Vec<String>) -> AppResult<bool>,
+) -> AppResult<Vec<String>> {
+    let channel = remote_channel::active();
+    let config_path = get_config_path();
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| AppError::Io(format!("Failed to read config: {}", e)))?;
+    // Parse errors are fatal here: saving over a list we failed to read
+    // would drop it.
+    let config: SynthiaConfigYaml = if content.trim().is_empty() {
+        SynthiaConfigYaml::default()
+    } else {
+        serde_yaml::from_str(&content)?
+    };
+    let mut users = channel.allowed_users(&config);
+    if !change(channel, &mut users)? {
+        return Ok(users);
+    }
+    // Numeric IDs stay numbers; Matrix IDs are quoted for the leading `@`.
+    let items: Vec<String> = users
+        .iter()
+        .map(|id| {
+            if id.bytes().all(|b| b.is_ascii_digit() || b == b'-') {
+                id.clone()
+            } else {
+                serde_json::Value::from(id.as_str()).to_string()
+            }
+        })
+        .collect();
+    let content = write_synthia_config_list(&content, channel.allowlist_key(), &items);
+    write_atomic_private(&config_path, content)?;
+    restart_bot(app)?;
+    Ok(users)
+}
+
+/// Stop and start a running bot so it launches with the current config.
+/// Nothing to do while remote mode is off. Blocking.
+fn restart_bot(app: &AppHandle) -> AppResult<()> {
+    if !bot_running(&app.state::<AppState>()) {
+        return Ok(());
+    }
+    tracing::info!("restarting remote bot to apply the new allowlist");
+    let result = stop(app).and_then(|_| start(app));
+    publish_status(app, bot_running(&app.state::<AppState>()));
+    result.map(drop)
+}
+
+/// The active channel's allowed user IDs, from config.yaml.
+#[tauri::command]
+pub async fn list_allowed_users() -> AppResult<Vec<String>> {
+    run_blocking(|| {
+        let channel = remote_channel::active();
+        Ok(channel.allowed_users(&remote_channel::synthia_config()))
+    })
+    .await
+}
+
+/// Let user `id` of the active channel talk to the bot. Adding one already
+/// listed changes nothing.
+#[tauri::command]
+pub async fn add_allowed_user(app: tauri::AppHandle, id: String) -> AppResult<Vec<String>> {
+    run_blocking(move || {
+        update_allowlist(&app, |channel, users| {
+            let id = channel.parse_user_id(&id)?;
+            if users.contains(&id) {
+                return Ok(false);
+            }
+            tracing::info!(channel = channel.name(), %id, "user added to remote allowlist");
+            users.push(id);
+            Ok(true)
+        })
+    })
+    .await
+}
+
+/// Stop user `id` of the active channel from talking to the bot. A running
+/// bot is restarted without them straight away.
+#[tauri::command]
+pub async fn remove_allowed_user(app: tauri::AppHandle, id: String) -> AppResult<Vec<String>> {
+    run_blocking(move || {
+        update_allowlist(&app, |channel, users| {
+            let id = channel.parse_user_id(&id)?;
+            let before = users.len();
+            users.retain(|user| *user != id);
+            if users.len() == before {
+                return Err(AppError::NotFound(format!(
+                    "{id} is not in {}",
+                    channel.allowlist_key()
+                )));
+            }
+            tracing::info!(channel = channel.name(), %id, "user removed from remote allowlist");
+            Ok(true)
+        })
+    })
+    .await
+}
+
+/// Messages the bot is holding until they are approved, oldest first.
+#[tauri::command]
+pub async fn list_remote_approvals(app: tauri::AppHandle) -> AppResult<Vec<PendingApproval>> {
+    run_blocking(move || Ok(remote_approval::pending(&app))).await
+}
+
+/// Let a held message through to Claude Code.
+#[tauri::command]
+pub async fn approve_remote_command(
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<PendingApproval> {
+    run_blocking(move || remote_approval::decide(&app, &id, true)).await
+}
+
+/// Drop a held message; the bot tells the sender it was denied.
+#[tauri::command]
+pub async fn deny_remote_command(app: tauri::AppHandle, id: String) -> AppResult<PendingApproval> {
+    run_blocking(move || remote_approval::decide(&app, &id, false)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_transitions_refuse_overlaps_and_no_ops() {
+        use RemoteState::*;
+        assert_eq!(begin_transition(Stopped, true, false), Ok(Starting));
+        assert_eq!(begin_transition(Running, false, true), Ok(Stopping));
+        assert!(begin_transition(Starting, true, false).is_err());
+        assert!(begin_transition(Starting, false, true).is_err());
+        assert!(begin_transition(Stopping, true, true).is_err());
+        assert!(begin_transition(Running, true, true).is_err());
+        assert!(begin_transition(Stopped, false, false).is_err());
+        // The process table overrides a stale record.
+        assert_eq!(begin_transition(Running, true, false), Ok(Starting));
+        assert_eq!(begin_transition(Stopped, false, true), Ok(Stopping));
+    }
+
+    #[test]
+    fn status_markers_are_recognised() {
+        assert_eq!(parse_marker("SYNTHIA_REMOTE_STATUS connected\n"), Some(true));
+        assert_eq!(parse_marker("SYNTHIA_REMOTE_STATUS disconnected"), Some(false));
+        assert_eq!(parse_marker("SYNTHIA_REMOTE_STATUS sideways"), None);
+        assert_eq!(parse_marker("Error: telegram_bot_token not set in config"), None);
+        assert_eq!(parse_marker(""), None);
+    }
+
+    #[test]
+    fn activity_reports_are_recognised() {
+        let payload = r#"{"direction":"in","sender":"42","kind":"command","text":"/dev"}"#;
+        let activity = parse_activity(&format!("SYNTHIA_REMOTE_ACTIVITY {payload}")).unwrap();
+        assert_eq!((activity.sender.as_str(), activity.kind.as_str()), ("42", "command"));
+        assert_eq!(activity.text, "/dev");
+        assert!(parse_activity("SYNTHIA_REMOTE_ACTIVITY {}").is_none());
+        assert!(parse_activity("SYNTHIA_REMOTE_STATUS connected").is_none());
+    }
+
+    #[test]
+    fn remote_idle_counts_from_latest_activity() {
+        let now = Utc::now();
+        let mins = |m| now - chrono::Duration::minutes(m);
+        assert!(remote_idle(now, mins(30), None, 30));
+        assert!(!remote_idle(now, mins(30), Some(mins(5)), 30));
+        // Activity from before the bot started doesn't count against it.
+        assert!(!remote_idle(now, mins(5), Some(mins(60)), 30));
+        assert!(!remote_idle(now, mins(600), None, 0));
+    }
 }
@@ -0,0 +1,23 @@
+//! Settings Tauri commands: typed get/set over `gui.toml` and `config.yaml`.
+
+use serde_json::Value;
+
+use crate::error::AppResult;
+use crate::settings::{self, SettingKey, Settings};
+
+#[tauri::command]
+pub fn get_setting(key: &str) -> AppResult<Value> {
+    settings::get(key.parse()?)
+}
+
+/// Validate and save one setting; returns the value now stored.
+#[tauri::command]
+pub fn set_setting(app: tauri::AppHandle, key: &str, value: Value) -> AppResult<Value> {
+    let key: SettingKey = key.parse()?;
+    settings::set(&app, key, value)
+}
+
+#[tauri::command]
+pub fn get_all_settings() -> AppResult<Settings> {
+    settings::all()
+}
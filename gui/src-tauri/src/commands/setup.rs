@@ -0,0 +1,176 @@
+//! First-run installation check Tauri command.
+//!
+//! On a machine where `install.sh` hasn't been run, every other command
+//! fails with its own opaque error. `check_installation` looks at the pieces
+//! those commands rely on and returns a checklist the UI shows as a setup
+//! screen until the required ones pass.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::Manager;
+
+use crate::{
+    dry_run, get_config_path, get_python_path, get_run_script, get_synthia_root, sidecar,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Expect {
+    Dir,
+    File,
+    Executable,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct InstallCheck {
+    pub name: &'static str,
+    pub path: String,
+    pub ok: bool,
+    /// What's wrong, when `ok` is false.
+    pub problem: Option<String>,
+    /// How to fix it.
+    pub hint: &'static str,
+    /// Whether Synthia can't run without it.
+    pub required: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct InstallStatus {
+    /// True when every required check passes, or in dry-run mode, where
+    /// nothing from the install is used.
+    pub configured: bool,
+    pub root: String,
+    pub checks: Vec<InstallCheck>,
+}
+
+/// Why `path` doesn't meet `expect`, or `None` if it does.
+fn path_problem(path: &Path, expect: Expect) -> Option<String> {
+    let meta = match fs::metadata(path) {
+        Ok(meta) => meta,
+        Err(_) => return Some("missing".to_string()),
+    };
+    match expect {
+        Expect::Dir if !meta.is_dir() => Some("not a directory".to_string()),
+        Expect::File | Expect::Executable if !meta.is_file() => Some("not a file".to_string()),
+        #[cfg(unix)]
+        Expect::Executable => {
+            use std::os::unix::fs::PermissionsExt;
+            (meta.permissions().mode() & 0o111 == 0).then(|| "not executable".to_string())
+        }
+        _ => None,
+    }
+}
+
+fn check(
+    name: &'static str,
+    path: PathBuf,
+    expect: Expect,
+    required: bool,
+    hint: &'static str,
+) -> InstallCheck {
+    let problem = path_problem(&path, expect);
+    InstallCheck {
+        name,
+        path: path.display().to_string(),
+        ok: problem.is_none(),
+        problem,
+        hint,
+        required,
+    }
+}
+
+fn icons_dir(app: &tauri::AppHandle, root: &Path) -> PathBuf {
+    let bundled = app
+        .path()
+        .resource_dir()
+        .map(|dir| dir.join("icons"))
+        .ok()
+        .filter(|dir| dir.is_dir());
+    bundled.unwrap_or_else(|| root.join("gui/src-tauri/icons"))
+}
+
+#[tauri::command]
+pub fn check_installation(app: tauri::AppHandle) -> InstallStatus {
+    let root = get_synthia_root();
+    // A bundled backend doesn't need the checkout at all.
+    let needs_checkout = sidecar::find(sidecar::BACKEND).is_none();
+    let checks = vec![
+        check(
+            "install_dir",
+            root.clone(),
+            Expect::Dir,
+            needs_checkout,
+            "Clone Synthia and set SYNTHIA_ROOT to its directory",
+        ),
+        check(
+            "run_script",
+            get_run_script(),
+            Expect::Executable,
+            needs_checkout,
+            "Restore run.sh from the repository and chmod +x it",
+        ),
+        check(
+            "python",
+            get_python_path(),
+            Expect::Executable,
+            needs_checkout,
+            "Run ./install.sh in the Synthia directory to create the venv",
+        ),
+        check(
+            "config",
+            get_config_path(),
+            Expect::File,
+            false,
+            "Copy config.example.yaml to ~/.config/synthia/config.yaml",
+        ),
+        check(
+            "icons",
+            icons_dir(&app, &root),
+            Expect::Dir,
+            false,
+            "Themed tray icons are missing; the built-in ones are used",
+        ),
+    ];
+    let configured = dry_run::enabled() || checks.iter().all(|c| c.ok || !c.required);
+    if !configured {
+        let missing: Vec<&str> = checks
+            .iter()
+            .filter(|c| c.required && !c.ok)
+            .map(|c| c.name)
+            .collect();
+        tracing::warn!(root = %root.display(), ?missing, "installation incomplete");
+    }
+    InstallStatus {
+        configured,
+        root: root.display().to_string(),
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_problem_distinguishes_missing_kind_and_mode() {
+        let dir = std::env::temp_dir().join(format!("synthia-setup-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("run.sh");
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+
+        assert_eq!(path_problem(&dir, Expect::Dir), None);
+        assert_eq!(path_problem(&dir.join("nope"), Expect::Dir).as_deref(), Some("missing"));
+        assert_eq!(path_problem(&dir, Expect::File).as_deref(), Some("not a file"));
+        assert_eq!(path_problem(&script, Expect::File), None);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+            assert_eq!(path_problem(&script, Expect::Executable).as_deref(), Some("not executable"));
+            fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+            assert_eq!(path_problem(&script, Expect::Executable), None);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
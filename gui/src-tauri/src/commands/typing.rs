@@ -0,0 +1,21 @@
+//! Typing transcriptions into the focused window, see `text_injection`.
+
+use crate::error::{AppError, AppResult};
+use crate::text_injection::{self, Backend};
+
+/// Type `text` at the cursor of the focused window and return the backend
+/// that typed it.
+#[tauri::command]
+pub async fn type_text(text: String) -> AppResult<Backend> {
+    if text.is_empty() {
+        return Err(AppError::Validation("nothing to type".to_string()));
+    }
+    super::run_blocking(move || text_injection::type_text(&text)).await
+}
+
+/// Text injection backends usable in this session, best first. Empty means
+/// `type_text` will fail until one is installed.
+#[tauri::command]
+pub fn get_text_injection_backends() -> Vec<Backend> {
+    text_injection::available()
+}
@@ -0,0 +1,103 @@
+//! Version Tauri commands for the About dialog and bug reports.
+
+use std::process::Command;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::Manager;
+
+use super::run_blocking;
+use crate::error::{AppError, AppResult};
+use crate::{get_python_path, get_synthia_root, sidecar};
+use crate::process::{describe_exit, output_with_timeout};
+use crate::state::AppState;
+
+/// `--version` exits before the models load, but importing the backend
+/// still pulls in its dependencies; allow for a cold disk cache.
+const VERSION_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Serialize, Debug, Clone)]
+pub struct VersionInfo {
+    pub gui: String,
+    /// `None` if the backend couldn't be queried (e.g. no venv yet).
+    pub backend: Option<String>,
+    pub backend_error: Option<String>,
+}
+
+/// Version from `synthia --version` output (`synthia 0.1.0`, or a bare `0.1.0`).
+fn parse_version(stdout: &str) -> Option<String> {
+    let line = stdout.lines().map(str::trim).find(|l| !l.is_empty())?;
+    line.split_whitespace().last().map(str::to_string)
+}
+
+fn query_backend_version() -> AppResult<String> {
+    let mut cmd = match sidecar::find(sidecar::BACKEND) {
+        Some(bin) => Command::new(bin),
+        None => {
+            let root = get_synthia_root();
+            let mut cmd = Command::new(get_python_path());
+            cmd.args(["-m", "synthia.main"])
+                .current_dir(&root)
+                .env("PYTHONPATH", root.join("src"));
+            cmd
+        }
+    };
+    let output = output_with_timeout(cmd.arg("--version"), VERSION_TIMEOUT)?;
+    if !output.status.success() {
+        return Err(AppError::Process(format!(
+            "synthia --version failed ({}): {}",
+            describe_exit(output.status),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    parse_version(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| AppError::Process("synthia --version printed nothing".to_string()))
+}
+
+/// Cached backend version; queried on first use after each backend start.
+pub(crate) fn backend_version(state: &AppState) -> AppResult<String> {
+    if let Some(version) = state.backend_version.lock().ok().and_then(|v| v.clone()) {
+        return Ok(version);
+    }
+    let version = query_backend_version()
+        .inspect_err(|e| tracing::warn!(error = %e, "backend version query failed"))?;
+    if let Ok(mut cached) = state.backend_version.lock() {
+        *cached = Some(version.clone());
+    }
+    Ok(version)
+}
+
+#[tauri::command]
+pub async fn get_backend_version(app: tauri::AppHandle) -> AppResult<String> {
+    run_blocking(move || backend_version(&app.state::<AppState>())).await
+}
+
+#[tauri::command]
+pub fn get_gui_version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+#[tauri::command]
+pub async fn get_version_info(app: tauri::AppHandle) -> AppResult<VersionInfo> {
+    run_blocking(move || {
+        let backend = backend_version(&app.state::<AppState>());
+        Ok(VersionInfo {
+            gui: get_gui_version(),
+            backend_error: backend.as_ref().err().map(|e| e.to_string()),
+            backend: backend.ok(),
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_accepts_prefixed_and_bare() {
+        assert_eq!(parse_version("synthia 0.1.0\n"), Some("0.1.0".to_string()));
+        assert_eq!(parse_version("\n0.2.0-dev\n"), Some("0.2.0-dev".to_string()));
+        assert_eq!(parse_version("  \n"), None);
+    }
+}
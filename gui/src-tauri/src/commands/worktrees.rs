@@ -315,7 +315,7 @@ pub(crate) fn find_session_for_worktree(project_path: &str, branch: &str, repo_p
     }
 
     // Return the best match
-    candidates.sort_by(|a, b| b.2.cmp(&a.2));
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.2));
     candidates.into_iter().next().map(|(id, dir, _)| (id, dir))
 }
 
@@ -0,0 +1,258 @@
+//! GPU detection, and the environment the backend and remote bot are
+//! spawned with for `backend.compute_device`.
+//!
+//! CUDA GPUs are listed by `nvidia-smi`, which ships with the NVIDIA driver,
+//! so a card without it can't run CUDA anyway. AMD GPUs are found in sysfs
+//! (`/sys/class/drm/card*/device`, vendor `0x1002`) and count as ROCm-capable
+//! when the kernel's compute interface `/dev/kfd` exists; `rocm-smi` is not
+//! needed. Both are Linux-only; elsewhere only the CPU is reported.
+//!
+//! The device reaches the backend as `SYNTHIA_COMPUTE_DEVICE` (`cpu`,
+//! `cuda` or `rocm`), and the GPUs of other vendors are hidden through
+//! `CUDA_VISIBLE_DEVICES` / `HIP_VISIBLE_DEVICES` so libraries that probe
+//! for them on their own stay off them too.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use serde::Serialize;
+
+use crate::gui_config::{self, ComputeDevice};
+
+const DRM_DIR: &str = "/sys/class/drm";
+const AMD_VENDOR: &str = "0x1002";
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Gpu {
+    pub name: String,
+    pub memory_mb: Option<u64>,
+}
+
+/// Result of `detect`, as returned by `detect_devices`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct DeviceReport {
+    pub cuda: Vec<Gpu>,
+    pub rocm: Vec<Gpu>,
+    pub cpu_threads: usize,
+    /// `backend.compute_device`, as configured.
+    pub selected: ComputeDevice,
+    /// What `auto` resolves to on this machine.
+    pub auto: ComputeDevice,
+}
+
+/// Rows of `nvidia-smi --query-gpu=name,memory.total --format=csv,noheader,nounits`.
+fn parse_nvidia_smi(output: &str) -> Vec<Gpu> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, memory) = line.rsplit_once(',')?;
+            let name = name.trim();
+            (!name.is_empty()).then(|| Gpu {
+                name: name.to_string(),
+                memory_mb: memory.trim().parse().ok(),
+            })
+        })
+        .collect()
+}
+
+fn cuda_gpus() -> Vec<Gpu> {
+    Command::new("nvidia-smi")
+        .args(["--query-gpu=name,memory.total", "--format=csv,noheader,nounits"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .map(|out| parse_nvidia_smi(&String::from_utf8_lossy(&out.stdout)))
+        .unwrap_or_default()
+}
+
+/// Rows of `nvidia-smi --query-compute-apps=pid,used_memory
+/// --format=csv,noheader,nounits`, summed per pid.
+fn parse_compute_apps(output: &str) -> HashMap<u32, u64> {
+    let mut used = HashMap::new();
+    for line in output.lines() {
+        let Some((pid, memory)) = line.split_once(',') else {
+            continue;
+        };
+        if let (Ok(pid), Ok(memory)) = (pid.trim().parse(), memory.trim().parse::<u64>()) {
+            *used.entry(pid).or_insert(0) += memory;
+        }
+    }
+    used
+}
+
+/// GPU memory in MiB held by each process on a CUDA GPU, or `None` where
+/// `nvidia-smi` isn't available.
+pub fn cuda_memory_by_pid() -> Option<HashMap<u32, u64>> {
+    let out = Command::new("nvidia-smi")
+        .args(["--query-compute-apps=pid,used_memory", "--format=csv,noheader,nounits"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())?;
+    Some(parse_compute_apps(&String::from_utf8_lossy(&out.stdout)))
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+/// AMD GPUs under `drm_dir`, one per `cardN` (connector entries such as
+/// `card0-DP-1` share the card's device and are skipped).
+fn amd_gpus(drm_dir: &Path) -> Vec<Gpu> {
+    let Ok(entries) = fs::read_dir(drm_dir) else {
+        return Vec::new();
+    };
+    let mut cards: Vec<_> = entries
+        .flatten()
+        .filter(|e| {
+            let name = e.file_name();
+            let name = name.to_string_lossy();
+            name.strip_prefix("card").is_some_and(|n| n.chars().all(|c| c.is_ascii_digit()))
+        })
+        .map(|e| e.path().join("device"))
+        .filter(|device| read_trimmed(&device.join("vendor")).as_deref() == Some(AMD_VENDOR))
+        .collect();
+    cards.sort();
+    cards
+        .into_iter()
+        .map(|device| Gpu {
+            name: read_trimmed(&device.join("product_name"))
+                .filter(|n| !n.is_empty())
+                .or_else(|| {
+                    read_trimmed(&device.join("device")).map(|id| format!("AMD GPU {id}"))
+                })
+                .unwrap_or_else(|| "AMD GPU".to_string()),
+            memory_mb: read_trimmed(&device.join("mem_info_vram_total"))
+                .and_then(|b| b.parse::<u64>().ok())
+                .map(|bytes| bytes / (1024 * 1024)),
+        })
+        .collect()
+}
+
+fn rocm_gpus() -> Vec<Gpu> {
+    if !Path::new("/dev/kfd").exists() {
+        return Vec::new();
+    }
+    amd_gpus(Path::new(DRM_DIR))
+}
+
+/// The best device present: CUDA, then ROCm, then the CPU.
+fn best(cuda: &[Gpu], rocm: &[Gpu]) -> ComputeDevice {
+    if !cuda.is_empty() {
+        ComputeDevice::Cuda
+    } else if !rocm.is_empty() {
+        ComputeDevice::Rocm
+    } else {
+        ComputeDevice::Cpu
+    }
+}
+
+pub fn detect() -> DeviceReport {
+    let (cuda, rocm) = if cfg!(target_os = "linux") {
+        (cuda_gpus(), rocm_gpus())
+    } else {
+        (Vec::new(), Vec::new())
+    };
+    DeviceReport {
+        auto: best(&cuda, &rocm),
+        cpu_threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        selected: gui_config::load().backend.compute_device,
+        cuda,
+        rocm,
+    }
+}
+
+/// `device`, with `auto` resolved by probing the machine.
+pub fn resolve(device: ComputeDevice) -> ComputeDevice {
+    match device {
+        ComputeDevice::Auto => detect().auto,
+        other => other,
+    }
+}
+
+/// Point `cmd` at `device` (already resolved).
+fn device_env(cmd: &mut Command, device: ComputeDevice) {
+    let (name, hide_cuda, hide_hip) = match device {
+        ComputeDevice::Cuda => ("cuda", false, true),
+        ComputeDevice::Rocm => ("rocm", true, false),
+        ComputeDevice::Cpu | ComputeDevice::Auto => ("cpu", true, true),
+    };
+    cmd.env("SYNTHIA_COMPUTE_DEVICE", name);
+    if hide_cuda {
+        cmd.env("CUDA_VISIBLE_DEVICES", "");
+    }
+    if hide_hip {
+        cmd.env("HIP_VISIBLE_DEVICES", "");
+    }
+}
+
+/// Apply the configured `backend.compute_device` to a spawned backend or bot.
+pub fn apply_env(cmd: &mut Command) {
+    let device = resolve(gui_config::load().backend.compute_device);
+    tracing::debug!(?device, "compute device for spawned process");
+    device_env(cmd, device);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nvidia_smi_rows() {
+        let out = "NVIDIA GeForce RTX 3080, 10240\nTesla T4, [N/A]\n\n";
+        assert_eq!(
+            parse_nvidia_smi(out),
+            [
+                Gpu { name: "NVIDIA GeForce RTX 3080".into(), memory_mb: Some(10240) },
+                Gpu { name: "Tesla T4".into(), memory_mb: None },
+            ]
+        );
+    }
+
+    #[test]
+    fn sums_gpu_memory_per_process() {
+        let out = "4242, 1530\n4242, 12\n77, [N/A]\n";
+        assert_eq!(parse_compute_apps(out), HashMap::from([(4242, 1542)]));
+    }
+
+    #[test]
+    fn finds_amd_cards_in_sysfs() {
+        let drm = std::env::temp_dir().join(format!("synthia-drm-{}", std::process::id()));
+        let card = |name: &str, vendor: &str| {
+            let device = drm.join(name).join("device");
+            fs::create_dir_all(&device).unwrap();
+            fs::write(device.join("vendor"), format!("{vendor}\n")).unwrap();
+            device
+        };
+        let amd = card("card1", AMD_VENDOR);
+        fs::write(amd.join("device"), "0x73bf\n").unwrap();
+        fs::write(amd.join("mem_info_vram_total"), "17163091968\n").unwrap();
+        card("card1-DP-1", AMD_VENDOR);
+        card("card0", "0x8086");
+
+        let gpus = amd_gpus(&drm);
+        fs::remove_dir_all(&drm).unwrap();
+        assert_eq!(gpus, [Gpu { name: "AMD GPU 0x73bf".into(), memory_mb: Some(16368) }]);
+    }
+
+    #[test]
+    fn cpu_hides_every_gpu() {
+        let mut cmd = Command::new("true");
+        device_env(&mut cmd, ComputeDevice::Cpu);
+        let env = |key: &str| {
+            cmd.get_envs().find(|(k, _)| *k == key).and_then(|(_, v)| v?.to_str())
+        };
+        assert_eq!(env("CUDA_VISIBLE_DEVICES"), Some(""));
+        assert_eq!(env("HIP_VISIBLE_DEVICES"), Some(""));
+        assert_eq!(env("SYNTHIA_COMPUTE_DEVICE"), Some("cpu"));
+    }
+
+    #[test]
+    fn auto_prefers_cuda_then_rocm() {
+        let gpu = || vec![Gpu { name: "x".into(), memory_mb: None }];
+        assert_eq!(best(&gpu(), &gpu()), ComputeDevice::Cuda);
+        assert_eq!(best(&[], &gpu()), ComputeDevice::Rocm);
+        assert_eq!(best(&[], &[]), ComputeDevice::Cpu);
+    }
+}
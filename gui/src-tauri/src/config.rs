@@ -20,6 +20,17 @@ pub struct SynthiaConfigYaml {
     pub use_local_tts: bool,
     #[serde(default)]
     pub local_stt_model: String,
+    #[serde(default = "default_language")]
+    pub language: String,
+    /// Phrase that arms the assistant hands-free; empty disables it.
+    #[serde(default)]
+    pub wake_word: String,
+    /// Voice activity detection sensitivity, 0.0 (least) to 1.0 (most).
+    #[serde(default = "default_vad_sensitivity")]
+    pub vad_sensitivity: f64,
+    /// Wake word sensitivity, 0.0 (fewest false triggers) to 1.0 (fewest misses).
+    #[serde(default = "default_wake_sensitivity")]
+    pub wake_sensitivity: f64,
     #[serde(default)]
     pub local_llm_model: String,
     #[serde(default)]
@@ -36,11 +47,51 @@ pub struct SynthiaConfigYaml {
     pub show_notifications: bool,
     #[serde(default)]
     pub play_sound_on_record: bool,
+    /// Remote-mode bot token from @BotFather. Never serialized back out.
+    #[serde(default, skip_serializing)]
+    pub telegram_bot_token: String,
+    /// Telegram user IDs the remote-mode bot accepts commands from.
+    #[serde(default)]
+    pub telegram_allowed_users: Vec<i64>,
+    /// Discord bot token, for `remote.channel = "discord"`. Never
+    /// serialized back out.
+    #[serde(default, skip_serializing)]
+    pub discord_bot_token: String,
+    /// Discord user IDs the bot accepts commands from.
+    #[serde(default)]
+    pub discord_allowed_users: Vec<u64>,
+    /// Base URL of the Matrix bot account's homeserver.
+    #[serde(default)]
+    pub matrix_homeserver: String,
+    /// The Matrix bot account, e.g. `@synthia-bot:matrix.org`.
+    #[serde(default)]
+    pub matrix_user_id: String,
+    /// Access token for `matrix_user_id`. Never serialized back out.
+    #[serde(default, skip_serializing)]
+    pub matrix_access_token: String,
+    /// Matrix user IDs the bot accepts commands from.
+    #[serde(default)]
+    pub matrix_allowed_users: Vec<String>,
+    /// Room remote-mode notices are posted to.
+    #[serde(default)]
+    pub matrix_room_id: String,
     /// Inline `word_replacements: { from: to }` map — preserved as-is.
     #[serde(default)]
     pub word_replacements: HashMap<String, String>,
 }
 
+fn default_language() -> String {
+    "en-US".to_string()
+}
+
+fn default_vad_sensitivity() -> f64 {
+    0.5
+}
+
+fn default_wake_sensitivity() -> f64 {
+    0.5
+}
+
 fn default_tts_speed() -> f64 {
     1.0
 }
@@ -101,6 +152,13 @@ mod tests {
         assert!(cfg.word_replacements.is_empty());
     }
 
+    #[test]
+    fn synthia_config_parses_telegram_allowlist() {
+        let yaml = "telegram_allowed_users:\n  - 123456789\n  - 987654321\n";
+        let cfg: SynthiaConfigYaml = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(cfg.telegram_allowed_users, vec![123456789, 987654321]);
+    }
+
     #[test]
     fn synthia_config_ignores_unknown_keys() {
         let yaml = "use_local_stt: true\nfuture_setting: 42\n";
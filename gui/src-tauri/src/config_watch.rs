@@ -0,0 +1,194 @@
+//! Live reload of `gui.toml`.
+//!
+//! The config file's directory is watched with `notify`. When the file
+//! changes, the new config is compared with the last one seen and each
+//! changed setting is either applied to the running app or reported as
+//! needing a restart. The outcome is published as a `config-reloaded` event.
+//! Each reload also refreshes `gui_config::cached`.
+//!
+//! Most settings are read at the point of use (`install.*`,
+//! `backend.shutdown_*`, `recording.*`, `remote.*`,
+//! `tray.confirm_on_quit`) and so take effect on their own; they are still
+//! listed as applied. Window positions are
+//! written by the GUI itself as windows move and are ignored here.
+
+use std::sync::Mutex;
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::gui_config::{self, GuiConfig};
+use crate::state::AppState;
+use crate::synthia_state::read_synthia_state;
+use crate::{commands, global_hotkeys, i18n, overlay_transcript, power, push_to_talk, tray};
+
+/// Payload of the `config-reloaded` event.
+#[derive(Serialize, Debug, Default, Clone, PartialEq)]
+pub struct ConfigReload {
+    /// Settings that changed and are now in effect.
+    pub applied: Vec<&'static str>,
+    /// Settings that changed but only take effect after a restart.
+    pub restart_required: Vec<&'static str>,
+}
+
+impl ConfigReload {
+    fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.restart_required.is_empty()
+    }
+}
+
+/// Which settings differ between `old` and `new`, and whether each can be
+/// applied live.
+fn diff(old: &GuiConfig, new: &GuiConfig) -> ConfigReload {
+    let mut reload = ConfigReload::default();
+    let mut live = |changed: bool, key: &'static str| {
+        if changed {
+            reload.applied.push(key);
+        }
+    };
+    live(old.install != new.install, "install");
+    live(old.push_to_talk != new.push_to_talk, "push_to_talk");
+    live(old.hotkeys != new.hotkeys, "hotkeys");
+    live(
+        old.runtime.state_poll_interval_ms != new.runtime.state_poll_interval_ms,
+        "runtime.state_poll_interval_ms",
+    );
+    live(old.power != new.power, "power");
+    live(old.backend != new.backend, "backend");
+    live(old.recording != new.recording, "recording");
+    live(old.remote != new.remote, "remote");
+    live(old.tray != new.tray, "tray");
+    live(old.i18n != new.i18n, "i18n");
+    live(old.overlay.click_through != new.overlay.click_through, "overlay.click_through");
+    live(old.overlay.anchor != new.overlay.anchor, "overlay.anchor");
+    live(old.overlay.transcript != new.overlay.transcript, "overlay.transcript");
+    live(
+        old.overlay.visibility != new.overlay.visibility
+            || old.overlay.auto_hide_secs != new.overlay.auto_hide_secs,
+        "overlay.visibility",
+    );
+
+    let mut restart = |changed: bool, key: &'static str| {
+        if changed {
+            reload.restart_required.push(key);
+        }
+    };
+    restart(old.runtime.dir != new.runtime.dir, "runtime.dir");
+    restart(old.runtime.ipc_socket != new.runtime.ipc_socket, "runtime.ipc_socket");
+    restart(old.logging != new.logging, "logging");
+    restart(old.http_control != new.http_control, "http_control");
+    restart(old.ws_control != new.ws_control, "ws_control");
+    restart(old.dbus != new.dbus, "dbus");
+    reload
+}
+
+/// Push the changed settings into the running app.
+fn apply(app: &AppHandle, reload: &ConfigReload) {
+    let changed = |key: &str| reload.applied.contains(&key);
+    if changed("push_to_talk") {
+        // Re-registers the shortcut if it changed while push-to-talk is on.
+        push_to_talk::sync(app, &read_synthia_state());
+    }
+    if changed("hotkeys") {
+        global_hotkeys::sync(app);
+    }
+    if changed("power") {
+        tray::set_low_power(app, power::mode(&app.state::<AppState>()).low_power);
+    }
+    if changed("tray") {
+        tray::set_static_icons(app, !gui_config::cached().tray.animate);
+    }
+    if changed("runtime.state_poll_interval_ms") || changed("power") {
+        crate::install_state_watcher(app);
+    }
+    if changed("i18n") {
+        i18n::init();
+        tray::relocalize(app);
+    }
+    if changed("overlay.click_through") || changed("overlay.anchor") || changed("overlay.visibility")
+    {
+        commands::overlay::reapply_config(app);
+    }
+    if changed("overlay.transcript")
+        && gui_config::cached().overlay.transcript == gui_config::OverlayTranscriptMode::Off
+    {
+        overlay_transcript::clear(app);
+    }
+    // Icon files may have been swapped alongside the config; cheap to redo.
+    tray::reload_icons(app);
+}
+
+fn on_change(app: &AppHandle, last: &Mutex<GuiConfig>) {
+    let new = gui_config::refresh();
+    let reload = {
+        let Ok(mut last) = last.lock() else { return };
+        let reload = diff(&last, &new);
+        *last = new;
+        reload
+    };
+    if reload.is_empty() {
+        return;
+    }
+    tracing::info!(
+        applied = ?reload.applied,
+        restart_required = ?reload.restart_required,
+        "gui.toml reloaded"
+    );
+    if !reload.applied.is_empty() {
+        apply(app, &reload);
+    }
+    let _ = app.emit("config-reloaded", reload);
+}
+
+/// Watch `gui.toml` for the rest of the process. Without filesystem
+/// notifications, changes apply at the next start as before.
+pub(crate) fn spawn(app: AppHandle) {
+    let path = gui_config::gui_config_path();
+    let Some(dir) = path.parent().map(|p| p.to_path_buf()) else { return };
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        tracing::warn!(dir = %dir.display(), error = %e, "cannot create config dir to watch");
+        return;
+    }
+    let last = Mutex::new(gui_config::refresh());
+    let target = path.clone();
+    let handle = app.clone();
+    let handler = move |res: notify::Result<Event>| {
+        let Ok(event) = res else { return };
+        if matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_))
+            && event.paths.iter().any(|p| p == &target)
+        {
+            on_change(&handle, &last);
+        }
+    };
+    let watcher = notify::recommended_watcher(handler)
+        .and_then(|mut w| w.watch(&dir, RecursiveMode::NonRecursive).map(|()| w));
+    match watcher {
+        Ok(watcher) => {
+            tracing::info!(path = %path.display(), "watching GUI config");
+            if let Ok(mut watchers) = app.state::<AppState>().watchers.lock() {
+                watchers.push(Box::new(watcher));
+            }
+        }
+        Err(e) => tracing::warn!(error = %e, "GUI config changes need a restart to apply"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_splits_live_and_restart_settings() {
+        let old = GuiConfig::default();
+        assert!(diff(&old, &old).is_empty());
+
+        let mut new = old.clone();
+        new.push_to_talk.shortcut = "Alt+Space".into();
+        new.http_control.port = 9000;
+        new.overlay.position = Some(gui_config::SavedPosition { x: 1, y: 2, monitor: None });
+        let reload = diff(&old, &new);
+        assert_eq!(reload.applied, vec!["push_to_talk"]);
+        assert_eq!(reload.restart_required, vec!["http_control"]);
+    }
+}
@@ -0,0 +1,124 @@
+//! `org.synthia.Control1` on the session bus, so GNOME and KDE extensions,
+//! keybinding daemons and scripts can drive Synthia without a token:
+//! anything on the user's session bus already runs as the user.
+//!
+//! Object `/org/synthia/Control1`, interface `org.synthia.Control1`:
+//!
+//! - `StartRecording() -> s` and `StopRecording() -> s`, the message
+//!   `toggle_recording` would return; no-ops if already in that state. The
+//!   backend is asked over its IPC socket, and with none connected the call
+//!   fails with `org.freedesktop.DBus.Error.NoServer`.
+//! - `ToggleRemoteMode() -> b`, whether remote mode is now on.
+//! - signal `StateChanged(s status, b recording)`, sent for every backend
+//!   state change (heartbeats aside).
+//!
+//! For example: `busctl --user call org.synthia.Control1
+//! /org/synthia/Control1 org.synthia.Control1 StartRecording`. Linux only,
+//! and skipped when `dbus.enabled` is off.
+
+use std::sync::OnceLock;
+
+use tauri::{AppHandle, Manager};
+use zbus::blocking::connection;
+use zbus::fdo;
+use zbus::object_server::SignalEmitter;
+
+use crate::commands::{self, lifecycle, remote};
+use crate::error::{AppError, AppResult};
+use crate::gui_config;
+use crate::state::AppState;
+use crate::synthia_state::SynthiaState;
+use crate::tray;
+
+const NAME: &str = "org.synthia.Control1";
+const PATH: &str = "/org/synthia/Control1";
+
+static CONNECTION: OnceLock<zbus::blocking::Connection> = OnceLock::new();
+
+struct Control {
+    app: AppHandle,
+}
+
+fn to_fdo(e: AppError) -> fdo::Error {
+    fdo::Error::Failed(e.to_string())
+}
+
+#[zbus::interface(name = "org.synthia.Control1")]
+impl Control {
+    async fn start_recording(&self) -> fdo::Result<String> {
+        self.set_recording(true).await
+    }
+
+    async fn stop_recording(&self) -> fdo::Result<String> {
+        self.set_recording(false).await
+    }
+
+    async fn toggle_remote_mode(&self) -> fdo::Result<bool> {
+        let app = self.app.clone();
+        commands::run_blocking(move || {
+            let enable = !remote::bot_running(&app.state::<AppState>());
+            remote::set_enabled(&app, enable)?;
+            tray::sync_mode_items(&app);
+            Ok(enable)
+        })
+        .await
+        .map_err(to_fdo)
+    }
+
+    #[zbus(signal)]
+    async fn state_changed(
+        emitter: &SignalEmitter<'_>,
+        status: &str,
+        recording: bool,
+    ) -> zbus::Result<()>;
+}
+
+impl Control {
+    async fn set_recording(&self, recording: bool) -> fdo::Result<String> {
+        let app = self.app.clone();
+        commands::run_blocking(move || {
+            lifecycle::set_recording_to(&app.state::<AppState>(), recording)
+        })
+        .await
+        .map_err(|e| match e {
+            // No backend connected to act on it.
+            AppError::NotFound(message) => fdo::Error::NoServer(message),
+            e => to_fdo(e),
+        })
+    }
+}
+
+/// Claim `org.synthia.Control1` unless `dbus.enabled` is off.
+pub fn spawn_if_enabled(app: AppHandle) -> AppResult<()> {
+    if !gui_config::load().dbus.enabled {
+        return Ok(());
+    }
+    let connection = connection::Builder::session()
+        .and_then(|b| b.name(NAME))
+        .and_then(|b| b.serve_at(PATH, Control { app }))
+        .and_then(|b| b.build())
+        .map_err(|e| AppError::Other(format!("D-Bus: {e}")))?;
+    tracing::info!(name = NAME, "D-Bus control interface published");
+    let _ = CONNECTION.set(connection);
+    Ok(())
+}
+
+/// Send `StateChanged` for a new backend state, if the interface is up.
+pub(crate) fn state_changed(state: &SynthiaState) {
+    let Some(connection) = CONNECTION.get() else {
+        return;
+    };
+    let result = connection
+        .object_server()
+        .interface::<_, Control>(PATH)
+        .and_then(|iface| {
+            zbus::block_on(Control::state_changed(
+                iface.signal_emitter(),
+                &state.status,
+                state.recording,
+            ))
+        });
+    if let Err(e) = result {
+        tracing::debug!(error = %e, "StateChanged signal not sent");
+    }
+}
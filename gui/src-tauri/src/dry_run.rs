@@ -0,0 +1,112 @@
+//! Safe mode for exercising the GUI without a backend installed.
+//!
+//! With `SYNTHIA_DRY_RUN` set (to anything but `0`, `false`, `no`, `off` or
+//! empty) or `backend.dry_run = true` in `gui.toml`, the commands that start
+//! and stop the backend and the Telegram bot log the command line they
+//! would run and report success without spawning or signalling anything.
+//! Remote notifications are logged instead of sent. Running state is
+//! simulated in `Simulated`, so status queries agree with what the UI asked
+//! for.
+//!
+//! The state watcher is unaffected: point `runtime.dir` at a directory
+//! holding a hand-written `synthia-state.json` to drive the tray and
+//! overlay.
+
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::gui_config;
+
+pub(crate) const ENV_VAR: &str = "SYNTHIA_DRY_RUN";
+
+/// Whether the environment variable's value switches dry-run on.
+fn env_enables(value: Option<&str>) -> bool {
+    value.is_some_and(|v| {
+        !matches!(
+            v.trim().to_ascii_lowercase().as_str(),
+            "" | "0" | "false" | "no" | "off"
+        )
+    })
+}
+
+/// Whether commands should be simulated rather than executed.
+pub(crate) fn enabled() -> bool {
+    env_enables(std::env::var(ENV_VAR).ok().as_deref()) || gui_config::load().backend.dry_run
+}
+
+/// `cmd` as a shell-like line for the log. Environment overrides are left
+/// out; they can hold secrets such as the bot token.
+pub(crate) fn command_line(cmd: &Command) -> String {
+    std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|part| {
+            let part = part.to_string_lossy();
+            if part.is_empty() || part.contains(char::is_whitespace) {
+                format!("'{part}'")
+            } else {
+                part.into_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Log that `cmd` would have run.
+pub(crate) fn skip(what: &str, cmd: &Command) {
+    tracing::info!(what, command = %command_line(cmd), "dry run: not executing");
+}
+
+/// Log that processes matching `pattern` would have been signalled.
+pub(crate) fn skip_signal(what: &str, pattern: &str) {
+    tracing::info!(what, pattern, "dry run: not signalling");
+}
+
+/// What the simulated processes would be doing.
+#[derive(Default)]
+pub struct Simulated {
+    backend: AtomicBool,
+    remote: AtomicBool,
+}
+
+impl Simulated {
+    /// Record the simulated backend as running or not; returns the old value.
+    pub fn set_backend(&self, running: bool) -> bool {
+        self.backend.swap(running, Ordering::SeqCst)
+    }
+
+    pub fn backend(&self) -> bool {
+        self.backend.load(Ordering::SeqCst)
+    }
+
+    /// Record the simulated remote as running or not; returns the old value.
+    pub fn set_remote(&self, running: bool) -> bool {
+        self.remote.swap(running, Ordering::SeqCst)
+    }
+
+    pub fn remote(&self) -> bool {
+        self.remote.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_value_switches_dry_run() {
+        assert!(!env_enables(None));
+        for off in ["", "0", "false", "No", " off "] {
+            assert!(!env_enables(Some(off)), "{off:?}");
+        }
+        for on in ["1", "true", "yes"] {
+            assert!(env_enables(Some(on)), "{on:?}");
+        }
+    }
+
+    #[test]
+    fn command_line_quotes_arguments_with_spaces() {
+        let mut cmd = Command::new("/opt/synthia/run.sh");
+        cmd.args(["-f", "my script.py", ""]).env("TOKEN", "secret");
+        assert_eq!(command_line(&cmd), "/opt/synthia/run.sh -f 'my script.py' ''");
+    }
+}
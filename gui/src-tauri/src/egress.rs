@@ -157,13 +157,11 @@ struct UserAllowlistFile {
 }
 
 pub fn runtime_state_path() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(home).join(".config/synthia/runtime.json")
+    crate::platform::home_dir().join(".config/synthia/runtime.json")
 }
 
 fn user_allowlist_path() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(home).join(".config/synthia/security/allowlist.yaml")
+    crate::platform::home_dir().join(".config/synthia/security/allowlist.yaml")
 }
 
 pub fn is_enabled() -> bool {
@@ -23,6 +23,10 @@ pub enum AppError {
     Process(String),
     #[error("http: {0}")]
     Http(String),
+    #[error("keyring: {0}")]
+    Keyring(String),
+    #[error("database: {0}")]
+    Database(String),
     #[error("other: {0}")]
     Other(String),
 }
@@ -55,8 +59,22 @@ impl From<serde_json::Error> for AppError {
 }
 
 impl From<reqwest::Error> for AppError {
+    /// Drops the request URL from the message: some carry a credential, such
+    /// as the bot token in every Telegram Bot API path, and errors get logged.
     fn from(e: reqwest::Error) -> Self {
-        AppError::Http(e.to_string())
+        AppError::Http(e.without_url().to_string())
+    }
+}
+
+impl From<keyring::Error> for AppError {
+    fn from(e: keyring::Error) -> Self {
+        AppError::Keyring(e.to_string())
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(e: rusqlite::Error) -> Self {
+        AppError::Database(e.to_string())
     }
 }
 
@@ -0,0 +1,425 @@
+//! Keys read straight from `/dev/input`, for push-to-talk where global
+//! shortcuts don't work.
+//!
+//! Many Wayland compositors offer no global-shortcut portal, so the plugin
+//! behind `push_to_talk.shortcut` never sees the key. The kernel's evdev
+//! nodes see every key on every keyboard regardless of the session, at the
+//! price of a permission: `/dev/input/event*` belong to root and the `input`
+//! group, so the user has to be in that group (or granted access by a udev
+//! rule). Nothing here asks for more than read access.
+//!
+//! Keyboards are found through `/proc/bus/input/devices`: entries with the
+//! `kbd` handler, an `eventN` node, and the wanted key in their key bitmap.
+//! The nodes are opened non-blocking and waited on together with poll(2)
+//! from one thread. The wait wakes at once for a key, and otherwise every
+//! `STOP_CHECK_INTERVAL` so a stop flag is noticed when no key is pressed.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::error::{AppError, AppResult};
+use crate::gui_config::EvdevBinding;
+
+const DEVICES_LIST: &str = "/proc/bus/input/devices";
+
+/// Longest a listener blocks without events before checking its stop flag.
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// `struct input_event`: a `timeval` (two `long`s), then `u16` type, `u16`
+/// code and `i32` value.
+const TIME_SIZE: usize = 2 * std::mem::size_of::<usize>();
+const EVENT_SIZE: usize = TIME_SIZE + 8;
+
+const EV_KEY: u16 = 0x01;
+
+/// Values of an `EV_KEY` event.
+pub const KEY_RELEASED: i32 = 0;
+pub const KEY_PRESSED: i32 = 1;
+
+/// A keyboard as `list_input_devices` shows it.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct InputDevice {
+    /// Name the kernel reports, which `push_to_talk.evdev.device` matches.
+    pub name: String,
+    pub path: PathBuf,
+    /// Whether this user may read it; see `permission_hint`.
+    pub readable: bool,
+    /// The key bitmap as listed, for `has_key`.
+    #[serde(skip)]
+    keys: String,
+}
+
+impl InputDevice {
+    /// Whether the device can send `code`.
+    pub fn has_key(&self, code: u16) -> bool {
+        has_key(&self.keys, code)
+    }
+}
+
+/// A key pressed while `capture_key` waited.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CapturedKey {
+    pub device: String,
+    pub key: u16,
+    pub name: String,
+}
+
+/// Whether the `B: KEY=` bitmap has bit `code` set. The kernel prints it as
+/// hex words, most significant first, each one `long` wide.
+fn has_key(bitmap: &str, code: u16) -> bool {
+    let bits = usize::BITS as usize;
+    let (word, bit) = (code as usize / bits, code as usize % bits);
+    bitmap
+        .split_whitespace()
+        .rev()
+        .nth(word)
+        .and_then(|w| usize::from_str_radix(w, 16).ok())
+        .is_some_and(|w| (w >> bit) & 1 == 1)
+}
+
+/// Keyboards in the text of `/proc/bus/input/devices`, whose entries are
+/// separated by blank lines.
+fn parse_devices(text: &str) -> Vec<(String, PathBuf, String)> {
+    text.split("\n\n")
+        .filter_map(|entry| {
+            let mut name = None;
+            let mut node = None;
+            let mut keyboard = false;
+            let mut keys = String::new();
+            for line in entry.lines() {
+                if let Some(rest) = line.strip_prefix("N: Name=") {
+                    name = Some(rest.trim().trim_matches('"').to_string());
+                } else if let Some(rest) = line.strip_prefix("H: Handlers=") {
+                    keyboard = rest.split_whitespace().any(|h| h == "kbd");
+                    node = rest.split_whitespace().find(|h| h.starts_with("event"));
+                } else if let Some(rest) = line.strip_prefix("B: KEY=") {
+                    keys = rest.trim().to_string();
+                }
+            }
+            let node = node.filter(|_| keyboard && !keys.is_empty())?;
+            Some((name?, PathBuf::from("/dev/input").join(node), keys))
+        })
+        .collect()
+}
+
+/// Every keyboard, readable or not.
+pub fn list_devices() -> AppResult<Vec<InputDevice>> {
+    let text = std::fs::read_to_string(DEVICES_LIST).map_err(|e| {
+        AppError::Io(format!("Can't list input devices ({DEVICES_LIST}: {e}); evdev needs Linux"))
+    })?;
+    Ok(parse_devices(&text)
+        .into_iter()
+        .map(|(name, path, keys)| {
+            let readable = File::open(&path).is_ok();
+            InputDevice { name, path, readable, keys }
+        })
+        .collect())
+}
+
+/// What to tell a user who may not read `path`.
+fn permission_hint(path: &std::path::Path) -> String {
+    format!(
+        "No permission to read {}: add yourself to the input group \
+         (sudo usermod -aG input $USER) and log in again",
+        path.display()
+    )
+}
+
+#[cfg(target_os = "linux")]
+fn open_nonblocking(path: &std::path::Path) -> io::Result<File> {
+    use std::os::unix::fs::OpenOptionsExt;
+    std::fs::OpenOptions::new().read(true).custom_flags(libc::O_NONBLOCK).open(path)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn open_nonblocking(_path: &std::path::Path) -> io::Result<File> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "evdev input needs Linux"))
+}
+
+/// Open nodes being read for key events.
+struct Devices {
+    open: Vec<(String, File)>,
+}
+
+impl Devices {
+    /// Open the keyboard named `device`, or every keyboard that has `key`
+    /// if none is named. Fails if nothing could be opened, saying why.
+    fn open(device: Option<&str>, key: Option<u16>) -> AppResult<Self> {
+        let candidates: Vec<InputDevice> = list_devices()?
+            .into_iter()
+            .filter(|d| device.is_none_or(|name| d.name == name))
+            .filter(|d| key.is_none_or(|code| d.has_key(code)))
+            .collect();
+        if candidates.is_empty() {
+            return Err(AppError::NotFound(match device {
+                Some(name) => format!("No keyboard named {name:?}"),
+                None => "No keyboard with that key".to_string(),
+            }));
+        }
+        let mut open = Vec::new();
+        let mut denied = None;
+        for candidate in candidates {
+            match open_nonblocking(&candidate.path) {
+                Ok(file) => open.push((candidate.name, file)),
+                Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+                    denied = Some(candidate.path);
+                }
+                Err(e) => {
+                    let path = candidate.path.display();
+                    tracing::warn!(%path, error = %e, "can't open input device");
+                }
+            }
+        }
+        match (open.is_empty(), denied) {
+            (false, _) => Ok(Devices { open }),
+            (true, Some(path)) => Err(AppError::Validation(permission_hint(&path))),
+            (true, None) => Err(AppError::Io("No input device could be opened".to_string())),
+        }
+    }
+
+    /// Pass each key event waiting on any node to `on_key` as device name,
+    /// code and value. Nodes that fail (unplugged) are dropped; false once
+    /// none are left.
+    fn drain(&mut self, mut on_key: impl FnMut(&str, u16, i32)) -> bool {
+        let mut buf = [0u8; EVENT_SIZE * 64];
+        self.open.retain_mut(|(name, file)| loop {
+            match file.read(&mut buf) {
+                Ok(0) => return false,
+                Ok(n) => {
+                    for (code, value) in key_events(&buf[..n]) {
+                        on_key(name, code, value);
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return true,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    tracing::warn!(device = %name, error = %e, "input device went away");
+                    return false;
+                }
+            }
+        });
+        !self.open.is_empty()
+    }
+
+    /// Block until a node has something to read (an event, or an error once
+    /// it is unplugged) or `timeout` passes.
+    #[cfg(target_os = "linux")]
+    fn wait(&self, timeout: Duration) {
+        use std::os::fd::AsRawFd;
+
+        let mut fds: Vec<libc::pollfd> = self
+            .open
+            .iter()
+            .map(|(_, file)| libc::pollfd {
+                fd: file.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            })
+            .collect();
+        let millis = timeout.as_millis().min(i32::MAX as u128) as i32;
+        // SAFETY: `fds` holds `fds.len()` initialized pollfds for the whole
+        // call, and every fd stays open because `self` is borrowed.
+        let ready = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, millis) };
+        if ready < 0 {
+            let e = io::Error::last_os_error();
+            if e.kind() != io::ErrorKind::Interrupted {
+                tracing::debug!(error = %e, "poll on input devices failed");
+                // Don't spin if poll keeps failing.
+                std::thread::sleep(timeout);
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn wait(&self, timeout: Duration) {
+        std::thread::sleep(timeout);
+    }
+}
+
+/// `(code, value)` of the `EV_KEY` events in `buf`.
+fn key_events(buf: &[u8]) -> impl Iterator<Item = (u16, i32)> + '_ {
+    buf.chunks_exact(EVENT_SIZE).filter_map(|event| {
+        let field = &event[TIME_SIZE..];
+        let kind = u16::from_ne_bytes([field[0], field[1]]);
+        let code = u16::from_ne_bytes([field[2], field[3]]);
+        let value = i32::from_ne_bytes([field[4], field[5], field[6], field[7]]);
+        (kind == EV_KEY).then_some((code, value))
+    })
+}
+
+/// Check `binding` could be listened to, without listening.
+pub fn check(binding: &EvdevBinding) -> AppResult<()> {
+    Devices::open(binding.device.as_deref(), Some(binding.key)).map(|_| ())
+}
+
+/// Call `on_key(true)` when `binding`'s key goes down and `on_key(false)`
+/// when it comes back up, until the returned flag is set. Auto-repeat is
+/// not passed on.
+pub fn listen(
+    binding: &EvdevBinding,
+    on_key: impl Fn(bool) + Send + 'static,
+) -> AppResult<Arc<AtomicBool>> {
+    let key = binding.key;
+    listen_all(binding.device.as_deref(), key, move |code, value| match value {
+        KEY_PRESSED if code == key => on_key(true),
+        KEY_RELEASED if code == key => on_key(false),
+        _ => {}
+    })
+}
+
+/// Pass every key event on `device`, or on every keyboard that has `key`,
+/// to `on_event` as code and value until the returned flag is set.
+pub fn listen_all(
+    device: Option<&str>,
+    key: u16,
+    mut on_event: impl FnMut(u16, i32) + Send + 'static,
+) -> AppResult<Arc<AtomicBool>> {
+    let mut devices = Devices::open(device, Some(key))?;
+    let names: Vec<&str> = devices.open.iter().map(|(name, _)| name.as_str()).collect();
+    tracing::info!(key = %key_name(key), devices = ?names, "listening for evdev key");
+    let stop = Arc::new(AtomicBool::new(false));
+    let flag = stop.clone();
+    std::thread::spawn(move || {
+        while !flag.load(Ordering::SeqCst) {
+            let alive = devices.drain(|_, code, value| on_event(code, value));
+            if !alive {
+                tracing::warn!("no input devices left; evdev key listener stopped");
+                break;
+            }
+            devices.wait(STOP_CHECK_INTERVAL);
+        }
+    });
+    Ok(stop)
+}
+
+/// Wait up to `timeout` for the next key pressed on `device` (any keyboard
+/// if `None`), for picking the push-to-talk key.
+pub fn capture_key(device: Option<&str>, timeout: Duration) -> AppResult<CapturedKey> {
+    let mut devices = Devices::open(device, None)?;
+    let deadline = Instant::now() + timeout;
+    loop {
+        let mut captured = None;
+        let alive = devices.drain(|name, code, value| {
+            if value == KEY_PRESSED && captured.is_none() {
+                captured = Some(CapturedKey {
+                    device: name.to_string(),
+                    key: code,
+                    name: key_name(code),
+                });
+            }
+        });
+        if let Some(captured) = captured {
+            return Ok(captured);
+        }
+        let left = deadline.saturating_duration_since(Instant::now());
+        if !alive || left.is_zero() {
+            break;
+        }
+        devices.wait(left);
+    }
+    Err(AppError::Other("No key was pressed".to_string()))
+}
+
+/// `KEY_*` name of `code` from input-event-codes.h, for the keys a
+/// push-to-talk binding is likely to use; others read as `KEY_<code>`.
+pub fn key_name(code: u16) -> String {
+    let name = match code {
+        29 => "KEY_LEFTCTRL",
+        42 => "KEY_LEFTSHIFT",
+        54 => "KEY_RIGHTSHIFT",
+        56 => "KEY_LEFTALT",
+        57 => "KEY_SPACE",
+        58 => "KEY_CAPSLOCK",
+        59..=68 => return format!("KEY_F{}", code - 58),
+        70 => "KEY_SCROLLLOCK",
+        87 => "KEY_F11",
+        88 => "KEY_F12",
+        97 => "KEY_RIGHTCTRL",
+        99 => "KEY_SYSRQ",
+        100 => "KEY_RIGHTALT",
+        119 => "KEY_PAUSE",
+        125 => "KEY_LEFTMETA",
+        126 => "KEY_RIGHTMETA",
+        127 => "KEY_COMPOSE",
+        183..=194 => return format!("KEY_F{}", code - 170),
+        _ => return format!("KEY_{code}"),
+    };
+    name.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVICES: &str = "\
+I: Bus=0011 Vendor=0001 Product=0001 Version=ab41
+N: Name=\"AT Translated Set 2 keyboard\"
+H: Handlers=sysrq kbd leds event3
+B: EV=120013
+B: KEY=402000000 3803078f800d001 feffffdfffefffff fffffffffffffffe
+
+I: Bus=0003 Vendor=046d Product=c52b Version=0111
+N: Name=\"Logitech USB Receiver Mouse\"
+H: Handlers=mouse0 event5
+B: EV=17
+B: KEY=ffff0000 0 0 0 0
+
+I: Bus=0019 Vendor=0000 Product=0001 Version=0000
+N: Name=\"Power Button\"
+H: Handlers=kbd event0
+B: EV=3
+B: KEY=10000000000000 0
+";
+
+    #[test]
+    fn keyboards_are_found_with_their_nodes() {
+        let devices = parse_devices(DEVICES);
+        let names: Vec<&str> = devices.iter().map(|(name, _, _)| name.as_str()).collect();
+        assert_eq!(names, ["AT Translated Set 2 keyboard", "Power Button"]);
+        assert_eq!(devices[0].1, PathBuf::from("/dev/input/event3"));
+    }
+
+    #[test]
+    fn key_bitmaps_are_read_low_word_last() {
+        if usize::BITS != 64 {
+            return;
+        }
+        let (_, _, keyboard) = &parse_devices(DEVICES)[0];
+        assert!(has_key(keyboard, 97));
+        assert!(has_key(keyboard, 29));
+        assert!(!has_key(keyboard, 0));
+        let (_, _, power) = &parse_devices(DEVICES)[1];
+        assert!(has_key(power, 116));
+        assert!(!has_key(power, 97));
+    }
+
+    #[test]
+    fn only_key_events_are_decoded() {
+        let event = |kind: u16, code: u16, value: i32| {
+            let mut bytes = vec![0u8; TIME_SIZE];
+            bytes.extend(kind.to_ne_bytes());
+            bytes.extend(code.to_ne_bytes());
+            bytes.extend(value.to_ne_bytes());
+            bytes
+        };
+        let mut buf = event(EV_KEY, 97, KEY_PRESSED);
+        buf.extend(event(0, 0, 0));
+        buf.extend(event(EV_KEY, 97, KEY_RELEASED));
+        let events: Vec<(u16, i32)> = key_events(&buf).collect();
+        assert_eq!(events, [(97, KEY_PRESSED), (97, KEY_RELEASED)]);
+    }
+
+    #[test]
+    fn key_names_cover_function_keys() {
+        assert_eq!(key_name(97), "KEY_RIGHTCTRL");
+        assert_eq!(key_name(59), "KEY_F1");
+        assert_eq!(key_name(183), "KEY_F13");
+        assert_eq!(key_name(240), "KEY_240");
+    }
+}
@@ -0,0 +1,104 @@
+//! Advisory file locks shared with the backend, for the state file.
+//!
+//! The state file is replaced by rename, so nobody reads half of it, but the
+//! GUI and the backend both read-modify-write it and can lose each other's
+//! changes, and a writer that predates the rename can still be caught
+//! mid-write. Both sides take a `flock` (`LockFileEx` on Windows) on
+//! `synthia-state.lock` next to it: shared to read, exclusive to write. The
+//! state file itself can't carry the lock, as every rename gives it a new
+//! inode. The locks are advisory: a process that doesn't take them isn't
+//! kept out, so the state reader still retries unparseable content.
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::error::{AppError, AppResult};
+
+/// How long to wait for a lock before giving up. Holders only keep it for
+/// one read or write, so running into this means one of them is stuck.
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+
+const RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// A held lock, released on drop.
+#[derive(Debug)]
+pub struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    pub fn shared(path: &Path) -> AppResult<Self> {
+        Self::acquire(path, false, LOCK_TIMEOUT)
+    }
+
+    pub fn exclusive(path: &Path) -> AppResult<Self> {
+        Self::acquire(path, true, LOCK_TIMEOUT)
+    }
+
+    fn acquire(path: &Path, exclusive: bool, timeout: Duration) -> AppResult<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            let attempt = if exclusive { file.try_lock() } else { file.try_lock_shared() };
+            match attempt {
+                Ok(()) => return Ok(FileLock { file }),
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    std::thread::sleep(RETRY_DELAY);
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(AppError::Other(format!(
+                        "timed out waiting for lock on {}",
+                        path.display()
+                    )));
+                }
+                Err(TryLockError::Error(e)) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// The lock file guarding `path`: `synthia-state.json` is guarded by
+/// `synthia-state.lock` in the same directory.
+pub fn lock_path(path: &Path) -> PathBuf {
+    path.with_extension("lock")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exclusive_excludes_everyone_and_shared_only_writers() {
+        let path = std::env::temp_dir().join(format!("synthia-lock-{}.lock", std::process::id()));
+        let held = FileLock::exclusive(&path).unwrap();
+        assert!(FileLock::acquire(&path, false, Duration::ZERO).is_err());
+        drop(held);
+
+        let reader = FileLock::shared(&path).unwrap();
+        assert!(FileLock::acquire(&path, false, Duration::ZERO).is_ok());
+        assert!(FileLock::acquire(&path, true, Duration::ZERO).is_err());
+        drop(reader);
+        assert!(FileLock::acquire(&path, true, Duration::ZERO).is_ok());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn lock_sits_next_to_the_state_file() {
+        assert_eq!(
+            lock_path(Path::new("/run/user/1000/synthia-state.json")),
+            Path::new("/run/user/1000/synthia-state.lock")
+        );
+    }
+}
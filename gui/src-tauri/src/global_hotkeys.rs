@@ -0,0 +1,432 @@
+//! Global shortcuts registered by the GUI instead of the backend.
+//!
+//! The backend's own key listener needs X11 or evdev access, which Wayland
+//! compositors often withhold. The shortcuts in `[hotkeys]` go through the
+//! global-shortcut plugin instead: `dictation` and `assistant` forward press
+//! and release to the backend as `hotkey` commands over the IPC socket,
+//! `toggle_recording` toggles recording directly, like the tray item, and
+//! `copy_last_transcription` puts the latest transcription on the clipboard.
+//!
+//! Push-to-talk keeps its own registration in `push_to_talk`. The plugin
+//! has a single handler, `on_shortcut`, which routes each event to whichever
+//! action owns the shortcut.
+//!
+//! `hotkeys.double_tap` toggles recording on a modifier tapped twice. The
+//! plugin only takes shortcuts with a non-modifier key, so those taps are
+//! read from `/dev/input` (see `evdev_input`) and spotted by `DoubleTap`.
+
+use std::sync::atomic::Ordering;
+use std::sync::PoisonError;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::error::{AppError, AppResult};
+use crate::evdev_input::{self, KEY_PRESSED, KEY_RELEASED};
+use crate::gui_config::{self, DoubleTapConfig, EvdevBinding, GuiConfig, TapModifier};
+use crate::state::AppState;
+use crate::{commands, push_to_talk};
+
+/// Bounds on `hotkeys.double_tap.interval_ms`: shorter is hard to hit,
+/// longer makes two deliberate presses of Ctrl count.
+const TAP_INTERVAL_MS: std::ops::RangeInclusive<u64> = 100..=1000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyAction {
+    PushToTalk,
+    Dictation,
+    Assistant,
+    ToggleRecording,
+    CopyLastTranscription,
+}
+
+pub const ACTIONS: [HotkeyAction; 5] = [
+    HotkeyAction::PushToTalk,
+    HotkeyAction::Dictation,
+    HotkeyAction::Assistant,
+    HotkeyAction::ToggleRecording,
+    HotkeyAction::CopyLastTranscription,
+];
+
+/// Actions registered by `sync`; push-to-talk is registered by its module.
+const SYNCED: [HotkeyAction; 4] = [
+    HotkeyAction::Dictation,
+    HotkeyAction::Assistant,
+    HotkeyAction::ToggleRecording,
+    HotkeyAction::CopyLastTranscription,
+];
+
+impl HotkeyAction {
+    /// The accelerator configured for this action, if any.
+    pub fn shortcut(self, config: &GuiConfig) -> Option<String> {
+        match self {
+            HotkeyAction::PushToTalk => Some(config.push_to_talk.shortcut.clone()),
+            HotkeyAction::Dictation => config.hotkeys.dictation.clone(),
+            HotkeyAction::Assistant => config.hotkeys.assistant.clone(),
+            HotkeyAction::ToggleRecording => config.hotkeys.toggle_recording.clone(),
+            HotkeyAction::CopyLastTranscription => config.hotkeys.copy_last_transcription.clone(),
+        }
+    }
+
+    fn set_shortcut(self, config: &mut GuiConfig, shortcut: Option<String>) -> AppResult<()> {
+        match self {
+            HotkeyAction::PushToTalk => {
+                config.push_to_talk.shortcut = shortcut.ok_or_else(|| {
+                    AppError::Validation("Push-to-talk always needs a shortcut".into())
+                })?;
+            }
+            HotkeyAction::Dictation => config.hotkeys.dictation = shortcut,
+            HotkeyAction::Assistant => config.hotkeys.assistant = shortcut,
+            HotkeyAction::ToggleRecording => config.hotkeys.toggle_recording = shortcut,
+            HotkeyAction::CopyLastTranscription => {
+                config.hotkeys.copy_last_transcription = shortcut
+            }
+        }
+        Ok(())
+    }
+
+    /// Name the backend knows this action by, for held actions it runs.
+    fn backend_name(self) -> Option<&'static str> {
+        match self {
+            HotkeyAction::Dictation => Some("dictation"),
+            HotkeyAction::Assistant => Some("assistant"),
+            HotkeyAction::PushToTalk
+            | HotkeyAction::ToggleRecording
+            | HotkeyAction::CopyLastTranscription => None,
+        }
+    }
+}
+
+/// A shortcut `sync` registered with the plugin.
+pub struct RegisteredHotkey {
+    pub action: HotkeyAction,
+    pub accelerator: String,
+    shortcut: Shortcut,
+}
+
+/// Accelerator with case and spacing ignored, for spotting two actions
+/// bound to the same keys.
+fn normalize(accelerator: &str) -> String {
+    accelerator
+        .split('+')
+        .map(|part| part.trim().to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// The other action already bound to `accelerator`, if any.
+fn conflict(config: &GuiConfig, action: HotkeyAction, accelerator: &str) -> Option<HotkeyAction> {
+    let wanted = normalize(accelerator);
+    ACTIONS.into_iter().find(|other| {
+        *other != action
+            && other
+                .shortcut(config)
+                .is_some_and(|s| normalize(&s) == wanted)
+    })
+}
+
+/// Bind `action` to `shortcut` (or unbind it with `None`) in `gui.toml`
+/// and re-register. The accelerator must parse and be free.
+pub fn set(app: &AppHandle, action: HotkeyAction, shortcut: Option<String>) -> AppResult<()> {
+    let shortcut = shortcut
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    if let Some(accelerator) = &shortcut {
+        accelerator
+            .parse::<Shortcut>()
+            .map_err(|e| AppError::Validation(format!("Invalid shortcut '{accelerator}': {e}")))?;
+        if let Some(other) = conflict(&gui_config::load(), action, accelerator) {
+            return Err(AppError::Validation(format!(
+                "'{accelerator}' is already used for {other:?}"
+            )));
+        }
+    }
+    let mut result = Ok(());
+    gui_config::update(|cfg| result = action.set_shortcut(cfg, shortcut))?;
+    result?;
+    if action == HotkeyAction::PushToTalk {
+        push_to_talk::sync(app, &crate::synthia_state::read_synthia_state());
+    } else {
+        sync(app);
+    }
+    Ok(())
+}
+
+/// Evdev codes of `modifier`'s left and right keys.
+fn modifier_codes(modifier: TapModifier) -> [u16; 2] {
+    match modifier {
+        TapModifier::Ctrl => [29, 97],
+        TapModifier::Shift => [42, 54],
+        TapModifier::Alt => [56, 100],
+        TapModifier::Super => [125, 126],
+    }
+}
+
+/// Spots a modifier tapped twice on its own: pressed and released within
+/// the interval, then pressed again within the interval of that release
+/// and released as quickly. Any other key pressed in between, or holding
+/// the modifier until it repeats, starts over, so Ctrl+C or a held Ctrl
+/// never count. Either side's key makes a tap.
+struct DoubleTap {
+    codes: [u16; 2],
+    interval: Duration,
+    /// When the modifier went down, while that press may still be a tap.
+    down: Option<Instant>,
+    /// When the first tap ended, while waiting for the second.
+    tapped: Option<Instant>,
+}
+
+impl DoubleTap {
+    fn new(config: &DoubleTapConfig) -> Self {
+        DoubleTap {
+            codes: modifier_codes(config.key),
+            interval: Duration::from_millis(config.interval_ms),
+            down: None,
+            tapped: None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.down = None;
+        self.tapped = None;
+    }
+
+    /// Feed one key event; true when it completes a double tap.
+    fn feed(&mut self, code: u16, value: i32, at: Instant) -> bool {
+        if !self.codes.contains(&code) {
+            if value == KEY_PRESSED {
+                self.reset();
+            }
+            return false;
+        }
+        match value {
+            KEY_PRESSED => {
+                if self.tapped.is_some_and(|t| at.duration_since(t) > self.interval) {
+                    self.tapped = None;
+                }
+                self.down = Some(at);
+                false
+            }
+            KEY_RELEASED => {
+                let quick = self.down.take().is_some_and(|d| at.duration_since(d) <= self.interval);
+                if !quick {
+                    self.reset();
+                    false
+                } else if self.tapped.take().is_some() {
+                    true
+                } else {
+                    self.tapped = Some(at);
+                    false
+                }
+            }
+            // Auto-repeat: the modifier is being held, not tapped.
+            _ => {
+                self.reset();
+                false
+            }
+        }
+    }
+}
+
+/// Save `hotkeys.double_tap` (`None` turns it off) and start listening.
+/// The interval must be in `TAP_INTERVAL_MS` and a keyboard with the
+/// modifier readable.
+pub fn set_double_tap(app: &AppHandle, double_tap: Option<DoubleTapConfig>) -> AppResult<()> {
+    if let Some(config) = &double_tap {
+        if !TAP_INTERVAL_MS.contains(&config.interval_ms) {
+            return Err(AppError::Validation(format!(
+                "Tap interval must be {} to {} ms",
+                TAP_INTERVAL_MS.start(),
+                TAP_INTERVAL_MS.end()
+            )));
+        }
+        let key = modifier_codes(config.key)[0];
+        evdev_input::check(&EvdevBinding { device: None, key })?;
+    }
+    gui_config::update(|cfg| cfg.hotkeys.double_tap = double_tap)?;
+    sync(app);
+    Ok(())
+}
+
+/// Start, stop or restart the double-tap listener to match `wanted`.
+fn sync_double_tap(app: &AppHandle, wanted: Option<DoubleTapConfig>) {
+    let state = app.state::<AppState>();
+    let mut listening = state.double_tap.lock().unwrap_or_else(PoisonError::into_inner);
+    if listening.as_ref().map(|(config, _)| config) == wanted.as_ref() {
+        return;
+    }
+    if let Some((_, stop)) = listening.take() {
+        stop.store(true, Ordering::SeqCst);
+    }
+    let Some(config) = wanted else { return };
+
+    let mut recognizer = DoubleTap::new(&config);
+    let key = recognizer.codes[0];
+    let handle = app.clone();
+    let on_event = move |code, value| {
+        if recognizer.feed(code, value, Instant::now()) {
+            tracing::debug!("double tap");
+            toggle_recording(&handle);
+        }
+    };
+    match evdev_input::listen_all(None, key, on_event) {
+        Ok(stop) => {
+            tracing::info!(key = ?config.key, interval_ms = config.interval_ms, "double tap armed");
+            *listening = Some((config, stop));
+        }
+        Err(e) => tracing::error!(key = ?config.key, error = %e, "failed to listen for double tap"),
+    }
+}
+
+/// Register the `[hotkeys]` shortcuts from `gui.toml`, replacing whatever
+/// was registered before, and arm the double tap. Called at startup and
+/// when the config changes.
+pub fn sync(app: &AppHandle) {
+    let config = gui_config::load();
+    sync_double_tap(app, config.hotkeys.double_tap);
+    let wanted: Vec<(HotkeyAction, String)> = SYNCED
+        .into_iter()
+        .filter_map(|action| action.shortcut(&config).map(|s| (action, s)))
+        .collect();
+    let state = app.state::<AppState>();
+    let mut registered = state
+        .global_hotkeys
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let current: Vec<(HotkeyAction, String)> = registered
+        .iter()
+        .map(|h| (h.action, h.accelerator.clone()))
+        .collect();
+    if current == wanted {
+        return;
+    }
+
+    for old in registered.drain(..) {
+        if let Err(e) = app.global_shortcut().unregister(old.shortcut) {
+            tracing::warn!(shortcut = %old.accelerator, error = %e, "failed to unregister hotkey");
+        }
+        // A held key whose release will now never arrive.
+        forward(old.action, false);
+    }
+    for (action, accelerator) in wanted {
+        let shortcut = match accelerator.parse::<Shortcut>() {
+            Ok(shortcut) => shortcut,
+            Err(e) => {
+                tracing::error!(?action, shortcut = %accelerator, error = %e, "invalid hotkey");
+                continue;
+            }
+        };
+        match app.global_shortcut().register(shortcut) {
+            Ok(()) => {
+                tracing::info!(?action, shortcut = %accelerator, "hotkey registered");
+                registered.push(RegisteredHotkey {
+                    action,
+                    accelerator,
+                    shortcut,
+                });
+            }
+            Err(e) => {
+                tracing::error!(?action, shortcut = %accelerator, error = %e, "failed to register hotkey");
+            }
+        }
+    }
+}
+
+/// Tell the backend a held hotkey went down or up. Sent synchronously so
+/// press and release can't overtake each other.
+pub(crate) fn forward(action: HotkeyAction, pressed: bool) {
+    let Some(name) = action.backend_name() else {
+        return;
+    };
+    #[cfg(unix)]
+    if let Err(e) =
+        crate::ipc::send_command("hotkey", &serde_json::json!({ "action": name, "pressed": pressed }))
+    {
+        tracing::warn!(action = name, pressed, error = %e, "hotkey not forwarded to backend");
+    }
+    #[cfg(not(unix))]
+    tracing::warn!(action = name, pressed, "hotkey forwarding needs the IPC socket");
+}
+
+fn toggle_recording(app: &AppHandle) {
+    if let Err(e) = commands::lifecycle::toggle(&app.state::<AppState>()) {
+        tracing::warn!(error = %e, "toggle-recording hotkey failed");
+    }
+}
+
+/// Plugin handler for every registered global shortcut.
+pub fn on_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    let action = app
+        .state::<AppState>()
+        .global_hotkeys
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|h| &h.shortcut == shortcut)
+        .map(|h| h.action);
+    let pressed = event.state() == ShortcutState::Pressed;
+    match action {
+        Some(HotkeyAction::ToggleRecording) if pressed => toggle_recording(app),
+        Some(HotkeyAction::ToggleRecording) => {}
+        Some(HotkeyAction::CopyLastTranscription) if pressed => {
+            commands::clipboard::copy_last_transcription_or_notify()
+        }
+        Some(HotkeyAction::CopyLastTranscription) => {}
+        Some(action @ (HotkeyAction::Dictation | HotkeyAction::Assistant)) => {
+            forward(action, pressed)
+        }
+        Some(HotkeyAction::PushToTalk) | None => push_to_talk::on_shortcut(app, event),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conflicts_ignore_case_and_spacing() {
+        let mut config = GuiConfig::default();
+        config.hotkeys.dictation = Some("Super+D".into());
+        assert_eq!(
+            conflict(&config, HotkeyAction::Assistant, "super + d"),
+            Some(HotkeyAction::Dictation)
+        );
+        assert_eq!(conflict(&config, HotkeyAction::Dictation, "Super+D"), None);
+        assert_eq!(
+            conflict(&config, HotkeyAction::ToggleRecording, &config.push_to_talk.shortcut.clone()),
+            Some(HotkeyAction::PushToTalk)
+        );
+        assert_eq!(conflict(&config, HotkeyAction::Assistant, "Super+A"), None);
+    }
+
+    #[test]
+    fn double_tap_needs_two_quick_lone_taps() {
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+        let mut tap = DoubleTap::new(&DoubleTapConfig::default());
+        let (ctrl, right_ctrl, c) = (29, 97, 46);
+
+        assert!(!tap.feed(ctrl, KEY_PRESSED, ms(0)));
+        assert!(!tap.feed(ctrl, KEY_RELEASED, ms(80)));
+        assert!(!tap.feed(right_ctrl, KEY_PRESSED, ms(200)));
+        assert!(tap.feed(right_ctrl, KEY_RELEASED, ms(260)));
+
+        // Ctrl+C between the taps.
+        tap.feed(ctrl, KEY_PRESSED, ms(1000));
+        tap.feed(ctrl, KEY_RELEASED, ms(1050));
+        tap.feed(ctrl, KEY_PRESSED, ms(1100));
+        tap.feed(c, KEY_PRESSED, ms(1120));
+        assert!(!tap.feed(ctrl, KEY_RELEASED, ms(1150)));
+
+        // Too slow between taps, then too long a press.
+        tap.feed(ctrl, KEY_PRESSED, ms(2000));
+        tap.feed(ctrl, KEY_RELEASED, ms(2050));
+        tap.feed(ctrl, KEY_PRESSED, ms(2500));
+        assert!(!tap.feed(ctrl, KEY_RELEASED, ms(2550)));
+        tap.feed(ctrl, KEY_PRESSED, ms(2600));
+        assert!(!tap.feed(ctrl, KEY_RELEASED, ms(3200)));
+    }
+}
@@ -0,0 +1,973 @@
+//! GUI-owned settings, persisted as TOML at `~/.config/synthia/gui.toml`.
+//!
+//! `config.yaml` belongs to the Python backend and is written surgically by
+//! `yaml_writer.rs`; settings that only the tray app cares about (window
+//! placement and the like) live here instead, so the GUI can round-trip them
+//! through serde without touching the backend's file.
+//!
+//! Every field is `#[serde(default)]`: a missing or partially-written file
+//! loads as defaults rather than failing.
+
+use std::collections::HashSet;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::paths::write_atomic_private;
+use crate::synthia_state::Mode;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct GuiConfig {
+    pub install: InstallConfig,
+    pub overlay: OverlayConfig,
+    pub main_window: MainWindowConfig,
+    pub runtime: RuntimeConfig,
+    pub backend: BackendConfig,
+    pub logging: LoggingConfig,
+    pub push_to_talk: PushToTalkConfig,
+    pub hotkeys: HotkeysConfig,
+    pub recording: RecordingConfig,
+    pub remote: RemoteConfig,
+    pub tray: TrayConfig,
+    pub power: PowerConfig,
+    pub http_control: HttpControlConfig,
+    pub ws_control: WsControlConfig,
+    pub dbus: DbusConfig,
+    pub i18n: I18nConfig,
+    pub startup: StartupConfig,
+    pub notifications: NotificationsConfig,
+}
+
+/// Where the Synthia checkout and its interpreter live. Each setting can be
+/// overridden from the environment: `SYNTHIA_ROOT`, `SYNTHIA_PYTHON`,
+/// `SYNTHIA_RUN_SCRIPT`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct InstallConfig {
+    /// Checkout directory. `None` searches upward from the GUI executable
+    /// for `run.sh`, then tries `~/dev/misc/synthia`.
+    pub root: Option<PathBuf>,
+    /// Python interpreter. `None` uses `venv/bin/python` in the root
+    /// (`venv/Scripts/python.exe` on Windows); a relative path is taken
+    /// relative to the root.
+    pub python: Option<PathBuf>,
+    /// Backend launch script. `None` uses `run.sh` in the root; a relative
+    /// path is taken relative to the root.
+    pub run_script: Option<PathBuf>,
+}
+
+/// A non-empty environment override, else the configured path.
+fn env_or(var: Option<String>, configured: &Option<PathBuf>) -> Option<PathBuf> {
+    var.filter(|v| !v.trim().is_empty())
+        .map(PathBuf::from)
+        .or_else(|| configured.clone())
+}
+
+impl InstallConfig {
+    pub fn root(&self) -> Option<PathBuf> {
+        env_or(std::env::var("SYNTHIA_ROOT").ok(), &self.root)
+    }
+
+    pub fn python(&self) -> Option<PathBuf> {
+        env_or(std::env::var("SYNTHIA_PYTHON").ok(), &self.python)
+    }
+
+    pub fn run_script(&self) -> Option<PathBuf> {
+        env_or(std::env::var("SYNTHIA_RUN_SCRIPT").ok(), &self.run_script)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverlayConfig {
+    /// Last position the overlay was moved to; `None` keeps the
+    /// `tauri.conf.json` default placement.
+    pub position: Option<SavedPosition>,
+    /// Whether clicks pass through the overlay to the windows underneath.
+    /// `None` follows the overlay's state: click-through except while it
+    /// shows an error.
+    pub click_through: Option<bool>,
+    /// Pin the overlay to a corner of a chosen monitor instead of where it
+    /// was last dragged. Re-applied whenever the monitor layout changes.
+    pub anchor: Option<OverlayAnchor>,
+    /// When the overlay shows and hides itself.
+    pub visibility: OverlayVisibility,
+    /// With `visibility = "auto"`, how long the overlay stays up after the
+    /// assistant goes back to idle.
+    pub auto_hide_secs: u64,
+    /// Whether the overlay shows words as Whisper decodes them.
+    pub transcript: OverlayTranscriptMode,
+    /// Ordinary window or layer-shell surface; chosen once at startup.
+    pub backend: OverlayBackend,
+    /// How a layer-shell overlay sits relative to other surfaces.
+    pub layer_shell: OverlayLayerShellConfig,
+}
+
+impl Default for OverlayConfig {
+    fn default() -> Self {
+        OverlayConfig {
+            position: None,
+            click_through: None,
+            anchor: None,
+            visibility: OverlayVisibility::default(),
+            auto_hide_secs: 3,
+            transcript: OverlayTranscriptMode::default(),
+            backend: OverlayBackend::default(),
+            layer_shell: OverlayLayerShellConfig::default(),
+        }
+    }
+}
+
+/// What kind of surface the overlay is; see `overlay_layer_shell`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayBackend {
+    /// A layer-shell surface on Wayland when the build and compositor
+    /// support it, else an ordinary window.
+    #[default]
+    Auto,
+    /// A layer-shell surface, with a warning and an ordinary window where
+    /// that isn't possible.
+    LayerShell,
+    /// Always an ordinary always-on-top window, as on X11.
+    Window,
+}
+
+/// Which layer-shell layer the overlay is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayLayer {
+    /// Above normal windows but below full-screen ones.
+    Top,
+    /// Above everything, full-screen windows included.
+    #[default]
+    Overlay,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct OverlayLayerShellConfig {
+    pub layer: OverlayLayer,
+    /// Pixels along its edge that other windows keep clear of the overlay.
+    /// 0 floats over them; -1 also ignores space other panels reserve.
+    /// Compositors only honour it for the centre corners, where the overlay
+    /// is pinned to a single edge.
+    pub exclusive_zone: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayTranscriptMode {
+    /// Partial transcription text appears as it is decoded.
+    #[default]
+    Live,
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayVisibility {
+    /// Only `show_overlay`/`hide_overlay` change it.
+    #[default]
+    Manual,
+    /// Shown while listening, thinking, speaking or showing an error;
+    /// hidden `auto_hide_secs` after returning to idle.
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OverlayAnchor {
+    /// Monitor name as reported by the windowing system. If it isn't
+    /// connected, the overlay anchors to the primary monitor instead.
+    pub monitor: String,
+    #[serde(default)]
+    pub corner: OverlayCorner,
+    /// Nudge from the anchored spot, in physical pixels.
+    #[serde(default)]
+    pub offset: OverlayOffset,
+}
+
+/// Where on the monitor's work area an anchored overlay sits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayCorner {
+    TopLeft,
+    TopCenter,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+/// Positive `x` moves right, positive `y` moves down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct OverlayOffset {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct MainWindowConfig {
+    /// `None` keeps the `tauri.conf.json` defaults.
+    pub position: Option<SavedPosition>,
+    pub size: Option<SavedSize>,
+}
+
+/// How the GUI manages the backend process it spawns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackendConfig {
+    /// How long the backend gets to exit on its own after being sent the
+    /// `shutdown` command, before it is signalled. 0 skips straight to
+    /// SIGTERM.
+    pub shutdown_timeout_ms: u64,
+    /// Time between SIGTERM and SIGKILL when stopping the backend. Raise it
+    /// for backends that flush state on exit; lower it for ones that hang.
+    pub shutdown_grace_ms: u64,
+    /// Keep the backend exit history across GUI restarts.
+    pub persist_exit_history: bool,
+    /// Log what starting/stopping the backend and remote bot would run
+    /// instead of running it; see `dry_run`. `SYNTHIA_DRY_RUN` also enables it.
+    pub dry_run: bool,
+    /// Restart the backend when it exits without being asked to.
+    pub auto_restart: bool,
+    /// Crash restarts in a row before giving up; see `supervisor`.
+    pub max_restarts: u32,
+    /// Delay before the first crash restart, doubled for each one after.
+    pub restart_backoff_ms: u64,
+    /// Device the backend and remote bot transcribe on; read when they
+    /// start. See `compute_devices`.
+    pub compute_device: ComputeDevice,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ComputeDevice {
+    /// A CUDA GPU if there is one, else a ROCm GPU, else the CPU.
+    Auto,
+    /// GPUs hidden. The default, as GPU inference with the backend's
+    /// current CTranslate2 build is unreliable.
+    #[default]
+    Cpu,
+    Cuda,
+    Rocm,
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        BackendConfig {
+            shutdown_timeout_ms: 5000,
+            shutdown_grace_ms: 3000,
+            persist_exit_history: true,
+            dry_run: false,
+            auto_restart: true,
+            max_restarts: 3,
+            restart_backoff_ms: 2000,
+            compute_device: ComputeDevice::Cpu,
+        }
+    }
+}
+
+/// Longest wait between crash restarts, however many came before.
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(60);
+
+impl BackendConfig {
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_millis(self.shutdown_timeout_ms)
+    }
+
+    pub fn shutdown_grace(&self) -> Duration {
+        Duration::from_millis(self.shutdown_grace_ms)
+    }
+
+    /// How long to wait before crash restart number `attempt` (from 0), or
+    /// `None` once restarts are off or used up.
+    pub fn restart_delay(&self, attempt: u32) -> Option<Duration> {
+        restart_delay(self.auto_restart, self.max_restarts, self.restart_backoff_ms, attempt)
+    }
+}
+
+/// `backoff_ms` doubled for each restart before `attempt`, capped at
+/// `MAX_RESTART_BACKOFF`; `None` once restarts are off or used up.
+fn restart_delay(enabled: bool, max: u32, backoff_ms: u64, attempt: u32) -> Option<Duration> {
+    if !enabled || attempt >= max {
+        return None;
+    }
+    let factor = 1u64.checked_shl(attempt).unwrap_or(u64::MAX);
+    let delay = Duration::from_millis(backoff_ms.saturating_mul(factor));
+    Some(delay.min(MAX_RESTART_BACKOFF))
+}
+
+/// Where the backend's runtime files live and how the GUI watches them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuntimeConfig {
+    /// Directory holding `synthia-state.json`, the lock file, history, etc.
+    /// `None` uses `$XDG_RUNTIME_DIR`, else `/tmp` — the backend's own
+    /// default. Windows uses the temp directory.
+    pub dir: Option<PathBuf>,
+    /// Poll interval for the state file when filesystem notifications are
+    /// unavailable (e.g. inotify limits exhausted, network filesystems).
+    pub state_poll_interval_ms: u64,
+    /// Listen on `synthia.sock` in the runtime dir for state updates and
+    /// events pushed by the backend, and to send it commands. The state file
+    /// is still watched either way.
+    pub ipc_socket: bool,
+    /// A ready backend whose state heartbeat is older than this is shown as
+    /// unresponsive; 0 turns the check off. See `backend_health`.
+    pub heartbeat_timeout_secs: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// `EnvFilter` directive used when `RUST_LOG` is unset.
+    pub level: String,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        LoggingConfig {
+            level: "info".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteConfig {
+    /// Chat service remote mode runs over; see `remote_channel`. Read each
+    /// time remote mode starts.
+    pub channel: RemoteChannelKind,
+    /// Start the bot even with an empty allowlist in config.yaml, letting
+    /// anyone who finds it issue commands. Off unless set explicitly.
+    pub allow_all_users: bool,
+    /// Env file holding `TELEGRAM_BOT_TOKEN=...`, read when config.yaml has
+    /// no `telegram_bot_token`. Keeps the secret out of the main config.
+    pub token_env_file: Option<PathBuf>,
+    /// Bot script to run instead of the channel's own under
+    /// `src/synthia/remote/` in the Synthia root.
+    pub bot_script: Option<PathBuf>,
+    /// Remote mode is stopped after this many minutes without the bot
+    /// handling a message. `0` disables it.
+    pub idle_timeout_mins: u64,
+    /// Relaunch the bot when it exits while remote mode is on.
+    pub auto_restart: bool,
+    /// Crash relaunches in a row before remote mode is turned off.
+    pub max_restarts: u32,
+    /// Delay before the first relaunch, doubled for each one after.
+    pub restart_backoff_ms: u64,
+    /// Which messages for Claude Code wait for approval on the desktop;
+    /// see `remote_approval`.
+    pub approval: RemoteApproval,
+    /// The bot drops a message nobody approved within this many seconds.
+    pub approval_timeout_secs: u64,
+}
+
+impl Default for RemoteConfig {
+    fn default() -> Self {
+        RemoteConfig {
+            channel: RemoteChannelKind::Telegram,
+            allow_all_users: false,
+            token_env_file: None,
+            bot_script: None,
+            idle_timeout_mins: 120,
+            auto_restart: true,
+            max_restarts: 5,
+            restart_backoff_ms: 2000,
+            approval: RemoteApproval::Destructive,
+            approval_timeout_secs: 300,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteApproval {
+    /// Forward everything straight away.
+    Off,
+    /// Hold messages that look like they delete or overwrite things.
+    #[default]
+    Destructive,
+    /// Hold every message.
+    Always,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteChannelKind {
+    #[default]
+    Telegram,
+    Discord,
+    Matrix,
+}
+
+impl RemoteConfig {
+    /// How long to wait before relaunching the bot after crash number
+    /// `attempt` (from 0), or `None` once relaunches are off or used up.
+    pub fn restart_delay(&self, attempt: u32) -> Option<Duration> {
+        restart_delay(self.auto_restart, self.max_restarts, self.restart_backoff_ms, attempt)
+    }
+}
+
+/// Language for the tray, dialogs and notifications; see `i18n`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct I18nConfig {
+    /// Locale such as `de` or `fr_FR`. `None` follows the system locale.
+    pub locale: Option<String>,
+}
+
+/// What happens when the session starts; see `settings`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct StartupConfig {
+    /// Launch the GUI at login through an XDG autostart entry.
+    pub autostart: bool,
+    /// Recording mode restored into the state file at launch. `None` keeps
+    /// whatever the state file already says.
+    pub mode: Option<Mode>,
+}
+
+/// Local HTTP endpoint for driving Synthia from scripts; see `http_control`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpControlConfig {
+    /// Off by default: anything that can reach the port and knows the token
+    /// can start and stop the backend.
+    pub enabled: bool,
+    /// Port on 127.0.0.1.
+    pub port: u16,
+    /// Where older versions kept the API token. A token found here is moved
+    /// into the keyring on start and cleared.
+    pub token: String,
+}
+
+impl Default for HttpControlConfig {
+    fn default() -> Self {
+        HttpControlConfig {
+            enabled: false,
+            port: 7865,
+            token: String::new(),
+        }
+    }
+}
+
+/// WebSocket server for Stream Deck plugins, scripts and other machines;
+/// see `ws_control`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WsControlConfig {
+    pub enabled: bool,
+    /// Address to listen on. Loopback by default; `0.0.0.0` lets other
+    /// machines on the LAN connect, with nothing but the token to stop them.
+    pub bind: IpAddr,
+    pub port: u16,
+    /// Where older versions kept the handshake token. A token found here is
+    /// moved into the keyring on start and cleared.
+    pub token: String,
+}
+
+impl Default for WsControlConfig {
+    fn default() -> Self {
+        WsControlConfig {
+            enabled: false,
+            bind: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            port: 7866,
+            token: String::new(),
+        }
+    }
+}
+
+/// The `org.synthia.Control1` session bus interface; see `dbus_control`.
+/// Linux only.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DbusConfig {
+    pub enabled: bool,
+}
+
+impl Default for DbusConfig {
+    fn default() -> Self {
+        DbusConfig { enabled: true }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerConfig {
+    /// Back off while running on battery: slower state polling and no tray
+    /// animation. Full responsiveness returns on AC power.
+    pub low_power_on_battery: bool,
+    /// State file poll interval used instead of `runtime.state_poll_interval_ms`
+    /// while in low-power mode. Only matters when falling back to polling.
+    pub battery_poll_interval_ms: u64,
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        PowerConfig {
+            low_power_on_battery: true,
+            battery_poll_interval_ms: 500,
+        }
+    }
+}
+
+impl PowerConfig {
+    /// Battery poll interval, clamped like `RuntimeConfig::poll_interval`.
+    pub fn battery_poll_interval(&self) -> Duration {
+        Duration::from_millis(
+            self.battery_poll_interval_ms
+                .clamp(MIN_POLL_INTERVAL_MS, MAX_POLL_INTERVAL_MS),
+        )
+    }
+}
+
+/// Which desktop notifications the GUI shows; see `notifications`. Errors
+/// that need attention are shown regardless.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationsConfig {
+    /// Master switch for everything below.
+    pub enabled: bool,
+    /// A preview of each finished transcription, with a Copy action.
+    pub transcription: bool,
+    /// The backend crashing, whether or not it is restarted.
+    pub backend_crash: bool,
+    /// The remote-mode bot starting or being relaunched.
+    pub remote_connected: bool,
+    /// Transcriptions longer than this are cut off in the preview. Copy
+    /// always copies the full text.
+    pub preview_chars: usize,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        NotificationsConfig {
+            enabled: true,
+            transcription: true,
+            backend_crash: true,
+            remote_connected: true,
+            preview_chars: 120,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrayConfig {
+    /// Ask before the tray's Quit item stops the assistant.
+    pub confirm_on_quit: bool,
+    /// Animate the icon while recording and transcribing. Off keeps static
+    /// icons, for desktops that redraw tray icons slowly or not at all.
+    pub animate: bool,
+}
+
+impl Default for TrayConfig {
+    fn default() -> Self {
+        TrayConfig {
+            confirm_on_quit: true,
+            animate: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PushToTalkConfig {
+    /// Global shortcut held to record, in the global-shortcut plugin's
+    /// accelerator syntax (e.g. `CommandOrControl+Shift+Space`).
+    pub shortcut: String,
+    /// Recording is released after this long even without a key-up, in case
+    /// the release was missed (focus loss, suspend).
+    pub max_hold_secs: u64,
+    /// What is held to record.
+    pub input: PushToTalkInput,
+    /// The key held with `input = "evdev"`.
+    pub evdev: EvdevBinding,
+}
+
+impl Default for PushToTalkConfig {
+    fn default() -> Self {
+        PushToTalkConfig {
+            shortcut: "CommandOrControl+Shift+Space".to_string(),
+            max_hold_secs: 60,
+            input: PushToTalkInput::default(),
+            evdev: EvdevBinding::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PushToTalkInput {
+    /// `shortcut`, through the global-shortcut plugin.
+    #[default]
+    Shortcut,
+    /// A key read from `/dev/input`, for Wayland compositors without global
+    /// shortcuts; see `evdev_input`.
+    Evdev,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvdevBinding {
+    /// Keyboard name as in `/proc/bus/input/devices`; `None` listens on
+    /// every keyboard that has the key.
+    pub device: Option<String>,
+    /// Linux key code (`KEY_*` in input-event-codes.h).
+    pub key: u16,
+}
+
+impl Default for EvdevBinding {
+    fn default() -> Self {
+        // KEY_RIGHTCTRL, the backend's default dictation key.
+        EvdevBinding { device: None, key: 97 }
+    }
+}
+
+/// Global shortcuts the GUI registers itself, in the same accelerator syntax
+/// as `push_to_talk.shortcut`. `None` leaves the action to the backend's own
+/// key listener (or unbound); see `global_hotkeys`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct HotkeysConfig {
+    /// Held for dictation; press and release are forwarded to the backend.
+    pub dictation: Option<String>,
+    /// Held for the assistant; forwarded like `dictation`.
+    pub assistant: Option<String>,
+    /// Pressed to start or stop recording, like the tray's toggle.
+    pub toggle_recording: Option<String>,
+    /// Pressed to copy the latest transcription to the clipboard.
+    pub copy_last_transcription: Option<String>,
+    /// Tap a modifier twice to start or stop recording; read from
+    /// `/dev/input`, since a lone modifier can't be a global shortcut.
+    /// `None` turns it off.
+    pub double_tap: Option<DoubleTapConfig>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TapModifier {
+    /// Either Ctrl key.
+    #[default]
+    Ctrl,
+    Shift,
+    Alt,
+    /// Either Super (Windows, Command) key.
+    Super,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DoubleTapConfig {
+    pub key: TapModifier,
+    /// Longest a tap may last, and longest gap between the two taps.
+    pub interval_ms: u64,
+}
+
+impl Default for DoubleTapConfig {
+    fn default() -> Self {
+        DoubleTapConfig { key: TapModifier::Ctrl, interval_ms: 300 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingConfig {
+    /// A recording still active after this many seconds is forced off, as a
+    /// guard against a wedged listener. `0` disables the cutoff.
+    pub max_recording_secs: u64,
+    /// A recording is stopped once the state file has gone this many seconds
+    /// without a change. `0` disables it.
+    pub idle_timeout_secs: u64,
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        RecordingConfig {
+            max_recording_secs: 600,
+            idle_timeout_secs: 300,
+        }
+    }
+}
+
+pub const MIN_POLL_INTERVAL_MS: u64 = 10;
+pub const MAX_POLL_INTERVAL_MS: u64 = 5000;
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        RuntimeConfig {
+            dir: None,
+            state_poll_interval_ms: 50,
+            ipc_socket: true,
+            heartbeat_timeout_secs: 15,
+        }
+    }
+}
+
+/// `runtime.dir` values already warned about; the runtime dir is looked up
+/// on every state read, and one warning per bad value is enough.
+static WARNED_DIRS: Mutex<Option<HashSet<PathBuf>>> = Mutex::new(None);
+
+impl RuntimeConfig {
+    /// The configured runtime dir, if it is an absolute path to an existing
+    /// directory. Anything else is ignored so a typo can't strand the GUI.
+    pub fn validated_dir(&self) -> Option<PathBuf> {
+        let dir = self.dir.as_ref()?;
+        if dir.is_absolute() && dir.is_dir() {
+            return Some(dir.clone());
+        }
+        let mut warned = WARNED_DIRS.lock().unwrap_or_else(PoisonError::into_inner);
+        if warned.get_or_insert_with(HashSet::new).insert(dir.clone()) {
+            tracing::warn!(
+                dir = %dir.display(),
+                "ignoring runtime.dir: not an absolute path to an existing directory"
+            );
+        }
+        None
+    }
+
+    /// Poll interval clamped to `MIN_POLL_INTERVAL_MS..=MAX_POLL_INTERVAL_MS`.
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(
+            self.state_poll_interval_ms
+                .clamp(MIN_POLL_INTERVAL_MS, MAX_POLL_INTERVAL_MS),
+        )
+    }
+}
+
+/// A window's outer position in physical pixels, plus the name of the
+/// monitor it was on so a restore can tell whether that display is still
+/// connected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedPosition {
+    pub x: i32,
+    pub y: i32,
+    #[serde(default)]
+    pub monitor: Option<String>,
+}
+
+/// A window's inner size in physical pixels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+pub fn gui_config_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("synthia")
+        .join("gui.toml")
+}
+
+/// Load the GUI config, falling back to defaults if the file is missing or
+/// malformed. A malformed file is logged and left untouched on disk.
+pub fn load() -> GuiConfig {
+    try_load().unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "failed to load GUI config; using defaults");
+        GuiConfig::default()
+    })
+}
+
+/// Load the GUI config, treating a missing file as defaults but reporting a
+/// file that can't be read or parsed.
+pub fn try_load() -> AppResult<GuiConfig> {
+    read_from(&gui_config_path())
+}
+
+fn read_from(path: &Path) -> AppResult<GuiConfig> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(GuiConfig::default()),
+        Err(e) => return Err(e.into()),
+    };
+    toml::from_str(&content).map_err(|e| {
+        AppError::Validation(format!("Failed to parse {}: {}", path.display(), e))
+    })
+}
+
+pub fn save(config: &GuiConfig) -> AppResult<()> {
+    let path = gui_config_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = toml::to_string_pretty(config)
+        .map_err(|e| AppError::Other(format!("Failed to serialize GUI config: {}", e)))?;
+    write_atomic_private(&path, content)?;
+    *CACHE.lock().unwrap_or_else(PoisonError::into_inner) = Some(config.clone());
+    Ok(())
+}
+
+/// The config as last loaded, for hot paths like the install and runtime
+/// path helpers. Filled on first use and kept current by `save` and by
+/// `config_watch` calling `refresh` when the file changes.
+static CACHE: Mutex<Option<GuiConfig>> = Mutex::new(None);
+
+/// The cached config, loading it on first use.
+pub fn cached() -> GuiConfig {
+    if let Some(config) = CACHE.lock().unwrap_or_else(PoisonError::into_inner).as_ref() {
+        return config.clone();
+    }
+    refresh()
+}
+
+/// Re-read the file into the cache and return the result.
+pub fn refresh() -> GuiConfig {
+    let config = load();
+    *CACHE.lock().unwrap_or_else(PoisonError::into_inner) = Some(config.clone());
+    config
+}
+
+/// Read-modify-write helper for single-setting updates. Refuses to write if
+/// the existing file doesn't parse, so a typo isn't replaced by defaults.
+pub fn update(f: impl FnOnce(&mut GuiConfig)) -> AppResult<()> {
+    let mut config = try_load()?;
+    f(&mut config);
+    save(&config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restart_delay_doubles_up_to_the_limit() {
+        let backend = BackendConfig::default();
+        assert_eq!(backend.restart_delay(0), Some(Duration::from_secs(2)));
+        assert_eq!(backend.restart_delay(2), Some(Duration::from_secs(8)));
+        assert_eq!(backend.restart_delay(3), None);
+
+        let many = BackendConfig { max_restarts: 100, ..BackendConfig::default() };
+        assert_eq!(many.restart_delay(70), Some(MAX_RESTART_BACKOFF));
+        let off = BackendConfig { auto_restart: false, ..BackendConfig::default() };
+        assert_eq!(off.restart_delay(0), None);
+    }
+
+    #[test]
+    fn remote_restart_policy_has_its_own_limit() {
+        let remote = RemoteConfig::default();
+        assert_eq!(remote.restart_delay(4), Some(Duration::from_secs(32)));
+        assert_eq!(remote.restart_delay(5), None);
+        let off = RemoteConfig { auto_restart: false, ..RemoteConfig::default() };
+        assert_eq!(off.restart_delay(0), None);
+    }
+
+    #[test]
+    fn empty_file_loads_defaults() {
+        let cfg: GuiConfig = toml::from_str("").unwrap();
+        assert_eq!(cfg, GuiConfig::default());
+        assert!(cfg.overlay.position.is_none());
+    }
+
+    #[test]
+    fn missing_file_reads_as_defaults_but_malformed_file_errors() {
+        let dir = std::env::temp_dir()
+            .join(format!("synthia-gui-config-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("gui.toml");
+        let _ = fs::remove_file(&path);
+        assert_eq!(read_from(&path).unwrap(), GuiConfig::default());
+
+        fs::write(&path, "[overlay\n").unwrap();
+        assert!(matches!(read_from(&path), Err(AppError::Validation(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn runtime_defaults_match_backend() {
+        let cfg: GuiConfig = toml::from_str("[runtime]\n").unwrap();
+        assert_eq!(cfg.runtime.state_poll_interval_ms, 50);
+        assert!(cfg.runtime.dir.is_none());
+    }
+
+    #[test]
+    fn backend_shutdown_grace_is_configurable() {
+        let cfg: GuiConfig = toml::from_str("").unwrap();
+        assert_eq!(cfg.backend.shutdown_grace(), Duration::from_secs(3));
+        assert_eq!(cfg.backend.shutdown_timeout(), Duration::from_secs(5));
+        let cfg: GuiConfig =
+            toml::from_str("[backend]\nshutdown_grace_ms = 500\nshutdown_timeout_ms = 0\n")
+                .unwrap();
+        assert_eq!(cfg.backend.shutdown_grace(), Duration::from_millis(500));
+        assert!(cfg.backend.shutdown_timeout().is_zero());
+    }
+
+    #[test]
+    fn runtime_validation_rejects_bad_values() {
+        let cfg = RuntimeConfig {
+            dir: Some(PathBuf::from("relative/dir")),
+            state_poll_interval_ms: 0,
+            ..Default::default()
+        };
+        assert!(cfg.validated_dir().is_none());
+        assert_eq!(cfg.poll_interval(), Duration::from_millis(MIN_POLL_INTERVAL_MS));
+
+        let cfg = RuntimeConfig {
+            dir: Some(std::env::temp_dir()),
+            state_poll_interval_ms: 1_000_000,
+            ..Default::default()
+        };
+        assert_eq!(cfg.validated_dir(), Some(std::env::temp_dir()));
+        assert_eq!(cfg.poll_interval(), Duration::from_millis(MAX_POLL_INTERVAL_MS));
+    }
+
+    #[test]
+    fn install_paths_prefer_non_empty_env() {
+        let configured = Some(PathBuf::from("/opt/synthia"));
+        assert_eq!(env_or(None, &configured), configured);
+        assert_eq!(env_or(Some("  ".into()), &configured), configured);
+        assert_eq!(env_or(Some("/srv/synthia".into()), &configured), Some("/srv/synthia".into()));
+        assert_eq!(env_or(None, &None), None);
+    }
+
+    #[test]
+    fn overlay_position_round_trip() {
+        let mut cfg = GuiConfig::default();
+        cfg.overlay.position = Some(SavedPosition {
+            x: -1280,
+            y: 40,
+            monitor: Some("DP-1".to_string()),
+        });
+        let text = toml::to_string_pretty(&cfg).unwrap();
+        let parsed: GuiConfig = toml::from_str(&text).unwrap();
+        assert_eq!(parsed, cfg);
+    }
+
+    #[test]
+    fn overlay_anchor_without_offset_loads_unshifted() {
+        let cfg: GuiConfig =
+            toml::from_str("[overlay.anchor]\nmonitor = \"HDMI-1\"\ncorner = \"bottom_center\"\n")
+                .unwrap();
+        let anchor = cfg.overlay.anchor.unwrap();
+        assert_eq!(anchor.corner, OverlayCorner::BottomCenter);
+        assert_eq!(anchor.offset, OverlayOffset::default());
+    }
+
+    #[test]
+    fn overlay_backend_defaults_to_auto_on_the_overlay_layer() {
+        let cfg: GuiConfig = toml::from_str("[overlay]\nvisibility = \"auto\"\n").unwrap();
+        assert_eq!(cfg.overlay.backend, OverlayBackend::Auto);
+        assert_eq!(cfg.overlay.layer_shell.layer, OverlayLayer::Overlay);
+        assert_eq!(cfg.overlay.layer_shell.exclusive_zone, 0);
+
+        let cfg: GuiConfig = toml::from_str(
+            "[overlay]\nbackend = \"layer_shell\"\n[overlay.layer_shell]\nexclusive_zone = 40\n",
+        )
+        .unwrap();
+        assert_eq!(cfg.overlay.backend, OverlayBackend::LayerShell);
+        assert_eq!(cfg.overlay.layer_shell.exclusive_zone, 40);
+    }
+}
@@ -0,0 +1,160 @@
+//! Rendering transcription history as Markdown, JSON or CSV for
+//! `export_history`.
+//!
+//! Markdown groups entries under a heading per local day, for reading;
+//! JSON is the `HistoryEntry` list as the UI sees it; CSV has one row per
+//! entry with RFC 4180 quoting, for spreadsheets and scripts. All three
+//! list entries oldest first.
+
+use chrono::{DateTime, FixedOffset};
+use serde::Deserialize;
+
+use crate::error::AppResult;
+use crate::history_store::HistoryEntry;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+        }
+    }
+
+    /// Name shown for the file type in the save dialog.
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Csv => "CSV",
+        }
+    }
+}
+
+pub fn render(format: ExportFormat, entries: &[HistoryEntry]) -> AppResult<String> {
+    Ok(match format {
+        ExportFormat::Markdown => markdown(entries),
+        ExportFormat::Json => serde_json::to_string_pretty(entries)? + "\n",
+        ExportFormat::Csv => csv(entries),
+    })
+}
+
+fn parse_timestamp(entry: &HistoryEntry) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(&entry.timestamp).ok()
+}
+
+fn markdown(entries: &[HistoryEntry]) -> String {
+    let mut out = String::from("# Synthia transcriptions\n");
+    let mut day = None;
+    for entry in entries {
+        let at = parse_timestamp(entry);
+        let date = at.map(|t| t.format("%A, %-d %B %Y").to_string());
+        if date != day {
+            out.push_str(&format!("\n## {}\n\n", date.as_deref().unwrap_or("Unknown date")));
+            day = date;
+        }
+        let time = at.map(|t| t.format("%H:%M").to_string()).unwrap_or_default();
+        // Keep multi-line text inside its list item.
+        let text = entry.text.trim().replace('\n', "\n  ");
+        out.push_str(&format!("- **{time}** _{}_ — {text}\n", entry.mode));
+        if let Some(response) = entry.response.as_deref().filter(|r| !r.trim().is_empty()) {
+            out.push_str(&format!("  > {}\n", response.trim().replace('\n', "\n  > ")));
+        }
+    }
+    out
+}
+
+/// `field` quoted if it holds a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn csv(entries: &[HistoryEntry]) -> String {
+    let mut out = String::from("id,timestamp,mode,duration_secs,model,text,response\r\n");
+    for entry in entries {
+        let row = [
+            entry.id.to_string(),
+            entry.timestamp.clone(),
+            entry.mode.clone(),
+            entry.duration_secs.map(|d| format!("{d:.1}")).unwrap_or_default(),
+            entry.model.clone().unwrap_or_default(),
+            entry.text.clone(),
+            entry.response.clone().unwrap_or_default(),
+        ];
+        let row: Vec<String> = row.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&row.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: i64, timestamp: &str, text: &str, response: Option<&str>) -> HistoryEntry {
+        HistoryEntry {
+            id,
+            text: text.into(),
+            mode: if response.is_some() { "assistant" } else { "dictation" }.into(),
+            timestamp: timestamp.into(),
+            duration_secs: Some(2.5),
+            model: Some("small".into()),
+            response: response.map(Into::into),
+        }
+    }
+
+    fn sample() -> Vec<HistoryEntry> {
+        vec![
+            entry(1, "2024-05-06T09:30:00+02:00", "Buy milk, eggs", None),
+            entry(2, "2024-05-06T18:05:00+02:00", "What's \"up\"?", Some("Not much.\nYou?")),
+            entry(3, "2024-05-07T08:00:00+02:00", "New day", None),
+        ]
+    }
+
+    #[test]
+    fn markdown_groups_by_day() {
+        let md = markdown(&sample());
+        assert_eq!(
+            md,
+            "# Synthia transcriptions\n\
+             \n## Monday, 6 May 2024\n\n\
+             - **09:30** _dictation_ — Buy milk, eggs\n\
+             - **18:05** _assistant_ — What's \"up\"?\n  > Not much.\n  > You?\n\
+             \n## Tuesday, 7 May 2024\n\n\
+             - **08:00** _dictation_ — New day\n"
+        );
+    }
+
+    #[test]
+    fn csv_quotes_fields_that_need_it() {
+        let csv = csv(&sample());
+        let lines: Vec<_> = csv.split("\r\n").collect();
+        assert_eq!(lines[0], "id,timestamp,mode,duration_secs,model,text,response");
+        assert_eq!(lines[1], "1,2024-05-06T09:30:00+02:00,dictation,2.5,small,\"Buy milk, eggs\",");
+        assert_eq!(
+            lines[2],
+            "2,2024-05-06T18:05:00+02:00,assistant,2.5,small,\"What's \"\"up\"\"?\",\"Not much.\nYou?\""
+        );
+    }
+
+    #[test]
+    fn json_round_trips_entries() {
+        let json = render(ExportFormat::Json, &sample()).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[1]["response"], "Not much.\nYou?");
+        assert_eq!(parsed.as_array().unwrap().len(), 3);
+    }
+}
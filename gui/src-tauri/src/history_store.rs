@@ -0,0 +1,761 @@
+//! Transcription history, kept in SQLite at
+//! `$XDG_DATA_HOME/synthia/history.db`.
+//!
+//! Every `transcription` event from the backend becomes a row; see `ipc`.
+//! The backend's own `synthia-history.json` only holds its last 50 entries,
+//! so it is imported once, when the database is created, and not read again.
+//!
+//! Each caller opens its own connection: the IPC reader records while
+//! commands page and delete, and SQLite serializes the writers, with
+//! `BUSY_TIMEOUT` covering any overlap.
+//!
+//! An FTS5 index over the text and the assistant's reply, kept in step by
+//! triggers, backs `search`.
+//!
+//! Remote mode's exchanges are logged alongside, in `remote_activity`: each
+//! message from an allowed user and what became of it, as the bot reports
+//! them (see `commands::remote`).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::AppResult;
+use crate::get_history_file;
+
+/// Bumped whenever `migrate` learns a new step; stored as `user_version`.
+const SCHEMA_VERSION: i32 = 3;
+
+/// Entries per `get_history` page.
+pub const PAGE_SIZE: u32 = 50;
+
+/// How long a connection waits for another one's write to finish.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Most results `search` returns.
+pub const SEARCH_LIMIT: u32 = 100;
+
+/// Most entries `remote_log` returns.
+pub const REMOTE_LOG_LIMIT: u32 = 1000;
+
+/// Wrap matched terms in `snippet()` output. Private-use characters, so
+/// they can't clash with anything dictated.
+const MATCH_START: char = '\u{E000}';
+const MATCH_END: char = '\u{E001}';
+
+/// One stored transcription.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub id: i64,
+    pub text: String,
+    /// `dictation` or `assistant`.
+    pub mode: String,
+    /// RFC 3339, in local time.
+    pub timestamp: String,
+    /// Length of the recording, when the backend reported it.
+    pub duration_secs: Option<f64>,
+    /// Whisper model size, or `google`.
+    pub model: Option<String>,
+    /// The assistant's spoken reply.
+    pub response: Option<String>,
+}
+
+/// Payload of the backend's `transcription` event, as stored.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct NewEntry {
+    pub text: String,
+    pub mode: String,
+    pub duration_secs: Option<f64>,
+    pub model: Option<String>,
+    pub response: Option<String>,
+}
+
+/// Narrows `get_history`. Every field is optional; set ones must all match.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct HistoryFilter {
+    pub mode: Option<String>,
+    /// Case-insensitive substring of the text.
+    pub contains: Option<String>,
+    /// Inclusive bounds on when the entry was recorded.
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Inclusive bounds on when entries were recorded; either end may be open.
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(default)]
+pub struct DateRange {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// A `search` result.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub entry: HistoryEntry,
+    /// The best-matching stretch of the text or reply, split so matched
+    /// words can be highlighted.
+    pub snippet: Vec<SnippetPart>,
+    /// Relevance; higher is better.
+    pub score: f64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SnippetPart {
+    pub text: String,
+    pub highlight: bool,
+}
+
+/// One page of `get_history`, newest first.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct HistoryPage {
+    pub entries: Vec<HistoryEntry>,
+    /// Entries matching the filter across all pages.
+    pub total: u64,
+    pub page: u32,
+    pub page_size: u32,
+}
+
+/// One message of a remote-mode exchange.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct RemoteActivity {
+    pub id: i64,
+    /// RFC 3339, in local time.
+    pub timestamp: String,
+    /// The chat service, e.g. `Telegram`.
+    pub channel: String,
+    /// `in` from the user, `out` from Synthia.
+    pub direction: String,
+    /// The user's ID on the service, or `synthia`.
+    pub sender: String,
+    /// `message`, `command`, `voice`, `transcript`, `file`, `photo` coming
+    /// in; `forwarded`, `denied`, `expired`, `failed` or `reply` going out.
+    pub kind: String,
+    pub text: String,
+}
+
+/// A remote exchange as the bot reports it; the channel is added on record.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct NewRemoteActivity {
+    pub direction: String,
+    pub sender: String,
+    pub kind: String,
+    #[serde(default)]
+    pub text: String,
+}
+
+/// Emitted as `history-added` for each recorded transcription.
+#[cfg_attr(not(unix), allow(dead_code))]
+#[derive(Serialize, Debug, Clone)]
+struct HistoryAdded<'a> {
+    entry: &'a HistoryEntry,
+}
+
+fn db_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("synthia")
+        .join("history.db")
+}
+
+/// Open the history database, creating or migrating it as needed.
+pub fn open() -> AppResult<Connection> {
+    let path = db_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let conn = Connection::open(&path)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    migrate(&conn, Some(&get_history_file()))?;
+    Ok(conn)
+}
+
+/// Bring the schema up to `SCHEMA_VERSION`, importing `legacy` when the
+/// database is new.
+fn migrate(conn: &Connection, legacy: Option<&Path>) -> AppResult<()> {
+    let version: i32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version >= SCHEMA_VERSION {
+        return Ok(());
+    }
+    let tx = conn.unchecked_transaction()?;
+    if version < 1 {
+        tx.execute_batch(
+            "CREATE TABLE IF NOT EXISTS transcriptions (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 text TEXT NOT NULL,
+                 mode TEXT NOT NULL,
+                 created_at INTEGER NOT NULL,
+                 duration_secs REAL,
+                 model TEXT,
+                 response TEXT
+             );
+             CREATE INDEX IF NOT EXISTS transcriptions_created_at
+                 ON transcriptions (created_at);",
+        )?;
+        if let Some(path) = legacy {
+            import_legacy(&tx, path);
+        }
+    }
+    if version < 2 {
+        tx.execute_batch(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS transcriptions_fts USING fts5(
+                 text, response,
+                 content = 'transcriptions', content_rowid = 'id',
+                 tokenize = 'unicode61 remove_diacritics 2'
+             );
+             CREATE TRIGGER IF NOT EXISTS transcriptions_fts_insert
+             AFTER INSERT ON transcriptions BEGIN
+                 INSERT INTO transcriptions_fts (rowid, text, response)
+                 VALUES (new.id, new.text, new.response);
+             END;
+             CREATE TRIGGER IF NOT EXISTS transcriptions_fts_delete
+             AFTER DELETE ON transcriptions BEGIN
+                 INSERT INTO transcriptions_fts (transcriptions_fts, rowid, text, response)
+                 VALUES ('delete', old.id, old.text, old.response);
+             END;
+             CREATE TRIGGER IF NOT EXISTS transcriptions_fts_update
+             AFTER UPDATE ON transcriptions BEGIN
+                 INSERT INTO transcriptions_fts (transcriptions_fts, rowid, text, response)
+                 VALUES ('delete', old.id, old.text, old.response);
+                 INSERT INTO transcriptions_fts (rowid, text, response)
+                 VALUES (new.id, new.text, new.response);
+             END;
+             INSERT INTO transcriptions_fts (transcriptions_fts) VALUES ('rebuild');",
+        )?;
+    }
+    if version < 3 {
+        tx.execute_batch(
+            "CREATE TABLE IF NOT EXISTS remote_activity (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 created_at INTEGER NOT NULL,
+                 channel TEXT NOT NULL,
+                 direction TEXT NOT NULL,
+                 sender TEXT NOT NULL,
+                 kind TEXT NOT NULL,
+                 text TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS remote_activity_created_at
+                 ON remote_activity (created_at);",
+        )?;
+    }
+    tx.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// An entry of the backend's `synthia-history.json`.
+#[derive(Deserialize)]
+struct LegacyEntry {
+    text: String,
+    mode: String,
+    /// Naive local time, as Python's `datetime.now().isoformat()`.
+    timestamp: String,
+    response: Option<String>,
+}
+
+/// Copy the backend's JSON history in. Best effort: a missing or unreadable
+/// file just means there's nothing to import.
+fn import_legacy(conn: &Connection, path: &Path) {
+    let Ok(content) = fs::read_to_string(path) else { return };
+    let entries: Vec<LegacyEntry> = match serde_json::from_str(&content) {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::warn!(error = %e, "not importing unreadable history file");
+            return;
+        }
+    };
+    let mut imported = 0;
+    for entry in entries {
+        let Some(at) = entry
+            .timestamp
+            .parse::<NaiveDateTime>()
+            .ok()
+            .and_then(|t| t.and_local_timezone(Local).earliest())
+        else {
+            continue;
+        };
+        let new = NewEntry {
+            text: entry.text,
+            mode: entry.mode,
+            duration_secs: None,
+            model: None,
+            response: entry.response,
+        };
+        if insert(conn, &new, at.with_timezone(&Utc)).is_ok() {
+            imported += 1;
+        }
+    }
+    tracing::info!(imported, "imported backend history into history.db");
+}
+
+fn insert(conn: &Connection, entry: &NewEntry, at: DateTime<Utc>) -> AppResult<i64> {
+    conn.execute(
+        "INSERT INTO transcriptions (text, mode, created_at, duration_secs, model, response)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            entry.text,
+            entry.mode,
+            at.timestamp_millis(),
+            entry.duration_secs,
+            entry.model,
+            entry.response
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Store `entry` as recorded at `at` and return it with its id.
+#[cfg_attr(not(unix), allow(dead_code))]
+pub fn record(conn: &Connection, entry: NewEntry, at: DateTime<Utc>) -> AppResult<HistoryEntry> {
+    let id = insert(conn, &entry, at)?;
+    Ok(HistoryEntry {
+        id,
+        text: entry.text,
+        mode: entry.mode,
+        timestamp: local_rfc3339(at.timestamp_millis()),
+        duration_secs: entry.duration_secs,
+        model: entry.model,
+        response: entry.response,
+    })
+}
+
+fn local_rfc3339(millis: i64) -> String {
+    Local
+        .timestamp_millis_opt(millis)
+        .single()
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_default()
+}
+
+fn entry_from_row(row: &Row) -> rusqlite::Result<HistoryEntry> {
+    Ok(HistoryEntry {
+        id: row.get("id")?,
+        text: row.get("text")?,
+        mode: row.get("mode")?,
+        timestamp: local_rfc3339(row.get("created_at")?),
+        duration_secs: row.get("duration_secs")?,
+        model: row.get("model")?,
+        response: row.get("response")?,
+    })
+}
+
+/// `WHERE` clause and its parameters for `filter`.
+fn where_clause(filter: &HistoryFilter) -> (String, Vec<SqlValue>) {
+    let mut conditions = Vec::new();
+    let mut values = Vec::new();
+    if let Some(mode) = &filter.mode {
+        conditions.push("mode = ?");
+        values.push(SqlValue::Text(mode.clone()));
+    }
+    if let Some(contains) = filter.contains.as_deref().filter(|c| !c.is_empty()) {
+        conditions.push("instr(lower(text), lower(?)) > 0");
+        values.push(SqlValue::Text(contains.to_string()));
+    }
+    if let Some(since) = filter.since {
+        conditions.push("created_at >= ?");
+        values.push(SqlValue::Integer(since.timestamp_millis()));
+    }
+    if let Some(until) = filter.until {
+        conditions.push("created_at <= ?");
+        values.push(SqlValue::Integer(until.timestamp_millis()));
+    }
+    if conditions.is_empty() {
+        return (String::new(), values);
+    }
+    (format!("WHERE {}", conditions.join(" AND ")), values)
+}
+
+/// Page `page` (from 0) of the entries matching `filter`, newest first.
+pub fn page(conn: &Connection, page: u32, filter: &HistoryFilter) -> AppResult<HistoryPage> {
+    let (clause, values) = where_clause(filter);
+    let total: i64 = conn.query_row(
+        &format!("SELECT count(*) FROM transcriptions {clause}"),
+        params_from_iter(values.iter()),
+        |row| row.get(0),
+    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT * FROM transcriptions {clause} ORDER BY created_at DESC, id DESC LIMIT {} OFFSET {}",
+        PAGE_SIZE,
+        u64::from(page) * u64::from(PAGE_SIZE),
+    ))?;
+    let entries = stmt
+        .query_map(params_from_iter(values.iter()), entry_from_row)?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(HistoryPage { entries, total: total as u64, page, page_size: PAGE_SIZE })
+}
+
+/// Turn what the user typed into an FTS5 query: every word must appear, the
+/// last one as a prefix since it may be half typed. Words are quoted so
+/// FTS5 syntax in the input (`OR`, `-`, `*`, quotes) is matched literally.
+/// `None` if there's nothing searchable.
+fn fts_query(input: &str) -> Option<String> {
+    let words: Vec<String> = input
+        .split_whitespace()
+        .filter(|w| w.chars().any(char::is_alphanumeric))
+        .map(|w| format!("\"{}\"", w.replace('"', "\"\"")))
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", words.join(" ")))
+}
+
+/// Split `snippet()` output on the match markers.
+fn snippet_parts(snippet: &str) -> Vec<SnippetPart> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut highlight = false;
+    for c in snippet.chars() {
+        if c == MATCH_START || c == MATCH_END {
+            if !current.is_empty() {
+                parts.push(SnippetPart { text: std::mem::take(&mut current), highlight });
+            }
+            highlight = c == MATCH_START;
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        parts.push(SnippetPart { text: current, highlight });
+    }
+    parts
+}
+
+/// Entries matching `query` within `range`, best match first.
+pub fn search(conn: &Connection, query: &str, range: DateRange) -> AppResult<Vec<SearchHit>> {
+    let Some(fts) = fts_query(query) else {
+        return Ok(Vec::new());
+    };
+    let mut sql = format!(
+        "SELECT t.*,
+                snippet(transcriptions_fts, -1, '{MATCH_START}', '{MATCH_END}', '…', 16) AS snippet,
+                bm25(transcriptions_fts, 1.0, 0.5) AS score
+         FROM transcriptions_fts JOIN transcriptions t ON t.id = transcriptions_fts.rowid
+         WHERE transcriptions_fts MATCH ?"
+    );
+    let mut values = vec![SqlValue::Text(fts)];
+    if let Some(since) = range.since {
+        sql.push_str(" AND t.created_at >= ?");
+        values.push(SqlValue::Integer(since.timestamp_millis()));
+    }
+    if let Some(until) = range.until {
+        sql.push_str(" AND t.created_at <= ?");
+        values.push(SqlValue::Integer(until.timestamp_millis()));
+    }
+    sql.push_str(&format!(" ORDER BY score, t.created_at DESC LIMIT {SEARCH_LIMIT}"));
+    let mut stmt = conn.prepare(&sql)?;
+    let hits = stmt
+        .query_map(params_from_iter(values.iter()), |row| {
+            let snippet: String = row.get("snippet")?;
+            let score: f64 = row.get("score")?;
+            Ok(SearchHit {
+                entry: entry_from_row(row)?,
+                snippet: snippet_parts(&snippet),
+                // bm25() is lower for better matches.
+                score: -score,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(hits)
+}
+
+/// Every entry recorded within `range`, oldest first.
+pub fn entries_in(conn: &Connection, range: DateRange) -> AppResult<Vec<HistoryEntry>> {
+    let filter = HistoryFilter { since: range.since, until: range.until, ..HistoryFilter::default() };
+    let (clause, values) = where_clause(&filter);
+    let mut stmt =
+        conn.prepare(&format!("SELECT * FROM transcriptions {clause} ORDER BY created_at, id"))?;
+    let entries = stmt
+        .query_map(params_from_iter(values.iter()), entry_from_row)?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(entries)
+}
+
+pub fn get(conn: &Connection, id: i64) -> AppResult<Option<HistoryEntry>> {
+    Ok(conn
+        .query_row("SELECT * FROM transcriptions WHERE id = ?1", [id], entry_from_row)
+        .optional()?)
+}
+
+/// The most recently recorded entry.
+pub fn latest(conn: &Connection) -> AppResult<Option<HistoryEntry>> {
+    Ok(conn
+        .query_row(
+            "SELECT * FROM transcriptions ORDER BY created_at DESC, id DESC LIMIT 1",
+            [],
+            entry_from_row,
+        )
+        .optional()?)
+}
+
+/// Remove one entry; `false` if there was no entry `id`.
+pub fn delete(conn: &Connection, id: i64) -> AppResult<bool> {
+    Ok(conn.execute("DELETE FROM transcriptions WHERE id = ?1", [id])? > 0)
+}
+
+/// Remove every entry, returning how many there were.
+pub fn clear(conn: &Connection) -> AppResult<usize> {
+    Ok(conn.execute("DELETE FROM transcriptions", [])?)
+}
+
+/// Store a remote exchange over `channel` as happening at `at`.
+pub fn record_remote(
+    conn: &Connection,
+    channel: &str,
+    activity: NewRemoteActivity,
+    at: DateTime<Utc>,
+) -> AppResult<RemoteActivity> {
+    conn.execute(
+        "INSERT INTO remote_activity (created_at, channel, direction, sender, kind, text)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            at.timestamp_millis(),
+            channel,
+            activity.direction,
+            activity.sender,
+            activity.kind,
+            activity.text
+        ],
+    )?;
+    Ok(RemoteActivity {
+        id: conn.last_insert_rowid(),
+        timestamp: local_rfc3339(at.timestamp_millis()),
+        channel: channel.to_string(),
+        direction: activity.direction,
+        sender: activity.sender,
+        kind: activity.kind,
+        text: activity.text,
+    })
+}
+
+/// Remote exchanges within `range`, newest first, up to `REMOTE_LOG_LIMIT`.
+pub fn remote_log(conn: &Connection, range: DateRange) -> AppResult<Vec<RemoteActivity>> {
+    let mut sql = String::from("SELECT * FROM remote_activity WHERE 1");
+    let mut values = Vec::new();
+    if let Some(since) = range.since {
+        sql.push_str(" AND created_at >= ?");
+        values.push(SqlValue::Integer(since.timestamp_millis()));
+    }
+    if let Some(until) = range.until {
+        sql.push_str(" AND created_at <= ?");
+        values.push(SqlValue::Integer(until.timestamp_millis()));
+    }
+    sql.push_str(&format!(" ORDER BY created_at DESC, id DESC LIMIT {REMOTE_LOG_LIMIT}"));
+    let mut stmt = conn.prepare(&sql)?;
+    let entries = stmt
+        .query_map(params_from_iter(values.iter()), |row| {
+            Ok(RemoteActivity {
+                id: row.get("id")?,
+                timestamp: local_rfc3339(row.get("created_at")?),
+                channel: row.get("channel")?,
+                direction: row.get("direction")?,
+                sender: row.get("sender")?,
+                kind: row.get("kind")?,
+                text: row.get("text")?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(entries)
+}
+
+/// Store an exchange the remote bot reported over `channel` and tell the
+/// frontend with `remote-activity`.
+pub fn on_remote_activity(app: &AppHandle, channel: &str, activity: NewRemoteActivity) {
+    match open().and_then(|conn| record_remote(&conn, channel, activity, Utc::now())) {
+        Ok(entry) => {
+            let _ = app.emit("remote-activity", &entry);
+        }
+        Err(e) => tracing::warn!(error = %e, "failed to record remote activity"),
+    }
+}
+
+/// Handle a `transcription` event from the backend: store it and tell the
+/// frontend with `history-added`.
+#[cfg_attr(not(unix), allow(dead_code))]
+pub fn on_transcription(app: &AppHandle, payload: &Value) {
+    let entry: NewEntry = match serde_json::from_value(payload.clone()) {
+        Ok(entry) => entry,
+        Err(e) => {
+            tracing::warn!(error = %e, "not recording malformed transcription");
+            return;
+        }
+    };
+    if entry.text.trim().is_empty() {
+        return;
+    }
+    match open().and_then(|conn| record(&conn, entry, Utc::now())) {
+        Ok(entry) => {
+            let _ = app.emit("history-added", HistoryAdded { entry: &entry });
+        }
+        Err(e) => tracing::warn!(error = %e, "failed to record transcription in history"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn, None).unwrap();
+        conn
+    }
+
+    fn entry(text: &str, mode: &str) -> NewEntry {
+        NewEntry {
+            text: text.into(),
+            mode: mode.into(),
+            duration_secs: Some(1.5),
+            model: Some("small".into()),
+            response: None,
+        }
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    #[test]
+    fn pages_newest_first_with_filters() {
+        let conn = memory_db();
+        record(&conn, entry("Buy milk", "dictation"), at(0)).unwrap();
+        record(&conn, entry("what's the weather", "assistant"), at(10)).unwrap();
+        record(&conn, entry("milk and eggs", "dictation"), at(20)).unwrap();
+
+        let all = page(&conn, 0, &HistoryFilter::default()).unwrap();
+        assert_eq!(all.total, 3);
+        let texts: Vec<_> = all.entries.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, ["milk and eggs", "what's the weather", "Buy milk"]);
+        assert_eq!(all.entries[0].model.as_deref(), Some("small"));
+
+        let filter = HistoryFilter {
+            mode: Some("dictation".into()),
+            contains: Some("MILK".into()),
+            since: Some(at(5)),
+            ..HistoryFilter::default()
+        };
+        let found = page(&conn, 0, &filter).unwrap();
+        assert_eq!(found.total, 1);
+        assert_eq!(found.entries[0].text, "milk and eggs");
+        assert!(page(&conn, 1, &HistoryFilter::default()).unwrap().entries.is_empty());
+    }
+
+    #[test]
+    fn delete_and_clear() {
+        let conn = memory_db();
+        let first = record(&conn, entry("one", "dictation"), at(0)).unwrap();
+        record(&conn, entry("two", "dictation"), at(1)).unwrap();
+        assert!(delete(&conn, first.id).unwrap());
+        assert!(!delete(&conn, first.id).unwrap());
+        assert_eq!(clear(&conn).unwrap(), 1);
+        assert_eq!(page(&conn, 0, &HistoryFilter::default()).unwrap().total, 0);
+    }
+
+    #[test]
+    fn search_ranks_matches_and_marks_them() {
+        let conn = memory_db();
+        record(&conn, entry("remind me to call the dentist", "dictation"), at(0)).unwrap();
+        record(&conn, entry("dentist dentist appointment notes", "dictation"), at(10)).unwrap();
+        let mut reply = entry("when is my appointment", "assistant");
+        reply.response = Some("Your dentist appointment is on Tuesday".into());
+        record(&conn, reply, at(20)).unwrap();
+        record(&conn, entry("unrelated", "dictation"), at(30)).unwrap();
+
+        let hits = search(&conn, "dentist", DateRange::default()).unwrap();
+        assert_eq!(hits.len(), 3);
+        assert_eq!(hits[0].entry.text, "dentist dentist appointment notes");
+        assert!(hits.windows(2).all(|w| w[0].score >= w[1].score));
+        assert!(hits[0].snippet.iter().any(|p| p.highlight && p.text == "dentist"));
+
+        // Prefix on the last word, every word required, date range applied.
+        assert_eq!(search(&conn, "dentist appoint", DateRange::default()).unwrap().len(), 2);
+        let later = DateRange { since: Some(at(15)), until: None };
+        assert_eq!(search(&conn, "dentist", later).unwrap().len(), 1);
+        // Deleted entries drop out of the index.
+        let first = hits.iter().find(|h| h.entry.text.starts_with("remind")).unwrap();
+        delete(&conn, first.entry.id).unwrap();
+        assert_eq!(search(&conn, "dentist", DateRange::default()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn remote_log_is_newest_first_within_range() {
+        let conn = memory_db();
+        let activity = |direction: &str, sender: &str, kind: &str, text: &str| NewRemoteActivity {
+            direction: direction.into(),
+            sender: sender.into(),
+            kind: kind.into(),
+            text: text.into(),
+        };
+        record_remote(&conn, "Telegram", activity("in", "123", "command", "/dev"), at(0))
+            .unwrap();
+        record_remote(&conn, "Telegram", activity("in", "123", "message", "run tests"), at(10))
+            .unwrap();
+        let sent = activity("out", "synthia", "forwarded", "run tests");
+        let last = record_remote(&conn, "Telegram", sent, at(11)).unwrap();
+        assert_eq!(last.channel, "Telegram");
+
+        let log = remote_log(&conn, DateRange::default()).unwrap();
+        let kinds: Vec<_> = log.iter().map(|e| e.kind.as_str()).collect();
+        assert_eq!(kinds, ["forwarded", "message", "command"]);
+        assert_eq!(log[0], last);
+
+        let later = DateRange { since: Some(at(5)), until: Some(at(10)) };
+        let log = remote_log(&conn, later).unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].sender, "123");
+        // Transcription history is kept apart.
+        assert_eq!(page(&conn, 0, &HistoryFilter::default()).unwrap().total, 0);
+    }
+
+    #[test]
+    fn fts_query_quotes_user_input() {
+        assert_eq!(fts_query("buy milk").as_deref(), Some("\"buy\" \"milk\"*"));
+        assert_eq!(
+            fts_query(r#"say "hi" OR -x"#).as_deref(),
+            Some(r#""say" """hi""" "OR" "-x"*"#)
+        );
+        assert_eq!(fts_query("  - * "), None);
+    }
+
+    #[test]
+    fn snippet_parts_split_on_markers() {
+        let parts = snippet_parts("…call the \u{E000}dentist\u{E001} today");
+        assert_eq!(
+            parts,
+            [
+                SnippetPart { text: "…call the ".into(), highlight: false },
+                SnippetPart { text: "dentist".into(), highlight: true },
+                SnippetPart { text: " today".into(), highlight: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn new_database_imports_backend_json_once() {
+        let dir = std::env::temp_dir().join(format!("synthia-history-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let legacy = dir.join("synthia-history.json");
+        fs::write(
+            &legacy,
+            r#"[{"id": 1, "text": "hello", "mode": "dictation", "timestamp": "2024-05-01T09:30:00.123456"},
+                {"id": 2, "text": "hi", "mode": "assistant", "timestamp": "2024-05-01T09:31:00", "response": "Hello!"},
+                {"id": 3, "text": "bad", "mode": "dictation", "timestamp": "yesterday"}]"#,
+        )
+        .unwrap();
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn, Some(&legacy)).unwrap();
+        migrate(&conn, Some(&legacy)).unwrap();
+        let all = page(&conn, 0, &HistoryFilter::default()).unwrap();
+        assert_eq!(all.total, 2);
+        assert_eq!(all.entries[0].response.as_deref(), Some("Hello!"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
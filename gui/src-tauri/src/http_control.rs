@@ -0,0 +1,355 @@
+//! Optional HTTP API on localhost, for driving Synthia from shell scripts,
+//! Home Assistant, keyboard macro software and the like.
+//!
+//! Off unless `http_control.enabled` is set in `gui.toml`. The server binds
+//! `127.0.0.1:<http_control.port>` and every request must carry the API
+//! token, as `Authorization: Bearer <token>` or in an `X-Synthia-Token`
+//! header. The token is kept in the OS keyring (see `secrets`): one is
+//! generated on first start, `get_http_api_token` shows it, and a token
+//! left in `http_control.token` by an older version is moved over.
+//!
+//! Routes call the same functions as the Tauri commands:
+//!
+//! | Route                    | Does                                      |
+//! |--------------------------|-------------------------------------------|
+//! | `GET /status`            | backend and assistant state               |
+//! | `POST /start`            | `start_synthia`                           |
+//! | `POST /stop`             | `stop_synthia`                            |
+//! | `POST /toggle-recording` | `toggle_recording`                        |
+//! | `POST /recording/start`  | start recording, if not already           |
+//! | `POST /recording/stop`   | stop recording, if recording              |
+//! | `GET /history/latest`    | the last transcription, `entry` or `null` |
+//! | `GET /mode`              | current recording mode                    |
+//! | `POST /mode?mode=<mode>` | `set_mode`, e.g. `voice` or `push_to_talk`|
+//!
+//! The recording routes ask the backend over its IPC socket (see
+//! `lifecycle::send_recording`); with no backend connected they fail with
+//! 503 rather than report a recording that never starts.
+//!
+//! Replies are JSON: `{"ok": true, ...}` or `{"ok": false, "error": "..."}`.
+//! One request per connection; the server speaks just enough HTTP/1.1 for
+//! curl.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::lifecycle;
+use crate::error::{AppError, AppResult};
+use crate::process::{count_running, BACKEND_PATTERN};
+use crate::state::AppState;
+use crate::{gui_config, history_store, secrets};
+use crate::synthia_state::read_synthia_state;
+use crate::tray;
+
+const TOKEN_HEADER: &str = "x-synthia-token";
+
+/// Longest accepted request head (request line plus headers).
+const MAX_HEAD_BYTES: u64 = 8 * 1024;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The token requests are checked against, once the server has started.
+static TOKEN: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    token: Option<String>,
+}
+
+impl Request {
+    fn query(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Parse a request head: the request line and headers, up to the blank line.
+fn parse_request(head: &str) -> Option<Request> {
+    let mut lines = head.lines();
+    let mut parts = lines.next()?.split_whitespace();
+    let (method, target) = (parts.next()?, parts.next()?);
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            (k.to_string(), v.to_string())
+        })
+        .collect();
+    let mut token = None;
+    for (name, value) in lines.filter_map(|line| line.split_once(':')) {
+        let (name, value) = (name.trim(), value.trim());
+        if name.eq_ignore_ascii_case(TOKEN_HEADER) {
+            token = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("authorization")
+            && value.get(..7).is_some_and(|scheme| scheme.eq_ignore_ascii_case("bearer "))
+        {
+            token = Some(value[7..].trim().to_string());
+        }
+    }
+    Some(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        query,
+        token,
+    })
+}
+
+/// Compare without bailing at the first differing byte, so response timing
+/// doesn't leak how much of a guessed token was right.
+pub(crate) fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+pub(crate) fn status() -> Value {
+    let synthia = read_synthia_state();
+    json!({
+        "ok": true,
+        "backend": if count_running(BACKEND_PATTERN) > 0 { "running" } else { "stopped" },
+        "status": synthia.status,
+        "recording": synthia.recording,
+        "paused": synthia.paused,
+        "muted": synthia.muted,
+        "mode": synthia.mode.as_str(),
+    })
+}
+
+fn message(result: AppResult<String>) -> AppResult<Value> {
+    result.map(|message| json!({ "ok": true, "message": message }))
+}
+
+pub(crate) fn error_body(error: impl std::fmt::Display) -> Value {
+    json!({ "ok": false, "error": error.to_string() })
+}
+
+/// Run a control action by name: `status`, `start`, `stop` or
+/// `toggle-recording`. Shared with `ws_control`. `Ok(None)` means no such
+/// action.
+pub(crate) fn action(app: &AppHandle, name: &str) -> AppResult<Option<Value>> {
+    let state = app.state::<AppState>();
+    let reply = match name {
+        "status" => status(),
+        "start" => {
+            let result = lifecycle::start(&state);
+            if result.is_ok() {
+                tray::set_running(app, true);
+            }
+            message(result)?
+        }
+        "stop" => {
+            let result = lifecycle::stop(app);
+            if result.is_ok() {
+                tray::set_running(app, false);
+            }
+            message(result)?
+        }
+        "toggle-recording" => message(lifecycle::toggle(&state))?,
+        _ => return Ok(None),
+    };
+    Ok(Some(reply))
+}
+
+/// Dispatch an authenticated request. `Ok(None)` means no such route.
+fn route(app: &AppHandle, req: &Request) -> AppResult<Option<Value>> {
+    let state = app.state::<AppState>();
+    let reply = match (req.method.as_str(), req.path.as_str()) {
+        ("GET", "/status") | ("POST", "/start" | "/stop" | "/toggle-recording") => {
+            return action(app, &req.path[1..]);
+        }
+        ("POST", "/recording/start") => message(lifecycle::set_recording_to(&state, true))?,
+        ("POST", "/recording/stop") => message(lifecycle::set_recording_to(&state, false))?,
+        ("GET", "/history/latest") => {
+            let conn = history_store::open()?;
+            json!({ "ok": true, "entry": history_store::latest(&conn)? })
+        }
+        ("GET", "/mode") => json!({ "ok": true, "mode": read_synthia_state().mode.as_str() }),
+        ("POST", "/mode") => {
+            let mode = req
+                .query("mode")
+                .ok_or_else(|| AppError::Validation("missing ?mode=".to_string()))?;
+            message(lifecycle::change_mode(app, mode))?
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(reply))
+}
+
+fn respond(stream: &mut TcpStream, code: u16, body: &Value) {
+    let reason = match code {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {code} {reason}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    if let Err(e) = stream.write_all(response.as_bytes()) {
+        tracing::debug!(error = %e, "HTTP control response not sent");
+    }
+}
+
+fn read_head(stream: &TcpStream) -> std::io::Result<String> {
+    let mut reader = BufReader::new(stream).take(MAX_HEAD_BYTES);
+    let mut head = String::new();
+    loop {
+        let before = head.len();
+        if reader.read_line(&mut head)? == 0 || head[before..].trim().is_empty() {
+            return Ok(head);
+        }
+    }
+}
+
+fn handle(app: &AppHandle, mut stream: TcpStream) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let Some(req) = read_head(&stream).ok().as_deref().and_then(parse_request) else {
+        respond(&mut stream, 400, &error_body("malformed request"));
+        return;
+    };
+    let expected = TOKEN.lock().ok().and_then(|t| t.clone());
+    let authorized = match (req.token.as_deref(), expected.as_deref()) {
+        (Some(given), Some(expected)) => token_matches(given, expected),
+        _ => false,
+    };
+    if !authorized {
+        tracing::warn!(path = %req.path, "HTTP control request with a bad or missing token");
+        respond(&mut stream, 401, &error_body("missing or wrong bearer token"));
+        return;
+    }
+    tracing::info!(method = %req.method, path = %req.path, "HTTP control request");
+    match route(app, &req) {
+        Ok(Some(body)) => respond(&mut stream, 200, &body),
+        Ok(None) => respond(&mut stream, 404, &error_body("no such route")),
+        Err(e @ AppError::Validation(_)) => respond(&mut stream, 400, &error_body(e)),
+        // No backend to act on the request.
+        Err(e @ AppError::NotFound(_)) => respond(&mut stream, 503, &error_body(e)),
+        Err(e) => respond(&mut stream, 500, &error_body(e)),
+    }
+}
+
+/// The API token from the keyring, moving one over from `gui.toml` or
+/// generating one if there is none yet.
+pub(crate) fn ensure_token() -> AppResult<String> {
+    let legacy = gui_config::load().http_control.token;
+    let token = match secrets::http_token()? {
+        Some(token) => token,
+        None if !legacy.is_empty() => {
+            secrets::set_http_token(&legacy)?;
+            tracing::info!("moved HTTP API token from gui.toml to the keyring");
+            legacy.clone()
+        }
+        None => {
+            let token = new_token();
+            secrets::set_http_token(&token)?;
+            tracing::info!("generated HTTP API token");
+            token
+        }
+    };
+    if !legacy.is_empty() {
+        gui_config::update(|cfg| cfg.http_control.token.clear())?;
+    }
+    Ok(token)
+}
+
+/// Replace the API token. Requests bearing the old one are refused from
+/// now on.
+pub(crate) fn regenerate_token() -> AppResult<String> {
+    let token = new_token();
+    secrets::set_http_token(&token)?;
+    if let Ok(mut current) = TOKEN.lock() {
+        if current.is_some() {
+            *current = Some(token.clone());
+        }
+    }
+    tracing::info!("HTTP API token regenerated");
+    Ok(token)
+}
+
+pub(crate) fn new_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Start the server if `http_control.enabled` is set.
+pub fn spawn_if_enabled(app: AppHandle) -> AppResult<()> {
+    let config = gui_config::load().http_control;
+    if !config.enabled {
+        return Ok(());
+    }
+    let token = ensure_token()?;
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, config.port))?;
+    if let Ok(mut current) = TOKEN.lock() {
+        *current = Some(token);
+    }
+    tracing::info!(port = config.port, "HTTP control endpoint listening on localhost");
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let app = app.clone();
+                    std::thread::spawn(move || handle(&app, stream));
+                }
+                Err(e) => tracing::warn!(error = %e, "HTTP control accept failed"),
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_request_reads_route_query_and_token() {
+        let req = parse_request(
+            "POST /mode?mode=push_to_talk HTTP/1.1\r\nHost: localhost\r\nX-Synthia-Token: abc\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/mode");
+        assert_eq!(req.query("mode"), Some("push_to_talk"));
+        assert_eq!(req.token.as_deref(), Some("abc"));
+
+        let req = parse_request("GET /status HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(req.token, None);
+        assert!(parse_request("").is_none());
+    }
+
+    #[test]
+    fn bearer_token_is_accepted() {
+        let req = parse_request("GET /status HTTP/1.1\r\nAuthorization: bearer  abc \r\n\r\n");
+        assert_eq!(req.unwrap().token.as_deref(), Some("abc"));
+        let req = parse_request("GET /status HTTP/1.1\r\nAuthorization: Basic YWJj\r\n\r\n");
+        assert_eq!(req.unwrap().token, None);
+    }
+
+    #[test]
+    fn token_must_match_exactly() {
+        assert!(token_matches("secret", "secret"));
+        assert!(!token_matches("secreT", "secret"));
+        assert!(!token_matches("secret2", "secret"));
+        assert!(!token_matches("", "secret"));
+    }
+}
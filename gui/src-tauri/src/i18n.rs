@@ -0,0 +1,167 @@
+//! Translated strings for the tray, dialogs and desktop notifications.
+//!
+//! Each locale is a flat JSON map under `locales/`, compiled into the
+//! binary. The active locale is `i18n.locale` from `gui.toml` when set,
+//! otherwise the language of the system locale (`LC_ALL`, `LC_MESSAGES`,
+//! `LANG`), otherwise English. A key missing from the active locale falls
+//! back to English, and a key missing from English to the key itself, so a
+//! gap shows up as text rather than a blank.
+//!
+//! Until `init` runs, `t` answers in English; tests rely on that.
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use crate::gui_config;
+
+const FALLBACK: &str = "en";
+
+const BUNDLED: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.json")),
+    ("de", include_str!("../locales/de.json")),
+    ("es", include_str!("../locales/es.json")),
+    ("fr", include_str!("../locales/fr.json")),
+];
+
+type Strings = HashMap<String, String>;
+
+struct Catalog {
+    locale: &'static str,
+    strings: Strings,
+}
+
+static ACTIVE: OnceLock<RwLock<Catalog>> = OnceLock::new();
+static ENGLISH: OnceLock<Strings> = OnceLock::new();
+
+fn parse(locale: &str) -> Strings {
+    let Some((_, json)) = BUNDLED.iter().find(|(code, _)| *code == locale) else {
+        return Strings::new();
+    };
+    serde_json::from_str(json).unwrap_or_else(|e| {
+        tracing::error!(locale, error = %e, "bundled locale file is invalid");
+        Strings::new()
+    })
+}
+
+fn english() -> &'static Strings {
+    ENGLISH.get_or_init(|| parse(FALLBACK))
+}
+
+/// Language part of a POSIX locale: `de_DE.UTF-8` and `de-AT` give `de`.
+/// `C` and `POSIX` name no language.
+fn language_of(raw: &str) -> Option<String> {
+    let language = raw
+        .split(['.', '@'])
+        .next()?
+        .split(['_', '-'])
+        .next()?
+        .to_ascii_lowercase();
+    match language.as_str() {
+        "" | "c" | "posix" => None,
+        _ => Some(language),
+    }
+}
+
+fn system_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| language_of(&value))
+}
+
+/// The bundled locale to use: the override if we ship it, else the system
+/// language if we ship it, else English.
+fn resolve(preferred: Option<&str>, system: Option<&str>) -> &'static str {
+    [preferred.and_then(language_of), system.map(str::to_string)]
+        .into_iter()
+        .flatten()
+        .find_map(|language| BUNDLED.iter().find(|(code, _)| *code == language))
+        .map_or(FALLBACK, |(code, _)| code)
+}
+
+/// Pick the locale from `gui.toml` and the environment. Returns the locale
+/// now in use.
+pub(crate) fn init() -> &'static str {
+    let preferred = gui_config::load().i18n.locale;
+    let locale = resolve(preferred.as_deref(), system_language().as_deref());
+    let catalog = Catalog {
+        locale,
+        strings: parse(locale),
+    };
+    let slot = ACTIVE.get_or_init(|| RwLock::new(Catalog {
+        locale: FALLBACK,
+        strings: Strings::new(),
+    }));
+    if let Ok(mut active) = slot.write() {
+        *active = catalog;
+    }
+    tracing::info!(locale, ?preferred, "locale selected");
+    locale
+}
+
+/// The locale `t` is answering in.
+pub(crate) fn current() -> &'static str {
+    ACTIVE
+        .get()
+        .and_then(|slot| slot.read().ok().map(|c| c.locale))
+        .unwrap_or(FALLBACK)
+}
+
+/// Locales with a bundled string file.
+pub(crate) fn available() -> Vec<&'static str> {
+    BUNDLED.iter().map(|(code, _)| *code).collect()
+}
+
+/// The string for `key` in the active locale.
+pub(crate) fn t(key: &str) -> String {
+    let translated = ACTIVE
+        .get()
+        .and_then(|slot| slot.read().ok().and_then(|c| c.strings.get(key).cloned()));
+    translated
+        .or_else(|| english().get(key).cloned())
+        .unwrap_or_else(|| key.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_of_strips_region_and_encoding() {
+        assert_eq!(language_of("de_DE.UTF-8").as_deref(), Some("de"));
+        assert_eq!(language_of("pt-BR").as_deref(), Some("pt"));
+        assert_eq!(language_of("sr_RS@latin").as_deref(), Some("sr"));
+        assert_eq!(language_of("FR").as_deref(), Some("fr"));
+        assert_eq!(language_of("C.UTF-8"), None);
+        assert_eq!(language_of("POSIX"), None);
+        assert_eq!(language_of(""), None);
+    }
+
+    #[test]
+    fn resolve_prefers_override_then_system_then_english() {
+        assert_eq!(resolve(Some("fr_FR"), Some("de")), "fr");
+        assert_eq!(resolve(Some("ja"), Some("de")), "de");
+        assert_eq!(resolve(None, Some("es")), "es");
+        assert_eq!(resolve(None, Some("ja")), "en");
+        assert_eq!(resolve(None, None), "en");
+    }
+
+    #[test]
+    fn every_locale_translates_every_english_key() {
+        let english = english();
+        assert!(!english.is_empty());
+        for (locale, _) in BUNDLED {
+            let strings = parse(locale);
+            for key in english.keys() {
+                assert!(strings.contains_key(key), "{locale} is missing {key}");
+            }
+        }
+    }
+
+    #[test]
+    fn t_falls_back_to_english_then_key() {
+        assert_eq!(t("tray.quit"), "Quit");
+        assert_eq!(t("no.such.key"), "no.such.key");
+    }
+}
@@ -0,0 +1,329 @@
+//! Push-based state updates and backend commands over a Unix domain socket.
+//!
+//! The GUI listens on `synthia.sock` in the runtime dir (`XDG_RUNTIME_DIR`
+//! unless `runtime.dir` says otherwise). The backend keeps a connection open
+//! and exchanges frames: a 4-byte big-endian length, then that many bytes of
+//! JSON. A message is acted on only once its whole frame has arrived, so a
+//! reader can never see half an update the way it can with a file being
+//! rewritten.
+//!
+//! Backend → GUI:
+//! - `{"type": "state", "state": {...}}` — a document in the same shape as
+//!   `synthia-state.json`, applied to the tray and overlay straight away.
+//! - `{"type": "event", "name": "...", "payload": ...}` — re-emitted to the
+//!   frontend as a `backend-event`. A `transcription` event also raises a
+//!   desktop notification and is stored in the history database; see
+//!   `notifications` and `history_store`. `partial_transcript`
+//!   events carry text still being decoded and go to the overlay only; see
+//!   `overlay_transcript`.
+//!
+//! GUI → backend: `{"type": "command", "command": "...", "args": {...}}`,
+//! sent with `send_command` to every connected backend.
+//!
+//! Older backends connect per update and write one bare state document per
+//! line instead; a client whose first byte is `{` is read that way and never
+//! receives commands.
+//!
+//! The state file watcher keeps running alongside: backends without socket
+//! support, and GUI-side writers such as the mute and pause flags, still go
+//! through the file. If the socket can't be bound, the file is the only
+//! channel.
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::{AppError, AppResult};
+use crate::get_runtime_dir;
+use crate::history_store;
+use crate::notifications;
+use crate::overlay_transcript;
+use crate::synthia_state::{parse_synthia_state, SynthiaState};
+use crate::tray;
+
+/// Longest accepted message. State documents are a few hundred bytes; a
+/// client announcing or sending more than this is dropped.
+const MAX_MESSAGE_BYTES: u64 = 64 * 1024;
+
+/// How long a command write may block before the backend is given up on.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Incoming {
+    State { state: Value },
+    Event {
+        name: String,
+        #[serde(default)]
+        payload: Value,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Outgoing<'a> {
+    Command { command: &'a str, args: &'a Value },
+}
+
+/// A `backend-event` as seen by the frontend.
+#[derive(Serialize, Clone, Debug)]
+pub struct BackendEvent {
+    pub name: String,
+    pub payload: Value,
+}
+
+/// Write halves of the framed clients currently connected, by client id.
+struct Clients {
+    next_id: u64,
+    streams: Vec<(u64, UnixStream)>,
+}
+
+static CLIENTS: Mutex<Clients> = Mutex::new(Clients {
+    next_id: 0,
+    streams: Vec::new(),
+});
+
+pub fn socket_path() -> PathBuf {
+    get_runtime_dir().join("synthia.sock")
+}
+
+/// Remove a socket left behind by a previous GUI. Only sockets are removed;
+/// anything else at the path is left alone and makes the bind fail.
+pub fn remove_socket() {
+    let path = socket_path();
+    if fs::symlink_metadata(&path).is_ok_and(|m| m.file_type().is_socket()) {
+        let _ = fs::remove_file(&path);
+    }
+}
+
+fn parse_message(line: &str) -> Option<SynthiaState> {
+    parse_synthia_state(line)
+        .inspect_err(|e| tracing::warn!(error = %e, "ignoring malformed IPC message"))
+        .ok()
+}
+
+fn encode_frame(body: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(4 + body.len());
+    frame.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    frame.extend_from_slice(body);
+    frame
+}
+
+/// Read one frame's body. `Ok(None)` means the client hung up cleanly
+/// between frames.
+fn read_frame(reader: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+    let mut header = [0u8; 4];
+    match reader.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_be_bytes(header) as u64;
+    if len > MAX_MESSAGE_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {len} bytes exceeds the limit"),
+        ));
+    }
+    let mut body = vec![0u8; len as usize];
+    reader.read_exact(&mut body)?;
+    Ok(Some(body))
+}
+
+fn handle_message(app: &AppHandle, body: &[u8]) {
+    match serde_json::from_slice::<Incoming>(body) {
+        Ok(Incoming::State { state }) => {
+            if let Some(state) = parse_message(&state.to_string()) {
+                crate::apply_synthia_state(app, &state);
+            }
+        }
+        Ok(Incoming::Event { name, payload }) => {
+            tracing::debug!(name, "backend event");
+            if name == "transcription" {
+                history_store::on_transcription(app, &payload);
+                match serde_json::from_value::<notifications::Transcription>(payload.clone()) {
+                    Ok(transcription) => {
+                        tray::set_last_transcript(app, &transcription.text);
+                        notifications::notify_transcription(transcription);
+                    }
+                    Err(e) => tracing::warn!(error = %e, "malformed transcription event"),
+                }
+            }
+            if name == "partial_transcript" {
+                overlay_transcript::on_partial(app, payload);
+                return;
+            }
+            let _ = app.emit("backend-event", BackendEvent { name, payload });
+        }
+        Err(e) => tracing::warn!(error = %e, "ignoring malformed IPC message"),
+    }
+}
+
+/// Legacy clients: one bare state document per line.
+fn read_lines(app: &AppHandle, mut reader: BufReader<UnixStream>) {
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        match reader.by_ref().take(MAX_MESSAGE_BYTES).read_until(b'\n', &mut line) {
+            Ok(0) => return,
+            Ok(_) => {}
+            Err(e) => {
+                tracing::debug!(error = %e, "IPC client read failed");
+                return;
+            }
+        }
+        if line.last() != Some(&b'\n') {
+            // Either the limit cut the line short or the client hung up
+            // mid-message; neither leaves anything worth applying.
+            if line.len() as u64 >= MAX_MESSAGE_BYTES {
+                tracing::warn!("IPC message too long; dropping client");
+            }
+            return;
+        }
+        let text = String::from_utf8_lossy(&line);
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if let Some(state) = parse_message(text) {
+            crate::apply_synthia_state(app, &state);
+        }
+    }
+}
+
+fn read_frames(app: &AppHandle, mut reader: BufReader<UnixStream>) {
+    loop {
+        match read_frame(&mut reader) {
+            Ok(Some(body)) => handle_message(app, &body),
+            Ok(None) => return,
+            Err(e) => {
+                tracing::debug!(error = %e, "IPC client read failed");
+                return;
+            }
+        }
+    }
+}
+
+fn register(stream: &UnixStream) -> Option<u64> {
+    let writer = stream.try_clone().ok()?;
+    let _ = writer.set_write_timeout(Some(WRITE_TIMEOUT));
+    let mut clients = CLIENTS.lock().ok()?;
+    clients.next_id += 1;
+    let id = clients.next_id;
+    clients.streams.push((id, writer));
+    Some(id)
+}
+
+fn unregister(id: u64) {
+    if let Ok(mut clients) = CLIENTS.lock() {
+        clients.streams.retain(|(client, _)| *client != id);
+    }
+}
+
+fn handle_client(app: AppHandle, stream: UnixStream) {
+    let mut reader = BufReader::new(stream);
+    let legacy = match reader.fill_buf() {
+        Ok([]) => return,
+        Ok(buf) => buf[0] == b'{',
+        Err(e) => {
+            tracing::debug!(error = %e, "IPC client read failed");
+            return;
+        }
+    };
+    if legacy {
+        read_lines(&app, reader);
+        return;
+    }
+    let id = register(reader.get_ref());
+    tracing::info!("backend connected to IPC socket");
+    read_frames(&app, reader);
+    if let Some(id) = id {
+        unregister(id);
+    }
+    tracing::info!("backend disconnected from IPC socket");
+}
+
+/// Send `command` to every connected backend. Returns how many received
+/// it; backends whose socket fails are disconnected.
+pub fn send_command(command: &str, args: &Value) -> AppResult<usize> {
+    let body = serde_json::to_vec(&Outgoing::Command { command, args })?;
+    let frame = encode_frame(&body);
+    let mut clients = CLIENTS
+        .lock()
+        .map_err(|_| AppError::Other("IPC client list poisoned".into()))?;
+    clients.streams.retain_mut(|(_, stream)| match stream.write_all(&frame) {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!(error = %e, "dropping unresponsive IPC client");
+            let _ = stream.shutdown(std::net::Shutdown::Both);
+            false
+        }
+    });
+    if clients.streams.is_empty() {
+        return Err(AppError::NotFound(
+            "No backend is connected to the IPC socket".into(),
+        ));
+    }
+    tracing::info!(command, clients = clients.streams.len(), "sent backend command");
+    Ok(clients.streams.len())
+}
+
+/// Bind the socket and accept backend connections on a background thread.
+pub fn spawn_listener(app: AppHandle) -> AppResult<()> {
+    let path = socket_path();
+    remove_socket();
+    let listener = UnixListener::bind(&path)?;
+    // Only this user's processes may push state.
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    tracing::info!(path = %path.display(), "listening for backend state on IPC socket");
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let app = app.clone();
+                    std::thread::spawn(move || handle_client(app, stream));
+                }
+                Err(e) => tracing::warn!(error = %e, "IPC accept failed"),
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_message_migrates_like_the_state_file() {
+        let state = parse_message(r#"{"status": "recording"}"#).unwrap();
+        assert!(state.recording);
+        assert!(parse_message("{\"status\": ").is_none());
+    }
+
+    #[test]
+    fn frames_round_trip_and_reject_oversized_lengths() {
+        let mut bytes = encode_frame(br#"{"type":"event","name":"pong"}"#);
+        bytes.extend(encode_frame(b"{}"));
+        let mut reader = io::Cursor::new(bytes);
+        let first = read_frame(&mut reader).unwrap().unwrap();
+        assert!(matches!(
+            serde_json::from_slice::<Incoming>(&first).unwrap(),
+            Incoming::Event { name, payload: Value::Null } if name == "pong"
+        ));
+        assert_eq!(read_frame(&mut reader).unwrap().unwrap(), b"{}");
+        assert!(read_frame(&mut reader).unwrap().is_none());
+
+        let mut huge = io::Cursor::new((MAX_MESSAGE_BYTES as u32 + 1).to_be_bytes().to_vec());
+        assert!(read_frame(&mut huge).is_err());
+    }
+}
@@ -1,27 +1,71 @@
 use tauri::{
-    image::Image,
-    menu::{Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, WindowEvent,
+    Emitter, Manager, WindowEvent,
 };
-use std::process::Command;
 use std::fs;
 use std::path::PathBuf;
-use serde::Deserialize;
 
+mod secrets;
 mod security;
+mod sidecar;
 mod egress;
 mod error;
+mod evdev_input;
+mod file_lock;
+mod audio_meter;
+mod backend_health;
+mod command_stats;
+mod compute_devices;
+mod dry_run;
 mod paths;
+mod platform;
+mod process;
 mod state;
+mod supervisor;
+mod synthia_state;
 mod config;
+mod config_watch;
+#[cfg(target_os = "linux")]
+mod dbus_control;
+mod gui_config;
+mod global_hotkeys;
+mod logging;
+mod http_control;
+mod history_export;
+mod history_store;
+mod i18n;
+#[cfg(unix)]
+mod ipc;
+mod notifications;
+mod overlay_layer_shell;
+// Fed by backend events over the IPC socket, which is Unix-only.
+#[cfg_attr(not(unix), allow(dead_code))]
+mod overlay_transcript;
+mod power;
+mod push_to_talk;
+mod recording_timer;
+mod settings;
+mod remote_approval;
+mod remote_channel;
+mod remote_notify;
+mod resource_monitor;
+mod text_injection;
 mod yaml_writer;
+mod threads;
+mod tray;
+mod tray_animation;
+mod whisper_models;
+mod window_geometry;
+mod ws_control;
 mod commands;
 
-/// Get the Synthia project root directory.
-/// Resolves from the executable path (gui/src-tauri/target/release/synthia-gui)
-/// or falls back to finding run.sh relative to the binary.
+/// Get the Synthia project root directory: `SYNTHIA_ROOT` or `install.root`
+/// in gui.toml if set, else found by looking for run.sh above the executable
+/// (gui/src-tauri/target/release/synthia-gui), else the known dev path.
 pub(crate) fn get_synthia_root() -> PathBuf {
+    if let Some(root) = gui_config::cached().install.root() {
+        return root;
+    }
     // Try to find the root by looking for run.sh relative to the executable
     if let Ok(exe) = std::env::current_exe() {
         // The binary is at gui/src-tauri/target/release/synthia-gui
@@ -34,14 +78,8 @@ pub(crate) fn get_synthia_root() -> PathBuf {
             }
         }
     }
-    // Fallback: check if SYNTHIA_ROOT env var is set
-    if let Ok(root) = std::env::var("SYNTHIA_ROOT") {
-        return PathBuf::from(root);
-    }
     // Fallback: check the known development path
-    let dev_path = PathBuf::from(
-        std::env::var("HOME").unwrap_or_default()
-    ).join("dev/misc/synthia");
+    let dev_path = platform::home_dir().join("dev/misc/synthia");
     if dev_path.join("run.sh").exists() {
         return dev_path;
     }
@@ -49,74 +87,64 @@ pub(crate) fn get_synthia_root() -> PathBuf {
     std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
 }
 
-/// Get the XDG runtime directory for temp files (not world-readable /tmp).
-pub(crate) fn get_runtime_dir() -> PathBuf {
-    PathBuf::from(std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| "/tmp".to_string()))
-}
-
-#[allow(dead_code)]
-fn load_icon_from_path(path: &PathBuf) -> Option<Image<'static>> {
-    let img = image::open(path).ok()?.to_rgba8();
-    let (width, height) = img.dimensions();
-    let rgba = img.into_raw();
-    Some(Image::new_owned(rgba, width, height))
+/// `path` if absolute, else relative to the Synthia root.
+fn under_root(path: PathBuf) -> PathBuf {
+    if path.is_absolute() {
+        path
+    } else {
+        get_synthia_root().join(path)
+    }
 }
 
-// Embed icons directly in binary for better compatibility
-static TRAY_ICON_PNG: &[u8] = include_bytes!("../icons/tray-icon.png");
-static TRAY_RECORDING_PNG: &[u8] = include_bytes!("../icons/tray-recording.png");
-
-fn load_embedded_icon(data: &'static [u8]) -> Option<Image<'static>> {
-    let img = image::load_from_memory(data).ok()?.to_rgba8();
-    let (width, height) = img.dimensions();
-    let rgba = img.into_raw();
-    Some(Image::new_owned(rgba, width, height))
+/// The backend's Python interpreter: `SYNTHIA_PYTHON` or `install.python`,
+/// else the checkout's venv.
+pub(crate) fn get_python_path() -> PathBuf {
+    let python = gui_config::cached().install.python();
+    under_root(python.unwrap_or_else(|| platform::VENV_PYTHON.into()))
 }
 
-#[derive(Deserialize, Debug, Default)]
-struct SynthiaState {
-    #[allow(dead_code)]
-    status: String,
-    recording: bool,
+/// The backend launch script: `SYNTHIA_RUN_SCRIPT` or `install.run_script`,
+/// else the checkout's `run.sh`.
+pub(crate) fn get_run_script() -> PathBuf {
+    under_root(gui_config::cached().install.run_script().unwrap_or_else(|| "run.sh".into()))
 }
 
-pub(crate) fn get_lock_file() -> PathBuf {
-    let runtime_dir = std::env::var("XDG_RUNTIME_DIR")
-        .unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(runtime_dir).join("synthia-gui.lock")
+/// Get the runtime directory for the backend's state/lock files: the
+/// `runtime.dir` override from gui.toml, else `$XDG_RUNTIME_DIR` (not
+/// world-readable /tmp), else `/tmp` or the Windows temp directory.
+pub(crate) fn get_runtime_dir() -> PathBuf {
+    if let Some(dir) = gui_config::cached().runtime.validated_dir() {
+        return dir;
+    }
+    platform::runtime_dir()
 }
 
 pub(crate) fn get_state_file() -> PathBuf {
-    let runtime_dir = std::env::var("XDG_RUNTIME_DIR")
-        .unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(runtime_dir).join("synthia-state.json")
+    get_runtime_dir().join("synthia-state.json")
 }
 
 pub(crate) fn get_history_file() -> PathBuf {
-    let runtime_dir = std::env::var("XDG_RUNTIME_DIR")
-        .unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(runtime_dir).join("synthia-history.json")
+    get_runtime_dir().join("synthia-history.json")
+}
+
+pub(crate) fn get_last_transcript_file() -> PathBuf {
+    get_runtime_dir().join("synthia-last-transcript.txt")
 }
 
 pub(crate) fn get_clipboard_file() -> PathBuf {
-    let runtime_dir = std::env::var("XDG_RUNTIME_DIR")
-        .unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(runtime_dir).join("synthia-clipboard.json")
+    get_runtime_dir().join("synthia-clipboard.json")
 }
 
 pub(crate) fn get_inbox_file() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(home).join(".local/share/synthia/inbox/inbox.json")
+    platform::home_dir().join(".local/share/synthia/inbox/inbox.json")
 }
 
 pub(crate) fn get_worktrees_config_path() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(home).join(".config/synthia/worktrees.yaml")
+    platform::home_dir().join(".config/synthia/worktrees.yaml")
 }
 
 pub(crate) fn get_claude_dir() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(home).join(".claude")
+    platform::home_dir().join(".claude")
 }
 
 pub(crate) fn get_memory_dir() -> PathBuf {
@@ -144,13 +172,11 @@ pub(crate) fn get_plugins_file() -> PathBuf {
 }
 
 pub(crate) fn get_config_path() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(home).join(".config/synthia/config.yaml")
+    platform::home_dir().join(".config/synthia/config.yaml")
 }
 
 pub(crate) fn get_runtime_state_path() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(home).join(".config/synthia/runtime.json")
+    platform::home_dir().join(".config/synthia/runtime.json")
 }
 
 pub(crate) fn parse_frontmatter(content: &str) -> (std::collections::HashMap<String, String>, String) {
@@ -179,58 +205,121 @@ pub(crate) fn is_wayland_env() -> bool {
     std::env::var("WAYLAND_DISPLAY").is_ok()
 }
 
-fn acquire_lock() -> bool {
-    let lock_file = get_lock_file();
-
-    if lock_file.exists() {
-        if let Ok(pid_str) = fs::read_to_string(&lock_file) {
-            if let Ok(pid) = pid_str.trim().parse::<i32>() {
-                let check = Command::new("kill")
-                    .args(["-0", &pid.to_string()])
-                    .output();
-                if check.map(|o| o.status.success()).unwrap_or(false) {
-                    return false;
-                }
-            }
-        }
+/// Payload of the `second-instance` event: what a second launch of the GUI
+/// was started with, before it exited in favour of this one.
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct SecondInstance {
+    pub args: Vec<String>,
+    pub cwd: String,
+}
+
+/// Show, restore and focus the main window.
+pub(crate) fn show_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
     }
+}
 
-    let pid = std::process::id();
-    fs::write(&lock_file, pid.to_string()).ok();
-    true
+/// Called in this instance when the GUI is launched again: the new process
+/// exits straight away and its arguments are forwarded here.
+fn on_second_instance(app: &tauri::AppHandle, args: Vec<String>, cwd: String) {
+    tracing::info!(?args, cwd = %cwd, "second instance launched; focusing this one");
+    show_main_window(app);
+    let _ = app.emit("second-instance", SecondInstance { args, cwd });
 }
 
-fn release_lock() {
-    let lock_file = get_lock_file();
-    fs::remove_file(lock_file).ok();
+/// Stop the backend we own, clean up runtime files and exit. Stopping waits
+/// out the backend's shutdown grace period, so it runs on its own thread:
+/// the tray's Quit and its confirmation call this on the event loop, which
+/// would otherwise freeze until then. Repeat calls while quitting are ignored.
+pub(crate) fn quit_app(app: &tauri::AppHandle) {
+    static QUITTING: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+    if QUITTING.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        commands::lifecycle::stop_owned_backend(&app.state::<state::AppState>());
+        #[cfg(unix)]
+        ipc::remove_socket();
+        app.exit(0);
+    });
 }
 
-fn read_synthia_state() -> SynthiaState {
-    let state_file = get_state_file();
-    if let Ok(content) = fs::read_to_string(&state_file) {
-        serde_json::from_str(&content).unwrap_or_default()
-    } else {
-        SynthiaState::default()
+/// Drive the tray, overlay and state-dependent helpers from a new backend
+/// state, whether it came from the state file or the IPC socket. The backend
+/// sends each write both ways, stamped with a fresh heartbeat, so a state
+/// equal to the last one applied is that write arriving a second time and
+/// is dropped.
+pub(crate) fn apply_synthia_state(app: &tauri::AppHandle, state: &synthia_state::SynthiaState) {
+    let app_state = app.state::<state::AppState>();
+    if let Ok(mut last) = app_state.last_synthia_state.lock() {
+        if last.as_ref() == Some(state) {
+            return;
+        }
+        let heartbeat_only = last.as_ref().is_some_and(|prev| state.is_heartbeat_of(prev));
+        *last = Some(state.clone());
+        if heartbeat_only {
+            drop(last);
+            backend_health::record(app, state.heartbeat_at);
+            return;
+        }
     }
+    if let Ok(mut last) = app_state.last_state_change.lock() {
+        *last = Some(std::time::Instant::now());
+    }
+    if app_state.backend_ready.swap(state.ready, std::sync::atomic::Ordering::SeqCst) != state.ready {
+        tracing::info!(ready = state.ready, "backend readiness changed");
+        let _ = app.emit("backend-ready", state.ready);
+        if state.ready {
+            tray::set_recovery(app, None);
+        }
+    }
+    backend_health::record(app, state.heartbeat_at);
+    let _ = app.emit("synthia-state", state);
+    #[cfg(target_os = "linux")]
+    dbus_control::state_changed(state);
+    tray::apply_state(app, state);
+    commands::overlay::emit_overlay_state(app, state);
+    push_to_talk::sync(app, state);
+    recording_timer::sync(app, state);
+    audio_meter::sync(app, state);
 }
 
+/// How long the state file must stay quiet before it is read. One backend
+/// write shows up as several events (create, modify, rename); reading after
+/// the last of them avoids parsing a half-written file and applying the same
+/// state several times.
+const STATE_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(25);
+
 /// Spawn a notify-based watcher on the synthia state file's parent dir.
-/// Updates the tray icon whenever the state file changes. Returns the watcher
-/// handle, which the caller MUST keep alive (dropping it stops watching).
+/// Updates the tray icon and overlay, and emits `synthia-state` to the
+/// frontend, whenever the state file changes. Falls
+/// back to polling at `runtime.state_poll_interval_ms` (or
+/// `power.battery_poll_interval_ms` in low-power mode) if native filesystem
+/// notifications can't be set up. Returns the watcher handle, which the
+/// caller MUST keep alive (dropping it stops watching).
 fn spawn_state_watcher(
     app_handle: tauri::AppHandle,
-    normal_icon: Option<Image<'static>>,
-    recording_icon: Option<Image<'static>>,
-) -> Option<notify::RecommendedWatcher> {
-    use notify::{Event, EventKind, RecursiveMode, Watcher};
+) -> Option<Box<dyn std::any::Any + Send + Sync>> {
+    use notify::{Event, EventKind, PollWatcher, RecursiveMode, Watcher};
 
     let state_file = get_state_file();
     let parent = state_file.parent()?.to_path_buf();
     let target = state_file.clone();
-    let last_recording = std::sync::Arc::new(std::sync::Mutex::new(false));
-
-    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
-        let Ok(event) = res else { return };
+    let low_power = power::mode(&app_handle.state::<state::AppState>()).low_power;
+
+    let handler = move |res: notify::Result<Event>| {
+        let _span = tracing::debug_span!("state_watcher").entered();
+        let event = match res {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::warn!(error = %e, "state watcher error");
+                return;
+            }
+        };
         if !matches!(
             event.kind,
             EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)
@@ -240,81 +329,139 @@ fn spawn_state_watcher(
         if !event.paths.iter().any(|p| p == &target) {
             return;
         }
-        let state = read_synthia_state();
-        let mut last = match last_recording.lock() {
-            Ok(g) => g,
-            Err(_) => return,
-        };
-        if state.recording == *last {
-            return;
-        }
-        *last = state.recording;
-        if let Some(tray) = app_handle.tray_by_id("main-tray") {
-            if state.recording {
-                if let Some(ref icon) = recording_icon {
-                    let _ = tray.set_icon(Some(icon.clone()));
-                    let _ = tray.set_tooltip(Some("Synthia - Recording..."));
-                }
-            } else if let Some(ref icon) = normal_icon {
-                let _ = tray.set_icon(Some(icon.clone()));
-                let _ = tray.set_tooltip(Some("Synthia - Voice Assistant"));
+        let ticket = app_handle
+            .state::<state::AppState>()
+            .state_event_seq
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+            + 1;
+        let app = app_handle.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(STATE_DEBOUNCE);
+            let seq = &app.state::<state::AppState>().state_event_seq;
+            if seq.load(std::sync::atomic::Ordering::SeqCst) != ticket {
+                return;
             }
+            // A panic here would kill the thread mid-update and freeze the tray.
+            threads::guarded("state watcher", || {
+                apply_synthia_state(&app, &synthia_state::read_synthia_state())
+            });
+        });
+    };
+
+    if let Ok(mut watcher) = notify::recommended_watcher(handler.clone()) {
+        if watcher.watch(&parent, RecursiveMode::NonRecursive).is_ok() {
+            tracing::info!(path = %state_file.display(), "watching state file");
+            return Some(Box::new(watcher));
         }
-    })
-    .ok()?;
+    }
 
+    let config = gui_config::load();
+    let interval = if low_power {
+        config.power.battery_poll_interval()
+    } else {
+        config.runtime.poll_interval()
+    };
+    tracing::warn!(
+        path = %state_file.display(),
+        interval_ms = interval.as_millis() as u64,
+        "native file watching unavailable; polling state file"
+    );
+    let config = notify::Config::default().with_poll_interval(interval);
+    let mut watcher = PollWatcher::new(handler, config).ok()?;
     watcher.watch(&parent, RecursiveMode::NonRecursive).ok()?;
-    Some(watcher)
+    Some(Box::new(watcher))
 }
 
-pub fn run() {
-    if !acquire_lock() {
-        eprintln!("Synthia GUI is already running");
-        std::process::exit(0);
-    }
-
-    egress::spawn_watcher();
+/// (Re)start the state file watcher, dropping the previous one first so the
+/// two never both apply the same change.
+pub(crate) fn install_state_watcher(app: &tauri::AppHandle) {
+    let state = app.state::<state::AppState>();
+    let Ok(mut slot) = state.state_watcher.lock() else { return };
+    *slot = None;
+    *slot = spawn_state_watcher(app.clone());
+}
 
+pub fn run() {
     tauri::Builder::default()
+        // First, so a second launch exits before any other plugin sets up.
+        .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            on_second_instance(app, args, cwd)
+        }))
         .manage(state::AppState::default())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(global_hotkeys::on_shortcut)
+                .build(),
+        )
         .setup(|app| {
+            // Only now, past the single-instance check: a second launch must
+            // not open (and maybe rotate) the log or start its own watchers.
+            logging::init();
+            egress::spawn_watcher();
+
             // Clean up any stale remote mode state from previous sessions
             let _ = fs::remove_file(get_runtime_dir().join("synthia-remote-mode"));
-            let _ = Command::new("pkill")
-                .args(["-f", "telegram_bot.py"])
-                .output();
+            if dry_run::enabled() {
+                tracing::warn!("dry run: backend and remote commands are simulated");
+                dry_run::skip_signal("clean up stale remote bots", process::BOT_PATTERN);
+            } else {
+                let stale = process::find_processes(process::BOT_PATTERN);
+                if !stale.is_empty() {
+                    std::thread::spawn(move || {
+                        process::terminate_foreign(&stale, std::time::Duration::from_secs(3))
+                    });
+                }
+            }
 
-            // Create tray menu
-            let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let show = MenuItem::with_id(app, "show", "Show Settings", true, None::<&str>)?;
+            // Create tray menu in the user's language
+            i18n::init();
+            let menu = tray::build_menu(app)?;
 
-            let menu = Menu::with_items(app, &[
-                &show,
-                &quit,
-            ])?;
+            // Load tray icons - try bundled resources first, then fall back to dev path
+            let icons = tray::load_icons(app.handle());
+            let theme = app
+                .get_webview_window("main")
+                .and_then(|w| w.theme().ok());
 
             // Load the tray icon immediately for COSMIC/StatusNotifierItem compatibility
-            let initial_icon = load_embedded_icon(TRAY_ICON_PNG)
-                .unwrap_or_else(|| app.default_window_icon().unwrap().clone());
+            let initial_icon = icons.pick(theme, false).cloned();
+
+            if let Ok(mut tray_state) = app.state::<state::AppState>().tray.lock() {
+                tray_state.icons = icons;
+                tray_state.theme = theme;
+                tray_state.static_icons = !gui_config::load().tray.animate;
+                tray_state.running =
+                    commands::lifecycle::backend_running(&app.state::<state::AppState>());
+            }
 
             // Create tray icon with ID so we can update it later
-            let _tray = TrayIconBuilder::with_id("main-tray")
-                .icon(initial_icon)
+            let mut tray_builder = TrayIconBuilder::with_id(tray::TRAY_ID);
+            match initial_icon {
+                Some(icon) => tray_builder = tray_builder.icon(icon),
+                None => tracing::error!("no usable tray icon; see get_health for paths tried"),
+            }
+            let _tray = tray_builder
                 .menu(&menu)
-                .tooltip("Synthia - Voice Assistant")
+                .icon_as_template(cfg!(target_os = "macos"))
+                .tooltip(i18n::t("tooltip.idle"))
                 .on_menu_event(|app, event| {
                     match event.id.as_ref() {
-                        "quit" => {
-                            release_lock();
-                            app.exit(0);
-                        }
-                        "show" => {
-                            if let Some(window) = app.get_webview_window("main") {
-                                let _ = window.show();
-                                let _ = window.set_focus();
+                        "quit" => tray::request_quit(app),
+                        tray::BACKEND_TOGGLE_ID => tray::on_backend_toggled(app),
+                        tray::REMOTE_TOGGLE_ID => tray::on_remote_toggled(app),
+                        tray::COPY_TRANSCRIPT_ID => commands::clipboard::copy_last_transcription_or_notify(),
+                        "open_logs" => {
+                            if let Err(e) = commands::diagnostics::open_log_dir(app.clone()) {
+                                tracing::warn!(error = %e, "open log folder failed");
+                                notifications::notify_error(&i18n::t("notify.error_title"), &e.to_string());
                             }
                         }
+                        id @ (tray::MODE_VOICE_ID
+                        | tray::MODE_PUSH_TO_TALK_ID
+                        | tray::MODE_REMOTE_ID) => tray::on_mode_selected(app, id),
+                        "show" => show_main_window(app),
                         _ => {}
                     }
                 })
@@ -325,82 +472,206 @@ pub fn run() {
                         ..
                     } = event
                     {
-                        let app = tray.app_handle();
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
+                        show_main_window(tray.app_handle());
                     }
                 })
                 .build(app)?;
 
-            // Handle window close - hide instead of quit
+            // Handle window close - hide instead of quit. Theme changes re-pick
+            // the tray icon so it stays visible on light and dark panels.
+            // Geometry is saved on move/resize and restored here at startup.
             if let Some(window) = app.get_webview_window("main") {
+                if let Err(e) = window_geometry::restore_main_window(&window) {
+                    tracing::warn!(error = %e, "failed to restore main window geometry");
+                }
                 let window_clone = window.clone();
-                window.on_window_event(move |event| {
-                    if let WindowEvent::CloseRequested { api, .. } = event {
+                let app_handle = app.handle().clone();
+                window.on_window_event(move |event| match event {
+                    WindowEvent::CloseRequested { api, .. } => {
                         api.prevent_close();
+                        let _ = window_geometry::save_main_window(&window_clone);
                         let _ = window_clone.hide();
                     }
+                    WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+                        window_geometry::on_main_window_changed(&app_handle);
+                    }
+                    WindowEvent::ThemeChanged(theme) => {
+                        tray::set_theme(&app_handle, *theme);
+                    }
+                    _ => {}
                 });
             }
 
-            // Load tray icons - try bundled resources first, then fall back to dev path
-            let resource_dir = app.path().resource_dir().unwrap_or_default();
-            let dev_icons_dir = get_synthia_root().join("gui/src-tauri/icons");
-
-            let _normal_icon_path = if resource_dir.join("icons/tray-icon.png").exists() {
-                resource_dir.join("icons/tray-icon.png")
-            } else {
-                dev_icons_dir.join("tray-icon.png")
-            };
-
-            let _recording_icon_path = if resource_dir.join("icons/tray-recording.png").exists() {
-                resource_dir.join("icons/tray-recording.png")
-            } else {
-                dev_icons_dir.join("tray-recording.png")
-            };
+            // Remember where the user drags the overlay to. On macOS it also
+            // has to join every Space, or it stays behind on the one it was
+            // created on, and full-screen apps would hide it. On Wayland it
+            // may become a layer surface instead, which has to happen before
+            // it is first shown.
+            if let Some(overlay) = app.get_webview_window("overlay") {
+                overlay_layer_shell::setup(&overlay);
+                #[cfg(target_os = "macos")]
+                if let Err(e) = overlay.set_visible_on_all_workspaces(true) {
+                    tracing::warn!(error = %e, "failed to show overlay on all Spaces");
+                }
+                let app_handle = app.handle().clone();
+                overlay.on_window_event(move |event| {
+                    if let WindowEvent::Moved(_) = event {
+                        commands::overlay::on_overlay_moved(&app_handle);
+                    }
+                });
+            }
 
             // Start state watcher (notify-based, event-driven)
             let app_handle = app.handle().clone();
-            let normal_icon = load_embedded_icon(TRAY_ICON_PNG);
-            let recording_icon = load_embedded_icon(TRAY_RECORDING_PNG);
+
+            // Restore the saved mode before anything reads the state
+            settings::startup();
 
             // Set initial icon immediately
-            if let Some(tray) = app_handle.tray_by_id("main-tray") {
-                if let Some(ref icon) = normal_icon {
-                    let _ = tray.set_icon(Some(icon.clone()));
+            tray::refresh(&app_handle);
+            let initial = synthia_state::read_synthia_state();
+            push_to_talk::sync(&app_handle, &initial);
+            global_hotkeys::sync(&app_handle);
+            recording_timer::sync(&app_handle, &initial);
+            commands::overlay::apply_click_through(&app_handle, initial.overlay_state());
+            commands::remote::spawn_status_monitor(app_handle.clone());
+            commands::overlay::spawn_monitor_watch(app_handle.clone());
+            if let Ok(mut history) = app.state::<state::AppState>().exit_history.lock() {
+                *history = supervisor::load_history();
+            }
+            supervisor::adopt(&app.state::<state::AppState>());
+            supervisor::spawn(app_handle.clone());
+            logging::follow_backend_log();
+
+            #[cfg(unix)]
+            if gui_config::load().runtime.ipc_socket {
+                if let Err(e) = ipc::spawn_listener(app_handle.clone()) {
+                    tracing::warn!(error = %e, "IPC socket unavailable; relying on the state file");
                 }
             }
 
-            if let Some(watcher) = spawn_state_watcher(app_handle, normal_icon, recording_icon) {
-                if let Ok(mut guard) = app.state::<state::AppState>().watchers.lock() {
-                    guard.push(Box::new(watcher));
-                }
+            if let Err(e) = http_control::spawn_if_enabled(app_handle.clone()) {
+                tracing::warn!(error = %e, "HTTP control endpoint not started");
+            }
+            if let Err(e) = ws_control::spawn_if_enabled(app_handle.clone()) {
+                tracing::warn!(error = %e, "WebSocket control server not started");
             }
+            #[cfg(target_os = "linux")]
+            if let Err(e) = dbus_control::spawn_if_enabled(app_handle.clone()) {
+                tracing::warn!(error = %e, "D-Bus control interface not published");
+            }
+
+            power::spawn_monitor(app_handle.clone());
+            resource_monitor::spawn_monitor(app_handle.clone());
+            backend_health::spawn_monitor(app_handle.clone());
+            install_state_watcher(&app_handle);
+            config_watch::spawn(app_handle.clone());
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::lifecycle::get_status,
+            commands::setup::check_installation,
+            commands::locale::get_locale,
+            commands::locale::set_locale,
+            commands::settings::get_setting,
+            commands::settings::set_setting,
+            commands::settings::get_all_settings,
             commands::lifecycle::start_synthia,
             commands::lifecycle::stop_synthia,
+            commands::lifecycle::restart_synthia_fresh,
+            commands::lifecycle::reload_backend_config,
+            commands::lifecycle::send_backend_command,
+            commands::audio::list_audio_inputs,
+            commands::audio::check_audio_input,
+            commands::audio::set_audio_input,
+            commands::backend_settings::get_backend_settings,
+            commands::backend_settings::set_backend_settings,
+            commands::backend_settings::get_wake_sensitivity,
+            commands::backend_settings::set_wake_sensitivity,
+            commands::lifecycle::reset_state,
+            commands::lifecycle::pause_synthia,
+            commands::lifecycle::resume_synthia,
+            commands::lifecycle::set_muted,
+            commands::lifecycle::get_muted,
+            commands::lifecycle::get_full_state,
+            commands::lifecycle::get_power_mode,
+            commands::lifecycle::toggle_recording,
+            commands::lifecycle::get_crash_history,
             commands::lifecycle::set_mode,
+            commands::health::get_health,
+            commands::health::get_command_stats,
+            commands::health::get_resource_usage,
+            commands::health::get_backend_health,
+            commands::diagnostics::export_diagnostics,
+            commands::diagnostics::get_recent_logs,
+            commands::diagnostics::tail_logs,
+            commands::diagnostics::open_log_dir,
+            commands::diagnostics::open_log_file,
+            commands::version::get_backend_version,
+            commands::version::get_gui_version,
+            commands::version::get_version_info,
             commands::overlay::show_overlay,
             commands::overlay::hide_overlay,
             commands::overlay::set_overlay_recording,
+            commands::overlay::set_overlay_state,
+            commands::overlay::set_overlay_click_through,
+            commands::overlay::get_overlay_click_through,
+            commands::overlay::toggle_overlay_click_through,
+            commands::overlay::set_overlay_visibility,
+            commands::overlay::get_overlay_visibility,
+            commands::overlay::set_overlay_transcript_mode,
+            commands::overlay::get_overlay_transcript_mode,
+            commands::overlay::get_monitors,
+            commands::overlay::set_overlay_position,
+            commands::overlay::get_overlay_backend,
             commands::remote::start_remote_mode,
             commands::remote::stop_remote_mode,
             commands::remote::get_remote_status,
+            commands::remote::get_remote_health,
+            commands::remote::set_remote_token,
+            commands::remote::has_remote_token,
+            commands::remote::clear_remote_token,
+            commands::remote::list_allowed_users,
+            commands::remote::add_allowed_user,
+            commands::remote::remove_allowed_user,
+            commands::remote::list_remote_approvals,
+            commands::remote::approve_remote_command,
+            commands::remote::deny_remote_command,
             commands::history::get_history,
+            commands::history::delete_history_entry,
+            commands::history::search_history,
+            commands::history::get_remote_log,
+            commands::history::export_history,
             commands::history::clear_history,
+            commands::history::get_last_transcript,
             commands::history::resend_to_assistant,
             commands::hotkeys::get_hotkeys,
             commands::hotkeys::save_hotkeys,
+            commands::hotkeys::get_global_hotkeys,
+            commands::hotkeys::set_hotkey,
+            commands::hotkeys::list_input_devices,
+            commands::hotkeys::capture_input_key,
+            commands::hotkeys::set_push_to_talk_input,
+            commands::hotkeys::get_double_tap,
+            commands::hotkeys::set_double_tap,
             commands::hotkeys::get_word_replacements,
             commands::hotkeys::save_word_replacements,
+            commands::http_api::get_http_api_token,
+            commands::http_api::get_ws_control_token,
+            commands::http_api::regenerate_http_api_token,
             commands::clipboard::get_clipboard_history,
             commands::clipboard::copy_from_clipboard_history,
+            commands::clipboard::copy_last_transcription,
+            commands::clipboard::copy_history_entry,
+            commands::typing::type_text,
+            commands::typing::get_text_injection_backends,
+            commands::models::list_models,
+            commands::models::download_model,
+            commands::models::delete_model,
+            commands::models::set_active_model,
+            commands::devices::detect_devices,
+            commands::devices::set_compute_device,
             commands::inbox::get_inbox_items,
             commands::inbox::open_inbox_item,
             commands::inbox::delete_inbox_item,
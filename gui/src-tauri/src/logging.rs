@@ -0,0 +1,371 @@
+//! `tracing` setup: events go to stderr and to a size-rotated log file in
+//! `$XDG_STATE_HOME/synthia/logs`.
+//!
+//! The level comes from `RUST_LOG` when set, else `logging.level` in
+//! `gui.toml`, else `info`. Both accept full `EnvFilter` directives
+//! (e.g. `info,synthia_gui_lib::tray=debug`).
+//!
+//! The backend writes its stdout/stderr to `backend.log` in the same
+//! directory rather than to a pipe, so it survives the GUI restarting.
+//! `follow_backend_log` re-logs each new line under the `backend` target,
+//! at the level Python's `logging` gave it, so `gui.log` holds both sides.
+//!
+//! Everything written to `gui.log` can also be streamed, line by line, to
+//! whoever called `start_tail` (the `tail_logs` command).
+
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use tracing::Level;
+use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+
+use crate::{gui_config, threads};
+
+/// The active log file is rotated once it would grow past this size.
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Rotated files kept alongside the active one (`gui.log.1` … `gui.log.N`).
+const KEEP_ROTATED: usize = 3;
+
+/// Target the backend's output is logged under.
+pub const BACKEND_TARGET: &str = "backend";
+
+/// How often `follow_backend_log` checks `backend.log` for new output.
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(500);
+
+pub fn log_dir() -> PathBuf {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("synthia")
+        .join("logs")
+}
+
+pub fn log_file_path() -> PathBuf {
+    log_dir().join("gui.log")
+}
+
+/// Where the backend's stdout/stderr go; truncated on each start.
+pub fn backend_log_path() -> PathBuf {
+    log_dir().join("backend.log")
+}
+
+/// `<path>.<n>`, the n-th most recent rotated copy of `path`.
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{n}"));
+    PathBuf::from(name)
+}
+
+/// Append-only log file that shifts itself to `.1`, `.2`, … when full.
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    written: u64,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata()?.len();
+        Ok(RotatingFile { path, file, written })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        for n in (1..KEEP_ROTATED).rev() {
+            let from = rotated_path(&self.path, n);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, n + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        *self = RotatingFile::open(self.path.clone())?;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > MAX_LOG_BYTES {
+            self.rotate()?;
+        }
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Receiver of streamed log output, while someone is tailing.
+static TAIL: Mutex<Option<Sender<String>>> = Mutex::new(None);
+
+/// Stream everything written to `gui.log` from now on, one chunk of
+/// complete lines per message. Replaces any earlier tail.
+pub fn start_tail() -> Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    if let Ok(mut tail) = TAIL.lock() {
+        *tail = Some(tx);
+    }
+    rx
+}
+
+pub fn stop_tail() {
+    if let Ok(mut tail) = TAIL.lock() {
+        *tail = None;
+    }
+}
+
+/// Copies whatever reaches the log file to the tail, if one is running.
+/// The tail is a channel rather than a callback: emitting to the frontend
+/// can log, and logging from inside the writer would deadlock.
+struct Tee<W>(W);
+
+impl<W: Write> Write for Tee<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.0.write(buf)?;
+        if let Ok(mut tail) = TAIL.try_lock() {
+            let gone = tail
+                .as_ref()
+                .is_some_and(|tx| tx.send(String::from_utf8_lossy(&buf[..n]).into_owned()).is_err());
+            if gone {
+                *tail = None;
+            }
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// One record from `gui.log`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: String,
+    /// Spans, target and message, as formatted in the file. Lines that
+    /// didn't start a record (e.g. a traceback) are appended to it.
+    pub message: String,
+}
+
+const LEVELS: [&str; 5] = ["TRACE", "DEBUG", "INFO", "WARN", "ERROR"];
+
+/// Rank of a level name for filtering; unknown names rank lowest.
+fn level_rank(level: &str) -> usize {
+    let level = level.trim().to_ascii_uppercase();
+    let level = if level == "WARNING" { "WARN" } else { level.as_str() };
+    LEVELS.iter().position(|l| *l == level).unwrap_or(0)
+}
+
+/// Split a `fmt` line (`<timestamp>  <LEVEL> <rest>`) into a record; `None`
+/// for a continuation line.
+fn parse_record(line: &str) -> Option<LogEntry> {
+    let (timestamp, rest) = line.split_once(' ')?;
+    let rest = rest.trim_start();
+    let (level, message) = rest.split_once(' ').unwrap_or((rest, ""));
+    if !LEVELS.contains(&level) || !timestamp.contains('T') {
+        return None;
+    }
+    Some(LogEntry {
+        timestamp: timestamp.to_string(),
+        level: level.to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// Records in `content`, continuation lines folded into the one before.
+pub fn parse_entries(content: &str) -> Vec<LogEntry> {
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        match (parse_record(line), entries.last_mut()) {
+            (Some(entry), _) => entries.push(entry),
+            (None, Some(last)) => {
+                last.message.push('\n');
+                last.message.push_str(line);
+            }
+            (None, None) => {}
+        }
+    }
+    entries
+}
+
+/// The last `limit` records at `min_level` or above, oldest first, reading
+/// into the most recent rotated file when the active one is short.
+pub fn recent_entries(limit: usize, min_level: &str) -> Vec<LogEntry> {
+    let min = level_rank(min_level);
+    let path = log_file_path();
+    let mut entries = VecDeque::new();
+    for source in [path.clone(), rotated_path(&path, 1)] {
+        let content = fs::read_to_string(&source).unwrap_or_default();
+        let matching = parse_entries(&content)
+            .into_iter()
+            .filter(|e| level_rank(&e.level) >= min);
+        for entry in matching.rev() {
+            if entries.len() >= limit {
+                break;
+            }
+            entries.push_front(entry);
+        }
+        if entries.len() >= limit {
+            break;
+        }
+    }
+    entries.into()
+}
+
+/// Level of a line in the backend's `asctime - name - levelname - message`
+/// format, if it is one.
+fn python_level(line: &str) -> Option<Level> {
+    match line.split(" - ").nth(2)? {
+        "DEBUG" => Some(Level::DEBUG),
+        "INFO" => Some(Level::INFO),
+        "WARNING" => Some(Level::WARN),
+        "ERROR" | "CRITICAL" => Some(Level::ERROR),
+        _ => None,
+    }
+}
+
+fn log_backend_line(line: &str) {
+    // Tracebacks and stray prints carry no level; they are usually bad news.
+    match python_level(line).unwrap_or(Level::WARN) {
+        Level::ERROR => tracing::error!(target: BACKEND_TARGET, "{line}"),
+        Level::WARN => tracing::warn!(target: BACKEND_TARGET, "{line}"),
+        Level::INFO => tracing::info!(target: BACKEND_TARGET, "{line}"),
+        _ => tracing::debug!(target: BACKEND_TARGET, "{line}"),
+    }
+}
+
+/// Re-log output the backend appends to `backend.log` from now on. A
+/// truncated file (a new backend) is read from the top.
+pub fn follow_backend_log() {
+    threads::spawn_supervised("backend log follower", || {
+        let path = backend_log_path();
+        let mut pos = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        let mut partial = String::new();
+        loop {
+            std::thread::sleep(FOLLOW_INTERVAL);
+            let Ok(mut file) = File::open(&path) else {
+                continue;
+            };
+            let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+            if len < pos {
+                pos = 0;
+                partial.clear();
+            }
+            if len == pos || file.seek(SeekFrom::Start(pos)).is_err() {
+                continue;
+            }
+            let mut chunk = Vec::new();
+            let Ok(read) = file.take(len - pos).read_to_end(&mut chunk) else {
+                continue;
+            };
+            pos += read as u64;
+            partial.push_str(&String::from_utf8_lossy(&chunk));
+            while let Some(end) = partial.find('\n') {
+                let line: String = partial.drain(..=end).collect();
+                let line = line.trim_end();
+                if !line.is_empty() {
+                    log_backend_line(line);
+                }
+            }
+        }
+    });
+}
+
+fn env_filter() -> EnvFilter {
+    if let Ok(filter) = EnvFilter::try_from_default_env() {
+        return filter;
+    }
+    let level = gui_config::load().logging.level;
+    EnvFilter::try_new(&level).unwrap_or_else(|_| EnvFilter::new("info"))
+}
+
+/// Install the global subscriber. Call once, before anything logs. If the
+/// log file can't be opened, logging continues on stderr only.
+pub fn init() {
+    let file = RotatingFile::open(log_file_path());
+    let file_error = file.as_ref().err().map(|e| e.to_string());
+    let file_layer = file
+        .ok()
+        .map(|f| fmt::layer().with_ansi(false).with_writer(Mutex::new(Tee(f))));
+
+    let _ = tracing_subscriber::registry()
+        .with(env_filter())
+        .with(fmt::layer().with_writer(io::stderr))
+        .with(file_layer)
+        .try_init();
+
+    if let Some(e) = file_error {
+        tracing::warn!(path = %log_file_path().display(), error = %e, "log file unavailable");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_fold_continuation_lines_and_filter_by_level() {
+        let content = "\
+2026-10-16T09:00:00.000001Z  INFO synthia_gui_lib: started
+2026-10-16T09:00:01.000001Z ERROR backend: Traceback (most recent call last):
+  File \"main.py\", line 1
+2026-10-16T09:00:02.000001Z DEBUG synthia_gui_lib::tray: refreshed
+";
+        let entries = parse_entries(content);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].level, "ERROR");
+        assert!(entries[1].message.ends_with("\n  File \"main.py\", line 1"));
+        let warnings: Vec<_> = entries.iter().filter(|e| level_rank(&e.level) >= level_rank("warning")).collect();
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn python_levels_are_mapped() {
+        assert_eq!(
+            python_level("2026-10-16 09:00:00,123 - synthia.main - WARNING - mic busy - retrying"),
+            Some(Level::WARN)
+        );
+        assert_eq!(python_level("Traceback (most recent call last):"), None);
+    }
+
+    #[test]
+    fn rotated_path_appends_index() {
+        assert_eq!(
+            rotated_path(Path::new("/tmp/logs/gui.log"), 2),
+            PathBuf::from("/tmp/logs/gui.log.2")
+        );
+    }
+
+    #[test]
+    fn rotation_shifts_old_files() {
+        let dir = std::env::temp_dir().join(format!("synthia-log-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("gui.log");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(rotated_path(&path, 1), "older").unwrap();
+
+        let mut log = RotatingFile::open(path.clone()).unwrap();
+        log.write_all(b"current").unwrap();
+        log.rotate().unwrap();
+        log.write_all(b"fresh").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fresh");
+        assert_eq!(fs::read_to_string(rotated_path(&path, 1)).unwrap(), "current");
+        assert_eq!(fs::read_to_string(rotated_path(&path, 2)).unwrap(), "older");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
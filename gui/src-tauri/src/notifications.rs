@@ -0,0 +1,257 @@
+//! Desktop notifications from the GUI process.
+//!
+//! Goes through `notify-send` like the backend's `notifications.py`, so both
+//! sides look the same and no extra dependency is needed. Sending is
+//! fire-and-forget: a missing `notify-send` only costs a debug log line.
+//!
+//! Errors and remote commands awaiting approval are always shown.
+//! Transcriptions, backend crashes and remote mode connecting are shown as
+//! `[notifications]` in `gui.toml` allows. Transcription notifications carry
+//! a Copy action, and approvals Approve and Deny, where `notify-send` is new
+//! enough to offer actions (libnotify 0.7.10+).
+
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::commands::clipboard::copy_to_clipboard;
+use crate::gui_config::{self, NotificationsConfig};
+use crate::i18n::t;
+
+const ERROR_TIMEOUT_MS: u32 = 5000;
+const INFO_TIMEOUT_MS: u32 = 4000;
+
+#[cfg_attr(not(unix), allow(dead_code))]
+const COPY_ACTION: &str = "copy";
+const APPROVE_ACTION: &str = "approve";
+const DENY_ACTION: &str = "deny";
+
+/// Optional notifications, each switched on or off in `[notifications]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    #[cfg_attr(not(unix), allow(dead_code))]
+    Transcription,
+    BackendCrash,
+    RemoteConnected,
+}
+
+impl Kind {
+    fn enabled(self, config: &NotificationsConfig) -> bool {
+        config.enabled
+            && match self {
+                Kind::Transcription => config.transcription,
+                Kind::BackendCrash => config.backend_crash,
+                Kind::RemoteConnected => config.remote_connected,
+            }
+    }
+}
+
+fn wanted(kind: Kind) -> bool {
+    kind.enabled(&gui_config::load().notifications)
+}
+
+/// Whether this `notify-send` takes `--action`, checked once.
+fn supports_actions() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+    *SUPPORTED.get_or_init(|| {
+        Command::new("notify-send")
+            .arg("--help")
+            .stderr(Stdio::null())
+            .output()
+            .map(|out| String::from_utf8_lossy(&out.stdout).contains("--action"))
+            .unwrap_or(false)
+    })
+}
+
+fn command(title: &str, message: &str, icon: &str, timeout_ms: u32) -> Command {
+    let mut cmd = Command::new("notify-send");
+    cmd.arg("--app-name=Synthia")
+        .arg(format!("--icon={icon}"))
+        .arg(format!("--expire-time={timeout_ms}"))
+        .args([title, message])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    cmd
+}
+
+fn send(title: &str, message: &str, icon: &str, timeout_ms: u32) {
+    let mut cmd = command(title, message, icon, timeout_ms);
+    let title = title.to_string();
+    // Waited on a side thread so the child is reaped without blocking the caller.
+    std::thread::spawn(move || {
+        if let Err(e) = cmd.status() {
+            tracing::debug!(error = %e, title, "desktop notification not sent");
+        }
+    });
+}
+
+/// Like `send`, with a Copy button that puts `copy` on the clipboard.
+/// `--wait` keeps `notify-send` running until the notification closes and
+/// prints the chosen action's key.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn send_with_copy(title: &str, message: &str, icon: &str, copy: String) {
+    if !supports_actions() {
+        send(title, message, icon, INFO_TIMEOUT_MS);
+        return;
+    }
+    let mut cmd = command(title, message, icon, INFO_TIMEOUT_MS);
+    cmd.arg(format!("--action={COPY_ACTION}={}", t("notify.copy")))
+        .arg("--wait")
+        .stdout(Stdio::piped());
+    let title = title.to_string();
+    std::thread::spawn(move || match cmd.output() {
+        Ok(out) if String::from_utf8_lossy(&out.stdout).trim() == COPY_ACTION => {
+            if let Err(e) = copy_to_clipboard(&copy) {
+                tracing::warn!(error = %e, "failed to copy from notification");
+            }
+        }
+        Ok(_) => {}
+        Err(e) => tracing::debug!(error = %e, title, "desktop notification not sent"),
+    });
+}
+
+pub fn notify_error(title: &str, message: &str) {
+    send(title, message, "dialog-error", ERROR_TIMEOUT_MS);
+}
+
+/// The backend exited unexpectedly; `restarting` says whether the
+/// supervisor will bring it back.
+pub fn notify_backend_crash(restarting: bool, description: Option<&str>) {
+    if !wanted(Kind::BackendCrash) {
+        return;
+    }
+    let body = t(if restarting {
+        "notify.backend_restarting"
+    } else {
+        "notify.backend_not_restarted"
+    });
+    let body = match description {
+        Some(description) => format!("{body} ({description})"),
+        None => body,
+    };
+    notify_error(&t("notify.backend_crashed"), &body);
+}
+
+/// The remote-mode bot came up: started from the GUI, or relaunched by the
+/// watchdog after it died.
+pub fn notify_remote_connected(reconnected: bool) {
+    if !wanted(Kind::RemoteConnected) {
+        return;
+    }
+    let body = t(if reconnected {
+        "notify.remote_reconnected_body"
+    } else {
+        "notify.remote_connected_body"
+    });
+    send(&t("notify.remote_connected"), &body, "network-transmit-receive", INFO_TIMEOUT_MS);
+}
+
+/// A message from remote mode is waiting for approval before it reaches
+/// Claude Code; see `remote_approval`. Stays up until `timeout` or a click,
+/// and calls `decide` with the button chosen. Without action support it
+/// only points the user at the window.
+pub fn notify_remote_approval(
+    text: &str,
+    timeout: Duration,
+    decide: impl FnOnce(bool) + Send + 'static,
+) {
+    let title = t("notify.remote_approval");
+    let text = escape_markup(&preview(text, gui_config::load().notifications.preview_chars));
+    let timeout_ms = u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX);
+    if !supports_actions() {
+        let body = format!("{text}\n\n{}", t("notify.remote_approval_in_window"));
+        send(&title, &body, "dialog-warning", timeout_ms);
+        return;
+    }
+    let mut cmd = command(&title, &text, "dialog-warning", timeout_ms);
+    cmd.arg("--urgency=critical")
+        .arg(format!("--action={APPROVE_ACTION}={}", t("notify.approve")))
+        .arg(format!("--action={DENY_ACTION}={}", t("notify.deny")))
+        .arg("--wait")
+        .stdout(Stdio::piped());
+    std::thread::spawn(move || match cmd.output() {
+        Ok(out) => match String::from_utf8_lossy(&out.stdout).trim() {
+            APPROVE_ACTION => decide(true),
+            DENY_ACTION => decide(false),
+            // Dismissed or expired: still pending in the window.
+            _ => {}
+        },
+        Err(e) => tracing::debug!(error = %e, title, "desktop notification not sent"),
+    });
+}
+
+/// Payload of the backend's `transcription` event.
+#[cfg_attr(not(unix), allow(dead_code))]
+#[derive(Deserialize, Debug)]
+pub struct Transcription {
+    pub text: String,
+    /// `dictation` or `assistant`.
+    pub mode: String,
+}
+
+#[cfg_attr(not(unix), allow(dead_code))]
+pub fn notify_transcription(transcription: Transcription) {
+    let config = gui_config::load().notifications;
+    let text = transcription.text.trim();
+    if !Kind::Transcription.enabled(&config) || text.is_empty() {
+        return;
+    }
+    let title = t(if transcription.mode == "assistant" {
+        "notify.assistant_heard"
+    } else {
+        "notify.transcription_done"
+    });
+    let body = escape_markup(&preview(text, config.preview_chars));
+    send_with_copy(&title, &body, "audio-input-microphone", text.to_string());
+}
+
+/// `text` on one line, cut to `max_chars` characters with an ellipsis.
+pub(crate) fn preview(text: &str, max_chars: usize) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= max_chars {
+        return line;
+    }
+    let mut cut: String = line.chars().take(max_chars.saturating_sub(1)).collect();
+    cut.truncate(cut.trim_end().len());
+    cut.push('…');
+    cut
+}
+
+/// Notification servers render a subset of HTML in the body, so a
+/// transcription mentioning `<b>` must not turn bold.
+fn escape_markup(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_flattens_and_truncates_on_char_boundaries() {
+        assert_eq!(preview("hello\n  world", 20), "hello world");
+        assert_eq!(preview("one two three", 8), "one two…");
+        assert_eq!(preview("héllo wörld", 6), "héllo…");
+        assert_eq!(preview("abc", 3), "abc");
+    }
+
+    #[test]
+    fn markup_is_escaped() {
+        assert_eq!(escape_markup("a <b> & c"), "a &lt;b&gt; &amp; c");
+    }
+
+    #[test]
+    fn kinds_follow_master_switch_and_their_own() {
+        let mut config = NotificationsConfig::default();
+        assert!(Kind::Transcription.enabled(&config));
+        config.backend_crash = false;
+        assert!(!Kind::BackendCrash.enabled(&config));
+        assert!(Kind::RemoteConnected.enabled(&config));
+        config.enabled = false;
+        assert!(!Kind::RemoteConnected.enabled(&config));
+    }
+}
@@ -0,0 +1,325 @@
+//! The overlay as a wlr-layer-shell surface on Wayland.
+//!
+//! Wayland gives ordinary windows no say in where they go or what they stay
+//! above, so on wlroots compositors (Sway, Hyprland, river) the overlay's
+//! `alwaysOnTop` and `set_position` are ignored and it lands wherever the
+//! compositor tiles it. The layer-shell protocol instead lets a surface pick
+//! a layer above normal windows, the edges it is pinned to with margins, and
+//! an exclusive zone; gtk-layer-shell speaks it for the overlay's GTK window.
+//!
+//! That takes the `layer-shell` cargo feature (and libgtk-layer-shell) at
+//! build time and a compositor offering `zwlr_layer_shell_v1` at run time,
+//! which GNOME doesn't. gtk-layer-shell can only convert a window that has
+//! never been shown, so `overlay.backend` is read once at startup: `auto`
+//! converts on a Wayland session when both hold, `layer_shell` insists and
+//! warns when it can't, `window` keeps the ordinary window X11 gets.
+//!
+//! A layer-shell overlay follows `overlay.anchor` like a window does, but as
+//! edges and margins the compositor applies: it can't be dragged, and
+//! without an anchor it sits top right of the output the compositor picks.
+
+use std::sync::atomic::Ordering;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+use crate::error::{AppError, AppResult};
+use crate::gui_config::{
+    self, OverlayAnchor, OverlayBackend, OverlayCorner, OverlayLayer, OverlayLayerShellConfig,
+};
+use crate::state::AppState;
+use crate::window_geometry::ANCHOR_MARGIN;
+
+/// The kind of display session the GUI started in.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplaySession {
+    Wayland,
+    X11,
+    /// Neither, e.g. macOS, Windows or no display at all.
+    Other,
+}
+
+impl DisplaySession {
+    pub(crate) fn detect() -> Self {
+        if !cfg!(target_os = "linux") {
+            return DisplaySession::Other;
+        }
+        let var = |name| std::env::var(name).ok().filter(|v| !v.is_empty());
+        session_from(var("XDG_SESSION_TYPE").as_deref(), crate::is_wayland_env(), var("DISPLAY"))
+    }
+}
+
+/// `XDG_SESSION_TYPE` when it names a graphical session, else whichever of
+/// `WAYLAND_DISPLAY` and `DISPLAY` is set, Wayland first: a compositor
+/// started from a tty leaves the session type at `tty`, and XWayland sets
+/// `DISPLAY` too.
+fn session_from(
+    session_type: Option<&str>,
+    wayland_display: bool,
+    x_display: Option<String>,
+) -> DisplaySession {
+    match session_type.map(str::to_ascii_lowercase).as_deref() {
+        Some("wayland") => DisplaySession::Wayland,
+        Some("x11") => DisplaySession::X11,
+        _ if wayland_display => DisplaySession::Wayland,
+        _ if x_display.is_some() => DisplaySession::X11,
+        _ => DisplaySession::Other,
+    }
+}
+
+/// Whether the overlay should become a layer surface.
+fn use_layer_shell(backend: OverlayBackend, session: DisplaySession, available: bool) -> bool {
+    match backend {
+        OverlayBackend::Window => false,
+        OverlayBackend::LayerShell => available,
+        OverlayBackend::Auto => session == DisplaySession::Wayland && available,
+    }
+}
+
+/// An edge of the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// What a layer-shell overlay asks of the compositor.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "layer-shell"), allow(dead_code))]
+struct Placement {
+    /// Edges the overlay is pinned to, each with its margin in pixels.
+    edges: Vec<(Side, i32)>,
+    layer: OverlayLayer,
+    exclusive_zone: i32,
+    /// Output by the name `get_monitors` reports; `None` lets the
+    /// compositor choose.
+    monitor: Option<String>,
+}
+
+/// `anchor` as edges and margins: `ANCHOR_MARGIN` in from the edges its
+/// corner touches, as `window_geometry::anchor_in_area` places a window,
+/// shifted by the offset but never pushed off the output. The centre
+/// corners pin only the top or bottom edge and are centred along it, so
+/// they ignore `offset.x`.
+fn placement(anchor: Option<&OverlayAnchor>, config: &OverlayLayerShellConfig) -> Placement {
+    let corner = anchor.map(|a| a.corner).unwrap_or_default();
+    let offset = anchor.map(|a| a.offset).unwrap_or_default();
+    let vertical = match corner {
+        OverlayCorner::TopLeft | OverlayCorner::TopCenter | OverlayCorner::TopRight => {
+            (Side::Top, ANCHOR_MARGIN + offset.y)
+        }
+        OverlayCorner::BottomLeft | OverlayCorner::BottomCenter | OverlayCorner::BottomRight => {
+            (Side::Bottom, ANCHOR_MARGIN - offset.y)
+        }
+    };
+    let horizontal = match corner {
+        OverlayCorner::TopLeft | OverlayCorner::BottomLeft => {
+            Some((Side::Left, ANCHOR_MARGIN + offset.x))
+        }
+        OverlayCorner::TopRight | OverlayCorner::BottomRight => {
+            Some((Side::Right, ANCHOR_MARGIN - offset.x))
+        }
+        OverlayCorner::TopCenter | OverlayCorner::BottomCenter => None,
+    };
+    Placement {
+        edges: std::iter::once(vertical)
+            .chain(horizontal)
+            .map(|(side, margin)| (side, margin.max(0)))
+            .collect(),
+        layer: config.layer,
+        exclusive_zone: config.exclusive_zone,
+        monitor: anchor.map(|a| a.monitor.clone()),
+    }
+}
+
+/// Whether the overlay was made a layer surface at startup.
+pub(crate) fn is_active(app: &AppHandle) -> bool {
+    app.state::<AppState>().overlay_layer_shell.load(Ordering::SeqCst)
+}
+
+/// Pick the overlay's backend for this session and convert it if that is
+/// layer-shell. Runs on the main thread during setup, before the overlay is
+/// first shown.
+pub(crate) fn setup(window: &WebviewWindow) {
+    let config = gui_config::load().overlay;
+    let session = DisplaySession::detect();
+    let available = imp::available();
+    let wanted = use_layer_shell(config.backend, session, available);
+    if config.backend == OverlayBackend::LayerShell && !available {
+        tracing::warn!(
+            "overlay.backend is layer_shell, but {}; using an ordinary window",
+            if cfg!(feature = "layer-shell") {
+                "the compositor doesn't support wlr-layer-shell"
+            } else {
+                "this build lacks the layer-shell feature"
+            }
+        );
+    }
+    let placement = placement(config.anchor.as_ref(), &config.layer_shell);
+    let active = wanted && imp::init(window, &placement);
+    tracing::info!(
+        ?session,
+        backend = ?config.backend,
+        layer_shell = active,
+        "overlay backend chosen"
+    );
+    window.state::<AppState>().overlay_layer_shell.store(active, Ordering::SeqCst);
+}
+
+/// Pin the layer-shell overlay per `anchor` and the `overlay.layer_shell`
+/// settings. GTK is only touched on the main thread, so this is queued
+/// there.
+pub(crate) fn place(window: &WebviewWindow, anchor: Option<&OverlayAnchor>) -> AppResult<()> {
+    let placement = placement(anchor, &gui_config::load().overlay.layer_shell);
+    let target = window.clone();
+    window
+        .run_on_main_thread(move || imp::apply(&target, &placement))
+        .map_err(|e| AppError::Other(e.to_string()))
+}
+
+#[cfg(all(target_os = "linux", feature = "layer-shell"))]
+mod imp {
+    use gtk::prelude::*;
+    use gtk_layer_shell::{Edge, KeyboardMode, Layer, LayerShell};
+    use tauri::WebviewWindow;
+
+    use super::{Placement, Side};
+    use crate::gui_config::OverlayLayer;
+
+    pub(super) fn available() -> bool {
+        gtk_layer_shell::is_supported()
+    }
+
+    /// Turn the overlay into a layer surface placed per `placement`. False
+    /// if its GTK window can't be had, leaving it an ordinary window.
+    pub(super) fn init(window: &WebviewWindow, placement: &Placement) -> bool {
+        let gtk_window = match window.gtk_window() {
+            Ok(gtk_window) => gtk_window,
+            Err(e) => {
+                tracing::warn!(error = %e, "no GTK window for the overlay; keeping it a window");
+                return false;
+            }
+        };
+        // The role can only be set before the window is realized.
+        gtk_window.hide();
+        if gtk_window.is_realized() {
+            gtk_window.unrealize();
+        }
+        gtk_window.init_layer_shell();
+        gtk_window.set_namespace("synthia-overlay");
+        // Never take the keyboard from the window being dictated into.
+        gtk_window.set_keyboard_mode(KeyboardMode::None);
+        configure(&gtk_window, placement);
+        true
+    }
+
+    pub(super) fn apply(window: &WebviewWindow, placement: &Placement) {
+        match window.gtk_window() {
+            Ok(gtk_window) => configure(&gtk_window, placement),
+            Err(e) => tracing::warn!(error = %e, "failed to re-place layer-shell overlay"),
+        }
+    }
+
+    fn configure(window: &gtk::ApplicationWindow, placement: &Placement) {
+        window.set_layer(match placement.layer {
+            OverlayLayer::Top => Layer::Top,
+            OverlayLayer::Overlay => Layer::Overlay,
+        });
+        for edge in [Edge::Top, Edge::Bottom, Edge::Left, Edge::Right] {
+            window.set_anchor(edge, false);
+            window.set_layer_shell_margin(edge, 0);
+        }
+        for &(side, margin) in &placement.edges {
+            let edge = match side {
+                Side::Top => Edge::Top,
+                Side::Bottom => Edge::Bottom,
+                Side::Left => Edge::Left,
+                Side::Right => Edge::Right,
+            };
+            window.set_anchor(edge, true);
+            window.set_layer_shell_margin(edge, margin);
+        }
+        window.set_exclusive_zone(placement.exclusive_zone);
+        // Tauri names monitors by their GDK model, so match on that. An
+        // output that isn't connected keeps the one already in use, or
+        // leaves the choice to the compositor.
+        let display = window.display();
+        let monitor = placement.monitor.as_deref().and_then(|name| {
+            (0..display.n_monitors())
+                .filter_map(|i| display.monitor(i))
+                .find(|m| m.model().as_deref() == Some(name))
+        });
+        if let Some(monitor) = &monitor {
+            window.set_monitor(monitor);
+        }
+    }
+}
+
+/// Builds without the feature: nothing is ever converted.
+#[cfg(not(all(target_os = "linux", feature = "layer-shell")))]
+mod imp {
+    use tauri::WebviewWindow;
+
+    use super::Placement;
+
+    pub(super) fn available() -> bool {
+        false
+    }
+
+    pub(super) fn init(_window: &WebviewWindow, _placement: &Placement) -> bool {
+        false
+    }
+
+    pub(super) fn apply(_window: &WebviewWindow, _placement: &Placement) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gui_config::OverlayOffset;
+
+    #[test]
+    fn session_type_wins_over_display_variables() {
+        let x = || Some(":0".to_string());
+        assert_eq!(session_from(Some("wayland"), false, x()), DisplaySession::Wayland);
+        assert_eq!(session_from(Some("X11"), true, x()), DisplaySession::X11);
+        assert_eq!(session_from(Some("tty"), true, x()), DisplaySession::Wayland);
+        assert_eq!(session_from(None, false, x()), DisplaySession::X11);
+        assert_eq!(session_from(None, false, None), DisplaySession::Other);
+    }
+
+    #[test]
+    fn auto_uses_layer_shell_only_on_a_supporting_wayland_session() {
+        use DisplaySession::*;
+        assert!(use_layer_shell(OverlayBackend::Auto, Wayland, true));
+        assert!(!use_layer_shell(OverlayBackend::Auto, Wayland, false));
+        assert!(!use_layer_shell(OverlayBackend::Auto, X11, true));
+        assert!(use_layer_shell(OverlayBackend::LayerShell, X11, true));
+        assert!(!use_layer_shell(OverlayBackend::LayerShell, Wayland, false));
+        assert!(!use_layer_shell(OverlayBackend::Window, Wayland, true));
+    }
+
+    #[test]
+    fn anchors_become_edges_and_margins() {
+        let config = OverlayLayerShellConfig { exclusive_zone: 40, ..Default::default() };
+        let unanchored = placement(None, &config);
+        assert_eq!(unanchored.edges, [(Side::Top, ANCHOR_MARGIN), (Side::Right, ANCHOR_MARGIN)]);
+        assert_eq!(unanchored.monitor, None);
+        assert_eq!(unanchored.exclusive_zone, 40);
+
+        let anchor = OverlayAnchor {
+            monitor: "DP-1".to_string(),
+            corner: OverlayCorner::BottomLeft,
+            offset: OverlayOffset { x: 10, y: 30 },
+        };
+        let placed = placement(Some(&anchor), &config);
+        assert_eq!(placed.edges, [(Side::Bottom, 0), (Side::Left, ANCHOR_MARGIN + 10)]);
+        assert_eq!(placed.monitor.as_deref(), Some("DP-1"));
+
+        let centred = OverlayAnchor { corner: OverlayCorner::TopCenter, ..anchor };
+        assert_eq!(placement(Some(&centred), &config).edges, [(Side::Top, ANCHOR_MARGIN + 30)]);
+    }
+}
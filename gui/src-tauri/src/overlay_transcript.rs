@@ -0,0 +1,217 @@
+//! Live transcription text in the overlay.
+//!
+//! While Whisper decodes, the backend sends `partial_transcript` events over
+//! the IPC socket: `{"utterance": n, "text": "...", "final": false}`, with
+//! `text` holding everything decoded so far for that utterance, and one
+//! last event with `"final": true` once the text is settled. If nothing was
+//! recognised there is no final event, unless partial text was already
+//! shown, which an empty final clears. The buffer here keeps the newest
+//! utterance only, drops partials that arrive after its final text or
+//! belong to an older one, and trims the text to the words the small
+//! overlay can show. Each change goes to the overlay as
+//! `overlay-transcript`.
+//!
+//! Final text stays up for `FINAL_LINGER`, then clears unless a newer
+//! utterance has started. `overlay.transcript = "off"` in `gui.toml`
+//! (`set_overlay_transcript_mode`) ignores partials altogether.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::gui_config::{self, OverlayTranscriptMode};
+use crate::state::AppState;
+
+/// Characters of the transcript the overlay has room for.
+const MAX_DISPLAY_CHARS: usize = 28;
+
+/// How long final text stays on the overlay.
+const FINAL_LINGER: Duration = Duration::from_millis(2500);
+
+/// Payload of the backend's `partial_transcript` event.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Partial {
+    /// When the recording started, in milliseconds since the epoch, which
+    /// tells utterances apart and orders them across backend restarts.
+    pub utterance: u64,
+    pub text: String,
+    #[serde(rename = "final", default)]
+    pub is_final: bool,
+}
+
+/// Payload of the `overlay-transcript` event. Empty `text` clears it.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct OverlayTranscript {
+    pub text: String,
+    #[serde(rename = "final")]
+    pub is_final: bool,
+}
+
+/// The utterance the overlay is showing.
+#[derive(Debug, Default)]
+pub struct TranscriptBuffer {
+    utterance: Option<u64>,
+    text: String,
+    is_final: bool,
+}
+
+impl TranscriptBuffer {
+    /// Take in `partial`, returning what the overlay should show now, or
+    /// `None` if it doesn't change anything.
+    fn apply(&mut self, partial: Partial) -> Option<OverlayTranscript> {
+        match self.utterance {
+            Some(current) if partial.utterance < current => return None,
+            Some(current) if partial.utterance == current && self.is_final => return None,
+            _ => {}
+        }
+        let text = display_tail(&partial.text, MAX_DISPLAY_CHARS);
+        if self.utterance == Some(partial.utterance) && self.text == text && !partial.is_final {
+            return None;
+        }
+        self.utterance = Some(partial.utterance);
+        self.text = text.clone();
+        self.is_final = partial.is_final;
+        Some(OverlayTranscript { text, is_final: partial.is_final })
+    }
+
+    /// Drop the text if it still belongs to `utterance`, forgetting the
+    /// utterance too so a backend whose clock stepped back isn't ignored.
+    fn expire(&mut self, utterance: u64) -> bool {
+        let expired = self.utterance == Some(utterance) && !self.text.is_empty();
+        if expired {
+            *self = TranscriptBuffer::default();
+        }
+        expired
+    }
+}
+
+/// The last `max_chars` characters of `text` on one line, starting at a
+/// word boundary behind an ellipsis when cut, so the newest words show.
+fn display_tail(text: &str, max_chars: usize) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let len = line.chars().count();
+    if len <= max_chars {
+        return line;
+    }
+    let tail: String = line
+        .chars()
+        .skip(len - max_chars.saturating_sub(1))
+        .collect();
+    // Start at the next whole word unless that would leave nothing.
+    let tail = match tail.split_once(' ') {
+        Some((_, rest)) if !rest.is_empty() => rest.to_string(),
+        _ => tail,
+    };
+    format!("…{tail}")
+}
+
+fn emit(app: &AppHandle, transcript: OverlayTranscript) {
+    if let Some(window) = app.get_webview_window("overlay") {
+        let _ = window.emit("overlay-transcript", transcript);
+    }
+}
+
+/// Handle a `partial_transcript` event from the backend.
+pub fn on_partial(app: &AppHandle, payload: Value) {
+    if gui_config::load().overlay.transcript == OverlayTranscriptMode::Off {
+        return;
+    }
+    let partial: Partial = match serde_json::from_value(payload) {
+        Ok(partial) => partial,
+        Err(e) => {
+            tracing::warn!(error = %e, "malformed partial_transcript event");
+            return;
+        }
+    };
+    let utterance = partial.utterance;
+    let state = app.state::<AppState>();
+    let Some(update) = state.overlay_transcript.lock().ok().and_then(|mut b| b.apply(partial)) else {
+        return;
+    };
+    let is_final = update.is_final;
+    emit(app, update);
+    if is_final {
+        let app = app.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(FINAL_LINGER);
+            let state = app.state::<AppState>();
+            let expired = state.overlay_transcript.lock().is_ok_and(|mut b| b.expire(utterance));
+            if expired {
+                emit(&app, OverlayTranscript { text: String::new(), is_final: true });
+            }
+        });
+    }
+}
+
+/// Clear the overlay's transcript, e.g. after live text was turned off.
+pub fn clear(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let cleared = state.overlay_transcript.lock().is_ok_and(|mut b| {
+        let had_text = !b.text.is_empty();
+        *b = TranscriptBuffer::default();
+        had_text
+    });
+    if cleared {
+        emit(app, OverlayTranscript { text: String::new(), is_final: true });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partial(utterance: u64, text: &str, is_final: bool) -> Partial {
+        Partial {
+            utterance,
+            text: text.into(),
+            is_final,
+        }
+    }
+
+    #[test]
+    fn buffer_follows_the_newest_utterance() {
+        let mut buffer = TranscriptBuffer::default();
+        assert_eq!(
+            buffer.apply(partial(1, "hello", false)).unwrap().text,
+            "hello"
+        );
+        // Same text again is not worth an event.
+        assert_eq!(buffer.apply(partial(1, "hello", false)), None);
+        assert_eq!(buffer.apply(partial(1, "hello world", false)).unwrap().text, "hello world");
+        let done = buffer.apply(partial(1, "Hello world.", true)).unwrap();
+        assert!(done.is_final);
+        // Stragglers after the final text, or from an older utterance, are dropped.
+        assert_eq!(buffer.apply(partial(1, "hello wor", false)), None);
+        assert_eq!(buffer.apply(partial(0, "old", false)), None);
+        assert_eq!(buffer.apply(partial(2, "next", false)).unwrap().text, "next");
+    }
+
+    #[test]
+    fn expiry_only_clears_its_own_utterance() {
+        let mut buffer = TranscriptBuffer::default();
+        buffer.apply(partial(1, "first", true));
+        buffer.apply(partial(2, "second", false));
+        assert!(!buffer.expire(1));
+        assert_eq!(buffer.text, "second");
+        assert!(buffer.expire(2));
+        assert!(!buffer.expire(2));
+        // Once expired, even an older utterance id shows again.
+        assert_eq!(
+            buffer.apply(partial(1, "again", false)).unwrap().text,
+            "again"
+        );
+    }
+
+    #[test]
+    fn display_tail_keeps_the_newest_whole_words() {
+        assert_eq!(display_tail("short  text\n", 28), "short text");
+        assert_eq!(
+            display_tail("the quick brown fox jumps over the lazy dog", 20),
+            "…over the lazy dog"
+        );
+        assert_eq!(display_tail("abcdefghijklmnop", 6), "…lmnop");
+        assert_eq!(display_tail("", 6), "");
+    }
+}
@@ -1,7 +1,9 @@
-//! Filename and path-traversal safety helpers.
+//! Filename and path-traversal safety helpers, plus atomic file replacement.
 
 use crate::error::{AppError, AppResult};
+use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Validate a single filename has no path separators, parent refs, or hidden-dot prefix.
 ///
@@ -127,10 +129,122 @@ pub fn safe_new_file(base: &Path, parent_rel: &str, filename: &str) -> AppResult
     Ok(parent_canonical.join(filename))
 }
 
+/// Replace `path` with `contents` so readers see either the old file or the
+/// new one, never a partial write: the data goes to a temp file in the same
+/// directory, which is then renamed over the target.
+///
+/// The temp name carries the pid and a timestamp so concurrent writers in
+/// this process don't rename each other's half-written files. The temp file
+/// is created with the target's permissions, so a file the user locked down
+/// stays that way; a new file gets the default ones.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> AppResult<()> {
+    write_atomic_mode(path, contents.as_ref(), existing_mode(path))
+}
+
+/// [`write_atomic`] for files only their owner may read, such as those that
+/// hold tokens: the temp file is created with mode 0600, so the data is
+/// never briefly world-readable, whatever the target's mode was.
+pub fn write_atomic_private(path: &Path, contents: impl AsRef<[u8]>) -> AppResult<()> {
+    write_atomic_mode(path, contents.as_ref(), Some(0o600))
+}
+
+fn write_atomic_mode(path: &Path, contents: &[u8], mode: Option<u32>) -> AppResult<()> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| AppError::Path(format!("no file name: {}", path.display())))?
+        .to_string_lossy();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!(".{name}.{}.{nanos}.tmp", std::process::id()));
+    create_with_mode(&temp_path, contents, mode)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn existing_mode(path: &Path) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).ok().map(|m| m.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn existing_mode(_path: &Path) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn create_with_mode(path: &Path, contents: &[u8], mode: Option<u32>) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    if let Some(mode) = mode {
+        options.mode(mode);
+    }
+    let mut file = options.open(path)?;
+    if let Some(mode) = mode {
+        // The umask may have taken bits off the mode asked for at creation.
+        file.set_permissions(fs::Permissions::from_mode(mode))?;
+    }
+    file.write_all(contents)
+}
+
+#[cfg(not(unix))]
+fn create_with_mode(path: &Path, contents: &[u8], _mode: Option<u32>) -> std::io::Result<()> {
+    fs::write(path, contents)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn write_atomic_replaces_without_leftovers() {
+        let dir = std::env::temp_dir().join(format!("synthia-atomic-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("state.json");
+        write_atomic(&path, "old").unwrap();
+        write_atomic(&path, "new").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_atomic_keeps_the_target_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("synthia-mode-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("gui.toml");
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+        write_atomic(&path, "new").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_atomic_private_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("synthia-private-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("state.json");
+        write_atomic_private(&path, "{}").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn rejects_empty_filename() {
         assert!(validate_filename("").is_err());
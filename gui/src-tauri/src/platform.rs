@@ -0,0 +1,149 @@
+//! What differs between Unix and Windows: stopping processes, giving the
+//! backend a process group of its own, and where the venv's interpreter and
+//! the runtime files live.
+//!
+//! Unix stops processes with signals, sent to one pid or to a whole process
+//! group. Windows has neither: a polite stop is `taskkill`, which asks the
+//! program to close (console programs without a window refuse), a forced
+//! one is `taskkill /F`, and `/T` takes in the process tree where Unix would
+//! signal the group.
+//!
+//! macOS follows the Unix path for processes; its differences are in where
+//! runtime files go, see `runtime_dir`.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// A virtualenv's interpreter, relative to the directory holding `venv`.
+#[cfg(not(windows))]
+pub const VENV_PYTHON: &str = "venv/bin/python";
+#[cfg(windows)]
+pub const VENV_PYTHON: &str = "venv/Scripts/python.exe";
+
+#[cfg(windows)]
+const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// How firmly to stop a process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// SIGTERM, or `taskkill` without `/F`: the process may clean up first.
+    Terminate,
+    /// SIGKILL, or `taskkill /F`.
+    Kill,
+}
+
+/// Per-user directory for sockets, lock and state files: `$XDG_RUNTIME_DIR`
+/// where there is one, else `/tmp` on Linux and other Unixes (the backend's
+/// own default). macOS and Windows have no runtime dir but do have a
+/// per-user temp directory (`$TMPDIR` under `/var/folders` on macOS), which
+/// is used instead; the backend is told about it through
+/// `XDG_RUNTIME_DIR`, see `runtime_env`.
+pub fn runtime_dir() -> PathBuf {
+    dirs::runtime_dir().unwrap_or_else(|| {
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            PathBuf::from("/tmp")
+        }
+        #[cfg(any(not(unix), target_os = "macos"))]
+        {
+            std::env::temp_dir()
+        }
+    })
+}
+
+/// Point a spawned backend or bot at the runtime dir this GUI uses. The
+/// Python side reads `XDG_RUNTIME_DIR` and falls back to `/tmp`, which
+/// would miss a `runtime.dir` override or the macOS temp directory.
+pub fn runtime_env(cmd: &mut Command, runtime_dir: &Path) {
+    cmd.env("XDG_RUNTIME_DIR", runtime_dir);
+}
+
+/// The user's home directory, or the temp directory if it can't be found.
+pub fn home_dir() -> PathBuf {
+    dirs::home_dir().unwrap_or_else(std::env::temp_dir)
+}
+
+/// Keep a console helper from flashing a window on Windows. A no-op
+/// elsewhere.
+pub fn no_window(cmd: &mut Command) -> &mut Command {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    cmd
+}
+
+/// Run a short helper with its output discarded. True if it succeeded.
+fn quietly(cmd: &mut Command) -> bool {
+    no_window(cmd)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+#[cfg(unix)]
+fn signal(how: Stop) -> &'static str {
+    match how {
+        Stop::Terminate => "-TERM",
+        Stop::Kill => "-KILL",
+    }
+}
+
+#[cfg(windows)]
+fn taskkill(how: Stop, tree: bool, pid: i32) -> Command {
+    let mut cmd = Command::new("taskkill");
+    if how == Stop::Kill {
+        cmd.arg("/F");
+    }
+    if tree {
+        cmd.arg("/T");
+    }
+    cmd.args(["/PID", &pid.to_string()]);
+    cmd
+}
+
+/// Stop exactly `pid`. True if the request was delivered.
+pub fn stop_pid(pid: i32, how: Stop) -> bool {
+    #[cfg(unix)]
+    return quietly(Command::new("kill").args([signal(how), &pid.to_string()]));
+    #[cfg(windows)]
+    return quietly(&mut taskkill(how, false, pid));
+}
+
+/// Stop every process in the group led by `pgid` (the process tree rooted
+/// at it on Windows). True if at least one process received the request.
+pub fn stop_group(pgid: i32, how: Stop) -> bool {
+    #[cfg(unix)]
+    return quietly(Command::new("kill").args([signal(how), "--", &format!("-{pgid}")]));
+    #[cfg(windows)]
+    return quietly(&mut taskkill(how, true, pgid));
+}
+
+/// Whether anything in the group led by `pgid` is still running. Windows
+/// has no groups, so there it is the leader or one of its children.
+pub fn group_alive(pgid: i32) -> bool {
+    #[cfg(unix)]
+    return quietly(Command::new("kill").args(["-0", "--", &format!("-{pgid}")]));
+    #[cfg(windows)]
+    return crate::process::pid_alive(pgid) || crate::process::has_children(pgid);
+}
+
+/// Start `cmd` in a process group of its own, so the whole group can be
+/// stopped together, and without a console window on Windows.
+pub fn isolate(cmd: &mut Command) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(CREATE_NEW_PROCESS_GROUP | CREATE_NO_WINDOW);
+    }
+}
@@ -0,0 +1,141 @@
+//! Low-power mode while running on battery.
+//!
+//! A monitor thread checks the power supply every `CHECK_INTERVAL`. When the
+//! machine goes onto battery and `power.low_power_on_battery` is set, the
+//! tray stops animating the recording icon and the state watcher, if it had
+//! to fall back to polling, is restarted at `power.battery_poll_interval_ms`.
+//! Both are restored on AC. Every change is published as a `power-mode`
+//! event.
+//!
+//! Detection reads `/sys/class/power_supply` and is Linux-only; elsewhere
+//! the machine is always treated as on AC.
+
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::gui_config;
+use crate::state::AppState;
+use crate::threads;
+use crate::tray;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+/// One entry under `/sys/class/power_supply`.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct PowerSupply {
+    /// `type`: `Mains`, `USB`, `Battery`, ...
+    kind: String,
+    /// `online`, for mains and USB adapters.
+    online: bool,
+    /// `status`, for batteries: `Charging`, `Discharging`, `Full`, ...
+    status: String,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct PowerMode {
+    pub on_battery: bool,
+    /// On battery and allowed to save power by `power.low_power_on_battery`.
+    pub low_power: bool,
+}
+
+/// On battery means no adapter is online and some battery is discharging.
+/// Machines without a battery (desktops, VMs) are never on battery.
+fn on_battery(supplies: &[PowerSupply]) -> bool {
+    let adapter_online = supplies
+        .iter()
+        .any(|s| matches!(s.kind.as_str(), "Mains" | "USB") && s.online);
+    let discharging = supplies
+        .iter()
+        .any(|s| s.kind == "Battery" && s.status == "Discharging");
+    !adapter_online && discharging
+}
+
+fn read_supply(dir: &Path) -> PowerSupply {
+    let read = |name: &str| {
+        fs::read_to_string(dir.join(name))
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default()
+    };
+    PowerSupply {
+        kind: read("type"),
+        online: read("online") == "1",
+        status: read("status"),
+    }
+}
+
+fn read_supplies() -> Vec<PowerSupply> {
+    let Ok(entries) = fs::read_dir(POWER_SUPPLY_DIR) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| read_supply(&entry.path()))
+        .collect()
+}
+
+/// The current mode, from the last power check and the live config.
+pub(crate) fn mode(state: &AppState) -> PowerMode {
+    let on_battery = state.on_battery.load(Ordering::SeqCst);
+    PowerMode {
+        on_battery,
+        low_power: on_battery && gui_config::load().power.low_power_on_battery,
+    }
+}
+
+/// Re-read the power supply and, if the mode changed, apply and publish it.
+fn check(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let before = mode(&state);
+    state.on_battery.store(on_battery(&read_supplies()), Ordering::SeqCst);
+    let after = mode(&state);
+    if after == before {
+        return;
+    }
+    tracing::info!(
+        on_battery = after.on_battery,
+        low_power = after.low_power,
+        "power mode changed"
+    );
+    tray::set_low_power(app, after.low_power);
+    if after.low_power != before.low_power {
+        crate::install_state_watcher(app);
+    }
+    let _ = app.emit("power-mode", after);
+}
+
+/// Read the power supply once, so the state watcher starts at the right
+/// interval, then keep checking on a background thread.
+pub(crate) fn spawn_monitor(app: AppHandle) {
+    let state = app.state::<AppState>();
+    state.on_battery.store(on_battery(&read_supplies()), Ordering::SeqCst);
+    tray::set_low_power(&app, mode(&state).low_power);
+    threads::spawn_supervised("power monitor", move || loop {
+        std::thread::sleep(CHECK_INTERVAL);
+        check(&app);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn supply(kind: &str, online: bool, status: &str) -> PowerSupply {
+        PowerSupply { kind: kind.into(), online, status: status.into() }
+    }
+
+    #[test]
+    fn on_battery_needs_a_discharging_battery_and_no_adapter() {
+        assert!(!on_battery(&[]));
+        assert!(on_battery(&[supply("Mains", false, ""), supply("Battery", false, "Discharging")]));
+        assert!(!on_battery(&[supply("Mains", true, ""), supply("Battery", false, "Charging")]));
+        assert!(!on_battery(&[supply("USB", true, ""), supply("Battery", false, "Discharging")]));
+        assert!(!on_battery(&[supply("Battery", false, "Full")]));
+    }
+}
@@ -0,0 +1,413 @@
+//! Process discovery helpers shared by the lifecycle and remote-mode commands.
+//!
+//! Nothing here kills by pattern. Processes are listed with `sysinfo`,
+//! matched on their command line and then checked against their real argv,
+//! so an editor or pager that merely has `telegram_bot.py` open never counts
+//! as the bot. Children this GUI spawned are stopped by exact pid and
+//! SIGKILLed if they ignore SIGTERM; processes started by someone else only
+//! ever get SIGTERM. See `platform` for what those mean on Windows.
+
+use std::io::Read;
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, Process, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+use crate::error::{AppError, AppResult};
+use crate::platform::{self, Stop};
+
+/// Command-line pattern matching the Python voice backend, run from the
+/// checkout or as the bundled `synthia-backend` binary.
+pub const BACKEND_PATTERN: &str = r"synthia[.]main|[/\\]synthia-backend([.]exe)?( |$)";
+
+/// Command-line pattern matching the Telegram remote-mode bot, as a script
+/// or the bundled `synthia-telegram-bot` binary.
+pub const TELEGRAM_BOT_PATTERN: &str =
+    r"telegram_bot[.]py|[/\\]synthia-telegram-bot([.]exe)?( |$)";
+
+/// The Discord bot, likewise.
+pub const DISCORD_BOT_PATTERN: &str = r"discord_bot[.]py|[/\\]synthia-discord-bot([.]exe)?( |$)";
+
+/// The Matrix bot, likewise.
+pub const MATRIX_BOT_PATTERN: &str = r"matrix_bot[.]py|[/\\]synthia-matrix-bot([.]exe)?( |$)";
+
+/// Any remote-mode bot, whichever channel it serves.
+pub const BOT_PATTERN: &str =
+    r"(telegram|discord|matrix)_bot[.]py|[/\\]synthia-(telegram|discord|matrix)-bot([.]exe)?( |$)";
+
+/// Programs that run the file or module named in their arguments.
+const INTERPRETERS: [&str; 3] = ["python", "bash", "sh"];
+
+/// Whether `argv` runs something matching `target`: either the program
+/// itself (a bundled binary) or the script or module an interpreter was
+/// given. Any other program, such as `vim telegram_bot.py`, doesn't count.
+fn runs(argv: &[String], target: &Regex) -> bool {
+    let Some(program) = argv.first() else {
+        return false;
+    };
+    if target.is_match(program) {
+        return true;
+    }
+    let name = program.rsplit(['/', '\\']).next().unwrap_or(program);
+    let name = name.strip_suffix(".exe").unwrap_or(name);
+    let interpreted = INTERPRETERS
+        .iter()
+        .any(|i| name == *i || name.strip_prefix(i).is_some_and(|v| v.starts_with(['2', '3'])));
+    if !interpreted {
+        return false;
+    }
+    let mut args = argv[1..].iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-m" => return args.next().is_some_and(|module| target.is_match(module)),
+            "-c" => return false,
+            flag if flag.starts_with('-') => continue,
+            script => return target.is_match(script),
+        }
+    }
+    false
+}
+
+/// The processes in `which`, with their command lines loaded.
+fn snapshot(which: ProcessesToUpdate<'_>) -> System {
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        which,
+        true,
+        ProcessRefreshKind::nothing().with_cmd(UpdateKind::Always),
+    );
+    system
+}
+
+fn process_argv(process: &Process) -> Vec<String> {
+    process
+        .cmd()
+        .iter()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect()
+}
+
+/// Pids of the processes running something matching `pattern` (see
+/// `runs`), excluding this one.
+pub fn find_processes(pattern: &str) -> Vec<i32> {
+    let Ok(target) = Regex::new(pattern) else {
+        return Vec::new();
+    };
+    let own = std::process::id();
+    let system = snapshot(ProcessesToUpdate::All);
+    let mut pids: Vec<i32> = system
+        .processes()
+        .values()
+        // Linux lists each thread as well; they share the process's argv.
+        .filter(|p| p.thread_kind().is_none() && p.pid().as_u32() != own)
+        .filter(|p| {
+            let argv = process_argv(p);
+            target.is_match(&argv.join(" ")) && runs(&argv, &target)
+        })
+        .map(|p| p.pid().as_u32() as i32)
+        .collect();
+    pids.sort_unstable();
+    pids
+}
+
+/// True if any process runs something matching `pattern`.
+pub fn is_running(pattern: &str) -> bool {
+    !find_processes(pattern).is_empty()
+}
+
+/// Number of processes running something matching `pattern`.
+pub fn count_running(pattern: &str) -> usize {
+    find_processes(pattern).len()
+}
+
+/// True if a process with `pid` exists.
+pub fn pid_alive(pid: i32) -> bool {
+    argv(pid).is_some()
+}
+
+/// Whether any process has `pid` as its parent.
+#[cfg(windows)]
+pub fn has_children(pid: i32) -> bool {
+    let parent = Pid::from_u32(pid as u32);
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::nothing());
+    system.processes().values().any(|p| p.parent() == Some(parent))
+}
+
+/// Arguments of `pid`; `None` once the process is gone.
+pub fn argv(pid: i32) -> Option<Vec<String>> {
+    let pid = Pid::from_u32(u32::try_from(pid).ok()?);
+    snapshot(ProcessesToUpdate::Some(&[pid]))
+        .process(pid)
+        .map(process_argv)
+}
+
+/// Command line of `pid` with arguments space-separated; `None` once the
+/// process is gone.
+pub fn cmdline(pid: i32) -> Option<String> {
+    argv(pid).map(|argv| argv.join(" "))
+}
+
+/// How a `terminate_process_group` call ended.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShutdownKind {
+    /// Nothing in the group was alive to signal.
+    AlreadyExited,
+    /// The group exited within the grace period after SIGTERM.
+    Graceful,
+    /// The grace period ran out and the group was SIGKILLed.
+    ForceKilled,
+}
+
+/// How a process ended, in both readable and machine-checkable form.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExitInfo {
+    /// `describe_exit` output, e.g. `exit code 1` or `signal 9`.
+    pub description: String,
+    pub code: Option<i32>,
+    /// Terminating signal on Unix; `None` for a normal exit.
+    pub signal: Option<i32>,
+}
+
+impl From<ExitStatus> for ExitInfo {
+    fn from(status: ExitStatus) -> Self {
+        #[cfg(unix)]
+        let signal = {
+            use std::os::unix::process::ExitStatusExt;
+            status.signal()
+        };
+        #[cfg(not(unix))]
+        let signal = None;
+        ExitInfo {
+            description: describe_exit(status),
+            code: status.code(),
+            signal,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ShutdownOutcome {
+    pub kind: ShutdownKind,
+    /// The group leader's exit, when we own it and could reap it.
+    pub exit_status: Option<ExitInfo>,
+    pub elapsed_ms: u64,
+    pub grace_ms: u64,
+    pub finished_at: DateTime<Local>,
+}
+
+/// SIGTERM the whole group, wait up to `grace` for it to drain, then SIGKILL
+/// whatever is left. `leader` is reaped along the way so it doesn't linger
+/// as a zombie that keeps the group looking alive.
+pub fn terminate_process_group(
+    pgid: i32,
+    mut leader: Option<&mut Child>,
+    grace: Duration,
+) -> ShutdownOutcome {
+    let started = Instant::now();
+    let leader_status = |leader: &mut Option<&mut Child>| {
+        leader
+            .as_mut()
+            .and_then(|c| c.try_wait().ok().flatten())
+            .map(ExitInfo::from)
+    };
+    let outcome = |kind, exit_status| ShutdownOutcome {
+        kind,
+        exit_status,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        grace_ms: grace.as_millis() as u64,
+        finished_at: Local::now(),
+    };
+
+    if !platform::stop_group(pgid, Stop::Terminate) {
+        return outcome(ShutdownKind::AlreadyExited, leader_status(&mut leader));
+    }
+    let deadline = started + grace;
+    loop {
+        let status = leader_status(&mut leader);
+        if !platform::group_alive(pgid) {
+            tracing::info!(pgid, status = ?status, "process group exited gracefully");
+            return outcome(ShutdownKind::Graceful, status);
+        }
+        if Instant::now() >= deadline {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    tracing::warn!(
+        pgid,
+        grace_ms = grace.as_millis() as u64,
+        "process group ignored SIGTERM; sending SIGKILL"
+    );
+    platform::stop_group(pgid, Stop::Kill);
+    let status = leader
+        .map(|c| c.wait())
+        .and_then(Result::ok)
+        .map(ExitInfo::from);
+    tracing::warn!(pgid, status = ?status, "process group force-killed");
+    outcome(ShutdownKind::ForceKilled, status)
+}
+
+/// SIGTERM a child this GUI spawned, wait up to `grace` for it to exit,
+/// then SIGKILL it. Only the child's own pid is signalled.
+pub fn terminate_child(child: &mut Child, grace: Duration) -> ShutdownOutcome {
+    let started = Instant::now();
+    let pid = child.id() as i32;
+    let outcome = |kind, exit_status| ShutdownOutcome {
+        kind,
+        exit_status,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        grace_ms: grace.as_millis() as u64,
+        finished_at: Local::now(),
+    };
+
+    if let Ok(Some(status)) = child.try_wait() {
+        return outcome(ShutdownKind::AlreadyExited, Some(status.into()));
+    }
+    platform::stop_pid(pid, Stop::Terminate);
+    let deadline = started + grace;
+    while Instant::now() < deadline {
+        if let Ok(Some(status)) = child.try_wait() {
+            tracing::info!(pid, status = %describe_exit(status), "child exited gracefully");
+            return outcome(ShutdownKind::Graceful, Some(status.into()));
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    tracing::warn!(pid, grace_ms = grace.as_millis() as u64, "child ignored SIGTERM; sending SIGKILL");
+    let _ = child.kill();
+    let status = child.wait().ok().map(ExitInfo::from);
+    outcome(ShutdownKind::ForceKilled, status)
+}
+
+/// SIGTERM processes this GUI didn't spawn and wait up to `grace` for them
+/// to exit. They are never SIGKILLed; returns the pids still alive.
+pub fn terminate_foreign(pids: &[i32], grace: Duration) -> Vec<i32> {
+    for &pid in pids {
+        tracing::info!(pid, cmdline = ?cmdline(pid), "sending SIGTERM to process started outside the GUI");
+        platform::stop_pid(pid, Stop::Terminate);
+    }
+    let deadline = Instant::now() + grace;
+    loop {
+        let alive: Vec<i32> = pids.iter().copied().filter(|pid| pid_alive(*pid)).collect();
+        if alive.is_empty() || Instant::now() >= deadline {
+            if !alive.is_empty() {
+                tracing::warn!(?alive, "processes started outside the GUI ignored SIGTERM");
+            }
+            return alive;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Human-readable exit reason: `exit code N`, or `signal N` on Unix kills.
+pub fn describe_exit(status: ExitStatus) -> String {
+    if let Some(code) = status.code() {
+        return format!("exit code {}", code);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return format!("signal {}", signal);
+        }
+    }
+    "unknown exit status".to_string()
+}
+
+/// Read `pipe` to the end on its own thread.
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+/// Run `cmd` to completion with captured output, killing it if it outlives
+/// `timeout`. Both pipes are drained while waiting, so a child writing more
+/// than a pipe buffer holds doesn't stall until the timeout.
+pub fn output_with_timeout(cmd: &mut Command, timeout: Duration) -> AppResult<Output> {
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::Process(format!("Failed to spawn: {}", e)))?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AppError::Process(format!(
+                    "Timed out after {}s",
+                    timeout.as_secs()
+                )));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(AppError::Process(format!("Failed to check process: {}", e))),
+        }
+    };
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(line: &str) -> Vec<String> {
+        line.split(' ').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn runs_requires_the_target_to_be_executed() {
+        let bot = Regex::new(BOT_PATTERN).unwrap();
+        assert!(runs(&argv("/opt/venv/bin/python3 /opt/synthia/telegram_bot.py --allow 1"), &bot));
+        assert!(runs(&argv("python -u telegram_bot.py"), &bot));
+        assert!(runs(&argv("/usr/lib/synthia/synthia-telegram-bot"), &bot));
+        assert!(!runs(&argv("vim telegram_bot.py"), &bot));
+        assert!(!runs(&argv("less /tmp/telegram_bot.py.log"), &bot));
+        assert!(!runs(&argv("python3 -c print('telegram_bot.py')"), &bot));
+        assert!(runs(&argv(r"C:\synthia\venv\Scripts\python.exe src\telegram_bot.py"), &bot));
+        assert!(runs(&argv(r"C:\Programs\Synthia\synthia-telegram-bot.exe"), &bot));
+        assert!(runs(&argv("python3 /opt/synthia/discord_bot.py"), &bot));
+        assert!(runs(&argv("/usr/lib/synthia/synthia-matrix-bot --allow-all"), &bot));
+
+        let telegram = Regex::new(TELEGRAM_BOT_PATTERN).unwrap();
+        let matrix = Regex::new(MATRIX_BOT_PATTERN).unwrap();
+        assert!(runs(&argv("python -u telegram_bot.py"), &telegram));
+        assert!(!runs(&argv("python -u matrix_bot.py"), &telegram));
+        assert!(runs(&argv("python -u matrix_bot.py"), &matrix));
+        assert!(!runs(&argv("/usr/lib/synthia/synthia-discord-bot"), &matrix));
+
+        let backend = Regex::new(BACKEND_PATTERN).unwrap();
+        assert!(runs(&argv("./venv/bin/python -m synthia.main"), &backend));
+        assert!(runs(&argv("python3.12 -u -m synthia.main"), &backend));
+        assert!(!runs(&argv("grep synthia.main"), &backend));
+        assert!(!runs(&argv("pythonista -m synthia.main"), &backend));
+        assert!(!runs(&[], &backend));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn output_with_timeout_drains_output_larger_than_a_pipe() {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "head -c 200000 /dev/zero"]);
+        let output = output_with_timeout(&mut cmd, Duration::from_secs(10)).unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout.len(), 200_000);
+    }
+}
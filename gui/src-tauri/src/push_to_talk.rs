@@ -0,0 +1,142 @@
+//! Push-to-talk: record only while a global shortcut is held.
+//!
+//! When the state file's `mode` is `push_to_talk`, the shortcut from
+//! `gui.toml` is registered with the global-shortcut plugin, or with
+//! `push_to_talk.input = "evdev"` its key is read from `/dev/input` instead
+//! (see `evdev_input`). Press and release go to the backend over IPC as the
+//! dictation hotkey, exactly as `global_hotkeys` forwards a held dictation
+//! shortcut; the state the backend then writes drives tray and overlay. A
+//! press that never sees its release is cut off after
+//! `push_to_talk.max_hold_secs`.
+
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutEvent, ShortcutState};
+
+use crate::global_hotkeys::{self, HotkeyAction};
+use crate::gui_config::{EvdevBinding, PushToTalkInput};
+use crate::{commands, evdev_input, gui_config};
+use crate::state::AppState;
+use crate::synthia_state::{read_synthia_state, Mode, SynthiaState};
+
+/// Handle an event for the push-to-talk shortcut; routed here by
+/// `global_hotkeys::on_shortcut`.
+pub fn on_shortcut(app: &AppHandle, event: ShortcutEvent) {
+    match event.state() {
+        ShortcutState::Pressed => press(app),
+        ShortcutState::Released => release(app),
+    }
+}
+
+fn press(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    // Key auto-repeat delivers repeated presses; only the first counts.
+    if state.ptt_held.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    if read_synthia_state().muted {
+        state.ptt_held.store(false, Ordering::SeqCst);
+        tracing::debug!("push-to-talk ignored while muted");
+        return;
+    }
+    let seq = state.ptt_press_seq.fetch_add(1, Ordering::SeqCst) + 1;
+    tracing::debug!("push-to-talk pressed");
+    global_hotkeys::forward(HotkeyAction::Dictation, true);
+    commands::audio::warn_if_unavailable();
+
+    let max_hold = Duration::from_secs(gui_config::load().push_to_talk.max_hold_secs);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(max_hold);
+        let state = app.state::<AppState>();
+        if state.ptt_press_seq.load(Ordering::SeqCst) == seq
+            && state.ptt_held.load(Ordering::SeqCst)
+        {
+            tracing::warn!(
+                secs = max_hold.as_secs(),
+                "push-to-talk release missed; stopping recording"
+            );
+            release(&app);
+        }
+    });
+}
+
+fn release(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    if !state.ptt_held.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    tracing::debug!("push-to-talk released");
+    global_hotkeys::forward(HotkeyAction::Dictation, false);
+}
+
+/// Register or unregister the shortcut, or start or stop the evdev
+/// listener, to match `synthia.mode`. Called from the state watcher and
+/// directly after `set_mode`.
+pub fn sync(app: &AppHandle, synthia: &SynthiaState) {
+    let config = (synthia.mode == Mode::PushToTalk).then(|| gui_config::load().push_to_talk);
+    let (shortcut, evdev) = match config {
+        Some(c) if c.input == PushToTalkInput::Evdev => (None, Some(c.evdev)),
+        Some(c) => (Some(c.shortcut), None),
+        None => (None, None),
+    };
+    sync_shortcut(app, shortcut);
+    sync_evdev(app, evdev);
+}
+
+fn sync_shortcut(app: &AppHandle, wanted: Option<String>) {
+    let state = app.state::<AppState>();
+    let Ok(mut registered) = state.ptt_shortcut.lock() else {
+        return;
+    };
+    if *registered == wanted {
+        return;
+    }
+
+    if let Some(old) = registered.take() {
+        if let Err(e) = app.global_shortcut().unregister(old.as_str()) {
+            tracing::warn!(shortcut = %old, error = %e, "failed to unregister push-to-talk shortcut");
+        }
+        // Leaving the mode mid-press must not strand recording on.
+        release(app);
+    }
+    if let Some(shortcut) = wanted {
+        match app.global_shortcut().register(shortcut.as_str()) {
+            Ok(()) => {
+                tracing::info!(shortcut = %shortcut, "push-to-talk shortcut registered");
+                *registered = Some(shortcut);
+            }
+            Err(e) => {
+                tracing::error!(shortcut = %shortcut, error = %e, "failed to register push-to-talk shortcut");
+            }
+        }
+    }
+}
+
+fn sync_evdev(app: &AppHandle, wanted: Option<EvdevBinding>) {
+    let state = app.state::<AppState>();
+    let Ok(mut listening) = state.ptt_evdev.lock() else {
+        return;
+    };
+    if listening.as_ref().map(|(binding, _)| binding) == wanted.as_ref() {
+        return;
+    }
+
+    if let Some((_, stop)) = listening.take() {
+        stop.store(true, Ordering::SeqCst);
+        release(app);
+    }
+    if let Some(binding) = wanted {
+        let handle = app.clone();
+        let on_key = move |pressed| if pressed { press(&handle) } else { release(&handle) };
+        match evdev_input::listen(&binding, on_key) {
+            Ok(stop) => *listening = Some((binding, stop)),
+            Err(e) => {
+                let key = evdev_input::key_name(binding.key);
+                tracing::error!(%key, error = %e, "failed to listen for push-to-talk key");
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Elapsed time of the current recording.
+//!
+//! The start comes from `recording_started_at` in the state file, or from
+//! the moment the watcher first saw `recording` if the writer didn't stamp
+//! one. While recording, a ticker thread emits `recording-elapsed` once a
+//! second for the overlay's timer and keeps the tray tooltip current.
+//!
+//! The ticker also enforces `recording.max_recording_secs`: a recording that
+//! outlives it is stopped by asking the backend over IPC, and once the state
+//! file shows it stopped a desktop notification says why. The same happens
+//! to a recording that sees no state update for `recording.idle_timeout_secs`.
+//! A stop the backend doesn't confirm is retried on the next tick.
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::lifecycle;
+use crate::gui_config;
+use crate::i18n;
+use crate::notifications;
+use crate::state::AppState;
+use crate::synthia_state::{read_synthia_state, SynthiaState};
+use crate::tray;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long the backend has to show a cut-off recording as stopped.
+const STOP_CONFIRM_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Payload of the `recording-elapsed` event.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct RecordingElapsed {
+    pub recording: bool,
+    pub elapsed_secs: u64,
+}
+
+/// Whole seconds from `started` to `now`; a start in the future (clock skew
+/// between writers) counts as zero.
+pub fn elapsed_secs(started: DateTime<Utc>, now: DateTime<Utc>) -> u64 {
+    (now - started).num_seconds().max(0) as u64
+}
+
+/// `MM:SS`, growing to `H:MM:SS` past an hour.
+pub fn format_elapsed(secs: u64) -> String {
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{m:02}:{s:02}")
+    }
+}
+
+/// Whether `elapsed` is past the cutoff. `max_secs == 0` disables it.
+pub fn over_limit(elapsed_secs: u64, max_secs: u64) -> bool {
+    max_secs > 0 && elapsed_secs >= max_secs
+}
+
+/// Ask the backend to stop recording, giving `detail` as the reason once
+/// the state file confirms it. Returns whether it did.
+fn cut_off(detail: &str) -> bool {
+    tracing::warn!(reason = %detail, "stopping recording");
+    if let Err(e) = lifecycle::send_recording(false, false) {
+        tracing::error!(error = %e, "could not ask the backend to stop recording");
+        return false;
+    }
+    let deadline = Instant::now() + STOP_CONFIRM_TIMEOUT;
+    while read_synthia_state().recording {
+        if Instant::now() >= deadline {
+            tracing::error!("backend did not stop the cut-off recording");
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    notifications::notify_error(&i18n::t("notify.recording_stopped"), detail);
+    true
+}
+
+fn publish(app: &AppHandle, elapsed: Option<u64>) {
+    let payload = RecordingElapsed {
+        recording: elapsed.is_some(),
+        elapsed_secs: elapsed.unwrap_or(0),
+    };
+    if let Err(e) = app.emit("recording-elapsed", payload) {
+        tracing::debug!(error = %e, "failed to emit recording-elapsed");
+    }
+    tray::set_recording_elapsed(app, elapsed);
+}
+
+/// Start or stop the timer to match `synthia.recording`. Called from the
+/// state watcher on every change.
+pub fn sync(app: &AppHandle, synthia: &SynthiaState) {
+    let state = app.state::<AppState>();
+    let Ok(mut current) = state.recording_started_at.lock() else {
+        return;
+    };
+    if synthia.recording {
+        let started = synthia
+            .recording_started_at
+            .or(*current)
+            .unwrap_or_else(Utc::now);
+        if *current != Some(started) {
+            *current = Some(started);
+            tracing::debug!(%started, "recording timer started");
+            spawn_ticker(app.clone(), started);
+        }
+    } else if current.take().is_some() {
+        drop(current);
+        publish(app, None);
+    }
+}
+
+/// Tick until the timer is stopped or restarted with a different start, or
+/// the recording hits the cutoff.
+fn spawn_ticker(app: AppHandle, started: DateTime<Utc>) {
+    let config = gui_config::load().recording;
+    std::thread::spawn(move || loop {
+        let idle_secs = {
+            let state = app.state::<AppState>();
+            let Ok(current) = state.recording_started_at.lock() else {
+                return;
+            };
+            if *current != Some(started) {
+                return;
+            }
+            drop(current);
+            let last_change = state.last_state_change.lock().ok().and_then(|l| *l);
+            last_change.map_or(0, |l| l.elapsed().as_secs())
+        };
+        let elapsed = elapsed_secs(started, Utc::now());
+        let reason = if over_limit(elapsed, config.max_recording_secs) {
+            Some(format!(
+                "Recording stopped after {} (max_recording_secs)",
+                format_elapsed(config.max_recording_secs)
+            ))
+        } else if over_limit(idle_secs, config.idle_timeout_secs) {
+            Some(format!(
+                "Recording stopped after {} without activity (idle_timeout_secs)",
+                format_elapsed(idle_secs)
+            ))
+        } else {
+            None
+        };
+        // Once stopped, the watcher sees `recording: false` and stops the timer.
+        if reason.is_some_and(|reason| cut_off(&reason)) {
+            return;
+        }
+        publish(&app, Some(elapsed));
+        std::thread::sleep(TICK_INTERVAL);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_elapsed_pads_minutes_and_seconds() {
+        assert_eq!(format_elapsed(0), "00:00");
+        assert_eq!(format_elapsed(12), "00:12");
+        assert_eq!(format_elapsed(754), "12:34");
+        assert_eq!(format_elapsed(3723), "1:02:03");
+    }
+
+    #[test]
+    fn zero_disables_cutoff() {
+        assert!(!over_limit(10_000, 0));
+        assert!(!over_limit(59, 60));
+        assert!(over_limit(60, 60));
+    }
+
+    #[test]
+    fn elapsed_ignores_future_start() {
+        let now = Utc::now();
+        assert_eq!(elapsed_secs(now - chrono::Duration::seconds(5), now), 5);
+        assert_eq!(elapsed_secs(now + chrono::Duration::seconds(5), now), 0);
+    }
+}
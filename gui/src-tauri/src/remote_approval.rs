@@ -0,0 +1,301 @@
+//! Desktop approval of what remote mode forwards to Claude Code.
+//!
+//! A bot launched with `--approval-timeout` prints each message it would
+//! type into Claude Code as `SYNTHIA_REMOTE_APPROVAL {"id":..,"text":..}`
+//! and holds it until an answer `{"id":..,"approved":..}` arrives on its
+//! stdin (see `ApprovalGate` in remote/common.py). Messages the
+//! `remote.approval` policy lets through are approved straight away; the
+//! rest queue here, raise a notification with Approve and Deny, and wait for
+//! `approve_remote_command` or `deny_remote_command`. The queue is emitted as
+//! `remote-approvals-changed` whenever it changes.
+//!
+//! The bot stops waiting after `remote.approval_timeout_secs` and drops the
+//! message, so an entry past `expires_at` is pruned rather than answered.
+
+use std::io::Write;
+use std::process::ChildStdin;
+use std::sync::{MutexGuard, PoisonError};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::error::{AppError, AppResult};
+use crate::gui_config::{self, RemoteApproval, RemoteConfig};
+use crate::notifications;
+use crate::security::{self, Severity};
+use crate::state::AppState;
+
+/// Prefix of the bot's stdout lines asking for approval; matches
+/// `APPROVAL_MARKER` in remote/common.py.
+const APPROVAL_MARKER: &str = "SYNTHIA_REMOTE_APPROVAL";
+
+/// Plain-language requests to Claude Code that would lose work, which the
+/// shell rules in `security` can't see. Matched case-insensitively.
+const DESTRUCTIVE_PHRASES: &[(&str, &str)] = &[
+    ("delete", r"\b(delete|remove|wipe|erase|purge|destroy)\b"),
+    ("force-push", r"\bforce[- ]push\b|\bpush\s+(-f|--force)\b"),
+    ("hard-reset", r"\breset\s+--hard\b|\bhard reset\b"),
+    ("discard-changes", r"\bgit\s+clean\s+-\w*f|\bdiscard\b.*\bchanges\b"),
+    ("drop-data", r"\b(drop|truncate)\s+(table|database|schema)\b"),
+    ("power", r"\b(shutdown|reboot|poweroff)\b"),
+];
+
+/// A bot's request, as printed after the marker.
+#[derive(Deserialize, Debug, PartialEq)]
+pub(crate) struct ApprovalRequest {
+    pub id: String,
+    pub text: String,
+}
+
+/// A message held until the user approves or denies it.
+#[derive(Serialize, Debug, Clone)]
+pub struct PendingApproval {
+    pub id: String,
+    pub text: String,
+    /// The rules it matched, or `always` under that policy.
+    pub reasons: Vec<String>,
+    pub requested_at: DateTime<Utc>,
+    /// When the bot gives up waiting and drops the message.
+    pub expires_at: DateTime<Utc>,
+    /// The bot that asked, so a relaunched one isn't answered by mistake.
+    #[serde(skip)]
+    pid: u32,
+}
+
+/// The running bot's stdin and the messages it is holding.
+#[derive(Default)]
+pub struct RemoteApprovals {
+    bot: Option<(u32, ChildStdin)>,
+    pending: Vec<PendingApproval>,
+}
+
+impl RemoteApprovals {
+    /// Tell bot `pid` whether message `id` may go through. An answer for a
+    /// bot that has since been replaced is dropped.
+    fn answer(&mut self, pid: u32, id: &str, approved: bool) {
+        let Some((_, stdin)) = self.bot.as_mut().filter(|(bot, _)| *bot == pid) else {
+            tracing::debug!(id, "remote bot gone; approval answer dropped");
+            return;
+        };
+        let line = json!({ "id": id, "approved": approved });
+        if let Err(e) = writeln!(stdin, "{line}").and_then(|()| stdin.flush()) {
+            tracing::warn!(error = %e, id, "failed to answer remote bot");
+        }
+    }
+
+    /// Drop entries the bot no longer waits for; true if any were dropped.
+    fn prune(&mut self, now: DateTime<Utc>) -> bool {
+        let before = self.pending.len();
+        self.pending.retain(|p| p.expires_at > now);
+        self.pending.len() != before
+    }
+}
+
+fn lock(state: &AppState) -> MutexGuard<'_, RemoteApprovals> {
+    state.remote_approvals.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Arguments that make the bot ask before forwarding, per `config`.
+pub(crate) fn bot_args(config: &RemoteConfig) -> Vec<String> {
+    match config.approval {
+        RemoteApproval::Off => Vec::new(),
+        RemoteApproval::Destructive | RemoteApproval::Always => {
+            vec!["--approval-timeout".to_string(), config.approval_timeout_secs.to_string()]
+        }
+    }
+}
+
+/// Why `text` needs approval under `policy`; empty if it can go through.
+pub(crate) fn reasons(text: &str, policy: RemoteApproval) -> Vec<String> {
+    match policy {
+        RemoteApproval::Off => Vec::new(),
+        RemoteApproval::Always => vec!["always".to_string()],
+        RemoteApproval::Destructive => {
+            let hits = security::evaluate_tool("Bash", &json!({ "command": text }));
+            let mut reasons: Vec<String> = hits
+                .into_iter()
+                .filter(|hit| hit.severity >= Severity::Medium)
+                .map(|hit| hit.rule.to_string())
+                .collect();
+            let lower = text.to_lowercase();
+            for (rule, pattern) in DESTRUCTIVE_PHRASES {
+                if Regex::new(pattern).expect("valid regex").is_match(&lower) {
+                    reasons.push(rule.to_string());
+                }
+            }
+            reasons.dedup();
+            reasons
+        }
+    }
+}
+
+/// The request on a line of the bot's stdout, if it is one.
+pub(crate) fn parse_request(line: &str) -> Option<ApprovalRequest> {
+    let payload = line.strip_prefix(APPROVAL_MARKER)?.strip_prefix(' ')?;
+    match serde_json::from_str(payload) {
+        Ok(request) => Some(request),
+        Err(e) => {
+            tracing::warn!(error = %e, "malformed approval request from remote bot");
+            None
+        }
+    }
+}
+
+/// Start answering bot `pid` on `stdin`; anything held for an earlier bot
+/// is dropped with it.
+pub(crate) fn attach(app: &AppHandle, pid: u32, stdin: ChildStdin) {
+    let state = app.state::<AppState>();
+    let had_pending = {
+        let mut approvals = lock(&state);
+        approvals.bot = Some((pid, stdin));
+        std::mem::take(&mut approvals.pending)
+    };
+    if !had_pending.is_empty() {
+        publish(app);
+    }
+}
+
+/// The bot is gone: nothing it held can be sent any more.
+pub(crate) fn detach(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let had_pending = {
+        let mut approvals = lock(&state);
+        approvals.bot = None;
+        std::mem::take(&mut approvals.pending)
+    };
+    if !had_pending.is_empty() {
+        publish(app);
+    }
+}
+
+/// Answer bot `pid`'s request at once if the policy allows it, else queue
+/// it for the user.
+pub(crate) fn request(app: &AppHandle, pid: u32, request: ApprovalRequest) {
+    let config = gui_config::load().remote;
+    let reasons = reasons(&request.text, config.approval);
+    let state = app.state::<AppState>();
+    if reasons.is_empty() {
+        lock(&state).answer(pid, &request.id, true);
+        return;
+    }
+    let timeout = Duration::from_secs(config.approval_timeout_secs);
+    let now = Utc::now();
+    let pending = PendingApproval {
+        id: request.id,
+        text: request.text,
+        reasons,
+        requested_at: now,
+        expires_at: now + chrono::Duration::seconds(config.approval_timeout_secs as i64),
+        pid,
+    };
+    tracing::warn!(id = %pending.id, reasons = ?pending.reasons, "remote command awaits approval");
+    {
+        let mut approvals = lock(&state);
+        approvals.prune(now);
+        approvals.pending.push(pending.clone());
+    }
+    publish(app);
+
+    let handle = app.clone();
+    let id = pending.id;
+    notifications::notify_remote_approval(&pending.text, timeout, move |approved| {
+        if let Err(e) = decide(&handle, &id, approved) {
+            tracing::debug!(error = %e, "approval from notification not applied");
+        }
+    });
+}
+
+/// Settle a held message: the bot sends it on if `approved`, else drops it.
+pub(crate) fn decide(app: &AppHandle, id: &str, approved: bool) -> AppResult<PendingApproval> {
+    let state = app.state::<AppState>();
+    let decided = {
+        let mut approvals = lock(&state);
+        approvals.prune(Utc::now());
+        let index = approvals.pending.iter().position(|p| p.id == id).ok_or_else(|| {
+            AppError::NotFound(format!("No remote command {id} awaiting approval"))
+        })?;
+        let decided = approvals.pending.remove(index);
+        approvals.answer(decided.pid, &decided.id, approved);
+        decided
+    };
+    tracing::info!(id, approved, "remote command settled");
+    publish(app);
+    Ok(decided)
+}
+
+/// Messages still awaiting approval, oldest first.
+pub(crate) fn pending(app: &AppHandle) -> Vec<PendingApproval> {
+    let state = app.state::<AppState>();
+    let mut approvals = lock(&state);
+    approvals.prune(Utc::now());
+    approvals.pending.clone()
+}
+
+fn publish(app: &AppHandle) {
+    let _ = app.emit("remote-approvals-changed", pending(app));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_are_parsed_from_marker_lines() {
+        let line = r#"SYNTHIA_REMOTE_APPROVAL {"id":"ab12","text":"rm -rf build"}"#;
+        assert_eq!(
+            parse_request(line),
+            Some(ApprovalRequest { id: "ab12".to_string(), text: "rm -rf build".to_string() })
+        );
+        assert_eq!(parse_request("SYNTHIA_REMOTE_STATUS connected"), None);
+        assert_eq!(parse_request("SYNTHIA_REMOTE_APPROVAL not json"), None);
+    }
+
+    #[test]
+    fn destructive_policy_holds_only_risky_messages() {
+        let policy = RemoteApproval::Destructive;
+        assert!(reasons("run the tests and fix what fails", policy).is_empty());
+        assert!(reasons("git status", policy).is_empty());
+        assert!(!reasons("rm -rf ~/projects", policy).is_empty());
+        assert_eq!(reasons("Delete the old migrations", policy), ["delete"]);
+        assert_eq!(reasons("force push to main", policy), ["force-push"]);
+        assert_eq!(reasons("git reset --hard origin/main", policy), ["hard-reset"]);
+
+        assert!(reasons("rm -rf ~/projects", RemoteApproval::Off).is_empty());
+        assert_eq!(reasons("git status", RemoteApproval::Always), ["always"]);
+    }
+
+    #[test]
+    fn bot_is_only_gated_when_policy_asks() {
+        let config = RemoteConfig { approval: RemoteApproval::Off, ..RemoteConfig::default() };
+        assert!(bot_args(&config).is_empty());
+        assert_eq!(bot_args(&RemoteConfig::default()), ["--approval-timeout", "300"]);
+    }
+
+    #[test]
+    fn expired_entries_are_pruned() {
+        let now = Utc::now();
+        let entry = |id: &str, expires_at| PendingApproval {
+            id: id.to_string(),
+            text: String::new(),
+            reasons: Vec::new(),
+            requested_at: now,
+            expires_at,
+            pid: 1,
+        };
+        let mut approvals = RemoteApprovals {
+            pending: vec![
+                entry("old", now - chrono::Duration::seconds(1)),
+                entry("new", now + chrono::Duration::seconds(60)),
+            ],
+            ..Default::default()
+        };
+        assert!(approvals.prune(now));
+        assert_eq!(approvals.pending.len(), 1);
+        assert_eq!(approvals.pending[0].id, "new");
+        assert!(!approvals.prune(now));
+    }
+}
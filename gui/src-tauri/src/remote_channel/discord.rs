@@ -0,0 +1,138 @@
+//! Discord, through a bot application talking to allowed users by DM.
+
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{http_client, recipients, RemoteChannel, RemoteNotice};
+use crate::config::SynthiaConfigYaml;
+use crate::error::{AppError, AppResult};
+use crate::gui_config::RemoteChannelKind;
+use crate::process::DISCORD_BOT_PATTERN;
+use crate::sidecar;
+
+const API: &str = "https://discord.com/api/v10";
+
+pub(crate) struct Discord;
+
+#[derive(Deserialize)]
+struct DmChannel {
+    id: String,
+}
+
+impl RemoteChannel for Discord {
+    fn kind(&self) -> RemoteChannelKind {
+        RemoteChannelKind::Discord
+    }
+
+    fn name(&self) -> &'static str {
+        "Discord"
+    }
+
+    fn sidecar(&self) -> &'static str {
+        sidecar::DISCORD_BOT
+    }
+
+    fn script(&self) -> &'static str {
+        "src/synthia/remote/discord_bot.py"
+    }
+
+    fn process_pattern(&self) -> &'static str {
+        DISCORD_BOT_PATTERN
+    }
+
+    fn token_env_var(&self) -> &'static str {
+        "SYNTHIA_DISCORD_BOT_TOKEN"
+    }
+
+    fn allowlist_key(&self) -> &'static str {
+        "discord_allowed_users"
+    }
+
+    fn allowed_users(&self, config: &SynthiaConfigYaml) -> Vec<String> {
+        config.discord_allowed_users.iter().map(u64::to_string).collect()
+    }
+
+    fn parse_user_id(&self, id: &str) -> AppResult<String> {
+        id.trim().parse::<u64>().map(|id| id.to_string()).map_err(|_| {
+            AppError::Validation(format!(
+                "{id:?} is not a Discord user ID: turn on Developer Mode and use Copy User ID"
+            ))
+        })
+    }
+
+    fn configured_token(&self, config: &SynthiaConfigYaml) -> AppResult<String> {
+        let token = config.discord_bot_token.trim();
+        if token.is_empty() {
+            return Err(AppError::Validation(
+                "No Discord bot token: save one in the keyring, or set discord_bot_token in \
+                 config.yaml"
+                    .to_string(),
+            ));
+        }
+        Ok(token.to_string())
+    }
+
+    fn validate_token(&self, token: &str) -> AppResult<()> {
+        let shape =
+            Regex::new(r"^[A-Za-z0-9_-]{20,}\.[A-Za-z0-9_-]{4,}\.[A-Za-z0-9_-]{20,}$")
+                .expect("valid regex");
+        if shape.is_match(token) {
+            Ok(())
+        } else {
+            Err(AppError::Validation(
+                "Discord bot token is malformed: copy it from the Bot page of the developer \
+                 portal"
+                    .to_string(),
+            ))
+        }
+    }
+
+    fn format(&self, notice: RemoteNotice) -> String {
+        let (emoji, headline, body) = notice.parts(self.name());
+        format!("{emoji} **{headline}**\n\n{body}")
+    }
+
+    /// Open (or reuse) a DM with each allowed user and post there.
+    fn send(&self, text: &str) -> AppResult<()> {
+        let users = recipients(self)?;
+        let auth = format!("Bot {}", self.token()?);
+        let client = http_client()?;
+        tauri::async_runtime::block_on(async {
+            for user in users {
+                let dm: DmChannel = client
+                    .post(format!("{API}/users/@me/channels"))
+                    .header("Authorization", &auth)
+                    .json(&json!({ "recipient_id": user }))
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                client
+                    .post(format!("{API}/channels/{}/messages", dm.id))
+                    .header("Authorization", &auth)
+                    .json(&json!({ "content": text }))
+                    .send()
+                    .await?
+                    .error_for_status()?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_shape_is_checked() {
+        let token = "MTIzNDU2Nzg5MDEyMzQ1Njc4.GabCdE.abcdefghijklmnopqrstuvwxyz0123456789AB";
+        assert!(Discord.validate_token(token).is_ok());
+        assert!(Discord.validate_token("123456789:AAHdqTcvCH1vGWJxfSeofSAs0K5PALDsaw").is_err());
+        assert!(Discord.validate_token("").is_err());
+        assert_eq!(Discord.parse_user_id("123456789012345678").unwrap(), "123456789012345678");
+        assert!(Discord.parse_user_id("someone#1234").is_err());
+    }
+}
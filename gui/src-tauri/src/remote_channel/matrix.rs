@@ -0,0 +1,181 @@
+//! Matrix, through an existing bot account's access token. Notices go to
+//! `matrix_room_id` rather than to each user.
+
+use chrono::Utc;
+use regex::Regex;
+use reqwest::Url;
+use serde_json::json;
+
+use super::{http_client, synthia_config, RemoteChannel, RemoteNotice};
+use crate::config::SynthiaConfigYaml;
+use crate::error::{AppError, AppResult};
+use crate::gui_config::RemoteChannelKind;
+use crate::process::MATRIX_BOT_PATTERN;
+use crate::sidecar;
+
+pub(crate) struct Matrix;
+
+/// `matrix_homeserver`, checked to be an http(s) URL.
+fn homeserver(config: &SynthiaConfigYaml) -> AppResult<Url> {
+    let raw = config.matrix_homeserver.trim();
+    if raw.is_empty() {
+        return Err(AppError::Validation(
+            "No Matrix homeserver: set matrix_homeserver in config.yaml".to_string(),
+        ));
+    }
+    let url = Url::parse(raw)
+        .map_err(|e| AppError::Validation(format!("matrix_homeserver {raw:?}: {e}")))?;
+    if !matches!(url.scheme(), "https" | "http") || url.cannot_be_a_base() {
+        return Err(AppError::Validation(format!(
+            "matrix_homeserver {raw:?} is not an http(s) URL"
+        )));
+    }
+    Ok(url)
+}
+
+/// `@localpart:server`, as the spec shapes user IDs.
+fn is_user_id(id: &str) -> bool {
+    Regex::new(r"^@[^:\s]+:\S+$").expect("valid regex").is_match(id)
+}
+
+impl RemoteChannel for Matrix {
+    fn kind(&self) -> RemoteChannelKind {
+        RemoteChannelKind::Matrix
+    }
+
+    fn name(&self) -> &'static str {
+        "Matrix"
+    }
+
+    fn sidecar(&self) -> &'static str {
+        sidecar::MATRIX_BOT
+    }
+
+    fn script(&self) -> &'static str {
+        "src/synthia/remote/matrix_bot.py"
+    }
+
+    fn process_pattern(&self) -> &'static str {
+        MATRIX_BOT_PATTERN
+    }
+
+    fn token_env_var(&self) -> &'static str {
+        "SYNTHIA_MATRIX_ACCESS_TOKEN"
+    }
+
+    fn allowlist_key(&self) -> &'static str {
+        "matrix_allowed_users"
+    }
+
+    fn allowed_users(&self, config: &SynthiaConfigYaml) -> Vec<String> {
+        config
+            .matrix_allowed_users
+            .iter()
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .collect()
+    }
+
+    fn parse_user_id(&self, id: &str) -> AppResult<String> {
+        let id = id.trim();
+        if is_user_id(id) {
+            Ok(id.to_string())
+        } else {
+            Err(AppError::Validation(format!(
+                "{id:?} is not a Matrix user ID like @you:matrix.org"
+            )))
+        }
+    }
+
+    fn configured_token(&self, config: &SynthiaConfigYaml) -> AppResult<String> {
+        let token = config.matrix_access_token.trim();
+        if token.is_empty() {
+            return Err(AppError::Validation(
+                "No Matrix access token: save one in the keyring, or set matrix_access_token \
+                 in config.yaml"
+                    .to_string(),
+            ));
+        }
+        Ok(token.to_string())
+    }
+
+    /// Homeservers are free to shape tokens as they like, so only rule out
+    /// what can't be one.
+    fn validate_token(&self, token: &str) -> AppResult<()> {
+        if token.len() >= 16 && token.chars().all(|c| c.is_ascii_graphic()) {
+            Ok(())
+        } else {
+            Err(AppError::Validation(
+                "Matrix access token is malformed: expected the bot account's access token"
+                    .to_string(),
+            ))
+        }
+    }
+
+    /// The homeserver and bot account, which the script would otherwise
+    /// read from config.yaml itself.
+    fn extra_env(&self, config: &SynthiaConfigYaml) -> AppResult<Vec<(&'static str, String)>> {
+        let user_id = config.matrix_user_id.trim();
+        if !is_user_id(user_id) {
+            return Err(AppError::Validation(format!(
+                "matrix_user_id {user_id:?} is not a Matrix user ID like @bot:example.org"
+            )));
+        }
+        Ok(vec![
+            ("SYNTHIA_MATRIX_HOMESERVER", homeserver(config)?.to_string()),
+            ("SYNTHIA_MATRIX_USER_ID", user_id.to_string()),
+        ])
+    }
+
+    fn format(&self, notice: RemoteNotice) -> String {
+        let (emoji, headline, body) = notice.parts(self.name());
+        format!("{emoji} {headline}\n\n{body}")
+    }
+
+    fn send(&self, text: &str) -> AppResult<()> {
+        let config = synthia_config();
+        let room = config.matrix_room_id.trim();
+        if room.is_empty() {
+            return Err(AppError::Validation("no matrix_room_id to post notices to".to_string()));
+        }
+        let txn = format!("synthia-{}", Utc::now().timestamp_millis());
+        let mut url = homeserver(&config)?;
+        url.path_segments_mut()
+            .map_err(|_| AppError::Validation("matrix_homeserver has no path".to_string()))?
+            .pop_if_empty()
+            .extend(["_matrix", "client", "v3", "rooms", room, "send", "m.room.message"])
+            .push(&txn);
+        let token = self.token()?;
+        let client = http_client()?;
+        tauri::async_runtime::block_on(async {
+            client
+                .put(url)
+                .bearer_auth(token)
+                .json(&json!({ "msgtype": "m.notice", "body": text }))
+                .send()
+                .await?
+                .error_for_status()?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_ids_and_homeservers_are_checked() {
+        assert!(is_user_id("@synthia-bot:matrix.org"));
+        assert!(!is_user_id("synthia-bot"));
+        assert!(!is_user_id("@synthia-bot"));
+
+        let config = |hs: &str| SynthiaConfigYaml {
+            matrix_homeserver: hs.to_string(),
+            ..Default::default()
+        };
+        assert!(homeserver(&config("https://matrix.org")).is_ok());
+        assert!(homeserver(&config("")).is_err());
+        assert!(homeserver(&config("matrix.org")).is_err());
+    }
+}
@@ -0,0 +1,268 @@
+//! The chat services remote mode can run over.
+//!
+//! Each `RemoteChannel` knows its bot script and bundled binary, where its
+//! token and allowlist live, and how to post a notice to the allowed users
+//! from the GUI itself. `remote.channel` in `gui.toml` picks the active one;
+//! only one bot runs at a time. Tokens are looked up in the OS keyring
+//! first, then in config.yaml.
+
+mod discord;
+mod matrix;
+mod telegram;
+
+use std::fs;
+use std::time::Duration;
+
+use crate::config::SynthiaConfigYaml;
+use crate::error::{AppError, AppResult};
+use crate::get_config_path;
+use crate::gui_config::{self, RemoteChannelKind};
+use crate::process::is_running;
+use crate::secrets;
+
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Every channel, in the order the UI lists them.
+pub(crate) const ALL: [&dyn RemoteChannel; 3] =
+    [&telegram::Telegram, &discord::Discord, &matrix::Matrix];
+
+pub(crate) fn get(kind: RemoteChannelKind) -> &'static dyn RemoteChannel {
+    match kind {
+        RemoteChannelKind::Telegram => &telegram::Telegram,
+        RemoteChannelKind::Discord => &discord::Discord,
+        RemoteChannelKind::Matrix => &matrix::Matrix,
+    }
+}
+
+/// The channel `remote.channel` selects.
+pub(crate) fn active() -> &'static dyn RemoteChannel {
+    get(gui_config::load().remote.channel)
+}
+
+/// What happened to remote mode, for a notice to the allowed users.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RemoteNotice {
+    Enabled,
+    Disabled,
+    /// The bot crashed and its replacement is connected.
+    Reconnected,
+    /// Stopped after this many idle minutes.
+    IdleStopped(u64),
+    /// The bot crashed too often and remote mode was turned off.
+    GaveUp,
+}
+
+impl RemoteNotice {
+    /// Emoji, headline and body, for each channel to lay out in its own
+    /// markup.
+    fn parts(self, channel: &str) -> (&'static str, &'static str, String) {
+        match self {
+            RemoteNotice::Enabled => (
+                "🟢",
+                "Remote Mode ENABLED",
+                format!("You can now control Claude Code via {channel}."),
+            ),
+            RemoteNotice::Disabled => {
+                ("🔴", "Remote Mode DISABLED", format!("{channel} bot stopped."))
+            }
+            RemoteNotice::Reconnected => (
+                "🟡",
+                "Remote Mode RECONNECTED",
+                format!("The {channel} bot stopped unexpectedly and was restarted."),
+            ),
+            RemoteNotice::IdleStopped(mins) => (
+                "⏸",
+                "Remote Mode STOPPED",
+                format!("No {channel} activity for {mins} minutes."),
+            ),
+            RemoteNotice::GaveUp => (
+                "🔴",
+                "Remote Mode STOPPED",
+                format!("The {channel} bot kept crashing and was not restarted."),
+            ),
+        }
+    }
+}
+
+/// Who the bot accepts commands from.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum BotAccess {
+    Users(Vec<String>),
+    AllowAll,
+}
+
+impl BotAccess {
+    /// The bot's command-line flags for this access; see `parse_access` in
+    /// remote/common.py.
+    pub(crate) fn args(&self) -> Vec<String> {
+        match self {
+            BotAccess::Users(ids) => vec!["--allowed-users".to_string(), ids.join(",")],
+            BotAccess::AllowAll => vec!["--allow-all".to_string()],
+        }
+    }
+}
+
+/// Bot access from an allowlist, or `None` for an empty list unless
+/// `remote.allow_all_users` is set in `gui.toml`.
+fn resolve_access(allowed_users: Vec<String>, allow_all: bool) -> Option<BotAccess> {
+    if !allowed_users.is_empty() {
+        Some(BotAccess::Users(allowed_users))
+    } else if allow_all {
+        Some(BotAccess::AllowAll)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn synthia_config() -> SynthiaConfigYaml {
+    fs::read_to_string(get_config_path())
+        .ok()
+        .and_then(|content| serde_yaml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// The token saved in the OS keyring. An unreachable keyring (no Secret
+/// Service running, say) is logged and treated as no token, so the
+/// plaintext fallbacks still work.
+fn keyring_token(kind: RemoteChannelKind) -> Option<String> {
+    match secrets::remote_token(kind) {
+        Ok(token) => token.map(|t| t.trim().to_string()).filter(|t| !t.is_empty()),
+        Err(e) => {
+            tracing::warn!(error = %e, ?kind, "could not read bot token from keyring");
+            None
+        }
+    }
+}
+
+/// The users a notice goes to, one by one: the allowlist, which mustn't be
+/// waived by `remote.allow_all_users`.
+fn recipients(channel: &dyn RemoteChannel) -> AppResult<Vec<String>> {
+    match channel.access()? {
+        BotAccess::Users(users) => Ok(users),
+        BotAccess::AllowAll => Err(AppError::Validation(format!(
+            "no {} to notify",
+            channel.allowlist_key()
+        ))),
+    }
+}
+
+fn http_client() -> AppResult<reqwest::Client> {
+    Ok(reqwest::Client::builder().timeout(SEND_TIMEOUT).build()?)
+}
+
+pub(crate) trait RemoteChannel {
+    fn kind(&self) -> RemoteChannelKind;
+
+    /// As shown to users, e.g. "Telegram".
+    fn name(&self) -> &'static str;
+
+    /// Bundled binary name; see `sidecar`.
+    fn sidecar(&self) -> &'static str;
+
+    /// Bot script, relative to the Synthia root.
+    fn script(&self) -> &'static str;
+
+    /// Command-line pattern matching this channel's bot; see `process`.
+    fn process_pattern(&self) -> &'static str;
+
+    /// Environment variable the bot reads its token from, which keeps it
+    /// out of `ps` output.
+    fn token_env_var(&self) -> &'static str;
+
+    /// config.yaml key holding the allowed user IDs.
+    fn allowlist_key(&self) -> &'static str;
+
+    fn allowed_users(&self, config: &SynthiaConfigYaml) -> Vec<String>;
+
+    /// `id` as `allowed_users` lists it, or why it isn't a user ID this
+    /// service issues.
+    fn parse_user_id(&self, id: &str) -> AppResult<String>;
+
+    /// The token from plaintext config, for when the keyring has none.
+    fn configured_token(&self, config: &SynthiaConfigYaml) -> AppResult<String>;
+
+    /// Refuse a token that can't be one of this service's, so a typo fails
+    /// here rather than as an auth error buried in the bot's log.
+    fn validate_token(&self, token: &str) -> AppResult<()>;
+
+    /// Further environment the bot needs, checked before it is launched.
+    fn extra_env(&self, _config: &SynthiaConfigYaml) -> AppResult<Vec<(&'static str, String)>> {
+        Ok(Vec::new())
+    }
+
+    /// `notice` in the service's own markup.
+    fn format(&self, notice: RemoteNotice) -> String;
+
+    /// Post `text` for the allowed users and wait for the service to accept
+    /// it. Blocking.
+    fn send(&self, text: &str) -> AppResult<()>;
+
+    /// The bot token: the OS keyring, else `configured_token`.
+    fn token(&self) -> AppResult<String> {
+        let token = match keyring_token(self.kind()) {
+            Some(token) => token,
+            None => self.configured_token(&synthia_config())?,
+        };
+        self.validate_token(&token)?;
+        Ok(token)
+    }
+
+    fn access(&self) -> AppResult<BotAccess> {
+        let allow_all = gui_config::load().remote.allow_all_users;
+        resolve_access(self.allowed_users(&synthia_config()), allow_all).ok_or_else(|| {
+            AppError::Validation(format!(
+                "No {} users allowed: add {} to config.yaml \
+                 (or set remote.allow_all_users in gui.toml)",
+                self.name(),
+                self.allowlist_key()
+            ))
+        })
+    }
+
+    /// Whether this channel's bot is in the process table.
+    fn running(&self) -> bool {
+        is_running(self.process_pattern())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_allowlist_is_refused_without_override() {
+        assert_eq!(resolve_access(vec![], false), None);
+        assert_eq!(resolve_access(vec![], true), Some(BotAccess::AllowAll));
+        // An explicit list always wins over the override.
+        assert_eq!(
+            resolve_access(vec!["42".into()], true),
+            Some(BotAccess::Users(vec!["42".into()]))
+        );
+    }
+
+    #[test]
+    fn access_args_match_bot_flags() {
+        assert_eq!(
+            BotAccess::Users(vec!["1".into(), "@me:example.org".into()]).args(),
+            vec!["--allowed-users".to_string(), "1,@me:example.org".to_string()]
+        );
+        assert_eq!(BotAccess::AllowAll.args(), vec!["--allow-all".to_string()]);
+    }
+
+    #[test]
+    fn channels_are_listed_once_each() {
+        for channel in ALL {
+            assert_eq!(get(channel.kind()).name(), channel.name());
+        }
+        let scripts: std::collections::HashSet<_> = ALL.iter().map(|c| c.script()).collect();
+        assert_eq!(scripts.len(), ALL.len());
+    }
+
+    #[test]
+    fn notices_name_the_channel() {
+        let text = get(RemoteChannelKind::Discord).format(RemoteNotice::IdleStopped(30));
+        assert_eq!(text, "⏸ **Remote Mode STOPPED**\n\nNo Discord activity for 30 minutes.");
+        let text = get(RemoteChannelKind::Telegram).format(RemoteNotice::Disabled);
+        assert_eq!(text, "🔴 *Remote Mode DISABLED*\n\nTelegram bot stopped.");
+    }
+}
@@ -0,0 +1,173 @@
+//! Telegram, through a bot from @BotFather and the Bot API.
+
+use std::fs;
+
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{http_client, recipients, RemoteChannel, RemoteNotice};
+use crate::config::SynthiaConfigYaml;
+use crate::error::{AppError, AppResult};
+use crate::gui_config::{self, RemoteChannelKind};
+use crate::process::TELEGRAM_BOT_PATTERN;
+use crate::sidecar;
+
+pub(crate) struct Telegram;
+
+#[derive(Deserialize)]
+struct ApiResponse {
+    ok: bool,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+/// `TELEGRAM_BOT_TOKEN` from env-file `content`, with optional `export` and
+/// quotes. A file holding nothing but the token also works.
+fn parse_env_token(content: &str) -> Option<String> {
+    let unquote = |v: &str| v.trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+    let lines: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .collect();
+    let assigned = lines.iter().find_map(|line| {
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == "TELEGRAM_BOT_TOKEN").then(|| unquote(value))
+    });
+    let bare = match lines.as_slice() {
+        [only] if !only.contains('=') => Some(unquote(only)),
+        _ => None,
+    };
+    assigned.or(bare).filter(|t| !t.is_empty())
+}
+
+impl RemoteChannel for Telegram {
+    fn kind(&self) -> RemoteChannelKind {
+        RemoteChannelKind::Telegram
+    }
+
+    fn name(&self) -> &'static str {
+        "Telegram"
+    }
+
+    fn sidecar(&self) -> &'static str {
+        sidecar::TELEGRAM_BOT
+    }
+
+    fn script(&self) -> &'static str {
+        "src/synthia/remote/telegram_bot.py"
+    }
+
+    fn process_pattern(&self) -> &'static str {
+        TELEGRAM_BOT_PATTERN
+    }
+
+    fn token_env_var(&self) -> &'static str {
+        "SYNTHIA_TELEGRAM_BOT_TOKEN"
+    }
+
+    fn allowlist_key(&self) -> &'static str {
+        "telegram_allowed_users"
+    }
+
+    fn allowed_users(&self, config: &SynthiaConfigYaml) -> Vec<String> {
+        config.telegram_allowed_users.iter().map(i64::to_string).collect()
+    }
+
+    fn parse_user_id(&self, id: &str) -> AppResult<String> {
+        id.trim().parse::<i64>().map(|id| id.to_string()).map_err(|_| {
+            AppError::Validation(format!(
+                "{id:?} is not a Telegram user ID: it is a number, which @userinfobot tells you"
+            ))
+        })
+    }
+
+    /// `telegram_bot_token` in config.yaml, else `remote.token_env_file`
+    /// from `gui.toml`.
+    fn configured_token(&self, config: &SynthiaConfigYaml) -> AppResult<String> {
+        let from_config = config.telegram_bot_token.trim();
+        if !from_config.is_empty() {
+            return Ok(from_config.to_string());
+        }
+        let Some(path) = gui_config::load().remote.token_env_file else {
+            return Err(AppError::Validation(
+                "No Telegram bot token: save one in the keyring, or set \
+                 telegram_bot_token in config.yaml (or remote.token_env_file in gui.toml)"
+                    .to_string(),
+            ));
+        };
+        let content = fs::read_to_string(&path).map_err(|e| {
+            AppError::Io(format!("Failed to read token file {}: {}", path.display(), e))
+        })?;
+        parse_env_token(&content).ok_or_else(|| {
+            AppError::Validation(format!("No TELEGRAM_BOT_TOKEN in {}", path.display()))
+        })
+    }
+
+    fn validate_token(&self, token: &str) -> AppResult<()> {
+        let shape = Regex::new(r"^\d+:[A-Za-z0-9_-]{30,}$").expect("valid regex");
+        if shape.is_match(token) {
+            Ok(())
+        } else {
+            Err(AppError::Validation(
+                "Telegram bot token is malformed: expected <bot id>:<secret> as issued by \
+                 @BotFather"
+                    .to_string(),
+            ))
+        }
+    }
+
+    fn format(&self, notice: RemoteNotice) -> String {
+        let (emoji, headline, body) = notice.parts(self.name());
+        format!("{emoji} *{headline}*\n\n{body}")
+    }
+
+    /// Message every allowed user; their user IDs double as chat IDs.
+    fn send(&self, text: &str) -> AppResult<()> {
+        let users = recipients(self)?;
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.token()?);
+        let client = http_client()?;
+        tauri::async_runtime::block_on(async {
+            for chat_id in users {
+                let response: ApiResponse = client
+                    .post(&url)
+                    .json(&json!({ "chat_id": chat_id, "text": text, "parse_mode": "Markdown" }))
+                    .send()
+                    .await?
+                    .json()
+                    .await?;
+                if !response.ok {
+                    return Err(AppError::Http(
+                        response.description.unwrap_or_else(|| "sendMessage refused".to_string()),
+                    ));
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_is_read_from_env_file_forms() {
+        let token = "123456789:AAHdqTcvCH1vGWJxfSeofSAs0K5PALDsaw";
+        for content in [
+            format!("TELEGRAM_BOT_TOKEN={token}\n"),
+            format!("# bot\nexport TELEGRAM_BOT_TOKEN=\"{token}\"\nOTHER=1\n"),
+            format!("{token}\n"),
+        ] {
+            assert_eq!(parse_env_token(&content).as_deref(), Some(token), "{content}");
+        }
+        assert_eq!(parse_env_token("OTHER=1\n"), None);
+        assert_eq!(parse_env_token("TELEGRAM_BOT_TOKEN=\n"), None);
+        assert!(Telegram.validate_token(token).is_ok());
+        assert!(Telegram.validate_token("not-a-token").is_err());
+        assert_eq!(Telegram.parse_user_id(" 0123 ").unwrap(), "123");
+        assert!(Telegram.parse_user_id("@me").is_err());
+    }
+}
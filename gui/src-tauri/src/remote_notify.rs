@@ -0,0 +1,139 @@
+//! Notifications about remote mode, sent straight from the GUI over the
+//! active remote channel.
+//!
+//! `notify_remote` queues a message for a single notifier thread, which
+//! posts it through the channel's API with the same token and allowlist the
+//! bot runs with. Nothing is spawned per message, so a notification can't
+//! race the bot starting up.
+//!
+//! Messages are coalesced: one is sent only after `QUIET_PERIOD` passes
+//! with nothing newer queued, and only the newest is sent. Toggling remote
+//! mode on and off quickly sends one message with the final state, not
+//! one per toggle. `MAX_DELAY` bounds how long a steady stream can hold a
+//! message back.
+
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::gui_config::RemoteChannelKind;
+use crate::remote_channel::{self, RemoteNotice};
+
+const QUIET_PERIOD: Duration = Duration::from_secs(3);
+const MAX_DELAY: Duration = Duration::from_secs(15);
+
+/// A formatted message and the channel it was formatted for, which it goes
+/// out on even if `remote.channel` changes meanwhile.
+type Message = (RemoteChannelKind, String);
+
+static QUEUE: OnceLock<Mutex<Sender<Message>>> = OnceLock::new();
+
+/// Pending message plus when the current burst started and last grew.
+#[derive(Debug)]
+struct Coalescer<T> {
+    pending: Option<T>,
+    first_at: Option<Instant>,
+    last_at: Option<Instant>,
+}
+
+impl<T> Default for Coalescer<T> {
+    fn default() -> Self {
+        Coalescer { pending: None, first_at: None, last_at: None }
+    }
+}
+
+impl<T> Coalescer<T> {
+    fn push(&mut self, message: T, now: Instant) {
+        self.pending = Some(message);
+        self.first_at.get_or_insert(now);
+        self.last_at = Some(now);
+    }
+
+    /// How long to wait before the pending message is due; `None` if there
+    /// is nothing pending.
+    fn wait(&self, now: Instant) -> Option<Duration> {
+        let (first, last) = (self.first_at?, self.last_at?);
+        let due = (last + QUIET_PERIOD).min(first + MAX_DELAY);
+        Some(due.saturating_duration_since(now))
+    }
+
+    fn take(&mut self) -> Option<T> {
+        self.first_at = None;
+        self.last_at = None;
+        self.pending.take()
+    }
+}
+
+fn run(queue: mpsc::Receiver<Message>) {
+    let mut coalescer = Coalescer::default();
+    loop {
+        let received = match coalescer.wait(Instant::now()) {
+            None => queue.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            Some(wait) => queue.recv_timeout(wait),
+        };
+        match received {
+            Ok(message) => coalescer.push(message, Instant::now()),
+            Err(RecvTimeoutError::Timeout) => {
+                let Some((kind, message)) = coalescer.take() else { continue };
+                match remote_channel::get(kind).send(&message) {
+                    Ok(()) => tracing::info!("remote notification delivered"),
+                    Err(e) => tracing::warn!(error = %e, "remote notification not delivered"),
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+/// Queue `notice` for the active channel. Returns immediately; delivery is
+/// logged.
+pub(crate) fn notify_remote(notice: RemoteNotice) {
+    let channel = remote_channel::active();
+    let message = channel.format(notice);
+    if crate::dry_run::enabled() {
+        tracing::info!(
+            message = message.as_str(),
+            channel = channel.name(),
+            "dry run: not sending remote notification"
+        );
+        return;
+    }
+    let queue = QUEUE.get_or_init(|| {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || run(rx));
+        Mutex::new(tx)
+    });
+    if let Ok(tx) = queue.lock() {
+        let _ = tx.send((channel.kind(), message));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burst_collapses_to_latest_after_quiet_period() {
+        let start = Instant::now();
+        let mut c = Coalescer::<String>::default();
+        assert_eq!(c.wait(start), None);
+
+        c.push("enabled".into(), start);
+        c.push("disabled".into(), start + Duration::from_secs(1));
+        assert_eq!(c.wait(start + Duration::from_secs(1)), Some(QUIET_PERIOD));
+        assert_eq!(c.take().as_deref(), Some("disabled"));
+        assert_eq!(c.wait(start), None);
+    }
+
+    #[test]
+    fn steady_stream_is_flushed_after_max_delay() {
+        let start = Instant::now();
+        let mut c = Coalescer::<String>::default();
+        c.push("a".into(), start);
+        c.push("b".into(), start + MAX_DELAY - Duration::from_secs(1));
+        assert_eq!(
+            c.wait(start + MAX_DELAY - Duration::from_secs(1)),
+            Some(Duration::from_secs(1))
+        );
+    }
+}
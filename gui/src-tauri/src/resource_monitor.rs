@@ -0,0 +1,153 @@
+//! Resource usage of the backend, for the settings window's graph.
+//!
+//! A monitor thread samples every backend process (found by
+//! `BACKEND_PATTERN`, so one started by hand counts too) each
+//! `SAMPLE_INTERVAL`: CPU, resident memory and, where `nvidia-smi` is
+//! available, GPU memory. Each sample is published as a `resource-usage`
+//! event and the last `HISTORY_LEN` are kept for `get_resource_usage`, so
+//! a window opened late can draw the graph straight away.
+//!
+//! `memory_pressure` is set while system memory is nearly exhausted and the
+//! backend holds a good share of it, which is what a model too large for
+//! the machine looks like. The change is logged once each way.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::{AppHandle, Emitter};
+
+use crate::compute_devices;
+use crate::process::{find_processes, BACKEND_PATTERN};
+use crate::threads;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Samples kept for late subscribers: three minutes' worth.
+const HISTORY_LEN: usize = 60;
+
+/// System memory still available below which memory counts as short.
+const LOW_AVAILABLE_FRACTION: f64 = 0.10;
+
+/// Share of total memory the backend must hold for a shortage to be
+/// blamed on it.
+const BACKEND_SHARE: f64 = 0.40;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ResourceUsage {
+    /// Milliseconds since the Unix epoch.
+    pub at: i64,
+    /// Backend pids sampled; empty when no backend is running.
+    pub pids: Vec<i32>,
+    /// Summed over the backend's processes, 100 per fully used core.
+    pub cpu_percent: f32,
+    pub rss_bytes: u64,
+    /// `None` where GPU memory can't be read (no `nvidia-smi`).
+    pub vram_mb: Option<u64>,
+    pub system_total_bytes: u64,
+    pub system_available_bytes: u64,
+    pub memory_pressure: bool,
+}
+
+static HISTORY: Mutex<VecDeque<ResourceUsage>> = Mutex::new(VecDeque::new());
+
+/// The samples kept, oldest first.
+pub fn history() -> Vec<ResourceUsage> {
+    HISTORY.lock().map(|h| h.iter().cloned().collect()).unwrap_or_default()
+}
+
+fn remember(sample: &ResourceUsage) {
+    if let Ok(mut history) = HISTORY.lock() {
+        if history.len() == HISTORY_LEN {
+            history.pop_front();
+        }
+        history.push_back(sample.clone());
+    }
+}
+
+fn memory_pressure(rss: u64, total: u64, available: u64) -> bool {
+    total > 0
+        && (available as f64) < total as f64 * LOW_AVAILABLE_FRACTION
+        && rss as f64 >= total as f64 * BACKEND_SHARE
+}
+
+/// Sample the backend with `system`, which must be reused between calls:
+/// CPU usage is measured from one refresh to the next.
+fn sample(system: &mut System) -> ResourceUsage {
+    let pids = find_processes(BACKEND_PATTERN);
+    let sys_pids: Vec<Pid> = pids.iter().map(|&p| Pid::from_u32(p as u32)).collect();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&sys_pids),
+        true,
+        ProcessRefreshKind::nothing().with_cpu().with_memory(),
+    );
+    system.refresh_memory();
+
+    let processes: Vec<_> = sys_pids.iter().filter_map(|pid| system.process(*pid)).collect();
+    let rss_bytes = processes.iter().map(|p| p.memory()).sum();
+    let vram_mb = if pids.is_empty() {
+        None
+    } else {
+        compute_devices::cuda_memory_by_pid()
+            .map(|used| pids.iter().filter_map(|p| used.get(&(*p as u32))).sum())
+    };
+    let (total, available) = (system.total_memory(), system.available_memory());
+    ResourceUsage {
+        at: chrono::Utc::now().timestamp_millis(),
+        cpu_percent: processes.iter().map(|p| p.cpu_usage()).sum(),
+        rss_bytes,
+        vram_mb,
+        system_total_bytes: total,
+        system_available_bytes: available,
+        memory_pressure: memory_pressure(rss_bytes, total, available),
+        pids,
+    }
+}
+
+/// Sample on a background thread for the app's lifetime. While no backend
+/// runs, only the first empty sample is published.
+pub(crate) fn spawn_monitor(app: AppHandle) {
+    threads::spawn_supervised("resource monitor", move || {
+        let mut system = System::new();
+        let mut last: Option<ResourceUsage> = None;
+        loop {
+            let usage = sample(&mut system);
+            let was_running = last.as_ref().is_some_and(|l| !l.pids.is_empty());
+            if !usage.pids.is_empty() || was_running || last.is_none() {
+                let was_pressured = last.as_ref().is_some_and(|l| l.memory_pressure);
+                if usage.memory_pressure != was_pressured {
+                    if usage.memory_pressure {
+                        tracing::warn!(
+                            rss_mb = usage.rss_bytes / (1024 * 1024),
+                            available_mb = usage.system_available_bytes / (1024 * 1024),
+                            "backend is using most of the system's memory"
+                        );
+                    } else {
+                        tracing::info!("memory pressure from backend eased");
+                    }
+                }
+                remember(&usage);
+                let _ = app.emit("resource-usage", &usage);
+            }
+            last = Some(usage);
+            std::thread::sleep(SAMPLE_INTERVAL);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn pressure_needs_low_memory_and_a_large_backend() {
+        assert!(memory_pressure(7 * GB, 16 * GB, GB));
+        assert!(!memory_pressure(2 * GB, 16 * GB, GB));
+        assert!(!memory_pressure(7 * GB, 16 * GB, 4 * GB));
+        assert!(!memory_pressure(0, 0, 0));
+    }
+}
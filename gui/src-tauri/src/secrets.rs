@@ -0,0 +1,75 @@
+//! Credentials kept in the OS keyring (Secret Service, macOS Keychain,
+//! Windows Credential Manager) rather than in config files.
+//!
+//! Two kinds live here: each remote channel's bot token, which the GUI
+//! reads when launching the bot and hands over in the child's environment,
+//! and the tokens `http_control` and `ws_control` check clients against.
+//! None is written to disk in plaintext.
+
+use keyring::Entry;
+
+use crate::error::AppResult;
+use crate::gui_config::RemoteChannelKind;
+
+const SERVICE: &str = "synthia";
+const HTTP_TOKEN_USER: &str = "http-api-token";
+const WS_TOKEN_USER: &str = "ws-control-token";
+
+fn remote_token_user(channel: RemoteChannelKind) -> &'static str {
+    match channel {
+        RemoteChannelKind::Telegram => "telegram-bot-token",
+        RemoteChannelKind::Discord => "discord-bot-token",
+        RemoteChannelKind::Matrix => "matrix-access-token",
+    }
+}
+
+fn entry(user: &str) -> AppResult<Entry> {
+    Ok(Entry::new(SERVICE, user)?)
+}
+
+fn get(user: &str) -> AppResult<Option<String>> {
+    match entry(user)?.get_password() {
+        Ok(token) => Ok(Some(token)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The stored token for `channel`'s bot, or `None` if there isn't one.
+pub fn remote_token(channel: RemoteChannelKind) -> AppResult<Option<String>> {
+    get(remote_token_user(channel))
+}
+
+pub fn set_remote_token(channel: RemoteChannelKind, token: &str) -> AppResult<()> {
+    entry(remote_token_user(channel))?.set_password(token)?;
+    Ok(())
+}
+
+/// Remove the stored token for `channel`. Returns whether there was one.
+pub fn clear_remote_token(channel: RemoteChannelKind) -> AppResult<bool> {
+    match entry(remote_token_user(channel))?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The HTTP API's bearer token, or `None` before one is generated.
+pub fn http_token() -> AppResult<Option<String>> {
+    get(HTTP_TOKEN_USER)
+}
+
+pub fn set_http_token(token: &str) -> AppResult<()> {
+    entry(HTTP_TOKEN_USER)?.set_password(token)?;
+    Ok(())
+}
+
+/// The WebSocket control server's token, or `None` before one is generated.
+pub fn ws_token() -> AppResult<Option<String>> {
+    get(WS_TOKEN_USER)
+}
+
+pub fn set_ws_token(token: &str) -> AppResult<()> {
+    entry(WS_TOKEN_USER)?.set_password(token)?;
+    Ok(())
+}
@@ -58,8 +58,7 @@ pub struct RuleHit {
 }
 
 fn security_dir() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(home).join(".config/synthia/security")
+    crate::platform::home_dir().join(".config/synthia/security")
 }
 
 fn events_path() -> PathBuf {
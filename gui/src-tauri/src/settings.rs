@@ -0,0 +1,368 @@
+//! One typed view over the user settings spread across `gui.toml` and the
+//! backend's `config.yaml`.
+//!
+//! Each setting has a key in `SettingKey` and a field of the same name in
+//! `Settings`. Values are validated by deserializing into the field's type
+//! and then by the same checks the dedicated commands use, and each key is
+//! saved to the file that already owns it: speech settings to `config.yaml`,
+//! everything else to `gui.toml`. `startup` restores what only lives in
+//! runtime files at launch.
+
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::commands::{backend_settings, lifecycle, locale, overlay, remote};
+use crate::error::{AppError, AppResult};
+use crate::global_hotkeys::{self, HotkeyAction};
+use crate::gui_config::{self, ComputeDevice, OverlayAnchor, RemoteChannelKind, SavedPosition};
+use crate::paths::write_atomic;
+use crate::synthia_state::{self, Mode};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingKey {
+    Model,
+    ComputeDevice,
+    Language,
+    Mode,
+    Locale,
+    Autostart,
+    OverlayPosition,
+    OverlayAnchor,
+    PushToTalkHotkey,
+    DictationHotkey,
+    AssistantHotkey,
+    ToggleRecordingHotkey,
+    CopyLastTranscriptionHotkey,
+    RemoteChannel,
+}
+
+pub const KEYS: [SettingKey; 14] = [
+    SettingKey::Model,
+    SettingKey::ComputeDevice,
+    SettingKey::Language,
+    SettingKey::Mode,
+    SettingKey::Locale,
+    SettingKey::Autostart,
+    SettingKey::OverlayPosition,
+    SettingKey::OverlayAnchor,
+    SettingKey::PushToTalkHotkey,
+    SettingKey::DictationHotkey,
+    SettingKey::AssistantHotkey,
+    SettingKey::ToggleRecordingHotkey,
+    SettingKey::CopyLastTranscriptionHotkey,
+    SettingKey::RemoteChannel,
+];
+
+impl SettingKey {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SettingKey::Model => "model",
+            SettingKey::ComputeDevice => "compute_device",
+            SettingKey::Language => "language",
+            SettingKey::Mode => "mode",
+            SettingKey::Locale => "locale",
+            SettingKey::Autostart => "autostart",
+            SettingKey::OverlayPosition => "overlay_position",
+            SettingKey::OverlayAnchor => "overlay_anchor",
+            SettingKey::PushToTalkHotkey => "push_to_talk_hotkey",
+            SettingKey::DictationHotkey => "dictation_hotkey",
+            SettingKey::AssistantHotkey => "assistant_hotkey",
+            SettingKey::ToggleRecordingHotkey => "toggle_recording_hotkey",
+            SettingKey::CopyLastTranscriptionHotkey => "copy_last_transcription_hotkey",
+            SettingKey::RemoteChannel => "remote_channel",
+        }
+    }
+
+    fn hotkey_action(self) -> Option<HotkeyAction> {
+        match self {
+            SettingKey::PushToTalkHotkey => Some(HotkeyAction::PushToTalk),
+            SettingKey::DictationHotkey => Some(HotkeyAction::Dictation),
+            SettingKey::AssistantHotkey => Some(HotkeyAction::Assistant),
+            SettingKey::ToggleRecordingHotkey => Some(HotkeyAction::ToggleRecording),
+            SettingKey::CopyLastTranscriptionHotkey => Some(HotkeyAction::CopyLastTranscription),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for SettingKey {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        KEYS.into_iter()
+            .find(|key| key.as_str() == s)
+            .ok_or_else(|| AppError::Validation(format!("unknown setting {s:?}")))
+    }
+}
+
+/// Every setting, as returned by `get_all_settings`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Settings {
+    /// Local Whisper model size.
+    pub model: String,
+    /// Device the backend transcribes on, from its next start.
+    pub compute_device: ComputeDevice,
+    /// BCP 47 language tag for speech recognition.
+    pub language: String,
+    pub mode: Mode,
+    /// Locale override for the GUI; `None` follows the system.
+    pub locale: Option<String>,
+    pub autostart: bool,
+    /// Where the overlay was last placed; `None` keeps the default spot.
+    pub overlay_position: Option<SavedPosition>,
+    /// Monitor and spot the overlay is pinned to; takes precedence over
+    /// `overlay_position`.
+    pub overlay_anchor: Option<OverlayAnchor>,
+    pub push_to_talk_hotkey: Option<String>,
+    pub dictation_hotkey: Option<String>,
+    pub assistant_hotkey: Option<String>,
+    pub toggle_recording_hotkey: Option<String>,
+    pub copy_last_transcription_hotkey: Option<String>,
+    /// Chat service remote mode runs over, from its next start.
+    pub remote_channel: RemoteChannelKind,
+}
+
+/// Emitted as `setting-changed` after a successful `set`.
+#[derive(Serialize, Debug, Clone)]
+pub struct SettingChanged {
+    pub key: SettingKey,
+    pub value: Value,
+}
+
+pub fn all() -> AppResult<Settings> {
+    let config = gui_config::load();
+    let backend = backend_settings::get_backend_settings()?;
+    Ok(Settings {
+        model: backend.stt_model,
+        compute_device: config.backend.compute_device,
+        language: backend.language,
+        mode: synthia_state::read_synthia_state().mode,
+        locale: config.i18n.locale.clone(),
+        autostart: config.startup.autostart,
+        overlay_position: config.overlay.position.clone(),
+        overlay_anchor: config.overlay.anchor.clone(),
+        push_to_talk_hotkey: HotkeyAction::PushToTalk.shortcut(&config),
+        dictation_hotkey: HotkeyAction::Dictation.shortcut(&config),
+        assistant_hotkey: HotkeyAction::Assistant.shortcut(&config),
+        toggle_recording_hotkey: HotkeyAction::ToggleRecording.shortcut(&config),
+        copy_last_transcription_hotkey: HotkeyAction::CopyLastTranscription.shortcut(&config),
+        remote_channel: config.remote.channel,
+    })
+}
+
+pub fn get(key: SettingKey) -> AppResult<Value> {
+    field(&all()?, key)
+}
+
+fn field(settings: &Settings, key: SettingKey) -> AppResult<Value> {
+    let mut all = serde_json::to_value(settings)?;
+    Ok(all
+        .get_mut(key.as_str())
+        .map(Value::take)
+        .unwrap_or(Value::Null))
+}
+
+/// `value` as the type `key` holds, or a validation error naming the key.
+fn parse<T: DeserializeOwned>(key: SettingKey, value: Value) -> AppResult<T> {
+    serde_json::from_value(value)
+        .map_err(|e| AppError::Validation(format!("invalid value for {}: {e}", key.as_str())))
+}
+
+/// Validate and save one setting, apply it to the running app, and return
+/// the value now stored.
+pub fn set(app: &AppHandle, key: SettingKey, value: Value) -> AppResult<Value> {
+    match key {
+        SettingKey::Model | SettingKey::Language => {
+            let mut backend = backend_settings::get_backend_settings()?;
+            let text: String = parse(key, value)?;
+            if key == SettingKey::Model {
+                backend.stt_model = text;
+            } else {
+                backend.language = text;
+            }
+            backend_settings::save(&backend)?;
+        }
+        SettingKey::ComputeDevice => {
+            let device: ComputeDevice = parse(key, value)?;
+            gui_config::update(|cfg| cfg.backend.compute_device = device)?;
+        }
+        SettingKey::Mode => lifecycle::apply_mode(app, parse(key, value)?)?,
+        SettingKey::Locale => locale::apply(app, parse(key, value)?)?,
+        SettingKey::Autostart => set_autostart(parse(key, value)?)?,
+        SettingKey::OverlayPosition => {
+            let position: Option<SavedPosition> = parse(key, value)?;
+            gui_config::update(|cfg| cfg.overlay.position = position)?;
+            overlay::reapply_config(app);
+        }
+        SettingKey::OverlayAnchor => overlay::apply_anchor(app, parse(key, value)?)?,
+        SettingKey::PushToTalkHotkey
+        | SettingKey::DictationHotkey
+        | SettingKey::AssistantHotkey
+        | SettingKey::ToggleRecordingHotkey
+        | SettingKey::CopyLastTranscriptionHotkey => {
+            let action = key.hotkey_action().expect("hotkey keys map to an action");
+            global_hotkeys::set(app, action, parse(key, value)?)?;
+        }
+        SettingKey::RemoteChannel => remote::set_channel(app, parse(key, value)?)?,
+    }
+    let value = get(key)?;
+    tracing::info!(key = key.as_str(), %value, "setting saved");
+    let _ = app.emit(
+        "setting-changed",
+        SettingChanged {
+            key,
+            value: value.clone(),
+        },
+    );
+    Ok(value)
+}
+
+/// `~/.config/autostart/synthia.desktop`, read by XDG-compliant sessions.
+fn autostart_entry_path() -> AppResult<PathBuf> {
+    dirs::config_dir()
+        .map(|dir| dir.join("autostart").join("synthia.desktop"))
+        .ok_or_else(|| AppError::NotFound("no config directory for autostart".into()))
+}
+
+/// Desktop entry launching `exe`. `Exec` arguments with spaces or quotes
+/// have to be double-quoted, with `"`, `` ` ``, `$` and `\` escaped.
+fn desktop_entry(exe: &str) -> String {
+    let exec = if exe.contains(|c: char| c.is_whitespace() || "\"'`$\\".contains(c)) {
+        let escaped: String = exe
+            .chars()
+            .flat_map(|c| match c {
+                '"' | '`' | '$' | '\\' => vec!['\\', c],
+                _ => vec![c],
+            })
+            .collect();
+        format!("\"{escaped}\"")
+    } else {
+        exe.to_string()
+    };
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=Synthia\n\
+         Comment=Voice assistant\n\
+         Exec={exec}\n\
+         Icon=synthia\n\
+         Terminal=false\n\
+         X-GNOME-Autostart-enabled=true\n"
+    )
+}
+
+fn write_autostart_entry() -> AppResult<()> {
+    let path = autostart_entry_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let exe = std::env::current_exe()?;
+    write_atomic(&path, desktop_entry(&exe.to_string_lossy()))
+}
+
+fn set_autostart(enabled: bool) -> AppResult<()> {
+    if enabled {
+        write_autostart_entry()?;
+    } else {
+        match fs::remove_file(autostart_entry_path()?) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    gui_config::update(|cfg| cfg.startup.autostart = enabled)
+}
+
+/// Bring runtime state in line with `gui.toml` at launch: restore the
+/// saved recording mode and refresh the autostart entry, whose `Exec` goes
+/// stale if the GUI binary moved.
+pub fn startup() {
+    let config = gui_config::load().startup;
+    if let Some(mode) = config.mode {
+        if synthia_state::read_synthia_state().mode != mode {
+            if let Err(e) = synthia_state::update_synthia_state(|s| s.mode = mode) {
+                tracing::warn!(error = %e, ?mode, "failed to restore saved mode");
+            }
+        }
+    }
+    if config.autostart {
+        if let Err(e) = write_autostart_entry() {
+            tracing::warn!(error = %e, "failed to refresh autostart entry");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Settings {
+        Settings {
+            model: "small".into(),
+            compute_device: ComputeDevice::Cpu,
+            language: "en-US".into(),
+            mode: Mode::PushToTalk,
+            locale: None,
+            autostart: true,
+            overlay_position: Some(SavedPosition {
+                x: 10,
+                y: 20,
+                monitor: None,
+            }),
+            overlay_anchor: None,
+            push_to_talk_hotkey: Some("Super+Space".into()),
+            dictation_hotkey: None,
+            assistant_hotkey: Some("Super+A".into()),
+            toggle_recording_hotkey: None,
+            copy_last_transcription_hotkey: Some("Super+Shift+C".into()),
+            remote_channel: RemoteChannelKind::Matrix,
+        }
+    }
+
+    #[test]
+    fn every_key_names_a_settings_field() {
+        let fields = serde_json::to_value(sample()).unwrap();
+        for key in KEYS {
+            assert!(fields.get(key.as_str()).is_some(), "{key:?}");
+            assert_eq!(key.as_str().parse::<SettingKey>().unwrap(), key);
+            assert_eq!(serde_json::to_value(key).unwrap(), key.as_str());
+        }
+        assert_eq!(fields.as_object().unwrap().len(), KEYS.len());
+        assert!("theme".parse::<SettingKey>().is_err());
+        assert_eq!(field(&sample(), SettingKey::Mode).unwrap(), "push_to_talk");
+        assert_eq!(field(&sample(), SettingKey::RemoteChannel).unwrap(), "matrix");
+    }
+
+    #[test]
+    fn parse_rejects_values_of_the_wrong_type() {
+        assert!(parse::<bool>(SettingKey::Autostart, Value::from("yes")).is_err());
+        assert!(parse::<Mode>(SettingKey::Mode, Value::from("always")).is_err());
+        assert_eq!(
+            parse::<Mode>(SettingKey::Mode, Value::from("voice")).unwrap(),
+            Mode::Voice
+        );
+        let position: Option<SavedPosition> =
+            parse(SettingKey::OverlayPosition, serde_json::json!({"x": 1, "y": 2})).unwrap();
+        assert_eq!(position.map(|p| (p.x, p.y)), Some((1, 2)));
+        assert!(parse::<Option<SavedPosition>>(
+            SettingKey::OverlayPosition,
+            serde_json::json!({"x": "left"})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn desktop_entry_quotes_exec_paths_that_need_it() {
+        assert!(desktop_entry("/usr/bin/synthia").contains("\nExec=/usr/bin/synthia\n"));
+        assert!(desktop_entry("/opt/My Apps/synthia")
+            .contains("\nExec=\"/opt/My Apps/synthia\"\n"));
+        assert!(desktop_entry("/opt/$x/syn\"thia").contains("\nExec=\"/opt/\\$x/syn\\\"thia\"\n"));
+    }
+}
@@ -0,0 +1,73 @@
+//! Locating the backend and remote-mode bot programs.
+//!
+//! A packaged build ships them as Tauri external binaries: list
+//! `binaries/synthia-backend` and `binaries/synthia-telegram-bot` (and
+//! `synthia-discord-bot` or `synthia-matrix-bot` for those channels) under
+//! `bundle.externalBin` in `tauri.conf.json` (one file per target triple,
+//! e.g. a PyInstaller build of `synthia.main`), and the bundler installs
+//! them next to the GUI executable with the triple stripped. When they are
+//! there they are used; otherwise the dev checkout's `run.sh` and venv
+//! python are, as before.
+//!
+//! They are spawned with `std::process::Command` just as the shell plugin's
+//! sidecar API would, so the backend still gets its own process group and
+//! the pidfile, supervision and shutdown paths stay the same.
+
+use std::path::{Path, PathBuf};
+
+pub const BACKEND: &str = "synthia-backend";
+pub const TELEGRAM_BOT: &str = "synthia-telegram-bot";
+pub const DISCORD_BOT: &str = "synthia-discord-bot";
+pub const MATRIX_BOT: &str = "synthia-matrix-bot";
+
+fn is_executable(path: &Path) -> bool {
+    let Ok(meta) = path.metadata() else { return false };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        meta.is_file() && meta.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        meta.is_file()
+    }
+}
+
+/// Where the bundled executable `name` would be installed (with `.exe`
+/// appended on Windows).
+pub(crate) fn expected_path(name: &str) -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .unwrap_or_default()
+        .join(format!("{name}{}", std::env::consts::EXE_SUFFIX))
+}
+
+/// The bundled executable `name`, if this build ships one.
+pub(crate) fn find(name: &str) -> Option<PathBuf> {
+    let path = expected_path(name);
+    is_executable(&path).then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sidecar_must_be_an_executable_file() {
+        let dir = std::env::temp_dir().join(format!("synthia-sidecar-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let exec = dir.join("synthia-exec");
+        std::fs::write(&exec, "#!/bin/sh\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert!(!is_executable(&exec));
+            std::fs::set_permissions(&exec, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        assert!(is_executable(&exec));
+        assert!(!is_executable(&dir));
+        assert!(!is_executable(&dir.join("missing")));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
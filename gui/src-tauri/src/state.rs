@@ -1,21 +1,124 @@
 //! Tauri-managed application state, replacing static `Mutex<Option<T>>` globals.
 
+use std::collections::VecDeque;
 use std::process::Child;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
+use chrono::{DateTime, Utc};
+
+use crate::command_stats::CommandStats;
+use crate::commands::remote::{RemoteHealth, RemoteState};
 use crate::commands::usage::UsageStats;
+use crate::dry_run::Simulated;
+use crate::global_hotkeys::RegisteredHotkey;
+use crate::gui_config::{DoubleTapConfig, EvdevBinding};
+use crate::overlay_transcript::TranscriptBuffer;
+use crate::process::ShutdownOutcome;
+use crate::remote_approval::RemoteApprovals;
+use crate::supervisor::{BackendExit, CrashRecovery};
+use crate::synthia_state::{OverlayPayload, SynthiaState};
+use crate::tray::TrayState;
 
 #[derive(Default)]
 pub struct AppState {
     pub synthia_process: Mutex<Option<Child>>,
+    /// Process group of the backend spawned by `start_synthia`, or adopted
+    /// from the pidfile at startup. `run.sh` is the group leader, so this is
+    /// its pid; signalling the group reaches the python workers it forks as
+    /// well.
+    pub backend_pgid: Mutex<Option<i32>>,
+    /// When the last `start_synthia` failed; `None` once one succeeds.
+    pub last_start_failure: Mutex<Option<Instant>>,
+    /// How the last GUI-initiated backend shutdown went; surfaced by `get_health`.
+    pub last_shutdown: Mutex<Option<ShutdownOutcome>>,
+    /// Recent backend exits, oldest first; see `supervisor`.
+    pub exit_history: Mutex<VecDeque<BackendExit>>,
+    /// Crash restarts so far and whether one is pending; see `supervisor`.
+    pub crash_recovery: Mutex<CrashRecovery>,
     /// Cached OAuth bearer token + the moment it was fetched.
     pub usage_cache: Mutex<Option<UsageTokenCache>>,
     /// Cached `UsageStats` response payload + fetch timestamp.
     pub usage_response_cache: Mutex<Option<UsageResponseCache>>,
-    /// Filesystem watchers kept alive for the app lifetime; populated in CP9.
-    #[allow(dead_code)] // wired up in CP9
+    /// Filesystem watchers kept alive for the app lifetime (e.g. `gui.toml`).
     pub watchers: Mutex<Vec<Box<dyn std::any::Any + Send + Sync>>>,
+    /// The state file watcher, kept separately so it can be replaced when
+    /// its poll interval changes (see `power`).
+    pub state_watcher: Mutex<Option<Box<dyn std::any::Any + Send + Sync>>>,
+    /// Whether the machine was on battery at the last power check.
+    pub on_battery: AtomicBool,
+    /// Loaded tray icons plus the theme/recording flags they're picked by.
+    pub tray: Mutex<TrayState>,
+    /// The remote bot this GUI spawned; the watchdog replaces it on
+    /// relaunch.
+    pub remote_bot: Mutex<Option<Child>>,
+    /// Stop flag for the remote bot watchdog; `Some` while remote mode is on.
+    pub remote_watchdog: Mutex<Option<Arc<AtomicBool>>>,
+    /// Stop flag for the overlay's audio level meter; `Some` while it runs.
+    pub audio_meter: Mutex<Option<Arc<AtomicBool>>>,
+    /// Last bot running state published as a `remote-status` event.
+    pub remote_running: AtomicBool,
+    /// Remote-mode transition in flight, if any; see `commands::remote`.
+    pub remote_state: Mutex<RemoteState>,
+    /// What the watchdog last learned about the remote bot.
+    pub remote_health: Mutex<RemoteHealth>,
+    /// Messages the remote bot holds for approval; see `remote_approval`.
+    pub remote_approvals: Mutex<RemoteApprovals>,
+    /// Whether the overlay was made a layer-shell surface at startup; see
+    /// `overlay_layer_shell`.
+    pub overlay_layer_shell: AtomicBool,
+    /// Bumped on every overlay `Moved` event; debounces position saves.
+    pub overlay_move_seq: AtomicU64,
+    /// Bumped on every overlay auto-show/hide; a delayed hide only goes
+    /// ahead if nothing newer happened meanwhile.
+    pub overlay_visibility_seq: AtomicU64,
+    /// Bumped on every main window move/resize; debounces geometry saves.
+    pub main_window_geometry_seq: AtomicU64,
+    /// Bumped on every state file event; only the last of a burst is read.
+    pub state_event_seq: AtomicU64,
+    /// Live transcription text on the overlay; see `overlay_transcript`.
+    pub overlay_transcript: Mutex<TranscriptBuffer>,
+    /// Last payload sent to the overlay, to suppress duplicate emits.
+    pub last_overlay_payload: Mutex<Option<OverlayPayload>>,
+    /// Backend `--version` output, cleared whenever the GUI starts or stops
+    /// the backend so an upgrade is picked up on the next start.
+    pub backend_version: Mutex<Option<String>>,
+    /// Start of the recording the timer is counting, as last seen by the
+    /// state watcher; `None` while not recording.
+    pub recording_started_at: Mutex<Option<DateTime<Utc>>>,
+    /// Last `ready` flag seen by the state watcher; a change is published as
+    /// a `backend-ready` event.
+    pub backend_ready: AtomicBool,
+    /// When a state update last arrived (file or IPC); drives the recording
+    /// idle timeout. Heartbeat-only updates don't count.
+    pub last_state_change: Mutex<Option<Instant>>,
+    /// Last state applied, to recognise heartbeat-only updates.
+    pub last_synthia_state: Mutex<Option<SynthiaState>>,
+    /// Latest `heartbeat_at` seen; see `backend_health`.
+    pub last_heartbeat: Mutex<Option<DateTime<Utc>>>,
+    /// Whether the tray currently shows the backend as unresponsive.
+    pub backend_unresponsive: AtomicBool,
+    /// Set while `stop_synthia` winds the backend down, so the supervisor
+    /// doesn't take the exit it asked for as a crash.
+    pub backend_stopping: AtomicBool,
+    /// Push-to-talk shortcut currently registered, if the mode is active.
+    pub ptt_shortcut: Mutex<Option<String>>,
+    /// The double tap being listened for, with its listener's stop flag;
+    /// see `global_hotkeys`.
+    pub double_tap: Mutex<Option<(DoubleTapConfig, Arc<AtomicBool>)>>,
+    /// Evdev key push-to-talk listens for, with its listener's stop flag.
+    pub ptt_evdev: Mutex<Option<(EvdevBinding, Arc<AtomicBool>)>>,
+    /// `[hotkeys]` shortcuts currently registered; see `global_hotkeys`.
+    pub global_hotkeys: Mutex<Vec<RegisteredHotkey>>,
+    /// True between a push-to-talk press and its release (or timeout).
+    pub ptt_held: AtomicBool,
+    /// Bumped on every press, so a max-hold timer only releases its own press.
+    pub ptt_press_seq: AtomicU64,
+    /// Per-command success/failure counts since launch; see `command_stats`.
+    pub command_stats: CommandStats,
+    /// Pretend backend and bot state while in dry-run mode.
+    pub simulated: Simulated,
 }
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,409 @@
+//! Backend supervision and exit history.
+//!
+//! A background thread polls the backend child the GUI spawned with
+//! `try_wait`. When it exits without being asked to, the exit is recorded,
+//! the stale handle is cleared and `backend-exited` is emitted. Exits caused
+//! by `stop_synthia` are recorded too, marked intentional, so the history
+//! shows crashes in context. The last `EXIT_HISTORY_LEN` exits are kept in
+//! memory and, unless `backend.persist_exit_history` is off, in
+//! `backend-exits.json` next to the GUI logs.
+//!
+//! The pid of each spawned backend is written to `synthia-backend.pid` in the
+//! runtime dir. A GUI restarted while its backend kept running reads it
+//! back, checks the process is still that backend, and adopts it: the
+//! `Child` is lost, so the adopted backend is tracked by process group
+//! only, and its exit status can't be collected.
+//!
+//! An unexpected exit is followed by `backend-crashed` and, unless
+//! `backend.auto_restart` is off, a restart after `backend.restart_backoff_ms`,
+//! doubling each time. After `backend.max_restarts` crashes in a row the
+//! supervisor gives up and leaves the backend stopped; the count starts over
+//! once the backend stays up for `STABLE_UPTIME`. Each successful restart
+//! emits `backend-restarted`. The tray tooltip shows both.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::PoisonError;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::lifecycle;
+use crate::error::AppResult;
+use crate::{gui_config, notifications, tray};
+use crate::paths::write_atomic;
+use crate::process::{self, ExitInfo, BACKEND_PATTERN};
+use crate::state::AppState;
+use crate::{get_run_script, get_runtime_dir, threads};
+
+pub const EXIT_HISTORY_LEN: usize = 20;
+
+const SUPERVISE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long the backend must go without crashing for the restart count to
+/// start over.
+const STABLE_UPTIME: Duration = Duration::from_secs(300);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BackendExit {
+    pub at: DateTime<Local>,
+    /// `None` when the status couldn't be collected (e.g. not our child).
+    pub exit: Option<ExitInfo>,
+    /// True when the GUI stopped the backend; false for crashes.
+    pub intentional: bool,
+}
+
+impl BackendExit {
+    pub fn now(exit: Option<ExitInfo>, intentional: bool) -> Self {
+        BackendExit {
+            at: Local::now(),
+            exit,
+            intentional,
+        }
+    }
+}
+
+/// Payload of `backend-crashed`.
+#[derive(Serialize, Debug, Clone)]
+pub struct BackendCrash {
+    pub exit: BackendExit,
+    /// Which crash in a row this is, from 1.
+    pub attempt: u32,
+    pub max_restarts: u32,
+    /// When the restart will happen; `None` when the supervisor gave up.
+    pub restart_in_ms: Option<u64>,
+}
+
+/// Payload of `backend-restarted`.
+#[derive(Serialize, Debug, Clone)]
+pub struct BackendRestart {
+    pub attempt: u32,
+    pub max_restarts: u32,
+}
+
+/// What the tray shows while the supervisor deals with a crash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recovery {
+    Restarting { attempt: u32, max_restarts: u32 },
+    GaveUp,
+}
+
+/// Crash restarts since the backend last stayed up for `STABLE_UPTIME`.
+/// The Telegram bot's watchdog keeps one too.
+#[derive(Default)]
+pub struct CrashRecovery {
+    attempts: u32,
+    last_crash: Option<Instant>,
+    /// A restart is waiting out its backoff; `cancel_restart` clears it.
+    pending: bool,
+}
+
+impl CrashRecovery {
+    /// Count a crash at `now` and return its number in the current run of
+    /// crashes, from 0.
+    pub(crate) fn next_attempt(&mut self, now: Instant) -> u32 {
+        if self
+            .last_crash
+            .is_some_and(|at| now.duration_since(at) >= STABLE_UPTIME)
+        {
+            self.attempts = 0;
+        }
+        self.last_crash = Some(now);
+        self.attempts += 1;
+        self.attempts - 1
+    }
+}
+
+fn history_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("synthia")
+        .join("backend-exits.json")
+}
+
+/// Append `exit`, dropping the oldest entries beyond `EXIT_HISTORY_LEN`.
+fn push_bounded(history: &mut VecDeque<BackendExit>, exit: BackendExit) {
+    history.push_back(exit);
+    while history.len() > EXIT_HISTORY_LEN {
+        history.pop_front();
+    }
+}
+
+/// Persisted history from a previous session, if any.
+pub fn load_history() -> VecDeque<BackendExit> {
+    if !gui_config::load().backend.persist_exit_history {
+        return VecDeque::new();
+    }
+    let mut history: VecDeque<BackendExit> = fs::read_to_string(history_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    while history.len() > EXIT_HISTORY_LEN {
+        history.pop_front();
+    }
+    history
+}
+
+fn persist(history: &VecDeque<BackendExit>) -> AppResult<()> {
+    let path = history_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(&path, serde_json::to_string_pretty(history)?)
+}
+
+/// Add an exit to the history (and its file).
+pub fn record_exit(state: &AppState, exit: BackendExit) {
+    let status = exit.exit.as_ref().map(|e| e.description.clone());
+    if exit.intentional {
+        tracing::info!(status = ?status, "backend stopped");
+    } else {
+        tracing::warn!(status = ?status, "backend exited unexpectedly");
+    }
+    let Ok(mut history) = state.exit_history.lock() else {
+        return;
+    };
+    push_bounded(&mut history, exit);
+    if gui_config::load().backend.persist_exit_history {
+        if let Err(e) = persist(&history) {
+            tracing::warn!(error = %e, "failed to persist backend exit history");
+        }
+    }
+}
+
+fn pidfile_path() -> PathBuf {
+    get_runtime_dir().join("synthia-backend.pid")
+}
+
+/// Remember `pid` as the backend this GUI runs, for the next GUI to adopt.
+pub fn write_pidfile(pid: i32) {
+    if let Err(e) = write_atomic(&pidfile_path(), pid.to_string()) {
+        tracing::warn!(pid, error = %e, "failed to write backend pidfile");
+    }
+}
+
+pub fn remove_pidfile() {
+    let _ = fs::remove_file(pidfile_path());
+}
+
+fn parse_pid(content: &str) -> Option<i32> {
+    content.trim().parse().ok().filter(|pid| *pid > 0)
+}
+
+/// Whether `cmdline` is the `run_script` wrapper (the group leader we spawn)
+/// or the backend itself, rather than an unrelated process that reused the
+/// pid.
+fn is_backend_cmdline(cmdline: &str, run_script: &Path) -> bool {
+    cmdline.contains(&*run_script.to_string_lossy())
+        || Regex::new(BACKEND_PATTERN).is_ok_and(|re| re.is_match(cmdline))
+}
+
+/// Take over a backend left running by a previous GUI session, if the
+/// pidfile names one. Returns its pid.
+pub fn adopt(state: &AppState) -> Option<i32> {
+    let content = fs::read_to_string(pidfile_path()).ok()?;
+    let Some(pid) = parse_pid(&content) else {
+        tracing::warn!(content = content.trim(), "ignoring malformed backend pidfile");
+        remove_pidfile();
+        return None;
+    };
+    match process::cmdline(pid) {
+        Some(cmdline) if is_backend_cmdline(&cmdline, &get_run_script()) => {
+            tracing::info!(pid, "adopted backend from a previous session");
+            if let Ok(mut pgid) = state.backend_pgid.lock() {
+                *pgid = Some(pid);
+            }
+            Some(pid)
+        }
+        Some(cmdline) => {
+            tracing::info!(pid, cmdline, "pidfile names a process that isn't the backend");
+            remove_pidfile();
+            None
+        }
+        None => {
+            tracing::info!(pid, "backend from a previous session has exited");
+            remove_pidfile();
+            None
+        }
+    }
+}
+
+/// Poll the owned or adopted backend for an exit nobody asked for.
+pub fn spawn(app: AppHandle) {
+    threads::spawn_supervised("backend supervisor", move || loop {
+        std::thread::sleep(SUPERVISE_INTERVAL);
+        let state = app.state::<AppState>();
+        // A backend leaving after the shutdown command is reaped by `stop`.
+        if state.backend_stopping.load(Ordering::SeqCst) {
+            continue;
+        }
+        let exited = {
+            let Ok(mut proc) = state.synthia_process.lock() else {
+                continue;
+            };
+            let Ok(mut pgid) = state.backend_pgid.lock() else {
+                continue;
+            };
+            let exit = match proc.as_mut() {
+                Some(child) => match child.try_wait() {
+                    Ok(Some(status)) => Some(status.into()),
+                    _ => continue,
+                },
+                // Adopted: no `Child` to reap, so only notice it's gone.
+                None => match *pgid {
+                    Some(pid) if process::cmdline(pid).is_none() => None,
+                    _ => continue,
+                },
+            };
+            *proc = None;
+            *pgid = None;
+            remove_pidfile();
+            BackendExit::now(exit, false)
+        };
+        record_exit(&state, exited.clone());
+        let _ = app.emit("backend-exited", &exited);
+        lifecycle::mark_starting(false);
+        tray::set_running(&app, false);
+        on_crash(&app, exited);
+    });
+}
+
+/// Announce a crash and schedule the restart the policy allows, if any.
+fn on_crash(app: &AppHandle, exit: BackendExit) {
+    let state = app.state::<AppState>();
+    let config = gui_config::load().backend;
+    let (attempt, delay) = {
+        let mut recovery = state
+            .crash_recovery
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let attempt = recovery.next_attempt(Instant::now());
+        let delay = config.restart_delay(attempt);
+        recovery.pending = delay.is_some();
+        (attempt + 1, delay)
+    };
+    let max_restarts = config.max_restarts;
+    let description = exit.exit.as_ref().map(|e| e.description.clone());
+    let _ = app.emit(
+        "backend-crashed",
+        BackendCrash {
+            exit,
+            attempt,
+            max_restarts,
+            restart_in_ms: delay.map(|d| d.as_millis() as u64),
+        },
+    );
+    let Some(delay) = delay else {
+        tracing::error!(attempt, "backend keeps crashing; not restarting it");
+        tray::set_recovery(app, Some(Recovery::GaveUp));
+        notifications::notify_backend_crash(false, description.as_deref());
+        return;
+    };
+    tracing::warn!(attempt, delay_ms = delay.as_millis() as u64, "restarting crashed backend");
+    notifications::notify_backend_crash(true, description.as_deref());
+    tray::set_recovery(app, Some(Recovery::Restarting { attempt, max_restarts }));
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(delay);
+        let state = app.state::<AppState>();
+        let pending = std::mem::take(
+            &mut state
+                .crash_recovery
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .pending,
+        );
+        if !pending {
+            tracing::info!("crash restart cancelled");
+            return;
+        }
+        match lifecycle::start(&state) {
+            Ok(_) => {
+                tracing::info!(attempt, "crashed backend restarted");
+                tray::set_running(&app, true);
+                let _ = app.emit("backend-restarted", BackendRestart { attempt, max_restarts });
+            }
+            // The failed start recorded its own exit; count it as the next crash.
+            Err(e) => {
+                tracing::warn!(attempt, error = %e, "crash restart failed");
+                let exit = state
+                    .exit_history
+                    .lock()
+                    .ok()
+                    .and_then(|h| h.back().cloned())
+                    .unwrap_or_else(|| BackendExit::now(None, false));
+                on_crash(&app, exit);
+            }
+        }
+    });
+}
+
+/// Drop a crash restart that is still waiting, because the user stopped
+/// the backend meanwhile, and clear the tray's crash notice.
+pub fn cancel_restart(state: &AppState) {
+    state
+        .crash_recovery
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .pending = false;
+    tray::clear_recovery(state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crash_count_starts_over_after_stable_uptime() {
+        let mut recovery = CrashRecovery::default();
+        let start = Instant::now();
+        assert_eq!(recovery.next_attempt(start), 0);
+        assert_eq!(recovery.next_attempt(start + Duration::from_secs(5)), 1);
+        assert_eq!(recovery.next_attempt(start + Duration::from_secs(10)), 2);
+        assert_eq!(recovery.next_attempt(start + Duration::from_secs(10) + STABLE_UPTIME), 0);
+    }
+
+    #[test]
+    fn pidfile_contents_and_cmdline_are_checked() {
+        assert_eq!(parse_pid("4242\n"), Some(4242));
+        assert_eq!(parse_pid("0"), None);
+        assert_eq!(parse_pid("not a pid"), None);
+
+        let root = Path::new("/opt/synthia/run.sh");
+        assert!(is_backend_cmdline("/bin/bash /opt/synthia/run.sh", root));
+        assert!(is_backend_cmdline("./venv/bin/python -m synthia.main", root));
+        assert!(is_backend_cmdline("/usr/lib/synthia/synthia-backend", root));
+        assert!(!is_backend_cmdline("cat /run/user/1000/synthia-backend.pid", root));
+        assert!(!is_backend_cmdline("/usr/bin/firefox", root));
+    }
+
+    #[test]
+    fn history_keeps_only_the_newest_entries() {
+        let mut history = VecDeque::new();
+        for i in 0..EXIT_HISTORY_LEN + 5 {
+            let exit = ExitInfo {
+                description: format!("exit code {i}"),
+                code: Some(i as i32),
+                signal: None,
+            };
+            push_bounded(&mut history, BackendExit::now(Some(exit), false));
+        }
+        assert_eq!(history.len(), EXIT_HISTORY_LEN);
+        assert_eq!(history.front().unwrap().exit.as_ref().unwrap().code, Some(5));
+    }
+
+    #[test]
+    fn exit_round_trips_through_json() {
+        let exit = BackendExit::now(
+            Some(ExitInfo { description: "signal 9".into(), code: None, signal: Some(9) }),
+            true,
+        );
+        let json = serde_json::to_string(&exit).unwrap();
+        assert_eq!(serde_json::from_str::<BackendExit>(&json).unwrap(), exit);
+    }
+}
@@ -0,0 +1,546 @@
+//! The backend's `synthia-state.json` runtime file.
+//!
+//! The Python backend writes `{status, recording}` on every transition; the
+//! GUI watches the file to drive the tray icon and overlay. Commands that
+//! change assistant state from the GUI side write through the same file, so
+//! the tray and overlay only ever react to one source of truth.
+//!
+//! Every writer must replace the file atomically — write a temp file in the
+//! same directory, then rename it over `synthia-state.json` — so a reader
+//! never sees a half-written document. The GUI does this through
+//! `paths::write_atomic`; the backend does the same with `os.replace`. Reads
+//! still retry briefly on a parse failure, to cover older backends that
+//! truncate and rewrite in place.
+//!
+//! Reads hold a shared and writes an exclusive lock on `synthia-state.lock`
+//! (see `file_lock`), which the backend takes too, so a read-modify-write
+//! from one side can't drop a change made by the other in between.
+//!
+//! The file carries a `schema_version` (`version` is accepted too). Files
+//! without one (written by backends that predate versioning) are version 0
+//! and are migrated in memory on read. New fields must be `#[serde(default)]`
+//! and come with a bump of `SCHEMA_VERSION` plus a step in `migrate`.
+//!
+//! A newer backend may add keys, which land in `extra` and are written back
+//! untouched, or change the shape of ones this GUI knows. Fields from v9 on
+//! are read with `lenient`, so one such change costs only that field, not
+//! the whole state.
+//!
+//! From v9 the file may hold the last transcription, so both sides write it
+//! readable by its owner only.
+
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::file_lock::{lock_path, FileLock};
+use crate::get_state_file;
+use crate::paths::write_atomic_private;
+
+/// Current state file schema. v1 added `schema_version` itself and the
+/// GUI-written `detail`, `paused` and `audio_input` fields; v2 added `mode`;
+/// v3 added `recording_started_at`; v4 added `muted`; v5 added
+/// `last_remote_activity`; v6 added `wake_detections`; v7 added `ready`;
+/// v8 added `heartbeat_at`; v9 added `model`, `language`,
+/// `last_transcription` and `error`.
+pub const SCHEMA_VERSION: u32 = 9;
+
+/// Reads of an unparseable state file before giving up. A write in progress
+/// finishes well within the retry window.
+const READ_ATTEMPTS: u32 = 3;
+const READ_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// How much of an unparseable state file to include in the log line.
+const LOG_CONTENT_LIMIT: usize = 200;
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct SynthiaState {
+    #[serde(default)]
+    pub schema_version: u32,
+    pub status: String,
+    pub recording: bool,
+    /// Set by the backend once models are loaded and hotkeys are live.
+    /// Cleared by the GUI when it launches or stops a backend, so a stale
+    /// file never claims a fresh process is usable.
+    #[serde(default)]
+    pub ready: bool,
+    /// When the current recording began; `None` while not recording. Set by
+    /// the backend when it starts recording.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recording_started_at: Option<DateTime<Utc>>,
+    /// Free-form context for the current status (e.g. an error message).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Set by the GUI to silence the assistant without stopping the process.
+    /// The backend keeps its models loaded but ignores audio while this is
+    /// true, and must carry the flag through its own writes.
+    #[serde(default)]
+    pub paused: bool,
+    /// Privacy mute set from the GUI: the backend drops all audio and no
+    /// recording may start until it is cleared. Unlike `paused`, it also
+    /// cuts off a recording in progress.
+    #[serde(default)]
+    pub muted: bool,
+    /// Input device chosen in the GUI (a cpal device name). Mirrors
+    /// `audio_device` in `config.yaml` so a running backend can switch
+    /// without a restart.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_input: Option<String>,
+    /// How recording is triggered. In `push_to_talk` the GUI forwards the
+    /// shortcut's press and release to the backend as the dictation hotkey.
+    #[serde(default)]
+    pub mode: Mode,
+    /// Last time the Telegram bot handled a message. Written by the bot;
+    /// remote mode is stopped once this is older than
+    /// `remote.idle_timeout_mins`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_remote_activity: Option<DateTime<Utc>>,
+    /// Wake word hit counts since the backend started, when it reports them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wake_detections: Option<WakeDetections>,
+    /// Rewritten by the backend every few seconds while it runs, so a file
+    /// left behind by a dead or hung backend can be told from a live one;
+    /// see `backend_health`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat_at: Option<DateTime<Utc>>,
+    /// Speech-to-text model the backend has loaded: a Whisper size such as
+    /// `small`, or `google` for Google STT.
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "lenient")]
+    pub model: Option<String>,
+    /// Language the backend transcribes, as configured (e.g. `en-US`).
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "lenient")]
+    pub language: Option<String>,
+    /// Text of the latest transcription since the backend started.
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "lenient")]
+    pub last_transcription: Option<String>,
+    /// What went wrong, while `status` is `error`.
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "lenient")]
+    pub error: Option<String>,
+    /// Keys written by the backend that the GUI doesn't model; preserved on write.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A field as `T`, or `None` if a newer backend gave it a shape this GUI
+/// doesn't know.
+fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).ok())
+}
+
+/// Wake word outcomes counted by the backend, so a sensitivity change can be
+/// judged by whether false triggers drop without missing real ones.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub struct WakeDetections {
+    /// Wake word heard and followed by a command.
+    #[serde(default)]
+    pub accepted: u32,
+    /// Wake word "heard" but nothing followed, or the user cancelled.
+    #[serde(default)]
+    pub false_triggers: u32,
+}
+
+impl SynthiaState {
+    /// A backend has been launched but hasn't reported ready yet.
+    pub fn is_starting(&self) -> bool {
+        !self.ready && self.status == OverlayState::Starting.as_status()
+    }
+
+    /// Whether `self` differs from `previous` only by a newer heartbeat,
+    /// which is nothing the tray or overlay need to hear about.
+    pub fn is_heartbeat_of(&self, previous: &SynthiaState) -> bool {
+        self.heartbeat_at != previous.heartbeat_at
+            && SynthiaState { heartbeat_at: previous.heartbeat_at, ..self.clone() } == *previous
+    }
+
+    pub fn overlay_state(&self) -> OverlayState {
+        if self.recording {
+            return OverlayState::Listening;
+        }
+        self.status.parse().unwrap_or(OverlayState::Idle)
+    }
+}
+
+/// Recording trigger mode.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+    /// The backend's own hotkeys toggle recording.
+    #[default]
+    Voice,
+    /// Record only while the GUI's push-to-talk shortcut is held.
+    PushToTalk,
+}
+
+impl Mode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Mode::Voice => "voice",
+            Mode::PushToTalk => "push_to_talk",
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "voice" | "toggle" => Ok(Mode::Voice),
+            "push_to_talk" | "push-to-talk" | "ptt" => Ok(Mode::PushToTalk),
+            other => Err(AppError::Validation(format!("unknown mode: {other}"))),
+        }
+    }
+}
+
+/// Visual states the overlay knows how to render.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OverlayState {
+    Starting,
+    Idle,
+    Listening,
+    Thinking,
+    Speaking,
+    Error,
+}
+
+impl OverlayState {
+    /// The `status` string the backend uses for this state.
+    pub fn as_status(self) -> &'static str {
+        match self {
+            OverlayState::Starting => "starting",
+            OverlayState::Idle => "ready",
+            OverlayState::Listening => "recording",
+            OverlayState::Thinking => "thinking",
+            OverlayState::Speaking => "speaking",
+            OverlayState::Error => "error",
+        }
+    }
+}
+
+impl FromStr for OverlayState {
+    type Err = AppError;
+
+    /// Accepts both the overlay names and the backend's status aliases.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "starting" | "loading" => Ok(OverlayState::Starting),
+            "idle" | "ready" | "" => Ok(OverlayState::Idle),
+            "listening" | "recording" => Ok(OverlayState::Listening),
+            "thinking" | "processing" => Ok(OverlayState::Thinking),
+            "speaking" => Ok(OverlayState::Speaking),
+            "error" => Ok(OverlayState::Error),
+            other => Err(AppError::Validation(format!("unknown overlay state: {other}"))),
+        }
+    }
+}
+
+/// Payload of the `overlay-state` event.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct OverlayPayload {
+    pub state: OverlayState,
+    pub detail: Option<String>,
+}
+
+impl From<&SynthiaState> for OverlayPayload {
+    /// An error state without its own `detail` shows the backend's `error`.
+    fn from(s: &SynthiaState) -> Self {
+        let state = s.overlay_state();
+        let error = s.error.clone().filter(|_| state == OverlayState::Error);
+        OverlayPayload {
+            state,
+            detail: s.detail.clone().or(error),
+        }
+    }
+}
+
+/// Bring a raw state document up to `SCHEMA_VERSION`, one step at a time.
+fn migrate(mut value: serde_json::Value) -> serde_json::Value {
+    let Some(obj) = value.as_object_mut() else {
+        return value;
+    };
+    let version = obj
+        .get("schema_version")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+
+    if version < 1 {
+        // Early backends wrote only `status`; derive the flag from it.
+        if !obj.contains_key("recording") {
+            let recording = obj.get("status").and_then(|s| s.as_str()) == Some("recording");
+            obj.insert("recording".into(), recording.into());
+        }
+        obj.insert("schema_version".into(), 1.into());
+    }
+    if version < 2 {
+        // `mode` defaults to voice, which is what every v1 backend did.
+        obj.insert("schema_version".into(), 2.into());
+    }
+    if version < 3 {
+        // No start time on record; the watcher falls back to first sight.
+        obj.insert("schema_version".into(), 3.into());
+    }
+    if version < 4 {
+        // `muted` defaults to false; nothing could have muted a v3 backend.
+        obj.insert("schema_version".into(), 4.into());
+    }
+    if version < 5 {
+        // No remote activity on record; the GUI times idleness from when it
+        // first saw the bot running.
+        obj.insert("schema_version".into(), 5.into());
+    }
+    if version < 6 {
+        // No wake word counts; the GUI shows none until the backend reports.
+        obj.insert("schema_version".into(), 6.into());
+    }
+    if version < 7 {
+        // Backends before v7 only wrote state once they were up, so a
+        // document from one describes a ready backend.
+        if !obj.contains_key("ready") {
+            obj.insert("ready".into(), true.into());
+        }
+        obj.insert("schema_version".into(), 7.into());
+    }
+    if version < 8 {
+        // No heartbeat; such a backend is never judged unresponsive.
+        obj.insert("schema_version".into(), 8.into());
+    }
+    if version < 9 {
+        // No model, language, transcription or error reported; the GUI shows
+        // them as unknown.
+        obj.insert("schema_version".into(), 9.into());
+    }
+    value
+}
+
+/// Parse and migrate state file content (or one IPC message).
+pub(crate) fn parse_synthia_state(content: &str) -> AppResult<SynthiaState> {
+    let mut value: serde_json::Value = serde_json::from_str(content)?;
+    // `version` is an accepted spelling; migration only looks for the other.
+    if let Some(obj) = value.as_object_mut() {
+        if let Some(version) = obj.remove("version") {
+            obj.entry("schema_version").or_insert(version);
+        }
+    }
+    let state: SynthiaState = serde_json::from_value(migrate(value))?;
+    if state.schema_version > SCHEMA_VERSION {
+        tracing::warn!(
+            found = state.schema_version,
+            supported = SCHEMA_VERSION,
+            "state file schema is newer than supported; reading known fields only"
+        );
+    }
+    Ok(state)
+}
+
+/// `content` cut to at most `limit` bytes on a char boundary, for logging.
+fn truncate_for_log(content: &str, limit: usize) -> &str {
+    if content.len() <= limit {
+        return content;
+    }
+    let mut end = limit;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    &content[..end]
+}
+
+/// Read the state file. A missing file is the normal "backend not started"
+/// case and yields the default. A file that can't be parsed is re-read a
+/// couple of times in case it was caught mid-write; if it still fails it is
+/// logged with its (truncated) content and the default is returned.
+pub fn read_synthia_state() -> SynthiaState {
+    let state_file = get_state_file();
+    // Better a read that might race a writer than none at all.
+    let _lock = FileLock::shared(&lock_path(&state_file))
+        .inspect_err(|e| tracing::debug!(error = %e, "reading state file unlocked"));
+    read_unlocked(&state_file)
+}
+
+fn read_unlocked(state_file: &Path) -> SynthiaState {
+    let mut attempt = 1;
+    loop {
+        let Ok(content) = fs::read_to_string(state_file) else {
+            return SynthiaState::default();
+        };
+        match parse_synthia_state(&content) {
+            Ok(state) => return state,
+            Err(e) if attempt >= READ_ATTEMPTS => {
+                tracing::error!(
+                    path = %state_file.display(),
+                    error = %e,
+                    attempts = attempt,
+                    content = truncate_for_log(&content, LOG_CONTENT_LIMIT),
+                    "failed to parse state file"
+                );
+                return SynthiaState::default();
+            }
+            Err(e) => {
+                tracing::debug!(error = %e, attempt, "state file unparseable; retrying");
+                attempt += 1;
+                thread::sleep(READ_RETRY_DELAY);
+            }
+        }
+    }
+}
+
+pub fn write_synthia_state(state: &SynthiaState) -> AppResult<()> {
+    let state_file = get_state_file();
+    let _lock = FileLock::exclusive(&lock_path(&state_file))?;
+    write_unlocked(&state_file, state)
+}
+
+fn write_unlocked(state_file: &Path, state: &SynthiaState) -> AppResult<()> {
+    let mut state = state.clone();
+    state.schema_version = state.schema_version.max(SCHEMA_VERSION);
+    let content = serde_json::to_string(&state)?;
+    write_atomic_private(state_file, content)
+}
+
+/// Read-modify-write the state file, holding the lock throughout.
+pub fn update_synthia_state(f: impl FnOnce(&mut SynthiaState)) -> AppResult<SynthiaState> {
+    let state_file = get_state_file();
+    let _lock = FileLock::exclusive(&lock_path(&state_file))?;
+    let mut state = read_unlocked(&state_file);
+    f(&mut state);
+    write_unlocked(&state_file, &state)?;
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlay_state_parses_aliases() {
+        assert_eq!("ready".parse::<OverlayState>().unwrap(), OverlayState::Idle);
+        assert_eq!("recording".parse::<OverlayState>().unwrap(), OverlayState::Listening);
+        assert_eq!("processing".parse::<OverlayState>().unwrap(), OverlayState::Thinking);
+        assert!("dancing".parse::<OverlayState>().is_err());
+    }
+
+    #[test]
+    fn mode_round_trips_as_snake_case() {
+        assert_eq!("ptt".parse::<Mode>().unwrap(), Mode::PushToTalk);
+        assert_eq!(serde_json::to_value(Mode::PushToTalk).unwrap(), "push_to_talk");
+        let state = parse_synthia_state(r#"{"status": "ready", "recording": false}"#).unwrap();
+        assert_eq!(state.mode, Mode::Voice);
+    }
+
+    #[test]
+    fn recording_flag_wins_over_status() {
+        let state: SynthiaState =
+            serde_json::from_str(r#"{"status": "thinking", "recording": true}"#).unwrap();
+        assert_eq!(state.overlay_state(), OverlayState::Listening);
+    }
+
+    #[test]
+    fn unversioned_file_is_migrated() {
+        let state = parse_synthia_state(r#"{"status": "recording"}"#).unwrap();
+        assert_eq!(state.schema_version, SCHEMA_VERSION);
+        assert!(state.recording);
+        assert!(!state.paused);
+
+        let state = parse_synthia_state(r#"{"status": "ready", "recording": false}"#).unwrap();
+        assert_eq!(state.schema_version, SCHEMA_VERSION);
+        assert!(!state.recording);
+    }
+
+    #[test]
+    fn pre_v7_state_counts_as_ready() {
+        let state = parse_synthia_state(r#"{"status": "ready", "recording": false}"#).unwrap();
+        assert!(state.ready);
+
+        let state =
+            parse_synthia_state(r#"{"status": "starting", "recording": false, "ready": false}"#)
+                .unwrap();
+        assert!(!state.ready);
+        assert!(state.is_starting());
+        assert_eq!(state.overlay_state(), OverlayState::Starting);
+    }
+
+    #[test]
+    fn recording_started_at_parses_backend_timestamp() {
+        let state = parse_synthia_state(
+            r#"{"status": "recording", "recording": true, "recording_started_at": "2026-10-16T09:30:00.250000+00:00"}"#,
+        )
+        .unwrap();
+        let started = state.recording_started_at.unwrap();
+        assert_eq!(started.to_rfc3339(), "2026-10-16T09:30:00.250+00:00");
+
+        let state = parse_synthia_state(r#"{"status": "ready", "recording": false}"#).unwrap();
+        assert!(state.recording_started_at.is_none());
+    }
+
+    #[test]
+    fn heartbeat_alone_is_not_a_change() {
+        let before = parse_synthia_state(
+            r#"{"status": "ready", "recording": false, "heartbeat_at": "2026-10-16T09:30:00Z"}"#,
+        )
+        .unwrap();
+        let mut after = before.clone();
+        after.heartbeat_at = Some("2026-10-16T09:30:05Z".parse().unwrap());
+        assert!(after.is_heartbeat_of(&before));
+        assert!(!before.is_heartbeat_of(&before));
+        after.recording = true;
+        assert!(!after.is_heartbeat_of(&before));
+    }
+
+    #[test]
+    fn malformed_file_is_an_error() {
+        assert!(parse_synthia_state("").is_err());
+        assert!(parse_synthia_state(r#"{"status": 3}"#).is_err());
+        assert!(parse_synthia_state("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn truncate_for_log_respects_char_boundaries() {
+        assert_eq!(truncate_for_log("short", 10), "short");
+        assert_eq!(truncate_for_log("héllo", 2), "h");
+    }
+
+    #[test]
+    fn v9_fields_are_read_and_tolerate_new_shapes() {
+        let state = parse_synthia_state(
+            r#"{"version": 9, "status": "error", "recording": false, "model": "small",
+                "language": "en-US", "error": "microphone unplugged"}"#,
+        )
+        .unwrap();
+        assert_eq!(state.schema_version, 9);
+        assert_eq!(state.model.as_deref(), Some("small"));
+        let payload = OverlayPayload::from(&state);
+        assert_eq!(payload.detail.as_deref(), Some("microphone unplugged"));
+
+        let state = parse_synthia_state(
+            r#"{"schema_version": 12, "status": "ready", "recording": false,
+                "model": {"name": "small"}, "error": 5, "language": "de"}"#,
+        )
+        .unwrap();
+        assert_eq!(state.model, None);
+        assert_eq!(state.error, None);
+        assert_eq!(state.language.as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn unknown_keys_survive_round_trip() {
+        let state: SynthiaState = serde_json::from_str(
+            r#"{"status": "ready", "recording": false, "future_field": "tiny"}"#,
+        )
+        .unwrap();
+        assert_eq!(state.extra["future_field"], "tiny");
+        let json = serde_json::to_value(&state).unwrap();
+        assert_eq!(json["future_field"], "tiny");
+        assert!(json.get("detail").is_none());
+    }
+}
@@ -0,0 +1,205 @@
+//! Typing text into the focused window, for `type_text`.
+//!
+//! Each platform has its own input-synthesis tool and none is always
+//! installed, so the backend is picked per call: the candidates for the
+//! session (see `candidates`) are tried in order, skipping any not on
+//! `PATH`, and the first that succeeds wins.
+//!
+//! On Wayland, `wtype` uses the virtual-keyboard protocol, which wlroots
+//! compositors (Sway, Hyprland) implement and GNOME and KDE do not;
+//! `ydotool` goes through uinput and works anywhere its daemon runs;
+//! `xdotool` last, for XWayland windows. X11 has only `xdotool`. macOS
+//! uses System Events through `osascript` (the app needs the
+//! Accessibility permission) and Windows `SendKeys` through PowerShell.
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+use serde::Serialize;
+
+use crate::error::{AppError, AppResult};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Backend {
+    Wtype,
+    Ydotool,
+    Xdotool,
+    Osascript,
+    SendKeys,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Session {
+    Wayland,
+    X11,
+    MacOs,
+    Windows,
+}
+
+impl Backend {
+    fn program(self) -> &'static str {
+        match self {
+            Backend::Wtype => "wtype",
+            Backend::Ydotool => "ydotool",
+            Backend::Xdotool => "xdotool",
+            Backend::Osascript => "osascript",
+            Backend::SendKeys => "powershell",
+        }
+    }
+
+    fn args(self, text: &str) -> Vec<String> {
+        match self {
+            Backend::Wtype => vec!["--".into(), text.into()],
+            Backend::Ydotool => vec!["type".into(), "--".into(), text.into()],
+            Backend::Xdotool => {
+                vec!["type".into(), "--clearmodifiers".into(), "--".into(), text.into()]
+            }
+            Backend::Osascript => {
+                let script = format!(
+                    "tell application \"System Events\" to keystroke \"{}\"",
+                    applescript_escape(text)
+                );
+                vec!["-e".into(), script]
+            }
+            Backend::SendKeys => vec![
+                "-NoProfile".into(),
+                "-NonInteractive".into(),
+                "-Command".into(),
+                format!(
+                    "Add-Type -AssemblyName System.Windows.Forms; \
+                     [System.Windows.Forms.SendKeys]::SendWait('{}')",
+                    sendkeys_escape(text).replace('\'', "''")
+                ),
+            ],
+        }
+    }
+}
+
+fn current_session() -> Session {
+    if cfg!(target_os = "macos") {
+        Session::MacOs
+    } else if cfg!(windows) {
+        Session::Windows
+    } else if crate::is_wayland_env()
+        || env::var("XDG_SESSION_TYPE").is_ok_and(|t| t.eq_ignore_ascii_case("wayland"))
+    {
+        Session::Wayland
+    } else {
+        Session::X11
+    }
+}
+
+/// Backends to try for `session`, best first.
+fn candidates(session: Session) -> &'static [Backend] {
+    match session {
+        Session::Wayland => &[Backend::Wtype, Backend::Ydotool, Backend::Xdotool],
+        Session::X11 => &[Backend::Xdotool],
+        Session::MacOs => &[Backend::Osascript],
+        Session::Windows => &[Backend::SendKeys],
+    }
+}
+
+fn on_path(program: &str) -> bool {
+    let Some(path) = env::var_os("PATH") else {
+        return false;
+    };
+    env::split_paths(&path).any(|dir| {
+        let candidate = dir.join(program);
+        is_file(&candidate) || (cfg!(windows) && is_file(&candidate.with_extension("exe")))
+    })
+}
+
+fn is_file(path: &Path) -> bool {
+    path.metadata().is_ok_and(|m| m.is_file())
+}
+
+/// Backends available in this session, best first.
+pub fn available() -> Vec<Backend> {
+    candidates(current_session()).iter().copied().filter(|b| on_path(b.program())).collect()
+}
+
+/// Type `text` into the focused window and return the backend that did it.
+pub fn type_text(text: &str) -> AppResult<Backend> {
+    let backends = available();
+    if backends.is_empty() {
+        let wanted: Vec<_> = candidates(current_session()).iter().map(|b| b.program()).collect();
+        return Err(AppError::NotFound(format!(
+            "no text injection tool found; install one of: {}",
+            wanted.join(", ")
+        )));
+    }
+
+    let mut last_error = String::new();
+    for backend in backends {
+        match Command::new(backend.program()).args(backend.args(text)).output() {
+            Ok(out) if out.status.success() => {
+                tracing::debug!(?backend, chars = text.chars().count(), "typed text");
+                return Ok(backend);
+            }
+            Ok(out) => {
+                last_error = format!(
+                    "{} exited with {}: {}",
+                    backend.program(),
+                    out.status,
+                    String::from_utf8_lossy(&out.stderr).trim()
+                );
+            }
+            Err(e) => last_error = format!("Failed to spawn {}: {}", backend.program(), e),
+        }
+        tracing::debug!(?backend, error = %last_error, "text injection backend failed");
+    }
+    Err(AppError::Process(last_error))
+}
+
+/// `text` as the body of an AppleScript string literal.
+fn applescript_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// `text` with the characters `SendKeys` treats as commands braced, and
+/// line breaks sent as Enter.
+fn sendkeys_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.replace("\r\n", "\n").chars() {
+        match c {
+            '+' | '^' | '%' | '~' | '(' | ')' | '{' | '}' | '[' | ']' => {
+                out.push('{');
+                out.push(c);
+                out.push('}');
+            }
+            '\n' => out.push_str("{ENTER}"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wayland_prefers_wtype_then_falls_back_to_xwayland() {
+        assert_eq!(
+            candidates(Session::Wayland),
+            &[Backend::Wtype, Backend::Ydotool, Backend::Xdotool]
+        );
+        assert_eq!(candidates(Session::X11), &[Backend::Xdotool]);
+    }
+
+    #[test]
+    fn text_is_passed_after_end_of_options() {
+        assert_eq!(Backend::Wtype.args("-n hi"), ["--", "-n hi"]);
+        assert_eq!(Backend::Xdotool.args("x"), ["type", "--clearmodifiers", "--", "x"]);
+    }
+
+    #[test]
+    fn escapes_for_scripted_backends() {
+        assert_eq!(applescript_escape(r#"say "hi" \o/"#), r#"say \"hi\" \\o/"#);
+        assert_eq!(sendkeys_escape("1+1 (maybe)\r\nok"), "1{+}1 {(}maybe{)}{ENTER}ok");
+        let script = &Backend::SendKeys.args("it's")[3];
+        assert!(script.ends_with("SendWait('it''s')"));
+    }
+}
@@ -0,0 +1,90 @@
+//! Background threads that outlive their own panics.
+//!
+//! A panic on one of the GUI's monitor threads used to end that thread
+//! quietly, leaving whatever it drove (tray icon, overlay placement, crash
+//! detection) frozen until the app was restarted. `guarded` contains a
+//! panic to one unit of work and logs it; `spawn_supervised` reruns a
+//! thread's body after it panics or returns, up to `MAX_RESTARTS` times.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Restarts allowed per supervised thread before it is left dead.
+const MAX_RESTARTS: u32 = 5;
+
+const RESTART_DELAY: Duration = if cfg!(test) {
+    Duration::from_millis(1)
+} else {
+    Duration::from_secs(1)
+};
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("non-string panic payload")
+}
+
+/// Run `f`, turning a panic into a logged error and `None`.
+pub(crate) fn guarded<T>(what: &str, f: impl FnOnce() -> T) -> Option<T> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => Some(value),
+        Err(payload) => {
+            tracing::error!(what, panic = panic_message(payload.as_ref()), "recovered from panic");
+            None
+        }
+    }
+}
+
+/// Spawn `body` on a named thread and run it again whenever it panics or
+/// returns, so a loop that should run for the app's lifetime keeps running.
+pub(crate) fn spawn_supervised(
+    name: &'static str,
+    body: impl Fn() + Send + 'static,
+) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        for attempt in 0..=MAX_RESTARTS {
+            if attempt > 0 {
+                std::thread::sleep(RESTART_DELAY);
+                tracing::warn!(thread = name, attempt, "restarting background thread");
+            }
+            if guarded(name, &body).is_some() {
+                tracing::warn!(thread = name, "background thread returned unexpectedly");
+            }
+        }
+        tracing::error!(
+            thread = name,
+            restarts = MAX_RESTARTS,
+            "background thread keeps failing; giving up"
+        );
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn guarded_contains_panics() {
+        assert_eq!(guarded("ok", || 7), Some(7));
+        assert_eq!(guarded("boom", || -> u32 { panic!("corrupt png") }), None);
+    }
+
+    #[test]
+    fn supervised_thread_is_restarted_up_to_the_cap() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = runs.clone();
+        spawn_supervised("test", move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            panic!("always fails");
+        })
+        .join()
+        .unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), MAX_RESTARTS + 1);
+    }
+}
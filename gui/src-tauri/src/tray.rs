@@ -0,0 +1,1357 @@
+//! Tray icon loading and theme-aware icon selection.
+//!
+//! The embedded `tray-icon.png` / `tray-recording.png` pair is the fallback
+//! set. Themed variants (`tray-icon-{light,dark}.png` and
+//! `tray-recording-{light,dark}.png`) are resolved from the bundled resources
+//! first, then the dev checkout, and picked according to the main window's
+//! current light/dark theme. A theme whose variants are missing on disk falls
+//! back to the embedded pair, and if that fails to decode, to the app's
+//! default window icon. Every path tried is logged and kept in
+//! `TrayIcons::statuses` for `get_health`. The paused icon is the normal one
+//! drawn at reduced opacity, and the muted icon is the normal one faded with
+//! a red strike-through, so neither needs extra assets.
+//!
+//! On macOS the menu bar wants template images: black shapes whose alpha
+//! alone is drawn, tinted by the system for light and dark menu bars and
+//! for the highlighted state. `tray-{icon,recording}-template.png` are used
+//! there when present, else templates derived from the embedded pair, and
+//! the light/dark variants are skipped. Animation frames are converted the
+//! same way.
+//!
+//! While recording, the icon cycles through `tray-recording-anim-<n>.png`
+//! frames (resolved the same way) on a timer thread. Without frames on disk
+//! it pulses a red dot instead, and while the backend transcribes it shows
+//! a spinner; both are drawn by `tray_animation`. `tray.animate = false`
+//! keeps the static icons.
+//!
+//! While recording, the tooltip carries the elapsed time, kept current by
+//! `recording_timer`.
+//!
+//! Between launching the backend and it reporting `ready`, the icon is
+//! faded and the tooltip says it is starting. After a crash it says the
+//! supervisor is restarting the backend, or that it gave up.
+//!
+//! While remote mode's bot is live, every icon gets a small green
+//! badge in the bottom-right corner and the tooltip says so.
+//!
+//! The Quit menu item asks for confirmation first unless
+//! `tray.confirm_on_quit` is turned off in `gui.toml`.
+//!
+//! Menu labels, tooltips and the quit dialog come from `i18n`; `relocalize`
+//! rebuilds the menu when the locale changes.
+//!
+//! The menu opens with a greyed-out status line (recording, idle, stopped,
+//! ...) and a Start/Stop Synthia entry, followed by a Remote Mode toggle,
+//! the Mode submenu and the last transcription, which copies it when
+//! clicked. Its entries are updated in place whenever the state they show
+//! changes, so the open menu never lies about the backend.
+//!
+//! The Mode submenu switches between voice, push-to-talk and remote mode.
+//! Its checks, like the Remote Mode toggle's, follow the state file's
+//! `mode` and the remote bot's status, so they stay right however the mode
+//! was changed. Remote is greyed out while no bot token is configured.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::MutexGuard;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::{image::Image, AppHandle, Manager, Theme, Wry};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+use crate::commands;
+use crate::commands::history::get_last_transcript;
+use crate::gui_config;
+use crate::i18n::t;
+use crate::notifications::{self, preview};
+use crate::recording_timer::format_elapsed;
+use crate::remote_channel;
+use crate::state::AppState;
+use crate::supervisor::Recovery;
+use crate::threads;
+use crate::synthia_state::{read_synthia_state, Mode, OverlayState, SynthiaState};
+use crate::tray_animation::Animation;
+
+pub const TRAY_ID: &str = "main-tray";
+
+pub const MODE_VOICE_ID: &str = "mode_voice";
+pub const MODE_PUSH_TO_TALK_ID: &str = "mode_push_to_talk";
+pub const MODE_REMOTE_ID: &str = "mode_remote";
+pub const BACKEND_TOGGLE_ID: &str = "toggle_backend";
+pub const REMOTE_TOGGLE_ID: &str = "toggle_remote";
+pub const COPY_TRANSCRIPT_ID: &str = "copy_transcript";
+
+/// Characters of the last transcription shown in the menu.
+const TRANSCRIPT_PREVIEW_CHARS: usize = 40;
+
+/// Remote-mode badge colour (RGBA).
+const REMOTE_BADGE_RGBA: [u8; 4] = [0x22, 0xc5, 0x5e, 0xff];
+
+/// Alpha multiplier applied to the normal icon to draw the paused variant.
+const PAUSED_ALPHA: f32 = 0.4;
+
+/// Strike-through colour of the muted icon (RGBA).
+const MUTED_STRIKE_RGBA: [u8; 4] = [0xef, 0x44, 0x44, 0xff];
+
+/// Upper bound on animation frames probed on disk.
+const MAX_ANIMATION_FRAMES: usize = 16;
+
+/// Time each frame from disk stays up. Slow enough that the timer thread
+/// sleeps almost all of the time.
+const FRAME_INTERVAL: Duration = Duration::from_millis(250);
+
+// Embed icons directly in binary for better compatibility
+static TRAY_ICON_PNG: &[u8] = include_bytes!("../icons/tray-icon.png");
+static TRAY_RECORDING_PNG: &[u8] = include_bytes!("../icons/tray-recording.png");
+
+/// Why an icon couldn't be loaded. Missing files are routine (themed
+/// variants are optional); the rest mean a broken install.
+#[derive(Debug)]
+pub enum IconError {
+    Missing,
+    Unreadable(std::io::Error),
+    Decode(image::ImageError),
+    /// The decoder panicked on the file; contained by `threads::guarded`.
+    DecoderPanicked,
+}
+
+impl std::fmt::Display for IconError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IconError::Missing => write!(f, "file missing"),
+            IconError::Unreadable(e) => write!(f, "unreadable: {e}"),
+            IconError::Decode(e) => write!(f, "decode failed: {e}"),
+            IconError::DecoderPanicked => write!(f, "decode failed: decoder panicked"),
+        }
+    }
+}
+
+fn decode_icon(data: &[u8]) -> Result<Image<'static>, IconError> {
+    let img = threads::guarded("tray icon decode", || image::load_from_memory(data))
+        .ok_or(IconError::DecoderPanicked)?
+        .map_err(IconError::Decode)?
+        .to_rgba8();
+    let (width, height) = img.dimensions();
+    let rgba = img.into_raw();
+    Ok(Image::new_owned(rgba, width, height))
+}
+
+pub fn load_icon_from_path(path: &Path) -> Result<Image<'static>, IconError> {
+    let data = std::fs::read(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => IconError::Missing,
+        _ => IconError::Unreadable(e),
+    })?;
+    decode_icon(&data)
+}
+
+pub fn load_embedded_icon(data: &'static [u8]) -> Result<Image<'static>, IconError> {
+    decode_icon(data)
+}
+
+/// Copy of `icon` with every pixel's alpha scaled by `factor`.
+fn faded(icon: &Image<'_>, factor: f32) -> Image<'static> {
+    let mut rgba = icon.rgba().to_vec();
+    for px in rgba.chunks_exact_mut(4) {
+        px[3] = (px[3] as f32 * factor).round() as u8;
+    }
+    Image::new_owned(rgba, icon.width(), icon.height())
+}
+
+/// Copy of `icon` as a macOS template image: every pixel black, keeping
+/// its alpha.
+fn template(icon: &Image<'_>) -> Image<'static> {
+    let mut rgba = icon.rgba().to_vec();
+    for px in rgba.chunks_exact_mut(4) {
+        px[..3].fill(0);
+    }
+    Image::new_owned(rgba, icon.width(), icon.height())
+}
+
+/// Copy of `icon` with a filled circle in the bottom-right corner, a third
+/// of the icon's width across.
+fn with_badge(icon: &Image<'_>, rgba: [u8; 4]) -> Image<'static> {
+    let (w, h) = (icon.width() as i64, icon.height() as i64);
+    let mut out = icon.rgba().to_vec();
+    let r = (w.min(h) / 6).max(1);
+    let (cx, cy) = (w - r - 1, h - r - 1);
+    for y in (cy - r).max(0)..=(cy + r).min(h - 1) {
+        for x in (cx - r).max(0)..=(cx + r).min(w - 1) {
+            if (x - cx).pow(2) + (y - cy).pow(2) <= r * r {
+                let i = ((y * w + x) * 4) as usize;
+                out[i..i + 4].copy_from_slice(&rgba);
+            }
+        }
+    }
+    Image::new_owned(out, icon.width(), icon.height())
+}
+
+/// Copy of `icon` with a diagonal line from top-left to bottom-right, an
+/// eighth of the icon's width thick.
+fn struck_through(icon: &Image<'_>, rgba: [u8; 4]) -> Image<'static> {
+    let (w, h) = (icon.width() as i64, icon.height() as i64);
+    let mut out = icon.rgba().to_vec();
+    let half = (w.min(h) / 16).max(1);
+    for y in 0..h {
+        // Scale y onto the x axis so non-square icons still get a corner-to-corner line.
+        let cx = y * w / h.max(1);
+        for x in (cx - half).max(0)..=(cx + half).min(w - 1) {
+            let i = ((y * w + x) * 4) as usize;
+            out[i..i + 4].copy_from_slice(&rgba);
+        }
+    }
+    Image::new_owned(out, icon.width(), icon.height())
+}
+
+/// Where `name` may live, in the order tried: `<resource_dir>/icons/<name>`
+/// (bundled build), then `<dev_icons_dir>/<name>` (running from a checkout).
+fn icon_candidates(resource_dir: &Path, dev_icons_dir: &Path, name: &str) -> [PathBuf; 2] {
+    [resource_dir.join("icons").join(name), dev_icons_dir.join(name)]
+}
+
+/// How one tray icon was resolved; reported by `get_health`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct IconStatus {
+    pub name: String,
+    /// Paths tried, in order. Empty for icons embedded in the binary.
+    pub tried: Vec<PathBuf>,
+    /// Path the icon was loaded from, or `embedded`; `None` if it wasn't.
+    pub loaded_from: Option<String>,
+    /// Why the last existing candidate couldn't be decoded, if one did exist.
+    pub error: Option<String>,
+}
+
+/// Load `name` from the first candidate path that decodes, logging each
+/// attempt so a missing or corrupt icon shows up in the log.
+fn load_from_disk(
+    resource_dir: &Path,
+    dev_icons_dir: &Path,
+    name: &str,
+) -> (Option<Image<'static>>, IconStatus) {
+    let tried = icon_candidates(resource_dir, dev_icons_dir, name).to_vec();
+    let mut status = IconStatus {
+        name: name.to_string(),
+        tried: tried.clone(),
+        loaded_from: None,
+        error: None,
+    };
+    for path in &tried {
+        match load_icon_from_path(path) {
+            Ok(icon) => {
+                tracing::debug!(path = %path.display(), "loaded tray icon");
+                status.loaded_from = Some(path.display().to_string());
+                status.error = None;
+                return (Some(icon), status);
+            }
+            Err(IconError::Missing) => {
+                tracing::trace!(path = %path.display(), "tray icon candidate missing");
+            }
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "tray icon present but unusable");
+                status.error = Some(e.to_string());
+            }
+        }
+    }
+    tracing::debug!(icon = name, tried = ?tried, "tray icon not found");
+    (None, status)
+}
+
+fn load_embedded(name: &str, data: &'static [u8]) -> (Option<Image<'static>>, IconStatus) {
+    let mut status = IconStatus {
+        name: name.to_string(),
+        tried: Vec::new(),
+        loaded_from: None,
+        error: None,
+    };
+    match load_embedded_icon(data) {
+        Ok(icon) => {
+            status.loaded_from = Some("embedded".to_string());
+            (Some(icon), status)
+        }
+        Err(e) => {
+            tracing::error!(icon = name, error = %e, "embedded tray icon is corrupt");
+            status.error = Some(e.to_string());
+            (None, status)
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct IconPair {
+    pub normal: Option<Image<'static>>,
+    pub recording: Option<Image<'static>>,
+}
+
+impl IconPair {
+    fn get(&self, recording: bool) -> Option<&Image<'static>> {
+        if recording {
+            self.recording.as_ref()
+        } else {
+            self.normal.as_ref()
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct TrayIcons {
+    fallback: IconPair,
+    light: IconPair,
+    dark: IconPair,
+    /// Menu bar template images; only loaded on macOS, where they win over
+    /// every other pair.
+    template: IconPair,
+    /// Recording animation, in display order. Empty disables animation.
+    recording_frames: Vec<Image<'static>>,
+    /// Last resort when even the embedded pair failed to decode: the app's
+    /// default window icon.
+    default: Option<Image<'static>>,
+    /// How each icon above was resolved, in load order.
+    pub statuses: Vec<IconStatus>,
+}
+
+impl TrayIcons {
+    pub fn load(
+        resource_dir: &Path,
+        dev_icons_dir: &Path,
+        default: Option<Image<'static>>,
+    ) -> Self {
+        let mut statuses = Vec::new();
+        let mut record = |(icon, status): (Option<Image<'static>>, IconStatus)| {
+            statuses.push(status);
+            icon
+        };
+        let from_disk = |name: &str| load_from_disk(resource_dir, dev_icons_dir, name);
+
+        let fallback = IconPair {
+            normal: record(load_embedded("tray-icon.png", TRAY_ICON_PNG)),
+            recording: record(load_embedded("tray-recording.png", TRAY_RECORDING_PNG)),
+        };
+        let light = IconPair {
+            normal: record(from_disk("tray-icon-light.png")),
+            recording: record(from_disk("tray-recording-light.png")),
+        };
+        let dark = IconPair {
+            normal: record(from_disk("tray-icon-dark.png")),
+            recording: record(from_disk("tray-recording-dark.png")),
+        };
+        // Frames are probed until the first gap; that miss is recorded too,
+        // so an absent animation is visible in `get_health`.
+        let mut recording_frames = (0..MAX_ANIMATION_FRAMES)
+            .map_while(|i| record(from_disk(&format!("tray-recording-anim-{i}.png"))))
+            .collect::<Vec<_>>();
+        let mut template_pair = IconPair::default();
+        if cfg!(target_os = "macos") {
+            template_pair = IconPair {
+                normal: record(from_disk("tray-icon-template.png"))
+                    .or_else(|| fallback.normal.as_ref().map(|i| template(i))),
+                recording: record(from_disk("tray-recording-template.png"))
+                    .or_else(|| fallback.recording.as_ref().map(|i| template(i))),
+            };
+            recording_frames = recording_frames.iter().map(|i| template(i)).collect();
+        }
+
+        let missing: Vec<&str> = statuses
+            .iter()
+            .filter(|s| s.loaded_from.is_none())
+            .map(|s| s.name.as_str())
+            .collect();
+        tracing::info!(
+            frames = recording_frames.len(),
+            missing = ?missing,
+            "tray icons resolved"
+        );
+        TrayIcons {
+            fallback,
+            light,
+            dark,
+            template: template_pair,
+            recording_frames,
+            default,
+            statuses,
+        }
+    }
+
+    /// The template icon on macOS, else the themed icon for `theme` if that
+    /// variant was found, else the embedded one, else the app's default icon.
+    pub fn pick(&self, theme: Option<Theme>, recording: bool) -> Option<&Image<'static>> {
+        if let Some(icon) = self.template.get(recording) {
+            return Some(icon);
+        }
+        let themed = match theme {
+            Some(Theme::Light) => self.light.get(recording),
+            Some(Theme::Dark) => self.dark.get(recording),
+            _ => None,
+        };
+        themed
+            .or_else(|| self.fallback.get(recording))
+            .or(self.default.as_ref())
+    }
+}
+
+/// Everything the tray rendering depends on. Lives in `AppState::tray` so the
+/// state watcher and the theme-change handler share one source of truth.
+#[derive(Default)]
+pub struct TrayState {
+    pub icons: TrayIcons,
+    pub theme: Option<Theme>,
+    pub recording: bool,
+    /// The backend is transcribing or working out a reply.
+    pub thinking: bool,
+    pub paused: bool,
+    pub muted: bool,
+    /// Whether remote mode's bot is running.
+    pub remote: bool,
+    /// Seconds into the current recording, once the timer has ticked.
+    pub recording_elapsed: Option<u64>,
+    /// On battery with low-power mode enabled: show the static recording icon.
+    pub low_power: bool,
+    /// `tray.animate` is off: never animate.
+    pub static_icons: bool,
+    /// Backend launched but not ready yet: faded icon, "starting" tooltip.
+    pub starting: bool,
+    /// The backend crashed and the supervisor is restarting it, or gave up.
+    pub recovery: Option<Recovery>,
+    /// Ready backend whose heartbeat has stopped; see `backend_health`.
+    pub unresponsive: bool,
+    /// Trigger mode from the state file, for the Mode submenu.
+    pub mode: Mode,
+    /// Whether a backend is running, as far as the GUI last saw. Picks
+    /// between Start and Stop in the menu.
+    pub running: bool,
+    /// The menu's live entries, once the menu is built.
+    menu: Option<TrayMenu>,
+    /// True while the animation thread is alive; guards against spawning two.
+    animating: bool,
+}
+
+impl TrayState {
+    fn animation(&self) -> Option<Animation> {
+        if self.static_icons || self.paused || self.muted || self.low_power {
+            None
+        } else if self.recording {
+            Some(Animation::Recording)
+        } else if self.thinking {
+            Some(Animation::Transcribing)
+        } else {
+            None
+        }
+    }
+
+    /// Frame `n` of `animation` and how long to show it. Recording frames
+    /// from disk win over the drawn pulse.
+    fn animation_frame(&self, animation: Animation, n: usize) -> Option<(Image<'static>, Duration)> {
+        let frames = &self.icons.recording_frames;
+        if animation == Animation::Recording && !frames.is_empty() {
+            return Some((frames[n % frames.len()].clone(), FRAME_INTERVAL));
+        }
+        let base = self.icons.pick(self.theme, false)?;
+        let mut frame = animation.frame(base, n);
+        if cfg!(target_os = "macos") {
+            frame = template(&frame);
+        }
+        Some((frame, animation.interval()))
+    }
+
+    /// Apply overlays that hold regardless of which base icon is showing.
+    fn decorate(&self, icon: Image<'static>) -> Image<'static> {
+        if self.remote {
+            with_badge(&icon, REMOTE_BADGE_RGBA)
+        } else {
+            icon
+        }
+    }
+
+    fn status(&self) -> Status {
+        if let Some(recovery) = self.recovery {
+            match recovery {
+                Recovery::Restarting { attempt, max_restarts } => {
+                    Status::Restarting { attempt, max_restarts }
+                }
+                Recovery::GaveUp => Status::Crashed,
+            }
+        } else if self.unresponsive {
+            Status::Unresponsive
+        } else if self.starting {
+            Status::Starting
+        } else if self.muted {
+            Status::Muted
+        } else if self.paused {
+            Status::Paused
+        } else if self.recording {
+            Status::Recording
+        } else if self.thinking {
+            Status::Transcribing
+        } else if !self.running {
+            Status::Stopped
+        } else {
+            Status::Idle
+        }
+    }
+
+    /// `status` worded with `prefix` (`tooltip` or `tray.status`), plus the
+    /// restart count or recording time where they apply.
+    fn describe(&self, prefix: &str) -> String {
+        let status = self.status();
+        let text = t(&format!("{prefix}.{}", status.key()));
+        match status {
+            Status::Restarting { attempt, max_restarts } => {
+                format!("{text} ({attempt}/{max_restarts})")
+            }
+            Status::Recording => match self.recording_elapsed {
+                Some(secs) => format!("{text} {}", format_elapsed(secs)),
+                None => format!("{text}..."),
+            },
+            _ => text,
+        }
+    }
+
+    fn tooltip(&self) -> String {
+        let suffix = if self.remote { t("tooltip.remote_suffix") } else { String::new() };
+        format!("{}{suffix}", self.describe("tooltip"))
+    }
+
+    /// The menu's greyed-out first line.
+    fn status_line(&self) -> String {
+        self.describe("tray.status")
+    }
+}
+
+/// What the tray reports, most pressing first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Restarting { attempt: u32, max_restarts: u32 },
+    Crashed,
+    Unresponsive,
+    Starting,
+    Muted,
+    Paused,
+    Recording,
+    Transcribing,
+    Stopped,
+    Idle,
+}
+
+impl Status {
+    /// Last segment of the `tooltip.*` and `tray.status.*` locale keys.
+    fn key(self) -> &'static str {
+        match self {
+            Status::Restarting { .. } => "restarting",
+            Status::Crashed => "crashed",
+            Status::Unresponsive => "unresponsive",
+            Status::Starting => "starting",
+            Status::Muted => "muted",
+            Status::Paused => "paused",
+            Status::Recording => "recording",
+            Status::Transcribing => "transcribing",
+            Status::Stopped => "stopped",
+            Status::Idle => "idle",
+        }
+    }
+}
+
+/// Lock the tray state, recovering it if a panic poisoned the mutex: the
+/// state is plain flags and icons, always safe to keep using, and giving up
+/// on it would freeze the tray for the rest of the session.
+fn lock_tray(state: &AppState) -> MutexGuard<'_, TrayState> {
+    state.tray.lock().unwrap_or_else(|poisoned| {
+        tracing::warn!("tray state lock was poisoned by a panic; recovering");
+        state.tray.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
+/// The Mode submenu's radio-style entries.
+#[derive(Clone)]
+struct ModeItems {
+    voice: CheckMenuItem<Wry>,
+    push_to_talk: CheckMenuItem<Wry>,
+    remote: CheckMenuItem<Wry>,
+}
+
+/// Id of the Mode entry to check. Remote mode replaces the local trigger
+/// modes while its bot runs.
+fn active_mode_id(mode: Mode, remote: bool) -> &'static str {
+    match (remote, mode) {
+        (true, _) => MODE_REMOTE_ID,
+        (false, Mode::Voice) => MODE_VOICE_ID,
+        (false, Mode::PushToTalk) => MODE_PUSH_TO_TALK_ID,
+    }
+}
+
+impl ModeItems {
+    fn sync(&self, mode: Mode, remote: bool, remote_available: bool) {
+        let active = active_mode_id(mode, remote);
+        for (item, id) in [
+            (&self.voice, MODE_VOICE_ID),
+            (&self.push_to_talk, MODE_PUSH_TO_TALK_ID),
+            (&self.remote, MODE_REMOTE_ID),
+        ] {
+            let _ = item.set_checked(id == active);
+        }
+        // A running bot stays selectable so it can be switched away from.
+        let _ = self.remote.set_enabled(remote || remote_available);
+    }
+}
+
+fn build_mode_menu<M: Manager<Wry>>(manager: &M) -> tauri::Result<(Submenu<Wry>, ModeItems)> {
+    let item = |id: &str, key: &str| CheckMenuItem::with_id(manager, id, t(key), true, false, None::<&str>);
+    let items = ModeItems {
+        voice: item(MODE_VOICE_ID, "tray.mode_voice")?,
+        push_to_talk: item(MODE_PUSH_TO_TALK_ID, "tray.mode_push_to_talk")?,
+        remote: item(MODE_REMOTE_ID, "tray.mode_remote")?,
+    };
+    let submenu = Submenu::with_items(
+        manager,
+        t("tray.mode"),
+        true,
+        &[&items.voice, &items.push_to_talk, &items.remote],
+    )?;
+    Ok((submenu, items))
+}
+
+/// The menu entries whose label, check or availability follows state.
+/// Clones share the underlying items, so a copy taken under the tray lock
+/// can be updated after the lock is released.
+#[derive(Clone)]
+struct TrayMenu {
+    status: MenuItem<Wry>,
+    backend: MenuItem<Wry>,
+    remote: CheckMenuItem<Wry>,
+    mode: ModeItems,
+    last_transcript: MenuItem<Wry>,
+}
+
+fn remote_toggle_label(available: bool) -> String {
+    t(if available { "tray.remote" } else { "tray.remote_no_token" })
+}
+
+fn backend_toggle_label(running: bool) -> String {
+    t(if running { "tray.stop" } else { "tray.start" })
+}
+
+/// Label of the copy entry: the transcript's start, or just the action
+/// while there is nothing to copy.
+fn transcript_label(text: Option<&str>) -> String {
+    let label = t("tray.copy_transcript");
+    match text {
+        Some(text) => format!("{label}: {}", preview(text, TRANSCRIPT_PREVIEW_CHARS)),
+        None => label,
+    }
+}
+
+impl TrayMenu {
+    /// Status line and Start/Stop, which change with every backend state.
+    fn sync_status(&self, status_line: String, running: bool) {
+        let _ = self.status.set_text(status_line);
+        let _ = self.backend.set_text(backend_toggle_label(running));
+    }
+
+    /// Remote Mode toggle and Mode submenu. Without a bot token the toggle
+    /// says so, since greyed out alone doesn't tell what is missing.
+    fn sync_mode(&self, mode: Mode, remote: bool, remote_available: bool) {
+        self.mode.sync(mode, remote, remote_available);
+        let _ = self.remote.set_checked(remote);
+        let _ = self.remote.set_enabled(remote || remote_available);
+        let _ = self.remote.set_text(remote_toggle_label(remote || remote_available));
+    }
+
+    /// Grey out both remote entries while the bot starts or stops.
+    fn remote_busy(&self) {
+        let _ = self.remote.set_enabled(false);
+        let _ = self.mode.remote.set_enabled(false);
+    }
+
+    fn sync_transcript(&self, text: Option<&str>) {
+        let _ = self.last_transcript.set_text(transcript_label(text));
+        let _ = self.last_transcript.set_enabled(text.is_some());
+    }
+}
+
+/// Bring the Mode submenu's checks, the Remote Mode toggle and the remote
+/// entries' availability up to date.
+pub fn sync_mode_items(app: &AppHandle) {
+    let remote_available = remote_channel::active().token().is_ok();
+    let (menu, mode, remote) = {
+        let state = app.state::<AppState>();
+        let tray_state = lock_tray(&state);
+        (tray_state.menu.clone(), tray_state.mode, tray_state.remote)
+    };
+    if let Some(menu) = menu {
+        menu.sync_mode(mode, remote, remote_available);
+    }
+}
+
+/// Show a new transcription in the menu.
+#[cfg_attr(not(unix), allow(dead_code))]
+pub fn set_last_transcript(app: &AppHandle, text: &str) {
+    let text = text.trim();
+    let menu = lock_tray(&app.state::<AppState>()).menu.clone();
+    if let Some(menu) = menu {
+        menu.sync_transcript((!text.is_empty()).then_some(text));
+    }
+}
+
+/// Handle a click on a Mode entry. Choosing voice or push-to-talk leaves
+/// remote mode; choosing remote starts it. Runs off the UI thread since
+/// starting or stopping the bot blocks.
+pub fn on_mode_selected(app: &AppHandle, id: &str) {
+    let app = app.clone();
+    let id = id.to_string();
+    std::thread::spawn(move || {
+        let remote = lock_tray(&app.state::<AppState>()).remote;
+        let result = match id.as_str() {
+            MODE_REMOTE_ID if !remote => commands::remote::set_enabled(&app, true).map(drop),
+            MODE_REMOTE_ID => Ok(()),
+            _ => {
+                let mode = if id == MODE_PUSH_TO_TALK_ID { Mode::PushToTalk } else { Mode::Voice };
+                let stopped = if remote {
+                    commands::remote::set_enabled(&app, false).map(drop)
+                } else {
+                    Ok(())
+                };
+                stopped.and_then(|()| commands::lifecycle::apply_mode(&app, mode))
+            }
+        };
+        if let Err(e) = result {
+            tracing::warn!(error = %e, mode = id, "switching mode from the tray failed");
+            notifications::notify_error(&t("notify.error_title"), &e.to_string());
+        }
+        // Clicking toggles the item's own check; put every check back to
+        // what actually happened.
+        sync_mode_items(&app);
+    });
+}
+
+/// Handle a click on Remote Mode: start the bot if it is off, else stop it,
+/// the same as `start_remote_mode` / `stop_remote_mode`.
+pub fn on_remote_toggled(app: &AppHandle) {
+    let remote = {
+        let state = app.state::<AppState>();
+        let tray_state = lock_tray(&state);
+        if let Some(menu) = &tray_state.menu {
+            menu.remote_busy();
+        }
+        tray_state.remote
+    };
+    let app = app.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = commands::remote::set_enabled(&app, !remote) {
+            tracing::warn!(error = %e, enable = !remote, "toggling remote mode from the tray failed");
+            notifications::notify_error(&t("notify.error_title"), &e.to_string());
+        }
+        sync_mode_items(&app);
+    });
+}
+
+/// Handle a click on Start/Stop Synthia. Runs off the UI thread: starting
+/// waits out the backend's grace period and stopping its shutdown.
+pub fn on_backend_toggled(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let state = app.state::<AppState>();
+        let running = lock_tray(&state).running;
+        let result = if running {
+            commands::lifecycle::stop(&app)
+        } else {
+            commands::lifecycle::start(&state)
+        };
+        match result {
+            Ok(_) => set_running(&app, !running),
+            Err(e) => {
+                tracing::warn!(error = %e, start = !running, "starting or stopping from the tray failed");
+                notifications::notify_error(&t("notify.error_title"), &e.to_string());
+            }
+        }
+    });
+}
+
+pub fn build_menu<M: Manager<Wry>>(manager: &M) -> tauri::Result<Menu<Wry>> {
+    let (mode_menu, mode_items) = build_mode_menu(manager)?;
+    let menu = TrayMenu {
+        status: MenuItem::new(manager, t("tray.status.idle"), false, None::<&str>)?,
+        backend: MenuItem::with_id(manager, BACKEND_TOGGLE_ID, t("tray.start"), true, None::<&str>)?,
+        remote: CheckMenuItem::with_id(
+            manager,
+            REMOTE_TOGGLE_ID,
+            t("tray.remote"),
+            true,
+            false,
+            None::<&str>,
+        )?,
+        mode: mode_items,
+        last_transcript: MenuItem::with_id(
+            manager,
+            COPY_TRANSCRIPT_ID,
+            t("tray.copy_transcript"),
+            true,
+            None::<&str>,
+        )?,
+    };
+    let show = MenuItem::with_id(manager, "show", t("tray.show"), true, None::<&str>)?;
+    let open_logs =
+        MenuItem::with_id(manager, "open_logs", t("tray.open_logs"), true, None::<&str>)?;
+    let quit = MenuItem::with_id(manager, "quit", t("tray.quit"), true, None::<&str>)?;
+    let separator = || PredefinedMenuItem::separator(manager);
+    let tray_menu = Menu::with_items(
+        manager,
+        &[
+            &menu.status,
+            &menu.backend,
+            &separator()?,
+            &menu.remote,
+            &mode_menu,
+            &separator()?,
+            &menu.last_transcript,
+            &show,
+            &open_logs,
+            &separator()?,
+            &quit,
+        ],
+    )?;
+    {
+        let state = manager.state::<AppState>();
+        let mut tray_state = lock_tray(&state);
+        tray_state.mode = read_synthia_state().mode;
+        menu.sync_status(tray_state.status_line(), tray_state.running);
+        menu.sync_mode(
+            tray_state.mode,
+            tray_state.remote,
+            remote_channel::active().token().is_ok(),
+        );
+        menu.sync_transcript(get_last_transcript().as_deref());
+        tray_state.menu = Some(menu);
+    }
+    Ok(tray_menu)
+}
+
+/// Rebuild the menu and tooltip after the locale changed.
+pub fn relocalize(app: &AppHandle) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        match build_menu(app) {
+            Ok(menu) => {
+                let _ = tray.set_menu(Some(menu));
+            }
+            Err(e) => tracing::warn!(error = %e, "failed to rebuild tray menu"),
+        }
+    }
+    refresh(app);
+}
+
+/// Push the icon + tooltip for the current `TrayState` to the tray.
+///
+/// Everything is worked out under the tray lock and pushed after it is
+/// released: the tray and menu setters run on the main thread, which may
+/// itself be waiting for the lock.
+pub fn refresh(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else { return };
+    let (icon, tooltip, status_line, running, menu) = {
+        let state = app.state::<AppState>();
+        let tray_state = lock_tray(&state);
+        // Muted, then paused, trump recording: the backend ignores audio in
+        // both. Starting shows faded too, until the backend can hear anything.
+        let icon = if tray_state.muted {
+            tray_state
+                .icons
+                .pick(tray_state.theme, false)
+                .map(|icon| struck_through(&faded(icon, PAUSED_ALPHA), MUTED_STRIKE_RGBA))
+        } else if tray_state.paused || tray_state.starting {
+            tray_state
+                .icons
+                .pick(tray_state.theme, false)
+                .map(|icon| faded(icon, PAUSED_ALPHA))
+        } else {
+            tray_state.icons.pick(tray_state.theme, tray_state.recording).cloned()
+        };
+        (
+            icon.map(|icon| tray_state.decorate(icon)),
+            tray_state.tooltip(),
+            tray_state.status_line(),
+            tray_state.running,
+            tray_state.menu.clone(),
+        )
+    };
+    if let Some(icon) = icon {
+        let _ = tray.set_icon(Some(icon));
+    }
+    let _ = tray.set_tooltip(Some(tooltip));
+    if let Some(menu) = menu {
+        menu.sync_status(status_line, running);
+    }
+}
+
+/// Record a system theme change and re-pick the icon.
+pub fn set_theme(app: &AppHandle, theme: Theme) {
+    {
+        let state = app.state::<AppState>();
+        let mut tray_state = lock_tray(&state);
+        if tray_state.theme == Some(theme) {
+            return;
+        }
+        tray_state.theme = Some(theme);
+    }
+    refresh(app);
+}
+
+/// Record whether remote mode is live and redraw. No-op if unchanged.
+pub fn set_remote(app: &AppHandle, remote: bool) {
+    {
+        let state = app.state::<AppState>();
+        let mut tray_state = lock_tray(&state);
+        if tray_state.remote == remote {
+            return;
+        }
+        tray_state.remote = remote;
+    }
+    refresh(app);
+    sync_mode_items(app);
+}
+
+/// Record whether a backend is running and redraw. No-op if unchanged.
+pub fn set_running(app: &AppHandle, running: bool) {
+    {
+        let state = app.state::<AppState>();
+        let mut tray_state = lock_tray(&state);
+        if tray_state.running == running {
+            return;
+        }
+        tray_state.running = running;
+    }
+    refresh(app);
+}
+
+/// Show or clear the supervisor's crash notice in the tooltip.
+pub fn set_recovery(app: &AppHandle, recovery: Option<Recovery>) {
+    {
+        let state = app.state::<AppState>();
+        let mut tray_state = lock_tray(&state);
+        if tray_state.recovery == recovery {
+            return;
+        }
+        tray_state.recovery = recovery;
+    }
+    refresh(app);
+}
+
+/// Show or clear the "not responding" status.
+pub fn set_unresponsive(app: &AppHandle, unresponsive: bool) {
+    {
+        let state = app.state::<AppState>();
+        let mut tray_state = lock_tray(&state);
+        if tray_state.unresponsive == unresponsive {
+            return;
+        }
+        tray_state.unresponsive = unresponsive;
+    }
+    refresh(app);
+}
+
+/// Drop the crash notice without a tray handle; the tooltip catches up on
+/// the next refresh.
+pub fn clear_recovery(state: &AppState) {
+    lock_tray(state).recovery = None;
+}
+
+/// Resolve the tray icons from the bundled resources, then the dev checkout.
+pub fn load_icons(app: &AppHandle) -> TrayIcons {
+    let resource_dir = app.path().resource_dir().unwrap_or_default();
+    let dev_icons_dir = crate::get_synthia_root().join("gui/src-tauri/icons");
+    TrayIcons::load(
+        &resource_dir,
+        &dev_icons_dir,
+        app.default_window_icon().map(|icon| icon.clone().to_owned()),
+    )
+}
+
+/// Re-resolve the icons, e.g. after the config changed, and redraw.
+pub fn reload_icons(app: &AppHandle) {
+    let icons = load_icons(app);
+    {
+        let state = app.state::<AppState>();
+        let mut tray_state = lock_tray(&state);
+        tray_state.icons = icons;
+    }
+    refresh(app);
+}
+
+/// Enter or leave low-power mode. Leaving it mid-recording restarts the
+/// animation; entering it lets the animation thread wind down on its own.
+pub fn set_low_power(app: &AppHandle, low_power: bool) {
+    {
+        let state = app.state::<AppState>();
+        let mut tray_state = lock_tray(&state);
+        if tray_state.low_power == low_power {
+            return;
+        }
+        tray_state.low_power = low_power;
+        if tray_state.animation().is_some() && !tray_state.animating {
+            tray_state.animating = true;
+            spawn_animation(app.clone());
+        }
+    }
+    refresh(app);
+}
+
+/// Turn animation off or back on after `tray.animate` changed.
+pub fn set_static_icons(app: &AppHandle, static_icons: bool) {
+    {
+        let state = app.state::<AppState>();
+        let mut tray_state = lock_tray(&state);
+        if tray_state.static_icons == static_icons {
+            return;
+        }
+        tray_state.static_icons = static_icons;
+        if tray_state.animation().is_some() && !tray_state.animating {
+            tray_state.animating = true;
+            spawn_animation(app.clone());
+        }
+    }
+    refresh(app);
+}
+
+/// Update the recording time shown in the tooltip. Only the tooltip is
+/// touched, so this never fights the animation thread over the icon.
+pub fn set_recording_elapsed(app: &AppHandle, elapsed: Option<u64>) {
+    let (tooltip, status_line, running, menu) = {
+        let state = app.state::<AppState>();
+        let mut tray_state = lock_tray(&state);
+        if tray_state.recording_elapsed == elapsed {
+            return;
+        }
+        tray_state.recording_elapsed = elapsed;
+        (
+            tray_state.tooltip(),
+            tray_state.status_line(),
+            tray_state.running,
+            tray_state.menu.clone(),
+        )
+    };
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+    if let Some(menu) = menu {
+        menu.sync_status(status_line, running);
+    }
+}
+
+/// Mirror the backend's recording/paused/muted flags and re-pick the icon.
+/// No-op if none changed.
+pub fn apply_state(app: &AppHandle, synthia: &SynthiaState) {
+    let mode_changed = {
+        let state = app.state::<AppState>();
+        let mut tray_state = lock_tray(&state);
+        // Only a live backend writes these, so they prove one is running;
+        // stopping is reported by whoever stopped it.
+        let running = tray_state.running || synthia.ready || synthia.is_starting();
+        let thinking = !synthia.recording && synthia.overlay_state() == OverlayState::Thinking;
+        if tray_state.running == running
+            && tray_state.recording == synthia.recording
+            && tray_state.thinking == thinking
+            && tray_state.paused == synthia.paused
+            && tray_state.muted == synthia.muted
+            && tray_state.starting == synthia.is_starting()
+            && tray_state.mode == synthia.mode
+        {
+            return;
+        }
+        let mode_changed = tray_state.mode != synthia.mode;
+        tray_state.mode = synthia.mode;
+        tray_state.running = running;
+        tray_state.starting = synthia.is_starting();
+        tray_state.recording = synthia.recording;
+        tray_state.thinking = thinking;
+        tray_state.paused = synthia.paused;
+        tray_state.muted = synthia.muted;
+        if tray_state.animation().is_some() && !tray_state.animating {
+            tray_state.animating = true;
+            spawn_animation(app.clone());
+        }
+        mode_changed
+    };
+    if mode_changed {
+        sync_mode_items(app);
+    }
+    refresh(app);
+}
+
+/// Cycle the current animation's frames until there is nothing left to
+/// animate (recording and transcribing over, or paused), then settle on the
+/// static icon for the current state.
+fn spawn_animation(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut frame = 0;
+        loop {
+            // Take the frame under the lock but set it after: `set_icon`
+            // runs on the main thread, which may be waiting for the lock.
+            let (icon, interval) = {
+                let state = app.state::<AppState>();
+                let mut tray_state = lock_tray(&state);
+                let next = tray_state
+                    .animation()
+                    .and_then(|animation| tray_state.animation_frame(animation, frame));
+                let Some((icon, interval)) = next else {
+                    tray_state.animating = false;
+                    break;
+                };
+                (tray_state.decorate(icon), interval)
+            };
+            if let Some(tray) = app.tray_by_id(TRAY_ID) {
+                let _ = tray.set_icon(Some(icon));
+            }
+            frame += 1;
+            std::thread::sleep(interval);
+        }
+        refresh(&app);
+    });
+}
+
+/// Body of the quit confirmation, calling out anything a misclick would cut
+/// short.
+fn quit_prompt(recording: bool, remote: bool) -> String {
+    let mut prompt = t("quit.prompt");
+    if recording {
+        prompt.push_str("\n\n");
+        prompt.push_str(&t("quit.prompt_recording"));
+    }
+    if remote {
+        prompt.push_str("\n\n");
+        prompt.push_str(&t("quit.prompt_remote"));
+    }
+    prompt
+}
+
+/// Tray "Quit": exit straight away, or after confirmation when
+/// `tray.confirm_on_quit` is set.
+pub fn request_quit(app: &AppHandle) {
+    if !gui_config::load().tray.confirm_on_quit {
+        crate::quit_app(app);
+        return;
+    }
+    let state = app.state::<AppState>();
+    let recording = lock_tray(&state).recording;
+    let remote = state.remote_running.load(Ordering::SeqCst);
+    let kind = if recording || remote {
+        MessageDialogKind::Warning
+    } else {
+        MessageDialogKind::Info
+    };
+    let handle = app.clone();
+    // Non-blocking: menu events arrive on the main thread, which the dialog needs.
+    app.dialog()
+        .message(quit_prompt(recording, remote))
+        .title(t("quit.title"))
+        .kind(kind)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            t("quit.confirm"),
+            t("quit.cancel"),
+        ))
+        .show(move |confirmed| {
+            if confirmed {
+                crate::quit_app(&handle);
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_takes_the_mode_check_while_running() {
+        assert_eq!(active_mode_id(Mode::Voice, false), MODE_VOICE_ID);
+        assert_eq!(active_mode_id(Mode::PushToTalk, false), MODE_PUSH_TO_TALK_ID);
+        assert_eq!(active_mode_id(Mode::PushToTalk, true), MODE_REMOTE_ID);
+    }
+
+    fn solid(width: u32) -> Image<'static> {
+        Image::new_owned(vec![0; (width * width * 4) as usize], width, width)
+    }
+
+    #[test]
+    fn pick_prefers_themed_variant() {
+        let icons = TrayIcons {
+            fallback: IconPair { normal: Some(solid(1)), recording: Some(solid(1)) },
+            light: IconPair { normal: Some(solid(2)), recording: Some(solid(3)) },
+            dark: IconPair::default(),
+            ..Default::default()
+        };
+        assert_eq!(icons.pick(Some(Theme::Light), false).map(|i| i.width()), Some(2));
+        assert_eq!(icons.pick(Some(Theme::Light), true).map(|i| i.width()), Some(3));
+    }
+
+    #[test]
+    fn pick_falls_back_when_variant_missing() {
+        let icons = TrayIcons {
+            fallback: IconPair { normal: Some(solid(1)), recording: Some(solid(4)) },
+            light: IconPair { normal: Some(solid(2)), recording: None },
+            dark: IconPair::default(),
+            ..Default::default()
+        };
+        assert_eq!(icons.pick(Some(Theme::Dark), false).map(|i| i.width()), Some(1));
+        assert_eq!(icons.pick(Some(Theme::Light), true).map(|i| i.width()), Some(4));
+        assert_eq!(icons.pick(None, false).map(|i| i.width()), Some(1));
+    }
+
+    #[test]
+    fn pick_uses_default_icon_when_nothing_loaded() {
+        let icons = TrayIcons { default: Some(solid(5)), ..Default::default() };
+        assert_eq!(icons.pick(Some(Theme::Dark), true).map(|i| i.width()), Some(5));
+    }
+
+    #[test]
+    fn template_wins_and_keeps_only_alpha() {
+        let colored = Image::new_owned(vec![200, 100, 50, 128], 1, 1);
+        assert_eq!(template(&colored).rgba(), &[0, 0, 0, 128]);
+        let icons = TrayIcons {
+            light: IconPair { normal: Some(solid(2)), recording: Some(solid(2)) },
+            template: IconPair { normal: Some(solid(7)), recording: None },
+            ..Default::default()
+        };
+        assert_eq!(icons.pick(Some(Theme::Light), false).map(|i| i.width()), Some(7));
+        assert_eq!(icons.pick(Some(Theme::Light), true).map(|i| i.width()), Some(2));
+    }
+
+    #[test]
+    fn missing_icon_records_every_path_tried() {
+        let dir = std::env::temp_dir().join(format!("synthia-tray-test-{}", std::process::id()));
+        let (icon, status) = load_from_disk(&dir.join("res"), &dir.join("dev"), "nope.png");
+        assert!(icon.is_none());
+        assert_eq!(
+            status.tried,
+            vec![dir.join("res/icons/nope.png"), dir.join("dev/nope.png")]
+        );
+        assert_eq!(status.loaded_from, None);
+    }
+
+    #[test]
+    fn load_errors_tell_missing_from_corrupt() {
+        let dir = std::env::temp_dir().join(format!("synthia-icon-err-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let corrupt = dir.join("corrupt.png");
+        std::fs::write(&corrupt, b"not a png").unwrap();
+
+        assert!(matches!(load_icon_from_path(&dir.join("gone.png")), Err(IconError::Missing)));
+        assert!(matches!(load_icon_from_path(&corrupt), Err(IconError::Decode(_))));
+        assert!(load_embedded_icon(TRAY_ICON_PNG).is_ok());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn animation_follows_recording_and_transcribing() {
+        let mut tray = TrayState { recording: true, ..Default::default() };
+        assert_eq!(tray.animation(), Some(Animation::Recording));
+        tray.paused = true;
+        assert_eq!(tray.animation(), None);
+        tray.paused = false;
+        tray.low_power = true;
+        assert_eq!(tray.animation(), None);
+        tray.low_power = false;
+        tray.recording = false;
+        tray.thinking = true;
+        assert_eq!(tray.animation(), Some(Animation::Transcribing));
+        assert_eq!(tray.status_line(), "Transcribing…");
+        tray.static_icons = true;
+        assert_eq!(tray.animation(), None);
+    }
+
+    #[test]
+    fn recording_frames_from_disk_replace_the_pulse() {
+        let mut tray = TrayState::default();
+        assert!(tray.animation_frame(Animation::Recording, 0).is_none());
+        tray.icons.fallback.normal = Some(solid(24));
+        let (drawn, interval) = tray.animation_frame(Animation::Recording, 0).unwrap();
+        assert_eq!((drawn.width(), interval), (24, Animation::Recording.interval()));
+        tray.icons.recording_frames = vec![solid(1), solid(2)];
+        let (frame, interval) = tray.animation_frame(Animation::Recording, 3).unwrap();
+        assert_eq!((frame.width(), interval), (2, FRAME_INTERVAL));
+        let (spinner, _) = tray.animation_frame(Animation::Transcribing, 0).unwrap();
+        assert_eq!(spinner.width(), 24);
+    }
+
+    #[test]
+    fn tooltip_shows_recording_time() {
+        let mut tray = TrayState { recording: true, ..Default::default() };
+        assert_eq!(tray.tooltip(), "Synthia - Recording...");
+        tray.recording_elapsed = Some(12);
+        assert_eq!(tray.tooltip(), "Synthia - Recording 00:12");
+        tray.remote = true;
+        assert_eq!(tray.tooltip(), "Synthia - Recording 00:12 (Remote ON)");
+        tray.paused = true;
+        assert_eq!(tray.tooltip(), "Synthia - Paused (Remote ON)");
+        tray.starting = true;
+        assert_eq!(tray.tooltip(), "Synthia - Starting… (Remote ON)");
+    }
+
+    #[test]
+    fn status_line_reports_stopped_backend_until_it_runs() {
+        let mut tray = TrayState::default();
+        assert_eq!(tray.status(), Status::Stopped);
+        assert_eq!(tray.status_line(), "Stopped");
+        assert_eq!(tray.tooltip(), "Synthia - Stopped");
+        tray.running = true;
+        assert_eq!(tray.status_line(), "Idle");
+        tray.recording = true;
+        tray.recording_elapsed = Some(5);
+        assert_eq!(tray.status_line(), "Recording 00:05");
+        tray.unresponsive = true;
+        assert_eq!(tray.status_line(), "Backend not responding");
+        tray.recovery = Some(Recovery::Restarting { attempt: 2, max_restarts: 5 });
+        assert_eq!(tray.status_line(), "Backend crashed, restarting (2/5)");
+    }
+
+    #[test]
+    fn transcript_label_previews_the_text() {
+        assert_eq!(transcript_label(None), "Copy Last Transcript");
+        assert_eq!(
+            transcript_label(Some("hello\nworld")),
+            "Copy Last Transcript: hello world"
+        );
+        let long = "word ".repeat(20);
+        assert!(transcript_label(Some(&long)).ends_with('…'));
+        assert_eq!(backend_toggle_label(true), "Stop Synthia");
+        assert_eq!(remote_toggle_label(false), "Remote Mode (no bot token)");
+    }
+
+    #[test]
+    fn quit_prompt_warns_about_interrupted_work() {
+        assert!(!quit_prompt(false, false).contains("Recording"));
+        assert!(quit_prompt(true, false).contains("Recording is in progress"));
+        assert!(quit_prompt(false, true).contains("Remote mode"));
+    }
+
+    #[test]
+    fn badge_fills_bottom_right_only() {
+        let icon = solid(12);
+        let out = with_badge(&icon, [1, 2, 3, 255]);
+        let px = |x: usize, y: usize| &out.rgba()[(y * 12 + x) * 4..(y * 12 + x) * 4 + 4];
+        assert_eq!(px(9, 9), &[1, 2, 3, 255]);
+        assert_eq!(px(0, 0), &[0, 0, 0, 0]);
+        assert_eq!(px(11, 0), &[0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn muted_wins_over_paused_and_recording() {
+        let mut tray = TrayState { recording: true, paused: true, muted: true, ..Default::default() };
+        assert_eq!(tray.tooltip(), "Synthia - Microphone muted");
+        tray.paused = false;
+        tray.icons.recording_frames = vec![solid(1)];
+        assert_eq!(tray.animation(), None);
+    }
+
+    #[test]
+    fn strike_runs_corner_to_corner() {
+        let out = struck_through(&solid(16), [9, 9, 9, 255]);
+        let px = |x: usize, y: usize| &out.rgba()[(y * 16 + x) * 4..(y * 16 + x) * 4 + 4];
+        assert_eq!(px(0, 0), &[9, 9, 9, 255]);
+        assert_eq!(px(15, 15), &[9, 9, 9, 255]);
+        assert_eq!(px(15, 0), &[0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn faded_scales_alpha_only() {
+        let icon = Image::new_owned(vec![10, 20, 30, 200, 1, 2, 3, 0], 2, 1);
+        let out = faded(&icon, 0.5);
+        assert_eq!(out.rgba(), &[10, 20, 30, 100, 1, 2, 3, 0]);
+        assert_eq!((out.width(), out.height()), (2, 1));
+    }
+}
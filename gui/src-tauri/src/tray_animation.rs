@@ -0,0 +1,192 @@
+//! Tray icon animations drawn at runtime: a pulsing red dot while
+//! recording and a spinner while the backend transcribes.
+//!
+//! Frames are painted over whatever base icon the tray would show, one per
+//! tick, so they follow theme changes and the remote badge without extra
+//! assets. `tray-recording-anim-<n>.png` frames on disk still replace the
+//! pulse when present; see `tray`. Turning `tray.animate` off in `gui.toml`
+//! keeps the static icons, for desktops that redraw animated tray icons
+//! badly or not at all.
+
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use tauri::image::Image;
+
+/// Recording dot colour (RGBA).
+const PULSE_RGBA: [u8; 4] = [0xef, 0x44, 0x44, 0xff];
+
+/// Spinner colour (RGBA).
+const SPINNER_RGBA: [u8; 4] = [0x3b, 0x82, 0xf6, 0xff];
+
+/// Lowest opacity of the recording dot, at the dim end of a pulse.
+const PULSE_MIN_ALPHA: f32 = 0.35;
+
+/// Alpha multiplier for the base icon under the spinner, so the spinner
+/// reads as the foreground.
+const SPINNER_BASE_ALPHA: f32 = 0.5;
+
+/// How far round the spinner's arc reaches, fading towards its tail.
+const SPINNER_ARC: f32 = TAU / 3.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Animation {
+    Recording,
+    Transcribing,
+}
+
+impl Animation {
+    /// Frames in one cycle.
+    pub fn frame_count(self) -> usize {
+        match self {
+            Animation::Recording => 16,
+            Animation::Transcribing => 12,
+        }
+    }
+
+    /// Time each frame stays up: a two-second pulse, a one-second-ish spin.
+    pub fn interval(self) -> Duration {
+        match self {
+            Animation::Recording => Duration::from_millis(125),
+            Animation::Transcribing => Duration::from_millis(100),
+        }
+    }
+
+    /// Frame `n` (wrapping) drawn over `base`.
+    pub fn frame(self, base: &Image<'_>, n: usize) -> Image<'static> {
+        let phase = (n % self.frame_count()) as f32 / self.frame_count() as f32;
+        match self {
+            Animation::Recording => pulse(base, phase),
+            Animation::Transcribing => spinner(base, phase),
+        }
+    }
+}
+
+/// Paint `rgba` over `px` at `coverage` (0..=1), source-over.
+fn blend(px: &mut [u8], rgba: [u8; 4], coverage: f32) {
+    let a = coverage.clamp(0.0, 1.0) * rgba[3] as f32 / 255.0;
+    let under = px[3] as f32 / 255.0;
+    let out = a + under * (1.0 - a);
+    if out <= 0.0 {
+        return;
+    }
+    for c in 0..3 {
+        let mixed = (rgba[c] as f32 * a + px[c] as f32 * under * (1.0 - a)) / out;
+        px[c] = mixed.round() as u8;
+    }
+    px[3] = (out * 255.0).round() as u8;
+}
+
+/// `base` with a dot in the top-right corner, a third of the icon across,
+/// whose opacity rises and falls once per cycle. `phase` runs 0..1.
+fn pulse(base: &Image<'_>, phase: f32) -> Image<'static> {
+    let (w, h) = (base.width() as i64, base.height() as i64);
+    let mut out = base.rgba().to_vec();
+    let strength = 0.5 + 0.5 * (phase * TAU).cos();
+    let alpha = PULSE_MIN_ALPHA + (1.0 - PULSE_MIN_ALPHA) * strength;
+    let r = (w.min(h) / 6).max(1);
+    let (cx, cy) = (w - r - 1, r);
+    for y in (cy - r).max(0)..=(cy + r).min(h - 1) {
+        for x in (cx - r).max(0)..=(cx + r).min(w - 1) {
+            if (x - cx).pow(2) + (y - cy).pow(2) <= r * r {
+                let i = ((y * w + x) * 4) as usize;
+                blend(&mut out[i..i + 4], PULSE_RGBA, alpha);
+            }
+        }
+    }
+    Image::new_owned(out, base.width(), base.height())
+}
+
+/// `base`, faded, under a ring-shaped arc whose head sits at `phase` of a
+/// turn clockwise from twelve o'clock and whose tail fades out behind it.
+fn spinner(base: &Image<'_>, phase: f32) -> Image<'static> {
+    let (w, h) = (base.width() as usize, base.height() as usize);
+    let mut out = base.rgba().to_vec();
+    for px in out.chunks_exact_mut(4) {
+        px[3] = (px[3] as f32 * SPINNER_BASE_ALPHA).round() as u8;
+    }
+    let outer = w.min(h) as f32 / 2.0 - 0.5;
+    let inner = outer - (w.min(h) as f32 / 8.0).max(1.5);
+    let (cx, cy) = (w as f32 / 2.0, h as f32 / 2.0);
+    let head = phase * TAU;
+    for y in 0..h {
+        for x in 0..w {
+            let (dx, dy) = (x as f32 + 0.5 - cx, y as f32 + 0.5 - cy);
+            let dist = dx.hypot(dy);
+            if dist < inner || dist > outer {
+                continue;
+            }
+            // Clockwise from twelve o'clock; screen y grows downwards.
+            let angle = dx.atan2(-dy).rem_euclid(TAU);
+            let behind = (head - angle).rem_euclid(TAU);
+            if behind < SPINNER_ARC {
+                let i = (y * w + x) * 4;
+                blend(&mut out[i..i + 4], SPINNER_RGBA, 1.0 - behind / SPINNER_ARC);
+            }
+        }
+    }
+    Image::new_owned(out, base.width(), base.height())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clear(size: u32) -> Image<'static> {
+        Image::new_owned(vec![0; (size * size * 4) as usize], size, size)
+    }
+
+    fn pixel(icon: &Image<'_>, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * icon.width() + x) * 4) as usize;
+        icon.rgba()[i..i + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn pulse_is_brightest_at_the_start_of_a_cycle() {
+        let base = clear(24);
+        let bright = Animation::Recording.frame(&base, 0);
+        let dim = Animation::Recording.frame(&base, 8);
+        // Dot centre: top-right corner, radius 4.
+        assert_eq!(pixel(&bright, 19, 4), PULSE_RGBA);
+        let faint = pixel(&dim, 19, 4);
+        assert_eq!(faint[..3], PULSE_RGBA[..3]);
+        assert_eq!(faint[3], (PULSE_MIN_ALPHA * 255.0).round() as u8);
+        assert_eq!(pixel(&bright, 4, 19), [0; 4]);
+        assert_eq!(pixel(&Animation::Recording.frame(&base, 16), 19, 4), PULSE_RGBA);
+    }
+
+    #[test]
+    fn spinner_head_moves_clockwise() {
+        let base = clear(32);
+        // Just anticlockwise of twelve and of three o'clock, inside the ring.
+        let top = (15, 1);
+        let right = (30, 15);
+        let first = Animation::Transcribing.frame(&base, 0);
+        assert!(pixel(&first, top.0, top.1)[3] > 200);
+        assert_eq!(pixel(&first, right.0, right.1)[3], 0);
+        // A quarter turn later the head is at three o'clock.
+        let quarter = Animation::Transcribing.frame(&base, 3);
+        assert!(pixel(&quarter, right.0, right.1)[3] > 200);
+        // The centre is never painted.
+        assert_eq!(pixel(&quarter, 16, 16), [0; 4]);
+    }
+
+    #[test]
+    fn spinner_fades_the_base_icon() {
+        let base = Image::new_owned([10, 20, 30, 200].repeat(32 * 32), 32, 32);
+        assert_eq!(pixel(&Animation::Transcribing.frame(&base, 0), 16, 16), [10, 20, 30, 100]);
+    }
+
+    #[test]
+    fn blend_over_opaque_and_transparent() {
+        let mut px = [0, 0, 255, 255];
+        blend(&mut px, [255, 0, 0, 255], 0.5);
+        assert_eq!(px, [128, 0, 128, 255]);
+        let mut px = [0; 4];
+        blend(&mut px, [255, 0, 0, 255], 0.5);
+        assert_eq!(px, [255, 0, 0, 128]);
+        let mut px = [9, 9, 9, 0];
+        blend(&mut px, [255, 0, 0, 255], 0.0);
+        assert_eq!(px, [9, 9, 9, 0]);
+    }
+}
@@ -0,0 +1,292 @@
+//! Whisper models for local speech recognition, kept under
+//! `XDG_DATA_HOME/synthia/models` (the platform data dir elsewhere).
+//!
+//! Each model is a directory named after its size holding the CTranslate2
+//! files faster-whisper loads, fetched from the `Systran/faster-whisper-*`
+//! repositories on Hugging Face. A download goes into `<name>.partial` and
+//! is renamed into place only once every file has arrived, so a model
+//! directory is always complete. Progress is published as
+//! `model-download-progress` events.
+//!
+//! The backend looks in the same directory before asking faster-whisper to
+//! download a model itself, and is told over IPC when the active model
+//! changes; see `commands::models`.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::{AppError, AppResult};
+
+/// A model the manager knows how to fetch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelSpec {
+    /// Name as in `local_stt_model`.
+    pub name: &'static str,
+    repo: &'static str,
+    files: &'static [&'static str],
+    /// Approximate download size, for the UI before a download starts.
+    pub approx_bytes: u64,
+}
+
+const FILES: &[&str] = &["config.json", "tokenizer.json", "vocabulary.txt", "model.bin"];
+const FILES_V3: &[&str] =
+    &["config.json", "preprocessor_config.json", "tokenizer.json", "vocabulary.json", "model.bin"];
+
+const fn model(
+    name: &'static str,
+    repo: &'static str,
+    files: &'static [&'static str],
+    approx_mb: u64,
+) -> ModelSpec {
+    ModelSpec { name, repo, files, approx_bytes: approx_mb * 1024 * 1024 }
+}
+
+/// Smallest first. `model.bin` is listed last in each, so the small files
+/// are read off while its response waits.
+pub const CATALOG: &[ModelSpec] = &[
+    model("tiny", "Systran/faster-whisper-tiny", FILES, 75),
+    model("base", "Systran/faster-whisper-base", FILES, 145),
+    model("small", "Systran/faster-whisper-small", FILES, 484),
+    model("medium", "Systran/faster-whisper-medium", FILES, 1530),
+    model("large", "Systran/faster-whisper-large-v3", FILES_V3, 3090),
+];
+
+/// The file a model directory must hold to count as installed.
+const WEIGHTS: &str = "model.bin";
+
+/// Minimum gap between progress events for one download.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Emitted as `model-download-progress` while a model downloads, and once
+/// more with `done` set when it is installed or has failed.
+#[derive(Serialize, Debug, Clone)]
+pub struct DownloadProgress {
+    pub model: String,
+    pub downloaded: u64,
+    pub total: u64,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+/// Names of models being downloaded, so a second request for one is
+/// refused rather than racing the first into the same `.partial` dir.
+static DOWNLOADING: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+static HTTP: OnceLock<reqwest::Client> = OnceLock::new();
+
+fn http_client() -> &'static reqwest::Client {
+    HTTP.get_or_init(|| {
+        // No overall timeout: the large model takes minutes on most links.
+        reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(15))
+            .read_timeout(Duration::from_secs(60))
+            .build()
+            .expect("reqwest client builds")
+    })
+}
+
+pub fn spec(name: &str) -> AppResult<&'static ModelSpec> {
+    CATALOG.iter().find(|m| m.name == name).ok_or_else(|| {
+        let names: Vec<_> = CATALOG.iter().map(|m| m.name).collect();
+        AppError::Validation(format!(
+            "unknown model {name:?}; expected one of {}",
+            names.join(", ")
+        ))
+    })
+}
+
+pub fn models_dir() -> PathBuf {
+    dirs::data_dir().unwrap_or_else(|| PathBuf::from(".")).join("synthia").join("models")
+}
+
+pub fn model_dir(name: &str) -> PathBuf {
+    models_dir().join(name)
+}
+
+fn partial_dir(name: &str) -> PathBuf {
+    models_dir().join(format!("{name}.partial"))
+}
+
+pub fn is_installed(name: &str) -> bool {
+    model_dir(name).join(WEIGHTS).is_file()
+}
+
+pub fn is_downloading(name: &str) -> bool {
+    DOWNLOADING.lock().is_ok_and(|d| d.iter().any(|n| n == name))
+}
+
+/// Total size of the files under `dir`, 0 if it doesn't exist.
+pub fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|e| match e.metadata() {
+            Ok(m) if m.is_dir() => dir_size(&e.path()),
+            Ok(m) => m.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Marks `name` as downloading until dropped.
+struct DownloadGuard(String);
+
+impl DownloadGuard {
+    fn claim(name: &str) -> AppResult<Self> {
+        let mut downloading =
+            DOWNLOADING.lock().map_err(|_| AppError::Other("download list poisoned".into()))?;
+        if downloading.iter().any(|n| n == name) {
+            return Err(AppError::Validation(format!("{name} is already downloading")));
+        }
+        downloading.push(name.to_string());
+        Ok(DownloadGuard(name.to_string()))
+    }
+}
+
+impl Drop for DownloadGuard {
+    fn drop(&mut self) {
+        if let Ok(mut downloading) = DOWNLOADING.lock() {
+            downloading.retain(|n| *n != self.0);
+        }
+    }
+}
+
+fn file_url(spec: &ModelSpec, file: &str) -> String {
+    format!("https://huggingface.co/{}/resolve/main/{file}", spec.repo)
+}
+
+/// Download model `name`, publishing progress, and return its directory.
+pub async fn download(app: &AppHandle, name: &str) -> AppResult<PathBuf> {
+    let spec = spec(name)?;
+    if is_installed(name) {
+        return Ok(model_dir(name));
+    }
+    let _guard = DownloadGuard::claim(name)?;
+
+    let partial = partial_dir(name);
+    let result = fetch_into(app, spec, &partial).await;
+    let result = result.and_then(|()| {
+        let dir = model_dir(name);
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+        fs::rename(&partial, &dir)?;
+        Ok(dir)
+    });
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&partial);
+    }
+
+    let mut last = DownloadProgress {
+        model: name.to_string(),
+        downloaded: 0,
+        total: 0,
+        done: true,
+        error: result.as_ref().err().map(ToString::to_string),
+    };
+    if result.is_ok() {
+        last.downloaded = dir_size(&model_dir(name));
+        last.total = last.downloaded;
+    }
+    let _ = app.emit("model-download-progress", &last);
+    match &result {
+        Ok(dir) => tracing::info!(model = name, path = %dir.display(), "model downloaded"),
+        Err(e) => tracing::warn!(model = name, error = %e, "model download failed"),
+    }
+    result
+}
+
+async fn fetch_into(app: &AppHandle, spec: &ModelSpec, dir: &Path) -> AppResult<()> {
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    fs::create_dir_all(dir)?;
+
+    // Request every file up front so the total is known before the first
+    // byte of weights arrives.
+    let mut responses = Vec::with_capacity(spec.files.len());
+    for file in spec.files {
+        let response = http_client().get(file_url(spec, file)).send().await?.error_for_status()?;
+        responses.push((*file, response));
+    }
+    let mut progress = DownloadProgress {
+        model: spec.name.to_string(),
+        downloaded: 0,
+        total: responses.iter().filter_map(|(_, r)| r.content_length()).sum(),
+        done: false,
+        error: None,
+    };
+    let mut last_emit: Option<Instant> = None;
+
+    for (file, mut response) in responses {
+        let mut out = File::create(dir.join(file))?;
+        while let Some(chunk) = response.chunk().await? {
+            out.write_all(&chunk)?;
+            progress.downloaded += chunk.len() as u64;
+            if last_emit.is_none_or(|t| t.elapsed() >= PROGRESS_INTERVAL) {
+                let _ = app.emit("model-download-progress", &progress);
+                last_emit = Some(Instant::now());
+            }
+        }
+        out.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Remove model `name`'s directory. Returns false if it wasn't installed.
+pub fn delete(name: &str) -> AppResult<bool> {
+    spec(name)?;
+    if is_downloading(name) {
+        return Err(AppError::Validation(format!("{name} is still downloading")));
+    }
+    let dir = model_dir(name);
+    if !dir.exists() {
+        return Ok(false);
+    }
+    fs::remove_dir_all(&dir)?;
+    tracing::info!(model = name, "model deleted");
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_names_match_backend_models() {
+        let names: Vec<_> = CATALOG.iter().map(|m| m.name).collect();
+        assert_eq!(names, ["tiny", "base", "small", "medium", "large"]);
+        assert!(CATALOG.iter().all(|m| m.files.last() == Some(&WEIGHTS)));
+    }
+
+    #[test]
+    fn unknown_model_is_rejected() {
+        assert!(spec("small").is_ok());
+        assert!(matches!(spec("../etc"), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn urls_point_at_the_repo() {
+        assert_eq!(
+            file_url(spec("large").unwrap(), "model.bin"),
+            "https://huggingface.co/Systran/faster-whisper-large-v3/resolve/main/model.bin"
+        );
+    }
+
+    #[test]
+    fn a_second_claim_is_refused_until_the_first_drops() {
+        let first = DownloadGuard::claim("tiny").unwrap();
+        assert!(is_downloading("tiny"));
+        assert!(DownloadGuard::claim("tiny").is_err());
+        drop(first);
+        assert!(!is_downloading("tiny"));
+    }
+}
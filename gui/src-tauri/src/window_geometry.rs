@@ -0,0 +1,239 @@
+//! Saving and restoring window placement across monitor changes.
+//!
+//! Shared by the overlay and the main settings window. Positions are stored
+//! in physical pixels together with the monitor name; on restore, a window
+//! whose monitor has gone is clamped onto the primary display instead.
+//! Windows can also be anchored to a corner of a monitor's work area.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, WebviewWindow};
+
+use crate::error::{AppError, AppResult};
+use crate::gui_config::{self, OverlayCorner, OverlayOffset, SavedPosition, SavedSize};
+use crate::state::AppState;
+
+/// Quiet period after the last move/resize event before geometry is written,
+/// so dragging a window doesn't rewrite the config on every pixel.
+pub const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Shift `pos` so a window of `size` lies fully inside the area at
+/// `area_pos`/`area_size`. Windows larger than the area pin to its top-left.
+pub fn clamp_to_area(
+    pos: (i32, i32),
+    size: (u32, u32),
+    area_pos: (i32, i32),
+    area_size: (u32, u32),
+) -> (i32, i32) {
+    let clamp_axis = |p: i32, len: u32, start: i32, span: u32| {
+        let max = start + span as i32 - len as i32;
+        p.min(max).max(start)
+    };
+    (
+        clamp_axis(pos.0, size.0, area_pos.0, area_size.0),
+        clamp_axis(pos.1, size.1, area_pos.1, area_size.1),
+    )
+}
+
+/// Gap between an anchored window and the edges of its corner.
+pub const ANCHOR_MARGIN: i32 = 16;
+
+/// Top-left position for a window of `size` sitting in `corner` of the area
+/// at `area_pos`/`area_size`, `ANCHOR_MARGIN` in from the edges it touches,
+/// shifted by `offset`, and never outside the area.
+pub fn anchor_in_area(
+    corner: OverlayCorner,
+    offset: OverlayOffset,
+    size: (u32, u32),
+    area_pos: (i32, i32),
+    area_size: (u32, u32),
+) -> (i32, i32) {
+    let left = area_pos.0 + ANCHOR_MARGIN;
+    let top = area_pos.1 + ANCHOR_MARGIN;
+    let right = area_pos.0 + area_size.0 as i32 - size.0 as i32 - ANCHOR_MARGIN;
+    let bottom = area_pos.1 + area_size.1 as i32 - size.1 as i32 - ANCHOR_MARGIN;
+    let center = area_pos.0 + (area_size.0 as i32 - size.0 as i32) / 2;
+    let (x, y) = match corner {
+        OverlayCorner::TopLeft => (left, top),
+        OverlayCorner::TopCenter => (center, top),
+        OverlayCorner::TopRight => (right, top),
+        OverlayCorner::BottomLeft => (left, bottom),
+        OverlayCorner::BottomCenter => (center, bottom),
+        OverlayCorner::BottomRight => (right, bottom),
+    };
+    clamp_to_area((x + offset.x, y + offset.y), size, area_pos, area_size)
+}
+
+/// The window's outer position plus the name of the monitor it is on.
+pub fn current_position(window: &WebviewWindow) -> AppResult<SavedPosition> {
+    let pos = window
+        .outer_position()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let monitor = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .and_then(|m| m.name().cloned());
+    Ok(SavedPosition {
+        x: pos.x,
+        y: pos.y,
+        monitor,
+    })
+}
+
+pub fn current_size(window: &WebviewWindow) -> AppResult<SavedSize> {
+    let size = window
+        .inner_size()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    Ok(SavedSize {
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// Restore `size` (if any) and then `saved`, shrinking and clamping both to
+/// fit the saved monitor if still connected, else the primary one.
+pub fn restore(
+    window: &WebviewWindow,
+    saved: &SavedPosition,
+    size: Option<&SavedSize>,
+) -> AppResult<()> {
+    let monitors = window
+        .available_monitors()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let saved_monitor = saved
+        .monitor
+        .as_ref()
+        .and_then(|name| monitors.iter().find(|m| m.name() == Some(name)));
+    let target = match saved_monitor {
+        Some(m) => Some(m.clone()),
+        None => window
+            .primary_monitor()
+            .map_err(|e| AppError::Other(e.to_string()))?,
+    };
+    let Some(target) = target else {
+        return Ok(());
+    };
+    let area_size = (target.size().width, target.size().height);
+
+    if let Some(size) = size {
+        window
+            .set_size(PhysicalSize::new(
+                size.width.min(area_size.0),
+                size.height.min(area_size.1),
+            ))
+            .map_err(|e| AppError::Other(e.to_string()))?;
+    }
+    let outer = window
+        .outer_size()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let (x, y) = clamp_to_area(
+        (saved.x, saved.y),
+        (outer.width, outer.height),
+        (target.position().x, target.position().y),
+        area_size,
+    );
+    window
+        .set_position(PhysicalPosition::new(x, y))
+        .map_err(|e| AppError::Other(e.to_string()))
+}
+
+/// Run `f` once `SAVE_DEBOUNCE` passes without another call for the same
+/// counter. `seq` picks the counter in `AppState`.
+pub fn debounce(
+    app: &AppHandle,
+    seq: fn(&AppState) -> &AtomicU64,
+    f: impl FnOnce(&AppHandle) + Send + 'static,
+) {
+    let ticket = seq(&app.state::<AppState>()).fetch_add(1, Ordering::SeqCst) + 1;
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(SAVE_DEBOUNCE);
+        if seq(&app.state::<AppState>()).load(Ordering::SeqCst) == ticket {
+            f(&app);
+        }
+    });
+}
+
+/// Persist the main settings window's position and size.
+pub fn save_main_window(window: &WebviewWindow) -> AppResult<()> {
+    let position = current_position(window)?;
+    let size = current_size(window)?;
+    gui_config::update(|cfg| {
+        cfg.main_window.position = Some(position);
+        cfg.main_window.size = Some(size);
+    })
+}
+
+/// Put the main window back where it was last left, if anywhere.
+pub fn restore_main_window(window: &WebviewWindow) -> AppResult<()> {
+    let saved = gui_config::load().main_window;
+    match saved.position {
+        Some(position) => restore(window, &position, saved.size.as_ref()),
+        None => Ok(()),
+    }
+}
+
+/// Called on every main window `Moved`/`Resized` event.
+pub fn on_main_window_changed(app: &AppHandle) {
+    debounce(app, |s| &s.main_window_geometry_seq, |app| {
+        if let Some(window) = app.get_webview_window("main") {
+            if let Err(e) = save_main_window(&window) {
+                tracing::warn!(error = %e, "failed to save main window geometry");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_keeps_position_inside_area() {
+        // Already inside: untouched.
+        assert_eq!(clamp_to_area((100, 100), (160, 50), (0, 0), (1920, 1080)), (100, 100));
+        // Off the right/bottom edge: pulled back in.
+        assert_eq!(clamp_to_area((1900, 1070), (160, 50), (0, 0), (1920, 1080)), (1760, 1030));
+        // On a monitor to the left (negative coords) that's now gone: pulled onto primary.
+        assert_eq!(clamp_to_area((-1200, 40), (160, 50), (0, 0), (1920, 1080)), (0, 40));
+    }
+
+    #[test]
+    fn anchor_places_window_in_corner_of_offset_area() {
+        let area = ((1920, 0), (2560, 1440));
+        let none = OverlayOffset::default();
+        assert_eq!(anchor_in_area(OverlayCorner::TopLeft, none, (160, 50), area.0, area.1), (1936, 16));
+        assert_eq!(
+            anchor_in_area(OverlayCorner::BottomRight, none, (160, 50), area.0, area.1),
+            (1920 + 2560 - 160 - 16, 1440 - 50 - 16)
+        );
+        assert_eq!(
+            anchor_in_area(OverlayCorner::BottomCenter, none, (160, 50), area.0, area.1),
+            (1920 + (2560 - 160) / 2, 1440 - 50 - 16)
+        );
+        // Too small for the margin: still inside.
+        assert_eq!(anchor_in_area(OverlayCorner::BottomRight, none, (160, 50), (0, 0), (160, 50)), (0, 0));
+    }
+
+    #[test]
+    fn anchor_offset_shifts_but_stays_inside_area() {
+        let area = ((0, 0), (1920, 1080));
+        let up = OverlayOffset { x: 0, y: -40 };
+        assert_eq!(
+            anchor_in_area(OverlayCorner::BottomCenter, up, (160, 50), area.0, area.1),
+            (880, 1080 - 50 - 16 - 40)
+        );
+        let far = OverlayOffset { x: -5000, y: 5000 };
+        assert_eq!(anchor_in_area(OverlayCorner::TopRight, far, (160, 50), area.0, area.1), (0, 1030));
+    }
+
+    #[test]
+    fn clamp_handles_offset_monitor() {
+        assert_eq!(
+            clamp_to_area((0, 0), (160, 50), (1920, 0), (2560, 1440)),
+            (1920, 0)
+        );
+    }
+}
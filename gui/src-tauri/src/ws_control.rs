@@ -0,0 +1,224 @@
+//! Optional WebSocket control server, for Stream Deck plugins, scripts and
+//! other machines on the LAN that want to keep a connection open rather
+//! than poll `http_control`.
+//!
+//! Off unless `ws_control.enabled` is set in `gui.toml`. The server binds
+//! `ws_control.bind:ws_control.port` (loopback unless configured otherwise)
+//! and the handshake must carry the token, either as a `?token=` query
+//! parameter (browsers can't set headers on a WebSocket) or an
+//! `X-Synthia-Token` header. The token is kept in the OS keyring (see
+//! `secrets`), since the server may face the LAN: one is generated on first
+//! start, `get_ws_control_token` shows it, and a token left in
+//! `ws_control.token` by an older version is moved over.
+//!
+//! Clients send text frames such as `{"id": 1, "action": "status"}`, where
+//! `action` is `status`, `start`, `stop` or `toggle-recording`, and get the
+//! same JSON the HTTP routes return, with `id` echoed. Whenever the status
+//! changes it is pushed unasked as `{"event": "status", ...}`, starting with
+//! the status at connect, so a button can show whether Synthia is recording.
+
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::AppHandle;
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::StatusCode;
+use tungstenite::{Message, WebSocket};
+
+use crate::error::AppResult;
+use crate::{gui_config, secrets};
+use crate::http_control::{self, error_body, token_matches};
+
+const TOKEN_HEADER: &str = "x-synthia-token";
+
+/// How often an idle connection checks for a status change to push.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Deserialize, Debug)]
+struct ClientMessage {
+    #[serde(default)]
+    id: Option<Value>,
+    action: String,
+}
+
+/// The token a handshake offers: the header, else `?token=`.
+fn offered_token(req: &Request) -> Option<String> {
+    if let Some(token) = req.headers().get(TOKEN_HEADER).and_then(|v| v.to_str().ok()) {
+        return Some(token.trim().to_string());
+    }
+    req.uri()
+        .query()?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == "token")
+        .map(|(_, v)| v.to_string())
+}
+
+fn unauthorized() -> ErrorResponse {
+    let mut response = ErrorResponse::new(Some("missing or wrong token".to_string()));
+    *response.status_mut() = StatusCode::UNAUTHORIZED;
+    response
+}
+
+/// The reply to one client frame.
+fn reply(app: &AppHandle, text: &str) -> Value {
+    let msg: ClientMessage = match serde_json::from_str(text) {
+        Ok(msg) => msg,
+        Err(e) => return error_body(format!("malformed message: {e}")),
+    };
+    tracing::info!(action = %msg.action, "WebSocket control request");
+    let mut body = match http_control::action(app, &msg.action) {
+        Ok(Some(body)) => body,
+        Ok(None) => error_body(format!("unknown action {:?}", msg.action)),
+        Err(e) => error_body(e),
+    };
+    if let Some(id) = msg.id {
+        body["id"] = id;
+    }
+    body
+}
+
+fn send(socket: &mut WebSocket<TcpStream>, body: &Value) -> bool {
+    socket.send(Message::text(body.to_string())).is_ok()
+}
+
+fn serve(app: &AppHandle, stream: TcpStream, expected_token: &str) {
+    let peer = stream.peer_addr().ok();
+    let _ = stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT));
+    // tungstenite fixes the callback's error type to a whole HTTP response.
+    #[allow(clippy::result_large_err)]
+    let check = |req: &Request, response: Response| {
+        if offered_token(req).is_some_and(|t| token_matches(&t, expected_token)) {
+            Ok(response)
+        } else {
+            tracing::warn!(?peer, "WebSocket control handshake with a bad or missing token");
+            Err(unauthorized())
+        }
+    };
+    let mut socket = match tungstenite::accept_hdr(stream, check) {
+        Ok(socket) => socket,
+        Err(e) => {
+            tracing::debug!(?peer, error = %e, "WebSocket control handshake failed");
+            return;
+        }
+    };
+    tracing::info!(?peer, "WebSocket control client connected");
+    // Reads time out so the loop gets round to pushing status changes.
+    let _ = socket.get_ref().set_read_timeout(Some(POLL_INTERVAL));
+
+    let mut last_status: Option<Value> = None;
+    loop {
+        let status = http_control::status();
+        if last_status.as_ref() != Some(&status) {
+            let mut event = status.clone();
+            event["event"] = json!("status");
+            if !send(&mut socket, &event) {
+                break;
+            }
+            last_status = Some(status);
+        }
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                if !send(&mut socket, &reply(app, &text)) {
+                    break;
+                }
+            }
+            // Pings are answered by tungstenite; a close is seen through
+            // to `ConnectionClosed` on the next read.
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) => {}
+            Err(_) => break,
+        }
+    }
+    tracing::info!(?peer, "WebSocket control client disconnected");
+}
+
+/// The token from the keyring, moving one over from `gui.toml` or
+/// generating one if there is none yet.
+pub(crate) fn ensure_token() -> AppResult<String> {
+    let legacy = gui_config::load().ws_control.token;
+    let token = match secrets::ws_token()? {
+        Some(token) => token,
+        None if !legacy.is_empty() => {
+            secrets::set_ws_token(&legacy)?;
+            tracing::info!("moved WebSocket control token from gui.toml to the keyring");
+            legacy.clone()
+        }
+        None => {
+            let token = http_control::new_token();
+            secrets::set_ws_token(&token)?;
+            tracing::info!("generated WebSocket control token");
+            token
+        }
+    };
+    if !legacy.is_empty() {
+        gui_config::update(|cfg| cfg.ws_control.token.clear())?;
+    }
+    Ok(token)
+}
+
+/// Start the server if `ws_control.enabled` is set.
+pub fn spawn_if_enabled(app: AppHandle) -> AppResult<()> {
+    let config = gui_config::load().ws_control;
+    if !config.enabled {
+        return Ok(());
+    }
+    let token = ensure_token()?;
+    let listener = TcpListener::bind((config.bind, config.port))?;
+    if !config.bind.is_loopback() {
+        tracing::warn!(bind = %config.bind, "WebSocket control server open to other machines");
+    }
+    tracing::info!(bind = %config.bind, port = config.port, "WebSocket control server listening");
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let app = app.clone();
+                    let token = token.clone();
+                    std::thread::spawn(move || serve(&app, stream, &token));
+                }
+                Err(e) => tracing::warn!(error = %e, "WebSocket control accept failed"),
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(uri: &str, header: Option<&str>) -> Request {
+        let mut builder = Request::builder().uri(uri);
+        if let Some(token) = header {
+            builder = builder.header("X-Synthia-Token", token);
+        }
+        builder.body(()).unwrap()
+    }
+
+    #[test]
+    fn token_comes_from_header_or_query() {
+        assert_eq!(offered_token(&request("/?token=abc", None)).as_deref(), Some("abc"));
+        assert_eq!(offered_token(&request("/?a=1&token=abc", None)).as_deref(), Some("abc"));
+        assert_eq!(offered_token(&request("/", Some(" xyz "))).as_deref(), Some("xyz"));
+        assert_eq!(offered_token(&request("/?tokenx=abc", None)), None);
+        assert_eq!(offered_token(&request("/", None)), None);
+    }
+
+    #[test]
+    fn client_messages_need_an_action() {
+        let msg: ClientMessage = serde_json::from_str(r#"{"id": 7, "action": "status"}"#).unwrap();
+        assert_eq!(msg.id, Some(json!(7)));
+        assert_eq!(msg.action, "status");
+        assert!(serde_json::from_str::<ClientMessage>(r#"{"id": 7}"#).is_err());
+    }
+}
@@ -52,6 +52,73 @@ pub fn write_synthia_config_keys(existing: &str, updates: &[(&str, String)]) ->
     joined
 }
 
+/// Set a single top-level scalar key, appending `key: value` at end-of-file
+/// if the key isn't present yet. For keys the stock `config.yaml` doesn't
+/// ship with, where `write_synthia_config_keys` would silently do nothing.
+pub fn upsert_synthia_config_key(existing: &str, key: &str, value: &str) -> String {
+    let present = existing.lines().any(|line| {
+        line.starts_with(|c: char| !c.is_whitespace())
+            && line.split_once(':').map(|(k, _)| k.trim()) == Some(key)
+    });
+    if present {
+        return write_synthia_config_keys(existing, &[(key, value.to_string())]);
+    }
+    let mut out = existing.to_string();
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(&format!("{key}: {value}\n"));
+    out
+}
+
+/// Set a top-level list such as `telegram_allowed_users:` to `items`, each
+/// already a YAML scalar, written in block style (`key: []` when empty).
+/// The old entries may be block or flow style; indented comments among them
+/// go with them. The list is appended at end-of-file if the key is missing.
+pub fn write_synthia_config_list(existing: &str, key: &str, items: &[String]) -> String {
+    let mut section = Vec::with_capacity(items.len() + 1);
+    if items.is_empty() {
+        section.push(format!("{key}: []"));
+    } else {
+        section.push(format!("{key}:"));
+        section.extend(items.iter().map(|item| format!("  - {item}")));
+    }
+
+    let mut out_lines: Vec<String> = Vec::with_capacity(existing.lines().count() + items.len());
+    let mut in_section = false;
+    let mut wrote_section = false;
+    for line in existing.lines() {
+        let trimmed = line.trim_start();
+        let is_top_level = line.starts_with(|c: char| !c.is_whitespace());
+        if in_section {
+            // Entries are indented, or start with `-` at column 0, which
+            // YAML also allows under a mapping key.
+            let is_entry = !trimmed.is_empty() && (!is_top_level || trimmed.starts_with('-'));
+            if is_entry {
+                continue;
+            }
+            in_section = false;
+        }
+        let is_key = is_top_level && line.split_once(':').map(|(k, _)| k.trim()) == Some(key);
+        if is_key && !wrote_section {
+            out_lines.extend(section.iter().cloned());
+            in_section = true;
+            wrote_section = true;
+            continue;
+        }
+        out_lines.push(line.to_string());
+    }
+    if !wrote_section {
+        out_lines.extend(section);
+    }
+
+    let mut joined = out_lines.join("\n");
+    if existing.ends_with('\n') || !wrote_section {
+        joined.push('\n');
+    }
+    joined
+}
+
 /// Replace the `word_replacements:` section in a config file with the given
 /// from→to mappings. Comments and other top-level sections are preserved.
 /// If the section does not exist it is appended at end-of-file with a
@@ -289,6 +356,40 @@ mod tests {
         assert!(out.contains("use_local_stt: true\n\n"));
     }
 
+    #[test]
+    fn upsert_synthia_config_key_replaces_or_appends() {
+        let input = "# Audio\naudio_device: \"old\"\ntts_speed: 1.0\n";
+        let out = upsert_synthia_config_key(input, "audio_device", "\"USB Mic\"");
+        assert_eq!(out, "# Audio\naudio_device: \"USB Mic\"\ntts_speed: 1.0\n");
+
+        let out = upsert_synthia_config_key("tts_speed: 1.0", "audio_device", "\"USB Mic\"");
+        assert_eq!(out, "tts_speed: 1.0\naudio_device: \"USB Mic\"\n");
+    }
+
+    #[test]
+    fn write_synthia_config_list_replaces_block_and_flow_lists() {
+        let input =
+            "# Remote\ntelegram_allowed_users:\n  - 123\n  # me\n  - 456\n\ntts_speed: 1.0\n";
+        let items = vec!["789".to_string()];
+        let out = write_synthia_config_list(input, "telegram_allowed_users", &items);
+        assert_eq!(out, "# Remote\ntelegram_allowed_users:\n  - 789\n\ntts_speed: 1.0\n");
+
+        let input = "matrix_allowed_users: [\"@a:b.org\"]\ntts_speed: 1.0\n";
+        let out = write_synthia_config_list(input, "matrix_allowed_users", &[]);
+        assert_eq!(out, "matrix_allowed_users: []\ntts_speed: 1.0\n");
+
+        let input = "discord_allowed_users:\n- 1\n- 2\ntts_speed: 1.0";
+        let out = write_synthia_config_list(input, "discord_allowed_users", &items);
+        assert_eq!(out, "discord_allowed_users:\n  - 789\ntts_speed: 1.0");
+    }
+
+    #[test]
+    fn write_synthia_config_list_appends_missing_key() {
+        let items = vec!["\"@me:matrix.org\"".to_string()];
+        let out = write_synthia_config_list("tts_speed: 1.0", "matrix_allowed_users", &items);
+        assert_eq!(out, "tts_speed: 1.0\nmatrix_allowed_users:\n  - \"@me:matrix.org\"\n");
+    }
+
     #[test]
     fn write_word_replacements_replaces_section_in_place() {
         let input = "use_local_stt: true\nword_replacements:\n  old: new\n  foo: bar\n# trailing comment\n";
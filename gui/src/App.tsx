@@ -1,17 +1,144 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { openUrl } from "@tauri-apps/plugin-opener";
 import Markdown from "react-markdown";
 import { JournalPanel } from "./components/JournalPanel";
+import { SetupPanel, type InstallStatus } from "./components/SetupPanel";
 import "./App.css";
-type Status = "stopped" | "running" | "recording" | "thinking";
+type Status = "stopped" | "starting" | "stopping" | "running" | "recording" | "thinking";
 
 interface HistoryEntry {
   id: number;
   text: string;
   mode: "dictation" | "assistant";
   timestamp: string;
-  response?: string;
+  duration_secs?: number | null;
+  model?: string | null;
+  response?: string | null;
+}
+
+interface RemoteActivity {
+  id: number;
+  timestamp: string;
+  channel: string;
+  direction: "in" | "out";
+  sender: string;
+  kind: string;
+  text: string;
+}
+
+type RemoteLogRange = "day" | "week" | "all";
+
+const REMOTE_LOG_RANGES: { value: RemoteLogRange; label: string; hours: number | null }[] = [
+  { value: "day", label: "Last 24 hours", hours: 24 },
+  { value: "week", label: "Last 7 days", hours: 24 * 7 },
+  { value: "all", label: "Everything", hours: null },
+];
+
+interface SnippetPart {
+  text: string;
+  highlight: boolean;
+}
+
+interface SearchHit {
+  entry: HistoryEntry;
+  snippet: SnippetPart[];
+  score: number;
+}
+
+interface HistoryPage {
+  entries: HistoryEntry[];
+  total: number;
+  page: number;
+  page_size: number;
+}
+
+interface WakeSensitivity {
+  sensitivity: number;
+  detections: { accepted: number; false_triggers: number } | null;
+}
+
+interface ResourceUsage {
+  at: number;
+  pids: number[];
+  cpu_percent: number;
+  rss_bytes: number;
+  vram_mb: number | null;
+  system_total_bytes: number;
+  system_available_bytes: number;
+  memory_pressure: boolean;
+}
+
+// Samples drawn in the usage graph: three minutes at one every 3s.
+const USAGE_SAMPLES = 60;
+
+function sparkline(values: number[], max: number): string {
+  if (values.length < 2 || max <= 0) return "";
+  const step = 100 / (USAGE_SAMPLES - 1);
+  const offset = 100 - step * (values.length - 1);
+  return values
+    .map((v, i) => `${(offset + i * step).toFixed(1)},${(30 - (Math.min(v, max) / max) * 30).toFixed(1)}`)
+    .join(" ");
+}
+
+interface RemoteHealth {
+  status:
+    | "stopped"
+    | "starting"
+    | "connected"
+    | "disconnected"
+    | "external"
+    | "restarting"
+    | "failed";
+  running: boolean;
+  restarts: number;
+  max_restarts: number;
+  restart_in_ms: number | null;
+}
+
+interface PendingApproval {
+  id: string;
+  text: string;
+  reasons: string[];
+  requested_at: string;
+  expires_at: string;
+}
+
+type RemoteChannel = "telegram" | "discord" | "matrix";
+
+const REMOTE_CHANNELS: { value: RemoteChannel; label: string; tokenHint: string }[] = [
+  { value: "telegram", label: "Telegram", tokenHint: "Bot token from @BotFather" },
+  { value: "discord", label: "Discord", tokenHint: "Bot token from the developer portal" },
+  { value: "matrix", label: "Matrix", tokenHint: "Access token of the bot account" },
+];
+
+function remoteChannelInfo(channel: RemoteChannel) {
+  return REMOTE_CHANNELS.find((c) => c.value === channel) ?? REMOTE_CHANNELS[0];
+}
+
+function describeRemoteHealth(
+  health: RemoteHealth | null,
+  remoteMode: boolean,
+  channel: RemoteChannel
+): string {
+  const name = remoteChannelInfo(channel).label;
+  switch (health?.status) {
+    case "starting":
+      return `${name} bot starting…`;
+    case "connected":
+      return `${name} bot active - control via phone`;
+    case "disconnected":
+      return `${name} unreachable - the bot keeps retrying`;
+    case "restarting":
+      return `${name} bot crashed - restarting in ${Math.round(
+        (health.restart_in_ms ?? 0) / 1000
+      )}s (attempt ${health.restarts} of ${health.max_restarts})`;
+    case "failed":
+      return `${name} bot kept crashing - remote mode turned off`;
+    default:
+      return remoteMode ? `${name} bot active - control via phone` : `${name} bot disabled`;
+  }
 }
 
 interface WordReplacement {
@@ -409,7 +536,7 @@ interface NoteEntry {
   is_dir: boolean;
 }
 
-type VoiceView = "main" | "history" | "words";
+type VoiceView = "main" | "history" | "words" | "remote";
 type MemoryCategory = "bug" | "pattern" | "arch" | "gotcha" | "stack" | null;
 type ConfigTab = "synthia" | "agents" | "commands" | "skills" | "hooks" | "plugins";
 
@@ -417,11 +544,26 @@ function App() {
   const [status, setStatus] = useState<Status>("stopped");
   const [remoteMode, setRemoteMode] = useState(false);
   const [remoteToggling, setRemoteToggling] = useState(false);
+  const [remoteHealth, setRemoteHealth] = useState<RemoteHealth | null>(null);
+  const [remoteApprovals, setRemoteApprovals] = useState<PendingApproval[]>([]);
+  const [hasRemoteToken, setHasRemoteToken] = useState(false);
+  const [remoteChannel, setRemoteChannel] = useState<RemoteChannel>("telegram");
+  const [remoteTokenInput, setRemoteTokenInput] = useState("");
+  const [allowedUsers, setAllowedUsers] = useState<string[]>([]);
+  const [allowedUserInput, setAllowedUserInput] = useState("");
   const [dictateKey, setDictateKey] = useState("Right Ctrl");
+  const [wake, setWake] = useState<WakeSensitivity | null>(null);
+  const [usage, setUsage] = useState<ResourceUsage[]>([]);
   const [assistantKey, setAssistantKey] = useState("Right Alt");
   const [editingKey, setEditingKey] = useState<"dictate" | "assistant" | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [installStatus, setInstallStatus] = useState<InstallStatus | null>(null);
   const [history, setHistory] = useState<HistoryEntry[]>([]);
+  const [remoteLog, setRemoteLog] = useState<RemoteActivity[]>([]);
+  const [remoteLogRange, setRemoteLogRange] = useState<RemoteLogRange>("day");
+  const [historyQuery, setHistoryQuery] = useState("");
+  const [searchHits, setSearchHits] = useState<SearchHit[] | null>(null);
+  const [exportFormat, setExportFormat] = useState<"markdown" | "json" | "csv">("markdown");
   const [currentSection, setCurrentSection] = useState<Section>("agents");
   const [voiceView, setVoiceView] = useState<VoiceView>("main");
   const [worktrees, setWorktrees] = useState<WorktreeInfo[]>([]);
@@ -549,16 +691,114 @@ function App() {
     error: string | null;
   } | null>(null);
 
+  // Flip from "starting" as soon as the backend reports it has loaded
+  useEffect(() => {
+    const unlisten = listen<boolean>("backend-ready", (event) => {
+      if (event.payload) setStatus((prev) => (prev === "starting" ? "running" : prev));
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Surface crash restarts from the backend supervisor
+  useEffect(() => {
+    const unlistenCrashed = listen<{
+      attempt: number;
+      max_restarts: number;
+      restart_in_ms: number | null;
+    }>("backend-crashed", (event) => {
+      const { attempt, max_restarts, restart_in_ms } = event.payload;
+      setStatus("stopped");
+      setError(
+        restart_in_ms === null
+          ? "Synthia crashed repeatedly and was not restarted"
+          : `Synthia crashed; restarting in ${Math.round(restart_in_ms / 1000)}s (${attempt}/${max_restarts})`
+      );
+    });
+    const unlistenRestarted = listen("backend-restarted", () => {
+      setStatus("starting");
+      setError(null);
+    });
+    return () => {
+      unlistenCrashed.then((fn) => fn());
+      unlistenRestarted.then((fn) => fn());
+    };
+  }, []);
+
+  // Show "Stopping…" while the backend winds down, however it was stopped
+  useEffect(() => {
+    const unlisten = listen<{ phase: "requested" | "signalling" | "stopped" }>(
+      "backend-stopping",
+      (event) => setStatus(event.payload.phase === "stopped" ? "stopped" : "stopping")
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    invoke<RemoteHealth>("get_remote_health").then(setRemoteHealth).catch(() => {});
+    const unlisten = listen<RemoteHealth>("remote-status-changed", (event) =>
+      setRemoteHealth(event.payload)
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    const unlisten = listen<RemoteActivity>("remote-activity", (event) =>
+      setRemoteLog((log) => [event.payload, ...log])
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    invoke<PendingApproval[]>("list_remote_approvals").then(setRemoteApprovals).catch(() => {});
+    const unlisten = listen<PendingApproval[]>("remote-approvals-changed", (event) =>
+      setRemoteApprovals(event.payload)
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    invoke<ResourceUsage[]>("get_resource_usage").then(setUsage).catch(() => {});
+    const unlisten = listen<ResourceUsage>("resource-usage", (event) => {
+      setUsage((prev) => [...prev, event.payload].slice(-USAGE_SAMPLES));
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Refresh status as soon as the backend writes its state file
+  useEffect(() => {
+    const unlisten = listen("synthia-state", () => {
+      checkStatus();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   useEffect(() => {
     // Auto-start Synthia when app opens
     async function initAndAutoStart() {
       try {
+        const install = await invoke<InstallStatus>("check_installation");
+        setInstallStatus(install);
+        if (!install.configured) return;
         const currentStatus = await invoke<string>("get_status");
         setStatus(currentStatus as Status);
         // If stopped, auto-start
         if (currentStatus === "stopped") {
           await invoke("start_synthia");
-          setStatus("running");
+          setStatus("starting");
         }
       } catch (e) {
         setError(String(e));
@@ -578,7 +818,10 @@ function App() {
 
     initAndAutoStart();
     loadHotkeys();
+    loadWakeSensitivity();
     checkRemoteStatus();
+    loadRemoteToken();
+    loadAllowedUsers();
     loadHistory();
     loadWordReplacements();
     loadWorktrees();
@@ -616,6 +859,7 @@ function App() {
       checkRemoteStatus();
       if (currentSection === "worktrees") loadWorktrees();
       if (currentSection === "voice" && voiceView === "history") loadHistory();
+      if (currentSection === "voice" && voiceView === "main") loadWakeSensitivity();
     }, 2000);
 
     return () => {
@@ -623,6 +867,22 @@ function App() {
     };
   }, [currentSection, voiceView, memoryFilter]);
 
+  // Search history as the user types, once they pause
+  useEffect(() => {
+    if (!historyQuery.trim()) {
+      setSearchHits(null);
+      return;
+    }
+    const timer = setTimeout(async () => {
+      try {
+        setSearchHits(await invoke<SearchHit[]>("search_history", { query: historyQuery }));
+      } catch (e) {
+        setError(String(e));
+      }
+    }, 200);
+    return () => clearTimeout(timer);
+  }, [historyQuery]);
+
   // Load note metadata (previews for pinned, timestamps for pinned + recent)
   useEffect(() => {
     if (currentSection === "knowledge" && (pinnedNotes.length > 0 || recentNotes.length > 0)) {
@@ -1678,7 +1938,7 @@ function App() {
   async function handleStart() {
     try {
       await invoke("start_synthia");
-      setStatus("running");
+      setStatus("starting");
       setError(null);
     } catch (e) {
       setError(String(e));
@@ -1686,6 +1946,7 @@ function App() {
   }
 
   async function handleStop() {
+    setStatus("stopping");
     try {
       await invoke("stop_synthia");
       setStatus("stopped");
@@ -1695,6 +1956,24 @@ function App() {
     }
   }
 
+  async function loadWakeSensitivity() {
+    try {
+      setWake(await invoke<WakeSensitivity>("get_wake_sensitivity"));
+    } catch (e) {
+      // Config unreadable; hide the slider
+    }
+  }
+
+  async function handleWakeSensitivity(value: number) {
+    setWake((prev) => (prev ? { ...prev, sensitivity: value } : prev));
+    try {
+      const applied = await invoke<number>("set_wake_sensitivity", { sensitivity: value });
+      setWake((prev) => (prev ? { ...prev, sensitivity: applied } : prev));
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
   async function checkRemoteStatus() {
     // Skip polling if we're in the middle of a toggle action
     if (remoteToggling) return;
@@ -1728,18 +2007,108 @@ function App() {
     setTimeout(() => setRemoteToggling(false), 3000);
   }
 
+  async function loadRemoteToken() {
+    try {
+      setRemoteChannel(await invoke<RemoteChannel>("get_setting", { key: "remote_channel" }));
+      setHasRemoteToken(await invoke<boolean>("has_remote_token"));
+    } catch (e) {
+      // No keyring available; the token can still come from config.yaml
+    }
+  }
+
+  async function handleRemoteChannelChange(channel: RemoteChannel) {
+    try {
+      await invoke("set_setting", { key: "remote_channel", value: channel });
+      setRemoteChannel(channel);
+      setRemoteTokenInput("");
+      setError(null);
+      // Each channel keeps its own token and allowlist
+      await loadRemoteToken();
+      await loadAllowedUsers();
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
+  async function loadAllowedUsers() {
+    try {
+      setAllowedUsers(await invoke<string[]>("list_allowed_users"));
+    } catch (e) {
+      console.error("Failed to load allowed users:", e);
+    }
+  }
+
+  async function handleAddAllowedUser() {
+    try {
+      setAllowedUsers(await invoke<string[]>("add_allowed_user", { id: allowedUserInput }));
+      setAllowedUserInput("");
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
+  async function handleRemoveAllowedUser(id: string) {
+    try {
+      setAllowedUsers(await invoke<string[]>("remove_allowed_user", { id }));
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
+  async function handleSaveRemoteToken() {
+    try {
+      await invoke("set_remote_token", { token: remoteTokenInput });
+      setRemoteTokenInput("");
+      setHasRemoteToken(true);
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
+  async function handleClearRemoteToken() {
+    try {
+      await invoke("clear_remote_token");
+      setHasRemoteToken(false);
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
+  async function handleRemoteApproval(id: string, approved: boolean) {
+    try {
+      await invoke(approved ? "approve_remote_command" : "deny_remote_command", { id });
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
+  async function loadRemoteLog(range: RemoteLogRange = remoteLogRange) {
+    const hours = REMOTE_LOG_RANGES.find((r) => r.value === range)?.hours ?? null;
+    const since = hours === null ? null : new Date(Date.now() - hours * 3600 * 1000).toISOString();
+    try {
+      setRemoteLog(await invoke<RemoteActivity[]>("get_remote_log", { range: { since } }));
+    } catch (e) {
+      console.error("Failed to load remote activity:", e);
+    }
+  }
+
   async function loadHistory() {
     try {
-      const result = await invoke<HistoryEntry[]>("get_history");
-      setHistory(result.reverse()); // Show newest first
+      const result = await invoke<HistoryPage>("get_history", { page: 0 });
+      setHistory(result.entries); // Newest first
     } catch (e) {
       // Ignore errors
     }
   }
 
-  async function handleCopy(text: string, id: number) {
+  async function handleCopy(id: number) {
     try {
-      await navigator.clipboard.writeText(text);
+      await invoke("copy_history_entry", { id });
       setCopiedId(id);
       setTimeout(() => setCopiedId(null), 2000);
     } catch (e) {
@@ -1755,6 +2124,25 @@ function App() {
     }
   }
 
+  async function handleDeleteHistoryEntry(id: number) {
+    try {
+      await invoke("delete_history_entry", { id });
+      setHistory((prev) => prev.filter((entry) => entry.id !== id));
+      setSearchHits((prev) => prev && prev.filter((hit) => hit.entry.id !== id));
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
+  async function handleExportHistory() {
+    try {
+      // Opens a save dialog; resolves to null if the user cancels
+      await invoke<string | null>("export_history", { format: exportFormat });
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
   async function handleClearHistory() {
     try {
       await invoke("clear_history");
@@ -1801,6 +2189,8 @@ function App() {
 
   const statusColors: Record<Status, string> = {
     stopped: "#6b7280",
+    starting: "#06b6d4",
+    stopping: "#06b6d4",
     running: "#22c55e",
     recording: "#ef4444",
     thinking: "#eab308",
@@ -2793,6 +3183,8 @@ function App() {
   function renderVoiceSection() {
     // History sub-view
     if (voiceView === "history") {
+      const shown = searchHits ? searchHits.map((hit) => hit.entry) : history;
+      const snippets = new Map(searchHits?.map((hit) => [hit.entry.id, hit.snippet]));
       return (
         <div className="voice-section">
           <div className="header history-view-header">
@@ -2801,21 +3193,48 @@ function App() {
             </button>
             <div className="logo-text-small">VOICE HISTORY</div>
             {history.length > 0 && (
-              <button className="clear-all-btn" onClick={handleClearHistory}>
-                Clear All
-              </button>
+              <div className="history-header-actions">
+                <select
+                  className="history-export-format"
+                  value={exportFormat}
+                  onChange={(e) => setExportFormat(e.target.value as typeof exportFormat)}
+                >
+                  <option value="markdown">Markdown</option>
+                  <option value="json">JSON</option>
+                  <option value="csv">CSV</option>
+                </select>
+                <button className="back-btn" onClick={handleExportHistory}>
+                  Export
+                </button>
+                <button className="clear-all-btn" onClick={handleClearHistory}>
+                  Clear All
+                </button>
+              </div>
             )}
           </div>
 
           <div className="history-view-content">
-            {history.length === 0 ? (
+            <input
+              className="history-search"
+              type="search"
+              placeholder="Search transcriptions…"
+              value={historyQuery}
+              onChange={(e) => setHistoryQuery(e.target.value)}
+            />
+            {shown.length === 0 ? (
               <div className="history-empty-state">
-                <p>No transcriptions yet</p>
-                <p className="empty-hint">Use voice dictation or assistant to see history here</p>
+                {searchHits ? (
+                  <p>No matches</p>
+                ) : (
+                  <>
+                    <p>No transcriptions yet</p>
+                    <p className="empty-hint">Use voice dictation or assistant to see history here</p>
+                  </>
+                )}
               </div>
             ) : (
               <div className="history-list-full">
-                {history.map((entry) => (
+                {shown.map((entry) => (
                   <div key={entry.id} className={`history-item ${entry.mode}`}>
                     <div className="history-item-header">
                       <span className={`history-mode-label ${entry.mode}`}>
@@ -2823,14 +3242,20 @@ function App() {
                       </span>
                       <span className="history-time">{formatTime(entry.timestamp)}</span>
                     </div>
-                    <p className="history-text">{entry.text}</p>
+                    <p className="history-text">
+                      {snippets.has(entry.id)
+                        ? snippets.get(entry.id)!.map((part, i) =>
+                            part.highlight ? <mark key={i}>{part.text}</mark> : part.text
+                          )
+                        : entry.text}
+                    </p>
                     {entry.response && (
                       <p className="history-response">→ {entry.response}</p>
                     )}
                     <div className="history-item-actions">
                       <button
                         className={`history-btn ${copiedId === entry.id ? 'copied' : ''}`}
-                        onClick={() => handleCopy(entry.text, entry.id)}
+                        onClick={() => handleCopy(entry.id)}
                       >
                         {copiedId === entry.id ? "✓" : "Copy"}
                       </button>
@@ -2840,6 +3265,12 @@ function App() {
                       >
                         Re-send
                       </button>
+                      <button
+                        className="history-btn"
+                        onClick={() => handleDeleteHistoryEntry(entry.id)}
+                      >
+                        Delete
+                      </button>
                     </div>
                   </div>
                 ))}
@@ -2850,6 +3281,61 @@ function App() {
       );
     }
 
+    // Remote activity sub-view
+    if (voiceView === "remote") {
+      return (
+        <div className="voice-section">
+          <div className="header history-view-header">
+            <button className="back-btn" onClick={() => setVoiceView("main")}>
+              ← Back
+            </button>
+            <div className="logo-text-small">REMOTE ACTIVITY</div>
+            <div className="history-header-actions">
+              <select
+                className="history-export-format"
+                value={remoteLogRange}
+                onChange={(e) => {
+                  const range = e.target.value as RemoteLogRange;
+                  setRemoteLogRange(range);
+                  loadRemoteLog(range);
+                }}
+              >
+                {REMOTE_LOG_RANGES.map((r) => (
+                  <option key={r.value} value={r.value}>{r.label}</option>
+                ))}
+              </select>
+            </div>
+          </div>
+
+          <div className="history-view-content">
+            {remoteLog.length === 0 ? (
+              <div className="history-empty-state">
+                <p>No remote activity</p>
+                <p className="empty-hint">Messages sent to the remote bot show up here</p>
+              </div>
+            ) : (
+              <div className="history-list-full">
+                {remoteLog.map((entry) => (
+                  <div key={entry.id} className={`history-item remote-${entry.direction}`}>
+                    <div className="history-item-header">
+                      <span className={`history-mode-label remote-${entry.direction}`}>
+                        {entry.kind}
+                      </span>
+                      <span className="history-time">
+                        {entry.direction === "in" ? `${entry.channel} ${entry.sender} · ` : ""}
+                        {new Date(entry.timestamp).toLocaleString()}
+                      </span>
+                    </div>
+                    {entry.text && <p className="history-text">{entry.text}</p>}
+                  </div>
+                ))}
+              </div>
+            )}
+          </div>
+        </div>
+      );
+    }
+
     // Words sub-view
     if (voiceView === "words") {
       return (
@@ -2923,7 +3409,10 @@ function App() {
             className={`status-indicator ${status === "running" ? "running" : ""}`}
             style={{ backgroundColor: statusColors[status] }}
           />
-          <span className="status-text">{status.charAt(0).toUpperCase() + status.slice(1)}</span>
+          <span className="status-text">
+            {status.charAt(0).toUpperCase() + status.slice(1)}
+            {status === "stopping" && "…"}
+          </span>
         </div>
 
         <div className="controls">
@@ -2932,7 +3421,11 @@ function App() {
               Start Synthia
             </button>
           ) : (
-            <button className="btn btn-stop" onClick={handleStop}>
+            <button
+              className="btn btn-stop"
+              onClick={handleStop}
+              disabled={status === "stopping"}
+            >
               Stop Synthia
             </button>
           )}
@@ -2940,7 +3433,18 @@ function App() {
 
         <div className="card">
           <div className="remote-toggle">
-            <span>Remote Mode (Telegram)</span>
+            <span>Remote Mode</span>
+            <select
+              className="remote-channel-select"
+              value={remoteChannel}
+              onChange={(e) => handleRemoteChannelChange(e.target.value as RemoteChannel)}
+              disabled={remoteMode}
+              title={remoteMode ? "Stop remote mode to switch channels" : undefined}
+            >
+              {REMOTE_CHANNELS.map((c) => (
+                <option key={c.value} value={c.value}>{c.label}</option>
+              ))}
+            </select>
             <button
               className={`toggle ${remoteMode ? "active" : ""}`}
               onClick={handleRemoteToggle}
@@ -2949,8 +3453,85 @@ function App() {
             </button>
           </div>
           <p className="remote-description">
-            {remoteMode ? "Telegram bot active - control via phone" : "Telegram bot disabled"}
+            {describeRemoteHealth(remoteHealth, remoteMode, remoteChannel)}
           </p>
+          {remoteApprovals.length > 0 && (
+            <ul className="remote-approvals">
+              {remoteApprovals.map((approval) => (
+                <li key={approval.id}>
+                  <code title={`Held for: ${approval.reasons.join(", ")}`}>{approval.text}</code>
+                  <button
+                    className="claude-btn small primary"
+                    onClick={() => handleRemoteApproval(approval.id, true)}
+                  >
+                    Approve
+                  </button>
+                  <button
+                    className="claude-btn small danger"
+                    onClick={() => handleRemoteApproval(approval.id, false)}
+                  >
+                    Deny
+                  </button>
+                </li>
+              ))}
+            </ul>
+          )}
+          <div className="remote-token">
+            {hasRemoteToken ? (
+              <>
+                <span>Bot token saved in keyring</span>
+                <button className="claude-btn small danger" onClick={handleClearRemoteToken}>
+                  Remove
+                </button>
+              </>
+            ) : (
+              <>
+                <input
+                  type="password"
+                  placeholder={remoteChannelInfo(remoteChannel).tokenHint}
+                  value={remoteTokenInput}
+                  onChange={(e) => setRemoteTokenInput(e.target.value)}
+                />
+                <button
+                  className="claude-btn small primary"
+                  onClick={handleSaveRemoteToken}
+                  disabled={!remoteTokenInput.trim()}
+                >
+                  Save
+                </button>
+              </>
+            )}
+          </div>
+          <div className="remote-allowlist">
+            <span>Allowed users</span>
+            {allowedUsers.length === 0 && <span className="remote-allowlist-empty">None yet</span>}
+            {allowedUsers.map((id) => (
+              <span key={id} className="remote-allowlist-user">
+                {id}
+                <button
+                  onClick={() => handleRemoveAllowedUser(id)}
+                  title={remoteMode ? "Applies when remote mode restarts" : "Remove"}
+                >
+                  ×
+                </button>
+              </span>
+            ))}
+            <div className="remote-token">
+              <input
+                placeholder={remoteChannel === "matrix" ? "@you:matrix.org" : "Numeric user ID"}
+                value={allowedUserInput}
+                onChange={(e) => setAllowedUserInput(e.target.value)}
+                onKeyDown={(e) => e.key === "Enter" && allowedUserInput.trim() && handleAddAllowedUser()}
+              />
+              <button
+                className="claude-btn small primary"
+                onClick={handleAddAllowedUser}
+                disabled={!allowedUserInput.trim()}
+              >
+                Add
+              </button>
+            </div>
+          </div>
         </div>
 
         <div className="card hotkeys">
@@ -2975,6 +3556,60 @@ function App() {
           </div>
         </div>
 
+        {wake && (
+          <div className="card wake-sensitivity">
+            <h3>Wake Word Sensitivity</h3>
+            <div className="wake-slider-row">
+              <span>Fewer false triggers</span>
+              <input
+                type="range"
+                min={0}
+                max={1}
+                step={0.05}
+                value={wake.sensitivity}
+                onChange={(e) => handleWakeSensitivity(parseFloat(e.target.value))}
+              />
+              <span>Fewer misses</span>
+            </div>
+            <p className="wake-detections">
+              {wake.detections
+                ? `${wake.detections.accepted} heard · ${wake.detections.false_triggers} false triggers since start`
+                : `Sensitivity ${wake.sensitivity.toFixed(2)}`}
+            </p>
+          </div>
+        )}
+
+        {usage.length > 0 && usage[usage.length - 1].pids.length > 0 && (() => {
+          const latest = usage[usage.length - 1];
+          const mb = (bytes: number) => Math.round(bytes / (1024 * 1024));
+          return (
+            <div className={`card resource-usage ${latest.memory_pressure ? "pressure" : ""}`}>
+              <h3>Backend Usage</h3>
+              <svg viewBox="0 0 100 30" preserveAspectRatio="none" className="usage-graph">
+                <polyline
+                  className="usage-memory"
+                  points={sparkline(usage.map((u) => u.rss_bytes), latest.system_total_bytes)}
+                />
+                <polyline
+                  className="usage-cpu"
+                  points={sparkline(usage.map((u) => u.cpu_percent), 100 * navigator.hardwareConcurrency)}
+                />
+              </svg>
+              <p className="usage-figures">
+                <span className="usage-cpu-label">CPU {latest.cpu_percent.toFixed(0)}%</span>
+                {" · "}
+                <span className="usage-memory-label">RAM {mb(latest.rss_bytes)} MB</span>
+                {latest.vram_mb !== null && ` · VRAM ${latest.vram_mb} MB`}
+              </p>
+              {latest.memory_pressure && (
+                <p className="usage-warning">
+                  The model is using most of this machine's memory; a smaller model may help.
+                </p>
+              )}
+            </div>
+          );
+        })()}
+
         <button
           className="history-nav-btn"
           onClick={() => { setVoiceView("history"); loadHistory(); }}
@@ -2991,6 +3626,13 @@ function App() {
           {wordReplacements.length > 0 && <span className="history-count">{wordReplacements.length}</span>}
         </button>
 
+        <button
+          className="history-nav-btn"
+          onClick={() => { setVoiceView("remote"); loadRemoteLog(); }}
+        >
+          <span>Remote Activity</span>
+        </button>
+
         {error && <div className="error">{error}</div>}
       </div>
     );
@@ -4413,6 +5055,22 @@ function App() {
     );
   }
 
+  if (installStatus && !installStatus.configured) {
+    return (
+      <div className="app-shell">
+        <SetupPanel
+          status={installStatus}
+          onResolved={(install) => {
+            setInstallStatus(install);
+            if (install.configured) {
+              invoke("start_synthia").then(() => setStatus("running")).catch((e) => setError(String(e)));
+            }
+          }}
+        />
+      </div>
+    );
+  }
+
   return (
     <div className="app-shell">
       <div className="app-layout">
@@ -3,8 +3,33 @@ import { listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import "./Overlay.css";
 
+function formatElapsed(secs: number) {
+  const m = Math.floor(secs / 60);
+  const s = secs % 60;
+  return `${String(m).padStart(2, "0")}:${String(s).padStart(2, "0")}`;
+}
+
+const BAR_COUNT = 5;
+const BAR_MIN_PX = 4;
+const BAR_MAX_PX = 20;
+const SILENT = Array<number>(BAR_COUNT).fill(0);
+
+// Backend states the overlay styles on top of recording; "idle" and
+// "listening" are covered by the `recording` event.
+type Phase = "idle" | "starting" | "thinking" | "speaking" | "error";
+const PHASES: readonly string[] = ["starting", "thinking", "speaking", "error"];
+
 function Overlay() {
   const [isActive, setIsActive] = useState(false);
+  const [elapsed, setElapsed] = useState<number | null>(null);
+  const [phase, setPhase] = useState<Phase>("idle");
+  // What went wrong, while the phase is "error"
+  const [errorDetail, setErrorDetail] = useState<string | null>(null);
+  // Last few input levels (0..1), newest on the right; null until the
+  // GUI's level meter reports, in which case the bars keep their CSS wave.
+  const [levels, setLevels] = useState<number[] | null>(null);
+  // Words decoded so far for the current utterance; empty hides the line
+  const [transcript, setTranscript] = useState({ text: "", final: false });
 
   useEffect(() => {
     // Listen for recording state changes from Tauri backend
@@ -12,11 +37,51 @@ function Overlay() {
       setIsActive(event.payload);
     });
 
+    const unlistenLevel = listen<{ rms: number; peak: number; level: number }>(
+      "audio-level",
+      (event) => {
+        setLevels((prev) => [...(prev ?? SILENT).slice(1), event.payload.level]);
+      }
+    );
+
+    const unlistenElapsed = listen<{ recording: boolean; elapsed_secs: number }>(
+      "recording-elapsed",
+      (event) => {
+        setElapsed(event.payload.recording ? event.payload.elapsed_secs : null);
+      }
+    );
+
+    const unlistenTranscript = listen<{ text: string; final: boolean }>(
+      "overlay-transcript",
+      (event) => {
+        setTranscript(event.payload);
+      }
+    );
+
+    // Loading models, working on a reply, speaking it, or failed
+    const unlistenState = listen<{ state: string; detail: string | null }>(
+      "overlay-state",
+      (event) => {
+        const { state, detail } = event.payload;
+        setPhase(PHASES.includes(state) ? (state as Phase) : "idle");
+        setErrorDetail(state === "error" ? detail : null);
+      }
+    );
+
     return () => {
       unlistenRecording.then((fn) => fn());
+      unlistenElapsed.then((fn) => fn());
+      unlistenLevel.then((fn) => fn());
+      unlistenState.then((fn) => fn());
+      unlistenTranscript.then((fn) => fn());
     };
   }, []);
 
+  // Each recording starts on the CSS wave until its first level arrives
+  useEffect(() => {
+    setLevels(null);
+  }, [isActive]);
+
   // Handle drag - must use startDragging for transparent windows
   async function startDrag(e: React.MouseEvent) {
     e.preventDefault();
@@ -24,9 +89,12 @@ function Overlay() {
     await appWindow.startDragging();
   }
 
-  // Generate bars
-  const bars = Array.from({ length: 5 }, (_, i) => {
-    const height = 6 + i * 2;
+  // Generate bars: live levels while metering, else a static ramp
+  const live = isActive ? levels : null;
+  const bars = Array.from({ length: BAR_COUNT }, (_, i) => {
+    const height = live
+      ? BAR_MIN_PX + live[i] * (BAR_MAX_PX - BAR_MIN_PX)
+      : 6 + i * 2;
     return (
       <div
         key={i}
@@ -38,12 +106,19 @@ function Overlay() {
 
   return (
     <div
-      className={`overlay-container ${isActive ? "active" : ""}`}
+      className={`overlay-container ${isActive ? "active" : ""} ${live ? "metered" : ""} ${phase}`}
       onMouseDown={startDrag}
     >
       <div className="pill">
         <div className="indicator" />
         <div className="bars">{bars}</div>
+        {elapsed !== null && <div className="timer">{formatElapsed(elapsed)}</div>}
+        {errorDetail && <div className="transcript error-detail">{errorDetail}</div>}
+        {transcript.text && !errorDetail && (
+          <div className={`transcript ${transcript.final ? "final" : ""}`}>
+            {transcript.text}
+          </div>
+        )}
       </div>
     </div>
   );
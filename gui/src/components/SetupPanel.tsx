@@ -0,0 +1,71 @@
+import { invoke } from "@tauri-apps/api/core";
+import { useState } from "react";
+
+export interface InstallCheck {
+  name: string;
+  path: string;
+  ok: boolean;
+  problem: string | null;
+  hint: string;
+  required: boolean;
+}
+
+export interface InstallStatus {
+  configured: boolean;
+  root: string;
+  checks: InstallCheck[];
+}
+
+const CHECK_LABELS: Record<string, string> = {
+  install_dir: "Synthia directory",
+  run_script: "run.sh",
+  python: "Python virtualenv",
+  config: "config.yaml",
+  icons: "Tray icons",
+};
+
+export function SetupPanel({
+  status,
+  onResolved,
+}: {
+  status: InstallStatus;
+  onResolved: (status: InstallStatus) => void;
+}) {
+  const [checking, setChecking] = useState(false);
+
+  async function recheck() {
+    setChecking(true);
+    try {
+      onResolved(await invoke<InstallStatus>("check_installation"));
+    } finally {
+      setChecking(false);
+    }
+  }
+
+  return (
+    <div className="setup-panel">
+      <h2>Finish setting up Synthia</h2>
+      <p className="setup-root">
+        Looking in <code>{status.root}</code>
+      </p>
+      <ul className="setup-checks">
+        {status.checks.map((check) => (
+          <li key={check.name} className={check.ok ? "ok" : check.required ? "missing" : "optional"}>
+            <span className="setup-check-mark">{check.ok ? "✓" : check.required ? "✗" : "–"}</span>
+            <div>
+              <div className="setup-check-name">
+                {CHECK_LABELS[check.name] ?? check.name}
+                {!check.ok && check.problem && <span className="setup-check-problem"> — {check.problem}</span>}
+              </div>
+              <code className="setup-check-path">{check.path}</code>
+              {!check.ok && <div className="setup-check-hint">{check.hint}</div>}
+            </div>
+          </li>
+        ))}
+      </ul>
+      <button className="setup-recheck" onClick={recheck} disabled={checking}>
+        {checking ? "Checking..." : "Check again"}
+      </button>
+    </div>
+  );
+}
@@ -22,7 +22,10 @@ class AudioRecorder:
     """Records audio from the microphone while a key is held."""
 
     def __init__(
-        self, target_sample_rate: int = 16000, channels: int = 1, device: Optional[int] = None
+        self,
+        target_sample_rate: int = 16000,
+        channels: int = 1,
+        device: int | str | None = None,
     ) -> None:
         self.target_sample_rate = target_sample_rate  # What Google expects
         self.channels = channels
@@ -21,6 +21,9 @@ DEFAULT_CONFIG: ConfigDict = {
     # Speech Recognition
     "language": "en-US",
     "sample_rate": 16000,
+    "wake_word": "",  # Hands-free trigger phrase; empty disables
+    "vad_sensitivity": 0.5,  # Voice activity detection, 0.0 (least) to 1.0 (most)
+    "wake_sensitivity": 0.5,  # Wake word, 0.0 (fewest false triggers) to 1.0 (fewest misses)
     # Text-to-Speech
     "tts_voice": "en-US-Neural2-J",
     "tts_speed": 1.0,
@@ -45,6 +48,14 @@ DEFAULT_CONFIG: ConfigDict = {
     # Telegram remote access (must be configured by user)
     "telegram_bot_token": "",  # Get from @BotFather
     "telegram_allowed_users": [],  # List of Telegram user IDs
+    # Discord and Matrix remote access; the GUI's remote.channel picks one
+    "discord_bot_token": "",  # From the Discord developer portal
+    "discord_allowed_users": [],  # List of Discord user IDs
+    "matrix_homeserver": "",  # e.g. https://matrix.org
+    "matrix_user_id": "",  # The bot's account, e.g. @synthia-bot:matrix.org
+    "matrix_access_token": "",  # Access token for that account
+    "matrix_allowed_users": [],  # List of Matrix user IDs, e.g. @you:matrix.org
+    "matrix_room_id": "",  # Room the GUI posts remote-mode notices to
     # Web search (Tavily)
     "tavily_api_key": "",  # Get from tavily.com
     # LLM Polish for dictation accuracy
@@ -128,6 +139,12 @@ def validate_config(config: ConfigDict) -> list[str]:
         if val is not None and (not isinstance(val, int) or val <= 0):
             warnings.append(f"{key}={val!r} must be a positive integer")
 
+    # VAD and wake word sensitivity
+    for key in ("vad_sensitivity", "wake_sensitivity"):
+        val = config.get(key)
+        if val is not None and (not isinstance(val, (int, float)) or not (0.0 <= val <= 1.0)):
+            warnings.append(f"{key}={val!r} is out of range (0.0–1.0)")
+
     # LLM polish timeout
     timeout = config.get("llm_polish_timeout")
     if timeout is not None and (not isinstance(timeout, (int, float)) or timeout <= 0):
@@ -0,0 +1,53 @@
+"""Advisory locks on the GUI's runtime files.
+
+The state file is shared with the GUI, which reads it on every change and
+read-modify-writes it for pause, mute and mode. Both sides hold a ``flock``
+on ``synthia-state.lock`` next to it while they touch it: shared to read,
+exclusive to write. The state file itself can't carry the lock, because
+every write renames a new file over it. See the GUI's ``file_lock.rs``.
+
+Where ``fcntl`` is missing (Windows) the lock is a no-op.
+"""
+
+from __future__ import annotations
+
+import contextlib
+import logging
+import os
+from typing import Iterator
+
+try:
+    import fcntl
+except ImportError:  # pragma: no cover - Windows
+    fcntl = None  # type: ignore[assignment]
+
+logger = logging.getLogger(__name__)
+
+
+def lock_path(path: str) -> str:
+    """The lock file guarding ``path``: ``x.json`` is guarded by ``x.lock``."""
+    return os.path.splitext(path)[0] + ".lock"
+
+
+@contextlib.contextmanager
+def locked(path: str, exclusive: bool = True) -> Iterator[None]:
+    """Hold the lock guarding ``path`` for the body of the ``with``.
+
+    If the lock file can't be opened the body runs unlocked; a missed lock
+    is better than a missed state update.
+    """
+    if fcntl is None:
+        yield
+        return
+    try:
+        fd = os.open(lock_path(path), os.O_RDWR | os.O_CREAT, 0o600)
+    except OSError as e:
+        logger.debug("Could not open lock file for %s: %s", path, e)
+        yield
+        return
+    try:
+        fcntl.flock(fd, fcntl.LOCK_EX if exclusive else fcntl.LOCK_SH)
+        yield
+    finally:
+        # Closing the descriptor releases the lock.
+        os.close(fd)
@@ -0,0 +1,178 @@
+"""Connection to the GUI's IPC socket.
+
+The GUI listens on ``synthia.sock`` next to the state file. Messages in both
+directions are frames: a 4-byte big-endian length followed by that many bytes
+of UTF-8 JSON, each an object with a ``type`` key:
+
+- ``state`` (backend -> GUI): ``{"type": "state", "state": {...}}``
+- ``event`` (backend -> GUI): ``{"type": "event", "name": ..., "payload": ...}``
+  (``transcription`` events, ``{"text": ..., "mode": ..., "duration_secs": ...,
+  "model": ..., "response": ...}``, are shown as desktop notifications and
+  kept in the GUI's history database; ``partial_transcript`` events,
+  ``{"utterance": ..., "text": ..., "final": ...}``, show text on the overlay
+  as it is decoded)
+- ``command`` (GUI -> backend): ``{"type": "command", "command": ..., "args": {...}}``
+  (``set_model``, ``{"name": ..., "path": ...}``, switches the Whisper model
+  and is answered with a ``model_changed`` event; ``recording``,
+  ``{"action": "start" | "stop" | "toggle", "discard": ...}``, starts or
+  stops a recording, dropping its audio if ``discard``; ``shutdown`` asks the
+  backend to write a ``stopped`` state and exit, and the GUI signals it if
+  it hasn't within ``backend.shutdown_timeout_ms``)
+
+The connection is opened lazily on the first send and reopened after the GUI
+restarts. The state file is still written either way, so a GUI that isn't
+listening misses nothing.
+
+Commands run one at a time, in order, on a worker thread: a push-to-talk
+release transcribes and answers before returning, and the reader has to keep
+draining the socket meanwhile. ``shutdown`` skips the queue so the GUI's
+shutdown timeout isn't spent waiting behind a transcription.
+"""
+
+from __future__ import annotations
+
+import json
+import logging
+import os
+import queue
+import socket
+import struct
+import threading
+from typing import Any, Callable, Optional
+
+logger = logging.getLogger(__name__)
+
+# Must match MAX_MESSAGE_BYTES in the GUI's ipc.rs.
+MAX_MESSAGE_BYTES = 64 * 1024
+CONNECT_TIMEOUT = 0.5
+
+_HEADER = struct.Struct(">I")
+
+CommandHandler = Callable[[str, dict], None]
+
+# Handled on the reader thread as soon as they arrive.
+URGENT_COMMANDS = frozenset({"shutdown"})
+
+
+def encode_frame(message: dict) -> bytes:
+    """Serialize a message into a length-prefixed frame."""
+    body = json.dumps(message).encode()
+    return _HEADER.pack(len(body)) + body
+
+
+def _recv_exact(sock: socket.socket, size: int) -> Optional[bytes]:
+    data = b""
+    while len(data) < size:
+        chunk = sock.recv(size - len(data))
+        if not chunk:
+            return None
+        data += chunk
+    return data
+
+
+def read_frame(sock: socket.socket) -> Optional[dict]:
+    """Read one message, or None once the peer has hung up."""
+    header = _recv_exact(sock, _HEADER.size)
+    if header is None:
+        return None
+    (size,) = _HEADER.unpack(header)
+    if size > MAX_MESSAGE_BYTES:
+        raise ValueError(f"frame of {size} bytes exceeds the limit")
+    body = _recv_exact(sock, size)
+    if body is None:
+        return None
+    return json.loads(body)
+
+
+class GuiLink:
+    """A persistent, reconnecting connection to the GUI."""
+
+    def __init__(self, sock_path: str, on_command: CommandHandler) -> None:
+        self.sock_path = sock_path
+        self.on_command = on_command
+        self._sock: Optional[socket.socket] = None
+        self._lock = threading.Lock()
+        self._commands: queue.Queue[tuple[str, dict]] = queue.Queue()
+        threading.Thread(target=self._command_loop, daemon=True).start()
+
+    def _connect(self) -> Optional[socket.socket]:
+        if self._sock is not None:
+            return self._sock
+        if not os.path.exists(self.sock_path):
+            return None
+        sock = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        try:
+            sock.settimeout(CONNECT_TIMEOUT)
+            sock.connect(self.sock_path)
+            sock.settimeout(None)
+        except OSError:
+            sock.close()
+            raise
+        self._sock = sock
+        threading.Thread(target=self._read_loop, args=(sock,), daemon=True).start()
+        logger.debug("Connected to GUI at %s", self.sock_path)
+        return sock
+
+    def _disconnect(self, sock: socket.socket) -> None:
+        with self._lock:
+            if self._sock is sock:
+                self._sock = None
+        sock.close()
+
+    def send(self, message: dict) -> bool:
+        """Send a message to the GUI. Returns False if it isn't reachable."""
+        with self._lock:
+            try:
+                sock = self._connect()
+                if sock is None:
+                    return False
+                sock.sendall(encode_frame(message))
+                return True
+            except OSError as e:
+                logger.debug("Could not send to GUI: %s", e)
+                if self._sock is not None:
+                    self._sock.close()
+                    self._sock = None
+                return False
+
+    def send_state(self, state: dict) -> bool:
+        return self.send({"type": "state", "state": state})
+
+    def send_event(self, name: str, payload: Any = None) -> bool:
+        return self.send({"type": "event", "name": name, "payload": payload})
+
+    def _read_loop(self, sock: socket.socket) -> None:
+        try:
+            while True:
+                message = read_frame(sock)
+                if message is None:
+                    break
+                if message.get("type") != "command":
+                    logger.debug("Ignoring GUI message of type %r", message.get("type"))
+                    continue
+                command = message.get("command", "")
+                args = message.get("args") or {}
+                if command in URGENT_COMMANDS:
+                    self._run_command(command, args)
+                else:
+                    self._commands.put((command, args))
+        except (OSError, ValueError) as e:
+            logger.debug("GUI connection closed: %s", e)
+        self._disconnect(sock)
+
+    def _command_loop(self) -> None:
+        while True:
+            command, args = self._commands.get()
+            self._run_command(command, args)
+
+    def _run_command(self, command: str, args: dict) -> None:
+        try:
+            self.on_command(command, args)
+        except Exception as e:
+            logger.warning("GUI command %r failed: %s", command, e)
+
+    def close(self) -> None:
+        with self._lock:
+            if self._sock is not None:
+                self._sock.close()
+                self._sock = None
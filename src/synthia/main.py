@@ -16,8 +16,10 @@ import signal
 import sys
 import threading
 import time
+from datetime import datetime, timezone
 from typing import Any, Optional
 
+from synthia import __version__
 from synthia.assistant import Assistant
 from synthia.audio import AudioRecorder, list_audio_devices
 from synthia.clipboard_monitor import ClipboardMonitor
@@ -29,6 +31,8 @@ from synthia.config import (
     load_config,
 )
 from synthia.display import get_display_server, is_wayland
+from synthia.file_lock import locked
+from synthia.gui_ipc import GuiLink
 from synthia.hotkeys import create_hotkey_listener
 from synthia.indicator import Status, TrayIndicator
 from synthia.llm_polish import TranscriptionPolisher
@@ -37,6 +41,20 @@ from synthia.output import type_text
 from synthia.sounds import SoundEffects
 from synthia.transcribe import Transcriber
 from synthia.tts import TextToSpeech
+from synthia.wakeword import WakeWordDetector
+
+# State file keys owned by the GUI (and the Telegram bot); preserved across backend writes.
+GUI_STATE_KEYS = ("paused", "muted", "mode", "audio_input", "last_remote_activity")
+
+# State file schema this backend writes; see SCHEMA_VERSION in the GUI's
+# synthia_state.rs for what each version added.
+STATE_SCHEMA_VERSION = 9
+
+# Seconds recorded for the assistant after the wake word is heard.
+WAKE_LISTEN_SECS = 5
+
+# Seconds between state heartbeats; well inside the GUI's default 15s timeout.
+HEARTBEAT_INTERVAL = 5
 
 logger = logging.getLogger(__name__)
 
@@ -65,7 +83,8 @@ class Synthia:
         # print("✅ System tray indicator started")
 
         # Initialize audio recorder
-        self.recorder = AudioRecorder(target_sample_rate=self.config["sample_rate"])
+        self.recorder_device: Optional[str] = None
+        self.recorder = self._make_recorder(self.config.get("audio_device") or None)
         logger.info("Audio recorder initialized")
 
         # Initialize transcriber (local Whisper or Google Cloud)
@@ -76,6 +95,7 @@ class Synthia:
             sample_rate=self.config["sample_rate"],
             use_local=use_local_stt,
             local_model=self.config.get("local_stt_model", "small"),
+            vad_sensitivity=self.config.get("vad_sensitivity", 0.5),
         )
         logger.info(
             "Transcriber initialized (%s)", "local Whisper" if use_local_stt else "Google Cloud"
@@ -130,6 +150,9 @@ class Synthia:
                 max_items=self.config.get("clipboard_history_max_items", 5),
             )
 
+        # Hands-free wake word, if one is configured; started in run()
+        self.wake_detector = self._make_wake_detector()
+
         # State tracking
         self.dictation_active = False
         self.assistant_active = False
@@ -139,15 +162,31 @@ class Synthia:
         self.state_file = os.path.join(
             os.environ.get("XDG_RUNTIME_DIR", "/tmp"), "synthia-state.json"
         )
+        # Push channel to the GUI; state updates go out here as well as to the file
+        self.gui_link = GuiLink(
+            os.path.join(os.path.dirname(self.state_file), "synthia.sock"),
+            self._on_gui_command,
+        )
         # History file for voice transcription history
         self.history_file = os.path.join(
             os.environ.get("XDG_RUNTIME_DIR", "/tmp"), "synthia-history.json"
         )
+        # Latest transcript, for the GUI's "Copy last transcript"
+        self.last_transcript_file = os.path.join(
+            os.environ.get("XDG_RUNTIME_DIR", "/tmp"), "synthia-last-transcript.txt"
+        )
         # Signal file for config reload (used by GUI to trigger live hotkey updates)
         self.reload_signal_file = os.path.join(
             os.environ.get("XDG_RUNTIME_DIR", "/tmp"), "synthia-reload-config"
         )
-        self._update_state("ready")
+        # Not ready until the hotkey listener is running (see run()); the GUI
+        # shows "starting" until then.
+        self.ready = False
+        self.last_transcription: Optional[str] = None
+        self._own_state: dict = {}
+        # Heartbeats and transitions write from different threads.
+        self._state_lock = threading.Lock()
+        self._update_state("starting")
 
         # Parse hotkeys from config (for X11/pynput)
         self.dictation_key = self._parse_key(self.config["dictation_key"])
@@ -191,17 +230,280 @@ class Synthia:
             return getattr(Key, key_name)
         return key_string
 
-    def _update_state(self, status: str) -> None:
-        """Update state file for GUI overlay communication."""
+    def _gui_state(self) -> dict:
+        """GUI-owned flags from the state file (pause, mute, mode, input)."""
+        try:
+            with open(self.state_file) as f:
+                current = json.load(f)
+        except (OSError, ValueError):
+            return {}
+        return {k: current[k] for k in GUI_STATE_KEYS if k in current}
+
+    def _selected_audio_input(self) -> Optional[str]:
+        """The input device picked in the GUI, or None to auto-detect. The
+        state file's ``audio_input`` is checked first since the GUI mirrors
+        its choice there for a running backend."""
+        return self._gui_state().get("audio_input") or self.config.get("audio_device") or None
+
+    def _make_recorder(self, device: Optional[str]) -> AudioRecorder:
+        """A recorder on ``device`` (a device name), or on an auto-detected
+        microphone when it is None or no longer plugged in."""
+        rate = self.config["sample_rate"]
         try:
-            state = {"status": status, "recording": status == "recording"}
-            with open(self.state_file, "w") as f:
-                json.dump(state, f)
+            recorder = AudioRecorder(target_sample_rate=rate, device=device)
+        except ValueError as e:
+            # sounddevice's answer to a name that matches no device
+            logger.warning("Audio input %r unavailable (%s); auto-detecting", device, e)
+            recorder = AudioRecorder(target_sample_rate=rate)
+        self.recorder_device = device
+        return recorder
+
+    def _open_selected_input(self) -> None:
+        """Re-open the recorder if the GUI has picked another input since.
+        Only called between recordings, so a switch never cuts one short."""
+        device = self._selected_audio_input()
+        if device != self.recorder_device:
+            self.recorder = self._make_recorder(device)
+            logger.info("Audio input switched to %s", device or "auto-detect")
+
+    def _make_wake_detector(self) -> Optional[WakeWordDetector]:
+        """A detector for the configured ``wake_word``, or None if it is empty."""
+        wake_word = str(self.config.get("wake_word") or "").strip()
+        if not wake_word:
+            return None
+        detector = WakeWordDetector(
+            wake_words=[wake_word],
+            on_wake=self._on_wake_word,
+            sample_rate=self.config["sample_rate"],
+            should_listen=self._wake_word_may_listen,
+            sensitivity=self.config.get("wake_sensitivity", 0.5),
+        )
+        detector.set_transcriber(self.transcriber)
+        logger.info("Wake word: %s", wake_word)
+        return detector
+
+    def _wake_word_may_listen(self) -> bool:
+        """Whether the wake word detector may use the microphone: once ready,
+        not during a recording, and not while the GUI has muted or paused."""
+        busy = self.dictation_active or self.assistant_active
+        return self.ready and not busy and not self._blocked_by_gui()
+
+    def _on_wake_word(self) -> Optional[bool]:
+        """Ask the assistant whatever follows the wake word.
+
+        Records for WAKE_LISTEN_SECS, as if the assistant key were held that
+        long. Returns False if nothing was said, which the detector counts
+        as a false trigger.
+        """
+        self._on_assistant_press()
+        if not self.assistant_active:
+            return None
+        time.sleep(WAKE_LISTEN_SECS)
+        return bool(self._on_assistant_release())
+
+    def _blocked_by_gui(self) -> Optional[str]:
+        """Why the GUI has recording switched off (mute or pause), or None."""
+        gui = self._gui_state()
+        if gui.get("muted"):
+            return "Microphone muted from the GUI"
+        if gui.get("paused"):
+            return "Synthia paused from the GUI"
+        return None
+
+    def _update_state(self, status: str, error: Optional[str] = None) -> None:
+        """Update state file for GUI overlay communication.
+
+        ``error`` is the message to show while ``status`` is ``error``.
+        """
+        own = {
+            "schema_version": STATE_SCHEMA_VERSION,
+            "status": status,
+            "recording": status == "recording",
+            "ready": self.ready,
+            "model": self.transcriber.model_name,
+            "language": self.config["language"],
+        }
+        if status == "recording":
+            own["recording_started_at"] = datetime.now(timezone.utc).isoformat()
+        if self.last_transcription is not None:
+            own["last_transcription"] = self.last_transcription
+        if error is not None:
+            own["error"] = error
+        self._own_state = own
+        self._write_state()
+
+    def _write_state(self) -> None:
+        """Write this process's state, stamped with a fresh heartbeat."""
+        # The file lock keeps the GUI from changing its flags between our
+        # read of them and our write.
+        with self._state_lock, locked(self.state_file):
+            # Carry the GUI's flags through, or every write would clear them.
+            state = {
+                **self._gui_state(),
+                **self._own_state,
+                "heartbeat_at": datetime.now(timezone.utc).isoformat(),
+            }
+            if self.wake_detector:
+                state["wake_detections"] = self.wake_detector.stats()
+            try:
+                # Write-then-rename so the GUI never reads a half-written file,
+                # and owner-only since it carries the last transcription.
+                tmp_path = f"{self.state_file}.{os.getpid()}.tmp"
+                fd = os.open(tmp_path, os.O_WRONLY | os.O_CREAT | os.O_TRUNC, 0o600)
+                with os.fdopen(fd, "w") as f:
+                    json.dump(state, f)
+                os.replace(tmp_path, self.state_file)
+            except Exception as e:
+                logger.debug("Could not update state file: %s", e)
+            self._push_state(state)
+
+    def _heartbeat(self) -> None:
+        """Rewrite the state every HEARTBEAT_INTERVAL so the GUI can tell
+        this process is alive; it flags a backend whose heartbeat stops."""
+        while self.running:
+            time.sleep(HEARTBEAT_INTERVAL)
+            if self.running:
+                self._write_state()
+
+    def _push_state(self, state: dict) -> None:
+        """Send a state update to the GUI's IPC socket, if it is listening."""
+        self.gui_link.send_state(state)
+
+    def _on_gui_command(self, command: str, args: dict) -> None:
+        """Handle a command sent by the GUI over the IPC socket."""
+        logger.info("GUI command: %s", command)
+        if command == "ping":
+            self.gui_link.send_event("pong", args)
+        elif command == "hotkey":
+            # Global shortcuts registered by the GUI, for when this process
+            # can't see the keyboard itself (e.g. on Wayland).
+            handlers = {
+                "dictation": (self._on_dictation_press, self._on_dictation_release),
+                "assistant": (self._on_assistant_press, self._on_assistant_release),
+            }
+            press, release = handlers[args["action"]]
+            (press if args.get("pressed") else release)()
+        elif command == "recording":
+            self._set_recording(args.get("action", "toggle"), bool(args.get("discard")))
+        elif command == "reload_config":
+            self._reload_config()
+            self.gui_link.send_event("config_reloaded")
+        elif command == "shutdown":
+            self._shutdown("GUI requested shutdown")
+        elif command == "set_model":
+            # Loading takes seconds; don't hold up the GUI's other commands.
+            threading.Thread(
+                target=self._set_model, args=(args["name"], args.get("path")), daemon=True
+            ).start()
+        else:
+            logger.warning("Unknown GUI command: %s", command)
+            self.gui_link.send_event("unknown_command", {"command": command})
+
+    def _set_recording(self, action: str, discard: bool = False) -> None:
+        """Start, stop or toggle recording as asked by the GUI.
+
+        Starting records a dictation, as the dictation hotkey does. Stopping
+        ends whichever recording is running; with ``discard`` its audio is
+        dropped instead of transcribed.
+        """
+        active = self.dictation_active or self.assistant_active
+        if action == "toggle":
+            action = "stop" if active else "start"
+        if action == "start":
+            self._on_dictation_press()
+        elif action != "stop":
+            logger.warning("Unknown recording action: %s", action)
+        elif discard:
+            self._discard_recording()
+        elif self.dictation_active:
+            self._on_dictation_release()
+        elif self.assistant_active:
+            self._on_assistant_release()
+
+    def _discard_recording(self) -> None:
+        """End the current recording without transcribing it."""
+        if not (self.dictation_active or self.assistant_active):
+            return
+        self.dictation_active = False
+        self.assistant_active = False
+        try:
+            self.recorder.stop_recording()
         except Exception as e:
-            logger.debug("Could not update state file: %s", e)
+            logger.warning("Could not stop recording: %s", e)
+        logger.info("Recording discarded")
+        self._update_state("ready")
+        if self.tray:
+            self.tray.set_status(Status.READY)
 
-    def _save_to_history(self, text: str, mode: str, response: Optional[str] = None) -> None:
+    def _set_model(self, name: str, path: Optional[str]) -> None:
+        """Switch the Whisper model as asked by the GUI's model manager."""
+        try:
+            self.transcriber.set_model(name, path)
+        except Exception as e:
+            logger.warning("Could not switch model to %s: %s", name, e)
+            self.gui_link.send_event("model_changed", {"name": name, "error": str(e)})
+            return
+        self.config["local_stt_model"] = name
+        self.gui_link.send_event("model_changed", {"name": name, "error": None})
+        with self._state_lock:
+            self._own_state["model"] = self.transcriber.model_name
+        self._write_state()
+
+    def _start_live_transcript(self) -> Any:
+        """Return a callback streaming one utterance's text to the GUI overlay.
+
+        Called as ``send(text)`` while Whisper decodes and ``send(text,
+        final=True)`` once the text is settled. A final call without text
+        sends nothing unless partial text is on the overlay, which it then
+        clears. The utterance id is the start time in milliseconds, so the
+        GUI can order utterances even across backend restarts.
+        """
+        utterance = time.time_ns() // 1_000_000
+        shown = False
+
+        def send(text: Optional[str], final: bool = False) -> None:
+            nonlocal shown
+            if final and not text and not shown:
+                return
+            shown = shown or bool(text)
+            self.gui_link.send_event(
+                "partial_transcript",
+                {"utterance": utterance, "text": text or "", "final": final},
+            )
+
+        return send
+
+    def _save_last_transcript(self, text: str) -> None:
+        """Write the latest transcript for the GUI, readable only by this user."""
+        try:
+            tmp_path = f"{self.last_transcript_file}.{os.getpid()}.tmp"
+            fd = os.open(tmp_path, os.O_WRONLY | os.O_CREAT | os.O_TRUNC, 0o600)
+            with os.fdopen(fd, "w") as f:
+                f.write(text)
+            os.replace(tmp_path, self.last_transcript_file)
+        except OSError as e:
+            logger.debug("Could not save last transcript: %s", e)
+
+    def _save_to_history(
+        self,
+        text: str,
+        mode: str,
+        response: Optional[str] = None,
+        duration: Optional[float] = None,
+    ) -> None:
         """Save transcription to history file for GUI display."""
+        self.last_transcription = text
+        self._save_last_transcript(text)
+        self.gui_link.send_event(
+            "transcription",
+            {
+                "text": text,
+                "mode": mode,
+                "duration_secs": duration,
+                "model": self.transcriber.model_name,
+                "response": response,
+            },
+        )
         try:
             from datetime import datetime
 
@@ -237,6 +539,60 @@ class Synthia:
         """Handle quit from tray icon."""
         self.running = False
 
+    def _shutdown(self, reason: str) -> None:
+        """Report the stopped state and let run() return and clean up."""
+        logger.info("%s, exiting...", reason)
+        self.ready = False
+        self._update_state("stopped")
+        self.running = False
+        self.hotkey_listener.stop()
+
+    def _reload_config(self) -> None:
+        """Re-read the config and apply what can change while running: the
+        hotkeys and the speech settings the GUI edits (see
+        _apply_speech_settings)."""
+        new_config = load_config()
+        new_dictation_key = new_config["dictation_key"]
+        new_assistant_key = new_config["assistant_key"]
+
+        # Update the hotkey listener
+        self.hotkey_listener.update_keys(new_dictation_key, new_assistant_key)
+
+        # Update our stored config
+        old_config = self.config
+        self.config = new_config
+
+        logger.info("Hotkeys updated dynamically")
+        self._apply_speech_settings(old_config, new_config)
+
+    def _apply_speech_settings(self, old: dict, new: dict) -> None:
+        """Apply a changed Whisper model, language, VAD sensitivity, wake word
+        or wake sensitivity. Switching between local Whisper and Google STT still needs a
+        restart."""
+        if new.get("use_local_stt", False) != old.get("use_local_stt", False):
+            logger.warning("use_local_stt changed; restart Synthia to switch speech engines")
+        model = new.get("local_stt_model", "small")
+        if self.transcriber.use_local and model != self.transcriber.local_model:
+            self._set_model(model, None)
+        if new["language"] != self.transcriber.language:
+            self.transcriber.set_language(new["language"])
+        self.transcriber.vad_sensitivity = new.get("vad_sensitivity", 0.5)
+
+        wake_word = str(new.get("wake_word") or "").strip()
+        if wake_word != str(old.get("wake_word") or "").strip():
+            if self.wake_detector:
+                self.wake_detector.stop()
+            self.wake_detector = self._make_wake_detector()
+            if self.wake_detector and self.ready:
+                self.wake_detector.start()
+        elif self.wake_detector:
+            self.wake_detector.set_sensitivity(new.get("wake_sensitivity", 0.5))
+
+        with self._state_lock:
+            self._own_state["model"] = self.transcriber.model_name
+            self._own_state["language"] = self.transcriber.language
+        self._write_state()
+
     def _watch_config_reload(self) -> None:
         """Watch for config reload signal file and update hotkeys dynamically."""
         while self.running:
@@ -244,19 +600,7 @@ class Synthia:
                 if os.path.exists(self.reload_signal_file):
                     # Remove the signal file
                     os.remove(self.reload_signal_file)
-
-                    # Reload config
-                    new_config = load_config()
-                    new_dictation_key = new_config["dictation_key"]
-                    new_assistant_key = new_config["assistant_key"]
-
-                    # Update the hotkey listener
-                    self.hotkey_listener.update_keys(new_dictation_key, new_assistant_key)
-
-                    # Update our stored config
-                    self.config = new_config
-
-                    logger.info("Hotkeys updated dynamically")
+                    self._reload_config()
             except Exception as e:
                 logger.warning("Config reload error: %s", e)
 
@@ -266,8 +610,13 @@ class Synthia:
         """Handle dictation key press (Right Ctrl)."""
         if not self.running or self.dictation_active or self.assistant_active:
             return
+        blocked = self._blocked_by_gui()
+        if blocked:
+            logger.info("%s; ignoring hotkey", blocked)
+            return
 
         try:
+            self._open_selected_input()
             self.recorder.start_recording()
             self.dictation_active = True
             self._update_state("recording")
@@ -291,38 +640,60 @@ class Synthia:
                 self.tray.set_status(Status.THINKING)
 
             audio_data = self.recorder.stop_recording()
+            if audio_data and self._gui_state().get("muted"):
+                # Muted mid-recording: none of it may be used.
+                logger.info("Microphone muted during recording; dropping audio")
+                audio_data = None
 
             if audio_data:
-                text = self.transcriber.transcribe(audio_data)
+                live = self._start_live_transcript()
+                text = self.transcriber.transcribe(audio_data, on_partial=live)
                 if text:
                     # LLM polish for improved accuracy (optional)
                     if self.polisher:
                         text = self.polisher.polish(text)
                     # Apply word replacements to fix common misrecognitions
                     text = apply_word_replacements(text, self.config)
+                live(text, final=True)
+                if text:
                     type_text(text)
-                    self._save_to_history(text, "dictation")
+                    self._save_to_history(
+                        text, "dictation", duration=self.transcriber.duration(audio_data)
+                    )
                     if self.config.get("show_notifications", True):
                         notify_dictation(text)
 
-            self._update_state("ready")
+            self._finish_utterance()
             if self.tray:
                 self.tray.set_status(Status.READY)
 
         except Exception as e:
             logger.error("Error: %s", e)
+            self._finish_utterance(str(e))
             self.sounds.play_error()
             if self.config.get("show_notifications", True):
                 notify_error(str(e))
             if self.tray:
                 self.tray.set_status(Status.READY)
 
+    def _finish_utterance(self, error: Optional[str] = None) -> None:
+        """Mark a released recording handled: ready again, or ``error``."""
+        if error is None:
+            self._update_state("ready")
+        else:
+            self._update_state("error", error=error)
+
     def _on_assistant_press(self) -> None:
         """Handle assistant key press (Right Alt)."""
         if not self.running or self.assistant_active or self.dictation_active:
             return
+        blocked = self._blocked_by_gui()
+        if blocked:
+            logger.info("%s; ignoring hotkey", blocked)
+            return
 
         try:
+            self._open_selected_input()
             self.recorder.start_recording()
             self.assistant_active = True
             self._update_state("recording")
@@ -333,11 +704,13 @@ class Synthia:
             logger.error("Could not start recording: %s", e)
             self.sounds.play_error()
 
-    def _on_assistant_release(self) -> None:
-        """Handle assistant key release (Right Alt)."""
+    def _on_assistant_release(self) -> Optional[str]:
+        """Handle assistant key release (Right Alt). Returns the command heard,
+        if any."""
         if not self.running or not self.assistant_active:
-            return
+            return None
 
+        text: Optional[str] = None
         try:
             self.assistant_active = False
             self._update_state("thinking")
@@ -346,10 +719,16 @@ class Synthia:
                 self.tray.set_status(Status.THINKING)
 
             audio_data = self.recorder.stop_recording()
+            if audio_data and self._gui_state().get("muted"):
+                # Muted mid-recording: none of it may be used.
+                logger.info("Microphone muted during recording; dropping audio")
+                audio_data = None
 
             if audio_data:
                 # Transcribe the command
-                text = self.transcriber.transcribe(audio_data)
+                live = self._start_live_transcript()
+                text = self.transcriber.transcribe(audio_data, on_partial=live)
+                live(text, final=True)
 
                 if text:
                     logger.info("Command: %s", text)
@@ -360,7 +739,12 @@ class Synthia:
                     # Speak the response
                     if response.get("speech"):
                         self.tts.speak(response["speech"])
-                        self._save_to_history(text, "assistant", response["speech"])
+                        self._save_to_history(
+                            text,
+                            "assistant",
+                            response["speech"],
+                            duration=self.transcriber.duration(audio_data),
+                        )
                         if self.config.get("show_notifications", True):
                             notify_assistant(response["speech"])
 
@@ -374,33 +758,38 @@ class Synthia:
                         if command_output:
                             self.tts.speak(command_output)
 
-            self._update_state("ready")
+            self._finish_utterance()
             if self.tray:
                 self.tray.set_status(Status.READY)
 
         except Exception as e:
             logger.error("Error: %s", e)
+            self._finish_utterance(str(e))
             self.sounds.play_error()
             if self.config.get("show_notifications", True):
                 notify_error(str(e))
             if self.tray:
                 self.tray.set_status(Status.READY)
+        return text or None
 
     def run(self) -> None:
         """Run the main keyboard listener loop."""
         logger.info("Use Ctrl+C to exit")
 
-        # Handle Ctrl+C gracefully
+        # Handle Ctrl+C, and the GUI's SIGTERM, gracefully
         def signal_handler(sig, frame):
-            logger.info("Interrupted, exiting...")
-            self.running = False
-            self.hotkey_listener.stop()
+            # Off the main thread: it may be holding the state lock.
+            threading.Thread(
+                target=self._shutdown, args=(signal.Signals(sig).name,), daemon=True
+            ).start()
 
         signal.signal(signal.SIGINT, signal_handler)
+        signal.signal(signal.SIGTERM, signal_handler)
 
         # Start config watcher thread (for live hotkey updates from GUI)
         config_watcher = threading.Thread(target=self._watch_config_reload, daemon=True)
         config_watcher.start()
+        threading.Thread(target=self._heartbeat, daemon=True).start()
 
         # Start clipboard monitor (if enabled)
         if self.clipboard_monitor:
@@ -409,9 +798,16 @@ class Synthia:
 
         # Start the hotkey listener (auto-detects Wayland vs X11)
         self.hotkey_listener.start()
+        self.ready = True
+        self._update_state("ready")
+        if self.wake_detector:
+            self.wake_detector.start()
         self.hotkey_listener.join()
 
         # Cleanup
+        if self.wake_detector:
+            self.wake_detector.stop()
+        self.gui_link.close()
         if self.clipboard_monitor:
             self.clipboard_monitor.stop()
         if self.tray:
@@ -511,6 +907,10 @@ def handle_memory_command(args: list[str]) -> None:
 
 def main() -> None:
     """Entry point."""
+    if "--version" in sys.argv:
+        print(f"synthia {__version__}")
+        return
+
     logging.basicConfig(
         level=logging.INFO,
         format="%(asctime)s - %(name)s - %(levelname)s - %(message)s",
@@ -532,6 +932,7 @@ def main() -> None:
         print("\nSubcommands:")
         print("  synthia memory    - Memory system TUI and CLI")
         print("  synthia --list-devices  - Show audio devices")
+        print("  synthia --version       - Show version")
         return
 
     try:
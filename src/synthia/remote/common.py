@@ -0,0 +1,425 @@
+"""What every remote-mode bot shares, whichever chat service it runs on.
+
+The GUI launches one bot at a time (see its ``remote_channel`` module),
+passing the token in an environment variable and the allowlist as
+``--allowed-users a,b`` or ``--allow-all``. Each bot forwards messages to
+Claude Code while remote mode is on, stamps ``last_remote_activity`` for
+the GUI's idle timeout, and prints connection markers on stdout. Given
+``--approval-timeout SECONDS``, it also holds each forwarded message until
+the GUI approves it (see ``ApprovalGate``). Every exchange is reported
+with ``report_activity`` for the GUI's remote activity log.
+"""
+
+from __future__ import annotations
+
+import json
+import logging
+import os
+import re
+import subprocess
+import sys
+import threading
+import uuid
+from datetime import datetime, timezone
+from typing import Any, Callable, TextIO
+
+from synthia.file_lock import locked
+
+logger = logging.getLogger(__name__)
+
+# Use XDG_RUNTIME_DIR for secure temp files (user-only access, not world-readable /tmp)
+RUNTIME_DIR = os.environ.get("XDG_RUNTIME_DIR", "/tmp")
+REMOTE_MODE_FILE = os.path.join(RUNTIME_DIR, "synthia-remote-mode")
+STATE_FILE = os.path.join(RUNTIME_DIR, "synthia-state.json")
+
+# Connection changes are printed to stdout as "<marker> connected" or
+# "<marker> disconnected" for the GUI supervising the bot; logging goes to
+# stderr, so the two don't mix. See the GUI's commands/remote.rs.
+STATUS_MARKER = "SYNTHIA_REMOTE_STATUS"
+
+# Messages waiting for the GUI's approval are printed to stdout as
+# '<marker> {"id": ..., "text": ...}'; the GUI answers on our stdin with
+# '{"id": ..., "approved": true|false}'. See the GUI's remote_approval.rs.
+APPROVAL_MARKER = "SYNTHIA_REMOTE_APPROVAL"
+
+# Exchanges are printed as '<marker> {"direction": "in"|"out", "sender": ...,
+# "kind": ..., "text": ...}' for the GUI to store in its history database.
+ACTIVITY_MARKER = "SYNTHIA_REMOTE_ACTIVITY"
+
+# Security: Input sanitization for text sent to terminal
+MAX_MESSAGE_LENGTH = 2000  # Limit message length
+DANGEROUS_SEQUENCES = [
+    "\x1b",  # Escape sequences
+    "\x00",  # Null bytes
+    "\x07",  # Bell
+    "\x08",  # Backspace
+    "\x7f",  # Delete
+]
+
+
+def sanitize_terminal_input(text: str) -> str:
+    """Sanitize text before sending to terminal via xdotool.
+
+    Removes control characters and escape sequences that could be used
+    to manipulate the terminal.
+    """
+    if not text:
+        return ""
+
+    # Truncate to max length
+    text = text[:MAX_MESSAGE_LENGTH]
+
+    # Remove dangerous control characters
+    for seq in DANGEROUS_SEQUENCES:
+        text = text.replace(seq, "")
+
+    # Remove ANSI escape sequences
+    text = re.sub(r"\x1b\[[0-9;]*[a-zA-Z]", "", text)
+
+    # Remove other control characters (except newline, tab)
+    text = "".join(char for char in text if char == "\n" or char == "\t" or ord(char) >= 32)
+
+    return text.strip()
+
+
+def parse_access(argv: list[str]) -> tuple[list[str] | None, bool]:
+    """The allowlist the GUI passed and whether ``--allow-all`` was given.
+
+    The list is ``None`` when ``--allowed-users`` is absent, so the caller
+    can fall back to config.yaml.
+    """
+    users = None
+    if "--allowed-users" in argv:
+        idx = argv.index("--allowed-users")
+        raw = argv[idx + 1] if idx + 1 < len(argv) else ""
+        users = [u.strip() for u in raw.split(",") if u.strip()]
+    return users, "--allow-all" in argv
+
+
+def parse_approval_timeout(argv: list[str]) -> float | None:
+    """Seconds to wait for the GUI's approval, or ``None`` to forward unasked.
+
+    Set from ``--approval-timeout``; a missing or unusable value leaves
+    forwarding ungated, as when the bot is started by hand.
+    """
+    if "--approval-timeout" not in argv:
+        return None
+    idx = argv.index("--approval-timeout")
+    try:
+        timeout = float(argv[idx + 1])
+    except (IndexError, ValueError):
+        logger.warning("Ignoring --approval-timeout without a number of seconds")
+        return None
+    return timeout if timeout > 0 else None
+
+
+def is_remote_mode() -> bool:
+    """Whether messages go to Claude Code rather than the local assistant."""
+    return os.path.exists(REMOTE_MODE_FILE)
+
+
+def set_remote_mode(enabled: bool) -> None:
+    """Switch between forwarding to Claude Code and the local assistant."""
+    if enabled:
+        with open(REMOTE_MODE_FILE, "w") as f:
+            f.write("remote")
+        os.chmod(REMOTE_MODE_FILE, 0o600)
+    else:
+        try:
+            os.remove(REMOTE_MODE_FILE)
+        except FileNotFoundError:
+            pass
+
+
+class ApprovalGate:
+    """Holds messages for Claude Code until the GUI on the desktop approves them.
+
+    With no timeout every message passes at once. Otherwise each one is
+    printed with ``APPROVAL_MARKER`` and held until the GUI answers on stdin
+    or ``timeout`` seconds pass. The GUI answers straight away for messages
+    its policy lets through, so only risky ones wait for the user.
+    """
+
+    def __init__(self, timeout: float | None, answers: TextIO | None = None):
+        self.timeout = timeout
+        self._lock = threading.Lock()
+        self._waiting: dict[str, tuple[threading.Event, list[bool]]] = {}
+        if timeout is not None:
+            reader = threading.Thread(
+                target=self._read_answers, args=(answers or sys.stdin,), daemon=True
+            )
+            reader.start()
+
+    def request(self, text: str) -> bool | None:
+        """Ask the GUI about ``text``. Blocking.
+
+        True once approved, False once denied, and None if no answer came
+        in time or the GUI can't be reached.
+        """
+        if self.timeout is None:
+            return True
+        request_id = uuid.uuid4().hex
+        answered = threading.Event()
+        with self._lock:
+            self._waiting[request_id] = (answered, [])
+        try:
+            print(
+                f"{APPROVAL_MARKER} {json.dumps({'id': request_id, 'text': text})}", flush=True
+            )
+            answered.wait(self.timeout)
+        except BrokenPipeError:
+            # The GUI that started us has gone; nobody can approve.
+            pass
+        finally:
+            with self._lock:
+                _, verdict = self._waiting.pop(request_id)
+        return verdict[0] if verdict else None
+
+    def _read_answers(self, answers: TextIO) -> None:
+        for line in answers:
+            try:
+                answer = json.loads(line)
+                request_id, approved = answer["id"], answer["approved"] is True
+            except (ValueError, KeyError, TypeError):
+                logger.warning("Ignoring malformed approval answer: %r", line)
+                continue
+            with self._lock:
+                waiting = self._waiting.get(request_id)
+                if waiting is not None:
+                    waiting[1].append(approved)
+                    waiting[0].set()
+
+
+def report_activity(direction: str, sender: str, kind: str, text: str = "") -> None:
+    """Print an exchange for the GUI's activity log.
+
+    ``direction`` is ``in`` for what an allowed user sent, with their ID as
+    ``sender``, and ``out`` for what Synthia did about it.
+    """
+    activity = {"direction": direction, "sender": sender, "kind": kind, "text": text}
+    try:
+        print(f"{ACTIVITY_MARKER} {json.dumps(activity)}", flush=True)
+    except BrokenPipeError:
+        # The GUI that started us has gone; nobody is reading.
+        pass
+
+
+def forward_to_claude_code(text: str, gate: ApprovalGate) -> str | None:
+    """Send ``text`` to Claude Code once ``gate`` lets it through. Blocking.
+
+    Returns None once sent, else what to tell the user instead.
+    """
+    text = sanitize_terminal_input(text)
+    verdict = gate.request(text)
+    if verdict is None:
+        logger.info("Not approved in time, dropped: %s", text)
+        report_activity("out", "synthia", "expired", text)
+        return "⌛ Not approved on the desktop in time, so not sent."
+    if not verdict:
+        logger.info("Denied on the desktop: %s", text)
+        report_activity("out", "synthia", "denied", text)
+        return "🚫 Denied on the desktop, so not sent."
+    if send_to_claude_code(text):
+        report_activity("out", "synthia", "forwarded", text)
+        return None
+    report_activity("out", "synthia", "failed", text)
+    return "❌ Failed to send. Is WezTerm running?"
+
+
+def reply_to(text: str, assistant: Callable[[], Any], gate: ApprovalGate) -> str:
+    """The reply to a plain message from an allowed user. Blocking.
+
+    In remote mode the text goes to Claude Code, once ``gate`` lets it
+    through; otherwise the local assistant, made by ``assistant`` on first
+    use, answers it.
+    """
+    if is_remote_mode():
+        return forward_to_claude_code(text, gate) or "📤 Sent to Claude Code."
+    response = assistant().process(text)
+    reply = response.get("speech", "Sorry, I could not process that.")
+    report_activity("out", "synthia", "reply", reply)
+    return reply
+
+
+def record_activity() -> None:
+    """Stamp `last_remote_activity` in the state file for the GUI's idle timeout."""
+    try:
+        with locked(STATE_FILE):
+            state = {}
+            if os.path.exists(STATE_FILE):
+                with open(STATE_FILE) as f:
+                    state = json.load(f)
+            state["last_remote_activity"] = datetime.now(timezone.utc).isoformat()
+            tmp_path = f"{STATE_FILE}.{os.getpid()}.tmp"
+            # Owner-only, like the backend's own writes: it carries the
+            # last transcription.
+            fd = os.open(tmp_path, os.O_WRONLY | os.O_CREAT | os.O_TRUNC, 0o600)
+            with os.fdopen(fd, "w") as f:
+                json.dump(state, f)
+            os.replace(tmp_path, STATE_FILE)
+    except (OSError, ValueError) as e:
+        logger.debug("Could not record remote activity: %s", e)
+
+
+class ConnectionReporter:
+    """Prints a status marker for the GUI whenever the connection changes."""
+
+    def __init__(self, service: str):
+        self.service = service
+        self.connected: bool | None = None
+
+    def report(self, connected: bool) -> None:
+        if connected == self.connected:
+            return
+        self.connected = connected
+        state = "connected" if connected else "disconnected"
+        logger.info("%s %s", self.service, state)
+        try:
+            print(f"{STATUS_MARKER} {state}", flush=True)
+        except BrokenPipeError:
+            # The GUI that started us has gone; nobody is reading.
+            pass
+
+
+def _is_wayland() -> bool:
+    """Check if running on Wayland."""
+    return bool(os.environ.get("WAYLAND_DISPLAY"))
+
+
+def _get_display() -> str:
+    """Get the active X display, trying common options."""
+    for display in [":1", ":0"]:
+        try:
+            result = subprocess.run(
+                ["wmctrl", "-l"],
+                capture_output=True,
+                text=True,
+                env={**os.environ, "DISPLAY": display},
+                timeout=2,
+            )
+            if result.returncode == 0 and result.stdout.strip():
+                return display
+        except Exception:
+            pass
+    return os.environ.get("DISPLAY", ":0")
+
+
+def send_to_claude_code(message: str) -> bool:
+    """Send a message to the Claude Code terminal.
+
+    On Wayland: Uses wtype to type to the focused window.
+    On X11: Uses xdotool with window targeting.
+
+    SECURITY: Input is sanitized to prevent terminal escape sequence injection.
+    """
+    # Sanitize input before sending to terminal
+    message = sanitize_terminal_input(message)
+    if not message:
+        logger.warning("Message was empty after sanitization")
+        return False
+
+    try:
+        # Use Wayland-native approach if available
+        if _is_wayland():
+            return _send_wayland(message)
+        else:
+            return _send_x11(message)
+
+    except Exception as e:
+        logger.error(f"Error sending to Claude Code: {e}")
+        return False
+
+
+def _send_wayland(message: str) -> bool:
+    """Send message using Wayland tools (wtype or ydotool)."""
+    # Try ydotool first (works even without focus on the window)
+    try:
+        # Check if ydotoold is running
+        result = subprocess.run(["pgrep", "-x", "ydotoold"], capture_output=True)
+        if result.returncode == 0:
+            # ydotool is available and daemon is running
+            subprocess.run(["ydotool", "type", "--", message], check=True)
+            subprocess.run(["ydotool", "key", "enter"], check=True)
+            logger.info(f"Sent via ydotool: {message}")
+            return True
+    except FileNotFoundError:
+        pass  # ydotool not installed, try wtype
+
+    # Fallback to wtype (requires the terminal to be focused)
+    try:
+        # wtype types to the currently focused window
+        subprocess.run(["wtype", message], check=True)
+        subprocess.run(["wtype", "-k", "Return"], check=True)
+        logger.info(f"Sent via wtype: {message}")
+        return True
+    except FileNotFoundError:
+        logger.error("No Wayland typing tool found. Install wtype or ydotool.")
+        return False
+    except subprocess.CalledProcessError as e:
+        logger.error(f"wtype failed: {e}")
+        return False
+
+
+def _send_x11(message: str) -> bool:
+    """Send message using X11 tools (xdotool with window targeting)."""
+    display = _get_display()
+
+    # Find terminal window - look for "Remote" tab in WezTerm
+    result = subprocess.run(
+        ["wmctrl", "-l"], capture_output=True, text=True, env={**os.environ, "DISPLAY": display}
+    )
+
+    # Priority order for window matching:
+    # 1. Window titled "Remote" (dedicated remote control tab)
+    # 2. Window with Claude Code indicator (✳ or Claude)
+    # 3. WezTerm windows (contain tab info like [1/4])
+    window_id = None
+    candidates: list[tuple[str, str]] = []
+    for line in result.stdout.strip().split("\n"):
+        parts = line.split(None, 4)
+        if len(parts) >= 4:
+            wid = parts[0]
+            title = parts[-1] if len(parts) > 4 else ""
+            # Skip empty titles and desktop/panel windows
+            if not title or title.startswith("@!") or title.startswith("N/A"):
+                continue
+            # Skip browser windows (Chrome, Firefox, etc)
+            if any(x in title for x in ["Chrome", "Firefox", "Zen", "Brave"]):
+                continue
+            # Highest priority - "Remote" tab
+            if title == "Remote":
+                candidates.insert(0, (wid, title))
+            # High priority - Claude Code window (has ✳ indicator)
+            elif "✳" in title:
+                candidates.insert(
+                    1 if candidates and candidates[0][1] == "Remote" else 0, (wid, title)
+                )
+            # Medium priority - WezTerm with tab indicator [X/Y]
+            elif title.startswith("[") and "/" in title[:6]:
+                candidates.append((wid, title))
+
+    if candidates:
+        wid, title = candidates[0]
+        window_id = str(int(wid, 16))
+        logger.info(f"Found terminal window: {title} ({wid})")
+
+    if not window_id:
+        logger.error("No terminal window found")
+        return False
+
+    # Type the message into the terminal
+    subprocess.run(
+        ["xdotool", "type", "--window", window_id, "--clearmodifiers", message],
+        check=True,
+        env={**os.environ, "DISPLAY": display},
+    )
+
+    # Press Enter
+    subprocess.run(
+        ["xdotool", "key", "--window", window_id, "Return"],
+        check=True,
+        env={**os.environ, "DISPLAY": display},
+    )
+
+    logger.info(f"Sent via xdotool: {message}")
+    return True
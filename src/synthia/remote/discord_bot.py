@@ -0,0 +1,186 @@
+#!/usr/bin/env python3
+"""
+Synthia Discord Bot - remote mode over Discord.
+
+Answers direct messages, and messages that mention it, from allowed users:
+forwarded to Claude Code while remote mode is on, answered by the local
+assistant otherwise. ``!dev`` and ``!quick`` switch between the two, like
+Telegram's ``/dev`` and ``/quick``.
+
+The bot needs the Message Content intent enabled in the Discord developer
+portal.
+"""
+
+import asyncio
+import logging
+import os
+import sys
+
+# Add src to path for imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.dirname(os.path.abspath(__file__)))))
+
+try:
+    import discord
+
+    HAS_DISCORD = True
+except ImportError:
+    HAS_DISCORD = False
+
+from synthia.config import load_config
+from synthia.remote.common import (
+    ApprovalGate,
+    ConnectionReporter,
+    parse_access,
+    parse_approval_timeout,
+    record_activity,
+    reply_to,
+    report_activity,
+    set_remote_mode,
+)
+
+# The GUI resolves the token itself and hands it over here, as for Telegram.
+TOKEN_ENV_VAR = "SYNTHIA_DISCORD_BOT_TOKEN"
+
+# Discord refuses messages longer than this.
+MAX_REPLY_LENGTH = 2000
+
+logging.basicConfig(
+    format="%(asctime)s - %(name)s - %(levelname)s - %(message)s", level=logging.INFO
+)
+logger = logging.getLogger(__name__)
+
+
+class SynthiaDiscordBot:
+    """Discord bot for remote Synthia access."""
+
+    def __init__(
+        self,
+        bot_token: str,
+        allowed_users: list[int],
+        allow_all: bool = False,
+        approval_timeout: float | None = None,
+    ):
+        self.bot_token = bot_token
+        self.allowed_users = allowed_users
+        self.allow_all = allow_all
+        self.config = load_config()
+        self._assistant = None
+        self.approvals = ApprovalGate(approval_timeout)
+        self._connection = ConnectionReporter("Discord")
+
+        intents = discord.Intents.default()
+        intents.message_content = True
+        self.client = discord.Client(intents=intents)
+        for handler in (self.on_ready, self.on_resumed, self.on_disconnect, self.on_message):
+            self.client.event(handler)
+
+    def is_authorized(self, user_id: int) -> bool:
+        authorized = self.allow_all or user_id in self.allowed_users
+        if authorized:
+            record_activity()
+        return authorized
+
+    def assistant(self):
+        """The local assistant, loaded on first use; Quick Mode only."""
+        if self._assistant is None:
+            from synthia.assistant import Assistant
+
+            self._assistant = Assistant(
+                use_local=True,
+                local_model=self.config.get("local_llm_model", "qwen2.5:1.5b-instruct-q4_0"),
+                ollama_url=self.config.get("ollama_url", "http://localhost:11434"),
+            )
+        return self._assistant
+
+    async def on_ready(self):
+        logger.info("Logged in as %s", self.client.user)
+        self._connection.report(True)
+
+    async def on_resumed(self):
+        self._connection.report(True)
+
+    async def on_disconnect(self):
+        self._connection.report(False)
+
+    def _addressed_to_us(self, message) -> bool:
+        """Direct messages, and channel messages that mention the bot."""
+        if isinstance(message.channel, discord.DMChannel):
+            return True
+        return self.client.user is not None and self.client.user in message.mentions
+
+    async def on_message(self, message):
+        if message.author == self.client.user or not self._addressed_to_us(message):
+            return
+        if not self.is_authorized(message.author.id):
+            return
+
+        text = message.content
+        if self.client.user is not None:
+            text = text.replace(self.client.user.mention, "")
+        text = text.strip()
+        if not text:
+            return
+        kind = "command" if text in ("!dev", "!quick") else "message"
+        report_activity("in", str(message.author.id), kind, text)
+
+        if text == "!dev":
+            set_remote_mode(True)
+            await message.reply("🟢 **Dev Mode ENABLED**\n\nYou can now control Claude Code.")
+            return
+        if text == "!quick":
+            set_remote_mode(False)
+            await message.reply("🟡 **Quick Mode ENABLED**\n\nBack to the local assistant.")
+            return
+
+        logger.info("Received text: %s", text)
+        try:
+            reply = await asyncio.to_thread(reply_to, text, self.assistant, self.approvals)
+        except Exception as e:
+            logger.error("Error processing text: %s", e)
+            reply = f"Error: {e}"
+        await message.reply(reply[:MAX_REPLY_LENGTH])
+
+    def run(self):
+        """Start the bot."""
+        logger.info("Starting Synthia Discord bot...")
+        # Our own logging is already set up.
+        self.client.run(self.bot_token, log_handler=None)
+
+
+def main():
+    """Main entry point."""
+    if not HAS_DISCORD:
+        print("Error: discord.py is not installed (pip install synthia[discord])")
+        sys.exit(1)
+
+    config = load_config()
+    bot_token = os.environ.get(TOKEN_ENV_VAR) or config.get("discord_bot_token", "")
+    allowed_users = [int(u) for u in config.get("discord_allowed_users", [])]
+
+    # The GUI passes the allowlist it validated, so both sides agree on it.
+    passed_users, allow_all = parse_access(sys.argv)
+    if passed_users is not None:
+        allowed_users = [int(u) for u in passed_users]
+
+    if not bot_token:
+        print("Error: discord_bot_token not set in config")
+        print("Add it to ~/.config/synthia/config.yaml")
+        sys.exit(1)
+
+    if allow_all:
+        logger.warning("Starting without a user allowlist (--allow-all)")
+    elif not allowed_users:
+        print("Error: discord_allowed_users not set in config")
+        print("Add your Discord user ID to ~/.config/synthia/config.yaml")
+        sys.exit(1)
+
+    SynthiaDiscordBot(
+        bot_token,
+        allowed_users,
+        allow_all=allow_all,
+        approval_timeout=parse_approval_timeout(sys.argv),
+    ).run()
+
+
+if __name__ == "__main__":
+    main()
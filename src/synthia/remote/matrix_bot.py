@@ -0,0 +1,222 @@
+#!/usr/bin/env python3
+"""
+Synthia Matrix Bot - remote mode over Matrix.
+
+Logs in with an existing access token, joins rooms it is invited to by
+allowed users, and answers their text messages: forwarded to Claude Code
+while remote mode is on, answered by the local assistant otherwise.
+``!dev`` and ``!quick`` switch between the two.
+
+Only unencrypted rooms are supported.
+"""
+
+import asyncio
+import logging
+import os
+import sys
+
+# Add src to path for imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.dirname(os.path.abspath(__file__)))))
+
+try:
+    from nio import AsyncClient, InviteMemberEvent, MatrixRoom, RoomMessageText, SyncError
+
+    HAS_NIO = True
+except ImportError:
+    HAS_NIO = False
+
+from synthia.config import load_config
+from synthia.remote.common import (
+    ApprovalGate,
+    ConnectionReporter,
+    parse_access,
+    parse_approval_timeout,
+    record_activity,
+    reply_to,
+    report_activity,
+    set_remote_mode,
+)
+
+# Set by the GUI from the keyring and config.yaml, as for Telegram.
+TOKEN_ENV_VAR = "SYNTHIA_MATRIX_ACCESS_TOKEN"
+HOMESERVER_ENV_VAR = "SYNTHIA_MATRIX_HOMESERVER"
+USER_ID_ENV_VAR = "SYNTHIA_MATRIX_USER_ID"
+
+# Long-poll timeout for /sync, in milliseconds.
+SYNC_TIMEOUT_MS = 30000
+
+# Wait between failed syncs, in seconds.
+RETRY_DELAY = 15
+
+logging.basicConfig(
+    format="%(asctime)s - %(name)s - %(levelname)s - %(message)s", level=logging.INFO
+)
+logger = logging.getLogger(__name__)
+
+
+class SynthiaMatrixBot:
+    """Matrix bot for remote Synthia access."""
+
+    def __init__(
+        self,
+        homeserver: str,
+        user_id: str,
+        access_token: str,
+        allowed_users: list[str],
+        allow_all: bool = False,
+        approval_timeout: float | None = None,
+    ):
+        self.user_id = user_id
+        self.allowed_users = allowed_users
+        self.allow_all = allow_all
+        self.config = load_config()
+        self._assistant = None
+        self.approvals = ApprovalGate(approval_timeout)
+        self._connection = ConnectionReporter("Matrix")
+        # Replies in progress, kept so they aren't garbage collected.
+        self._answering: set[asyncio.Task] = set()
+
+        self.client = AsyncClient(homeserver, user_id)
+        self.client.access_token = access_token
+
+    def is_authorized(self, user_id: str) -> bool:
+        authorized = self.allow_all or user_id in self.allowed_users
+        if authorized:
+            record_activity()
+        return authorized
+
+    def assistant(self):
+        """The local assistant, loaded on first use; Quick Mode only."""
+        if self._assistant is None:
+            from synthia.assistant import Assistant
+
+            self._assistant = Assistant(
+                use_local=True,
+                local_model=self.config.get("local_llm_model", "qwen2.5:1.5b-instruct-q4_0"),
+                ollama_url=self.config.get("ollama_url", "http://localhost:11434"),
+            )
+        return self._assistant
+
+    async def _reply(self, room_id: str, text: str) -> None:
+        await self.client.room_send(
+            room_id,
+            message_type="m.room.message",
+            content={"msgtype": "m.notice", "body": text},
+        )
+
+    async def on_invite(self, room: "MatrixRoom", event: "InviteMemberEvent") -> None:
+        if event.state_key != self.user_id or event.membership != "invite":
+            return
+        if not self.is_authorized(event.sender):
+            logger.info("Ignoring invite to %s from %s", room.room_id, event.sender)
+            return
+        logger.info("Joining %s at %s's invite", room.room_id, event.sender)
+        await self.client.join(room.room_id)
+
+    async def on_message(self, room: "MatrixRoom", event: "RoomMessageText") -> None:
+        if event.sender == self.user_id or not self.is_authorized(event.sender):
+            return
+        text = event.body.strip()
+        kind = "command" if text in ("!dev", "!quick") else "message"
+        report_activity("in", event.sender, kind, text)
+        if text == "!dev":
+            set_remote_mode(True)
+            await self._reply(
+                room.room_id, "🟢 Dev Mode ENABLED\n\nYou can now control Claude Code."
+            )
+            return
+        if text == "!quick":
+            set_remote_mode(False)
+            await self._reply(
+                room.room_id, "🟡 Quick Mode ENABLED\n\nBack to the local assistant."
+            )
+            return
+
+        logger.info("Received text: %s", text)
+        # Callbacks run inside /sync, and a reply can wait minutes on the
+        # desktop's approval, so answer in the background.
+        task = asyncio.create_task(self._answer(room.room_id, text))
+        self._answering.add(task)
+        task.add_done_callback(self._answering.discard)
+
+    async def _answer(self, room_id: str, text: str) -> None:
+        try:
+            reply = await asyncio.to_thread(reply_to, text, self.assistant, self.approvals)
+        except Exception as e:
+            logger.error("Error processing text: %s", e)
+            reply = f"Error: {e}"
+        await self._reply(room_id, reply)
+
+    async def _sync_once(self, full_state: bool = False) -> bool:
+        """One /sync round; reports the connection and returns whether it worked."""
+        try:
+            response = await self.client.sync(timeout=SYNC_TIMEOUT_MS, full_state=full_state)
+        except Exception as e:
+            logger.warning("Matrix sync failed: %s", e)
+            response = None
+        ok = response is not None and not isinstance(response, SyncError)
+        if response is not None and not ok:
+            logger.warning("Matrix sync refused: %s", response.message)
+        self._connection.report(ok)
+        return ok
+
+    async def run(self):
+        """Sync until stopped."""
+        logger.info("Starting Synthia Matrix bot...")
+        # The first sync only finds where the timeline stands, so messages
+        # sent before the bot started aren't answered now.
+        while not await self._sync_once(full_state=True):
+            await asyncio.sleep(RETRY_DELAY)
+        self.client.add_event_callback(self.on_invite, InviteMemberEvent)
+        self.client.add_event_callback(self.on_message, RoomMessageText)
+        logger.info("Bot is ready! Listening for messages...")
+        try:
+            while True:
+                if not await self._sync_once():
+                    await asyncio.sleep(RETRY_DELAY)
+        finally:
+            await self.client.close()
+
+
+def main():
+    """Main entry point."""
+    if not HAS_NIO:
+        print("Error: matrix-nio is not installed (pip install synthia[matrix])")
+        sys.exit(1)
+
+    config = load_config()
+    homeserver = os.environ.get(HOMESERVER_ENV_VAR) or config.get("matrix_homeserver", "")
+    user_id = os.environ.get(USER_ID_ENV_VAR) or config.get("matrix_user_id", "")
+    access_token = os.environ.get(TOKEN_ENV_VAR) or config.get("matrix_access_token", "")
+    allowed_users = list(config.get("matrix_allowed_users", []))
+
+    # The GUI passes the allowlist it validated, so both sides agree on it.
+    passed_users, allow_all = parse_access(sys.argv)
+    if passed_users is not None:
+        allowed_users = passed_users
+
+    if not (homeserver and user_id and access_token):
+        print("Error: matrix_homeserver, matrix_user_id and matrix_access_token must be set")
+        print("Add them to ~/.config/synthia/config.yaml")
+        sys.exit(1)
+
+    if allow_all:
+        logger.warning("Starting without a user allowlist (--allow-all)")
+    elif not allowed_users:
+        print("Error: matrix_allowed_users not set in config")
+        print("Add your Matrix user ID (e.g. @you:matrix.org) to ~/.config/synthia/config.yaml")
+        sys.exit(1)
+
+    bot = SynthiaMatrixBot(
+        homeserver,
+        user_id,
+        access_token,
+        allowed_users,
+        allow_all=allow_all,
+        approval_timeout=parse_approval_timeout(sys.argv),
+    )
+    asyncio.run(bot.run())
+
+
+if __name__ == "__main__":
+    main()
@@ -17,56 +17,21 @@ import subprocess
 import sys
 import tempfile
 import time
-from datetime import datetime
 from pathlib import Path
 
 # Add src to path for imports
 sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.dirname(os.path.abspath(__file__)))))
 
 
-# Security: Input sanitization for text sent to terminal
-MAX_MESSAGE_LENGTH = 2000  # Limit message length
-DANGEROUS_SEQUENCES = [
-    "\x1b",  # Escape sequences
-    "\x00",  # Null bytes
-    "\x07",  # Bell
-    "\x08",  # Backspace
-    "\x7f",  # Delete
-]
-
-
-def sanitize_terminal_input(text: str) -> str:
-    """Sanitize text before sending to terminal via xdotool.
-
-    Removes control characters and escape sequences that could be used
-    to manipulate the terminal.
-    """
-    if not text:
-        return ""
-
-    # Truncate to max length
-    text = text[:MAX_MESSAGE_LENGTH]
-
-    # Remove dangerous control characters
-    for seq in DANGEROUS_SEQUENCES:
-        text = text.replace(seq, "")
-
-    # Remove ANSI escape sequences
-    text = re.sub(r"\x1b\[[0-9;]*[a-zA-Z]", "", text)
-
-    # Remove other control characters (except newline, tab)
-    text = "".join(char for char in text if char == "\n" or char == "\t" or ord(char) >= 32)
-
-    return text.strip()
-
-
 try:
     from telegram import Update
+    from telegram.error import NetworkError
     from telegram.ext import (
         Application,
         CommandHandler,
         ContextTypes,
         MessageHandler,
+        TypeHandler,
         filters,
     )
 
@@ -76,13 +41,34 @@ except ImportError:
 
 from synthia.assistant import Assistant
 from synthia.config import load_config
+from synthia.remote.common import (
+    REMOTE_MODE_FILE,
+    RUNTIME_DIR,
+    ApprovalGate,
+    ConnectionReporter,
+    forward_to_claude_code,
+    is_remote_mode,
+    parse_access,
+    parse_approval_timeout,
+    record_activity,
+    report_activity,
+)
 from synthia.transcribe import Transcriber
 
-# Use XDG_RUNTIME_DIR for secure temp files (user-only access, not world-readable /tmp)
-_RUNTIME_DIR = os.environ.get("XDG_RUNTIME_DIR", "/tmp")
-REMOTE_MODE_FILE = os.path.join(_RUNTIME_DIR, "synthia-remote-mode")
-WAITING_APPROVAL_FILE = os.path.join(_RUNTIME_DIR, "synthia-waiting-approval")
-PLAN_APPROVED_FILE = os.path.join(_RUNTIME_DIR, "synthia-plan-approved")
+WAITING_APPROVAL_FILE = os.path.join(RUNTIME_DIR, "synthia-waiting-approval")
+PLAN_APPROVED_FILE = os.path.join(RUNTIME_DIR, "synthia-plan-approved")
+
+# The GUI resolves the token itself and hands it over here, so the bot
+# doesn't depend on finding the same config from its working directory.
+TOKEN_ENV_VAR = "SYNTHIA_TELEGRAM_BOT_TOKEN"
+
+# How often to check whether Telegram is reachable again after a network error.
+RECONNECT_CHECK_INTERVAL = 15
+
+
+def _bot_token(config: dict) -> str:
+    """Token passed by the GUI, else the one in config.yaml."""
+    return os.environ.get(TOKEN_ENV_VAR) or config.get("telegram_bot_token", "")
 
 # Set up logging
 logging.basicConfig(
@@ -94,10 +80,20 @@ logger = logging.getLogger(__name__)
 class SynthiaBot:
     """Telegram bot for remote Synthia access."""
 
-    def __init__(self, bot_token: str, allowed_users: list):
+    def __init__(
+        self,
+        bot_token: str,
+        allowed_users: list,
+        allow_all: bool = False,
+        approval_timeout: float | None = None,
+    ):
         self.bot_token = bot_token
         self.allowed_users = allowed_users
+        self.allow_all = allow_all
+        self.approvals = ApprovalGate(approval_timeout)
         self.app = None
+        self._connection = ConnectionReporter("Telegram")
+        self._reconnect_task = None
 
         # Load config and initialize components
         self.config = load_config()
@@ -118,7 +114,10 @@ class SynthiaBot:
 
     def is_authorized(self, user_id: int) -> bool:
         """Check if user is authorized to use the bot."""
-        return user_id in self.allowed_users
+        authorized = self.allow_all or user_id in self.allowed_users
+        if authorized:
+            record_activity()
+        return authorized
 
     async def start(self, update: Update, context: ContextTypes.DEFAULT_TYPE):
         """Handle /start command."""
@@ -248,7 +247,7 @@ class SynthiaBot:
             await update.message.reply_text("Taking screenshot...")
 
             # Take screenshot using gnome-screenshot or scrot
-            screenshot_path = os.path.join(_RUNTIME_DIR, "synthia_screenshot.png")
+            screenshot_path = os.path.join(RUNTIME_DIR, "synthia_screenshot.png")
 
             # Try gnome-screenshot first, fall back to scrot
             try:
@@ -468,7 +467,7 @@ class SynthiaBot:
                         f.write("approved")
                     await update.message.reply_text("✅ Approved! Executing plan...")
                     # Send "proceed" to Claude Code
-                    self._send_to_claude_code("proceed with the plan")
+                    self._forward(update, context, "proceed with the plan")
                     return
                 else:
                     # New request while waiting - cancel old plan
@@ -486,9 +485,7 @@ class SynthiaBot:
             ]
             await update.message.reply_text(random.choice(processing_msgs))
 
-            success = self._send_to_claude_code(text)
-            if not success:
-                await update.message.reply_text("❌ Failed to send. Is WezTerm running?")
+            self._forward(update, context, text)
             return
 
         try:
@@ -497,6 +494,7 @@ class SynthiaBot:
 
             # Extract the spoken response (key is 'speech' not 'response')
             reply = response.get("speech", "Sorry, I could not process that.")
+            report_activity("out", "synthia", "reply", reply)
 
             await update.message.reply_text(reply)
 
@@ -504,145 +502,6 @@ class SynthiaBot:
             logger.error(f"Error processing text: {e}")
             await update.message.reply_text(f"Error: {e}")
 
-    def _is_wayland(self) -> bool:
-        """Check if running on Wayland."""
-        return bool(os.environ.get("WAYLAND_DISPLAY"))
-
-    def _get_display(self) -> str:
-        """Get the active X display, trying common options."""
-        for display in [":1", ":0"]:
-            try:
-                result = subprocess.run(
-                    ["wmctrl", "-l"],
-                    capture_output=True,
-                    text=True,
-                    env={**os.environ, "DISPLAY": display},
-                    timeout=2,
-                )
-                if result.returncode == 0 and result.stdout.strip():
-                    return display
-            except Exception:
-                pass
-        return os.environ.get("DISPLAY", ":0")
-
-    def _send_to_claude_code(self, message: str) -> bool:
-        """Send a message to the Claude Code terminal.
-
-        On Wayland: Uses wtype to type to the focused window.
-        On X11: Uses xdotool with window targeting.
-
-        SECURITY: Input is sanitized to prevent terminal escape sequence injection.
-        """
-        # Sanitize input before sending to terminal
-        message = sanitize_terminal_input(message)
-        if not message:
-            logger.warning("Message was empty after sanitization")
-            return False
-
-        try:
-            # Use Wayland-native approach if available
-            if self._is_wayland():
-                return self._send_wayland(message)
-            else:
-                return self._send_x11(message)
-
-        except Exception as e:
-            logger.error(f"Error sending to Claude Code: {e}")
-            return False
-
-    def _send_wayland(self, message: str) -> bool:
-        """Send message using Wayland tools (wtype or ydotool)."""
-        # Try ydotool first (works even without focus on the window)
-        try:
-            # Check if ydotoold is running
-            result = subprocess.run(["pgrep", "-x", "ydotoold"], capture_output=True)
-            if result.returncode == 0:
-                # ydotool is available and daemon is running
-                subprocess.run(["ydotool", "type", "--", message], check=True)
-                subprocess.run(["ydotool", "key", "enter"], check=True)
-                logger.info(f"Sent via ydotool: {message}")
-                return True
-        except FileNotFoundError:
-            pass  # ydotool not installed, try wtype
-
-        # Fallback to wtype (requires the terminal to be focused)
-        try:
-            # wtype types to the currently focused window
-            subprocess.run(["wtype", message], check=True)
-            subprocess.run(["wtype", "-k", "Return"], check=True)
-            logger.info(f"Sent via wtype: {message}")
-            return True
-        except FileNotFoundError:
-            logger.error("No Wayland typing tool found. Install wtype or ydotool.")
-            return False
-        except subprocess.CalledProcessError as e:
-            logger.error(f"wtype failed: {e}")
-            return False
-
-    def _send_x11(self, message: str) -> bool:
-        """Send message using X11 tools (xdotool with window targeting)."""
-        display = self._get_display()
-
-        # Find terminal window - look for "Remote" tab in WezTerm
-        result = subprocess.run(
-            ["wmctrl", "-l"], capture_output=True, text=True, env={**os.environ, "DISPLAY": display}
-        )
-
-        # Priority order for window matching:
-        # 1. Window titled "Remote" (dedicated remote control tab)
-        # 2. Window with Claude Code indicator (✳ or Claude)
-        # 3. WezTerm windows (contain tab info like [1/4])
-        window_id = None
-        candidates: list[tuple[str, str]] = []
-        for line in result.stdout.strip().split("\n"):
-            parts = line.split(None, 4)
-            if len(parts) >= 4:
-                wid = parts[0]
-                title = parts[-1] if len(parts) > 4 else ""
-                # Skip empty titles and desktop/panel windows
-                if not title or title.startswith("@!") or title.startswith("N/A"):
-                    continue
-                # Skip browser windows (Chrome, Firefox, etc)
-                if any(x in title for x in ["Chrome", "Firefox", "Zen", "Brave"]):
-                    continue
-                # Highest priority - "Remote" tab
-                if title == "Remote":
-                    candidates.insert(0, (wid, title))
-                # High priority - Claude Code window (has ✳ indicator)
-                elif "✳" in title:
-                    candidates.insert(
-                        1 if candidates and candidates[0][1] == "Remote" else 0, (wid, title)
-                    )
-                # Medium priority - WezTerm with tab indicator [X/Y]
-                elif title.startswith("[") and "/" in title[:6]:
-                    candidates.append((wid, title))
-
-        if candidates:
-            wid, title = candidates[0]
-            window_id = str(int(wid, 16))
-            logger.info(f"Found terminal window: {title} ({wid})")
-
-        if not window_id:
-            logger.error("No terminal window found")
-            return False
-
-        # Type the message into the terminal
-        subprocess.run(
-            ["xdotool", "type", "--window", window_id, "--clearmodifiers", message],
-            check=True,
-            env={**os.environ, "DISPLAY": display},
-        )
-
-        # Press Enter
-        subprocess.run(
-            ["xdotool", "key", "--window", window_id, "Return"],
-            check=True,
-            env={**os.environ, "DISPLAY": display},
-        )
-
-        logger.info(f"Sent via xdotool: {message}")
-        return True
-
     async def handle_voice(self, update: Update, context: ContextTypes.DEFAULT_TYPE):
         """Handle voice notes - transcribe and process."""
         assert update.effective_user is not None
@@ -687,6 +546,7 @@ class SynthiaBot:
             if not transcript:
                 await update.message.reply_text("Couldn't understand that. Try again?")
                 return
+            report_activity("in", str(update.effective_user.id), "transcript", transcript)
 
             # If in remote mode, send to Claude Code
             if self._is_remote_mode():
@@ -701,9 +561,7 @@ class SynthiaBot:
                 ]
                 await update.message.reply_text(random.choice(sending_msgs))
 
-                success = self._send_to_claude_code(transcript)
-                if not success:
-                    await update.message.reply_text("❌ Failed to send. Is WezTerm running?")
+                self._forward(update, context, transcript)
                 return
 
             # Show what was heard (only in local mode)
@@ -712,6 +570,7 @@ class SynthiaBot:
             # Process with assistant
             response = self.assistant.process(transcript)
             reply = response.get("speech", "Sorry, I could not process that.")
+            report_activity("out", "synthia", "reply", reply)
 
             await update.message.reply_text(reply)
 
@@ -729,7 +588,23 @@ class SynthiaBot:
 
     def _is_remote_mode(self) -> bool:
         """Check if remote mode is enabled."""
-        return os.path.exists(REMOTE_MODE_FILE)
+        return is_remote_mode()
+
+    def _forward(self, update: Update, context: ContextTypes.DEFAULT_TYPE, text: str) -> None:
+        """Send ``text`` to Claude Code in the background.
+
+        It may wait minutes for approval on the desktop, and updates are
+        handled one at a time, so the bot keeps answering meanwhile.
+        """
+        message = update.message
+        assert message is not None
+
+        async def forward():
+            problem = await asyncio.to_thread(forward_to_claude_code, text, self.approvals)
+            if problem:
+                await message.reply_text(problem)
+
+        context.application.create_task(forward(), update=update)
 
     def _get_remote_chat_id(self) -> int | None:
         """Get the chat ID for remote notifications."""
@@ -783,6 +658,60 @@ class SynthiaBot:
         )
         logger.info("Quick Mode enabled")
 
+    def _report_connection(self, connected: bool) -> None:
+        self._connection.report(connected)
+
+    async def _on_startup(self, application) -> None:
+        # initialize() has already called getMe, so Telegram is reachable.
+        self._report_connection(True)
+
+    async def _on_update(self, update, context) -> None:
+        self._report_connection(True)
+        self._report_incoming(update)
+
+    def _report_incoming(self, update) -> None:
+        """Log an allowed user's message for the GUI, before any handler runs."""
+        message = getattr(update, "message", None)
+        user = getattr(update, "effective_user", None)
+        if message is None or user is None:
+            return
+        if not (self.allow_all or user.id in self.allowed_users):
+            return
+        if message.text:
+            kind = "command" if message.text.startswith("/") else "message"
+            text = message.text
+        elif message.voice:
+            kind, text = "voice", ""
+        elif message.document:
+            kind, text = "file", message.document.file_name or ""
+        elif message.photo:
+            kind, text = "photo", message.caption or ""
+        else:
+            return
+        report_activity("in", str(user.id), kind, text)
+
+    async def _await_reconnect(self, bot) -> None:
+        """Poll getMe until Telegram answers again, then report it."""
+        while True:
+            await asyncio.sleep(RECONNECT_CHECK_INTERVAL)
+            try:
+                await bot.get_me()
+            except NetworkError:
+                continue
+            self._report_connection(True)
+            return
+
+    async def _on_error(self, update, context) -> None:
+        if not isinstance(context.error, NetworkError):
+            logger.error("Unhandled error: %s", context.error, exc_info=context.error)
+            return
+        logger.warning("Telegram unreachable: %s", context.error)
+        self._report_connection(False)
+        if self._reconnect_task is None or self._reconnect_task.done():
+            self._reconnect_task = context.application.create_task(
+                self._await_reconnect(context.bot)
+            )
+
     async def send_notification(self, chat_id: int, message: str):
         """Send a push notification to a user."""
         if self.app:
@@ -793,7 +722,13 @@ class SynthiaBot:
         logger.info("Starting Synthia Telegram bot...")
 
         # Build application
-        self.app = Application.builder().token(self.bot_token).build()
+        self.app = (
+            Application.builder().token(self.bot_token).post_init(self._on_startup).build()
+        )
+
+        # Any update means Telegram is reachable; runs ahead of the handlers below.
+        self.app.add_handler(TypeHandler(Update, self._on_update), group=-1)
+        self.app.add_error_handler(self._on_error)
 
         # Add handlers
         self.app.add_handler(CommandHandler("start", self.start))
@@ -825,7 +760,7 @@ def send_telegram_notification(message: str):
     import requests
 
     config = load_config()
-    bot_token = config.get("telegram_bot_token")
+    bot_token = _bot_token(config)
     allowed_users = config.get("telegram_allowed_users", [])
 
     if not bot_token or not allowed_users:
@@ -853,20 +788,32 @@ def main():
 
     config = load_config()
 
-    bot_token = config.get("telegram_bot_token")
+    bot_token = _bot_token(config)
     allowed_users = config.get("telegram_allowed_users", [])
 
+    # The GUI passes the allowlist it validated, so both sides agree on it.
+    passed_users, allow_all = parse_access(sys.argv)
+    if passed_users is not None:
+        allowed_users = [int(u) for u in passed_users]
+
     if not bot_token:
         print("Error: telegram_bot_token not set in config")
         print("Add it to ~/.config/synthia/config.yaml")
         sys.exit(1)
 
-    if not allowed_users:
+    if allow_all:
+        logger.warning("Starting without a user allowlist (--allow-all)")
+    elif not allowed_users:
         print("Error: telegram_allowed_users not set in config")
         print("Add your Telegram user ID to ~/.config/synthia/config.yaml")
         sys.exit(1)
 
-    bot = SynthiaBot(bot_token, allowed_users)
+    bot = SynthiaBot(
+        bot_token,
+        allowed_users,
+        allow_all=allow_all,
+        approval_timeout=parse_approval_timeout(sys.argv),
+    )
     bot.run()
 
 
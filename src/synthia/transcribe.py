@@ -6,11 +6,15 @@ import logging
 import os
 import sys
 import tempfile
+from typing import Any, Callable, Optional
 
 import numpy as np
 
 logger = logging.getLogger(__name__)
 
+# Receives the text decoded so far, each time it grows.
+PartialCallback = Callable[[str], None]
+
 # Add cuDNN libraries to path for GPU support
 _cudnn_path = os.path.join(
     os.path.dirname(__file__),
@@ -21,6 +25,44 @@ if os.path.exists(_cudnn_path):
     os.environ["LD_LIBRARY_PATH"] = _cudnn_path + ":" + os.environ.get("LD_LIBRARY_PATH", "")
 
 
+
+def compute_device() -> str:
+    """``cpu``, ``cuda`` or ``rocm``, as chosen in the GUI and passed in
+    ``SYNTHIA_COMPUTE_DEVICE``. Defaults to ``cpu``."""
+    device = os.environ.get("SYNTHIA_COMPUTE_DEVICE", "cpu")
+    return device if device in ("cpu", "cuda", "rocm") else "cpu"
+
+
+def vad_threshold(sensitivity: float) -> float:
+    """Silero VAD speech threshold for a ``vad_sensitivity`` in 0.0–1.0.
+
+    Higher sensitivity keeps quieter or less certain speech. The default
+    0.5 gives faster-whisper's own default threshold of 0.5.
+    """
+    sensitivity = min(max(sensitivity, 0.0), 1.0)
+    return 0.8 - 0.6 * sensitivity
+
+
+def whisper_language(tag: str) -> str:
+    """Whisper's language code for a BCP 47 tag: ``en-US`` -> ``en``."""
+    return tag.split("-")[0].lower()
+
+
+def managed_model_path(name: str) -> str | None:
+    """The directory of Whisper model ``name`` if the GUI's model manager
+    has downloaded it, else None.
+
+    Models live in ``synthia/models`` under the user data dir: on Linux
+    ``$XDG_DATA_HOME``, defaulting to ``~/.local/share``, matching the GUI.
+    """
+    if sys.platform == "darwin":
+        data_dir = os.path.expanduser("~/Library/Application Support")
+    else:
+        data_dir = os.environ.get("XDG_DATA_HOME") or os.path.expanduser("~/.local/share")
+    path = os.path.join(data_dir, "synthia", "models", name)
+    return path if os.path.isfile(os.path.join(path, "model.bin")) else None
+
+
 class Transcriber:
     """Transcribes audio using Google Cloud Speech-to-Text or local Whisper."""
 
@@ -31,8 +73,10 @@ class Transcriber:
         sample_rate: int = 16000,
         use_local: bool = False,
         local_model: str = "small",
+        vad_sensitivity: float = 0.5,
     ) -> None:
         self.language = language
+        self.vad_sensitivity = vad_sensitivity
         self.sample_rate = sample_rate
         self.use_local = use_local
         self.local_model = local_model
@@ -47,6 +91,15 @@ class Transcriber:
         else:
             self._init_google(credentials_path)
 
+    @property
+    def model_name(self) -> str:
+        """The Whisper model size in use, or ``google`` for Google STT."""
+        return self.local_model if self.use_local else "google"
+
+    def duration(self, audio_data: bytes) -> float:
+        """Length in seconds of 16-bit mono ``audio_data``."""
+        return len(audio_data) / (2 * self.sample_rate)
+
     def _init_google(self, credentials_path: str | None) -> None:
         """Initialize Google Cloud Speech-to-Text."""
         from google.cloud import speech
@@ -67,22 +120,58 @@ class Transcriber:
         )
         logger.info("Google STT initialized")
 
-    def _init_whisper(self) -> None:
-        """Initialize local Whisper model using faster-whisper."""
+    def _init_whisper(self, path: str | None = None) -> None:
+        """Initialize local Whisper model using faster-whisper.
+
+        Loads from ``path``, else the GUI-managed copy of the model, else
+        lets faster-whisper fetch it by name into its own cache.
+        """
+        self.whisper_model = self._load_whisper(self.local_model, path)
+
+    def _load_whisper(self, model_name: str, path: str | None = None) -> Any:
         from faster_whisper import WhisperModel
 
-        model_name = self.local_model
-        logger.info("Loading faster-whisper model: %s...", model_name)
+        source = path or managed_model_path(model_name) or model_name
+        logger.info("Loading faster-whisper model: %s (%s)...", model_name, source)
 
-        # Use CPU - GPU inference produces garbage with current cuDNN version
-        # TODO: Re-enable GPU after upgrading ctranslate2 + matching cuDNN
-        self.whisper_model = WhisperModel(
-            model_name, device="cpu", compute_type="int8", cpu_threads=4
-        )
-        logger.info("Faster-whisper %s loaded (CPU int8)", model_name)
+        # CPU unless the GUI was told otherwise: GPU inference produces
+        # garbage with the current cuDNN version.
+        # TODO: Make GPU the default after upgrading ctranslate2 + matching cuDNN
+        if compute_device() == "cpu":
+            model = WhisperModel(source, device="cpu", compute_type="int8", cpu_threads=4)
+            logger.info("Faster-whisper %s loaded (CPU int8)", model_name)
+        else:
+            # CTranslate2's ROCm builds present AMD GPUs as CUDA devices.
+            model = WhisperModel(source, device="cuda", compute_type="float16")
+            logger.info("Faster-whisper %s loaded (%s float16)", model_name, compute_device())
+        return model
 
-    def transcribe(self, audio_data: bytes) -> str:
-        """Transcribe audio bytes to text."""
+    def set_language(self, language: str) -> None:
+        """Transcribe ``language``, a BCP 47 tag such as ``en-US``, from now on."""
+        self.language = language
+        if self.client is not None:
+            self.config.language_code = language
+        logger.info("Transcription language set to %s", language)
+
+    def set_model(self, model_name: str, path: str | None = None) -> None:
+        """Switch local Whisper to ``model_name``, loaded from ``path`` if given.
+
+        The new model is loaded before the old one is dropped, so a
+        transcription running meanwhile finishes on the old one.
+        """
+        if not self.use_local:
+            raise RuntimeError("Local Whisper is not in use")
+        model = self._load_whisper(model_name, path)
+        self.whisper_model = model
+        self.local_model = model_name
+
+    def transcribe(self, audio_data: bytes, on_partial: Optional[PartialCallback] = None) -> str:
+        """Transcribe audio bytes to text.
+
+        With local Whisper, ``on_partial`` is called with the text decoded so
+        far after every segment. Google STT returns all text at once and
+        never calls it.
+        """
         if not audio_data:
             return ""
 
@@ -90,7 +179,7 @@ class Transcriber:
 
         try:
             if self.use_local:
-                return self._transcribe_whisper(audio_data)
+                return self._transcribe_whisper(audio_data, on_partial)
             else:
                 return self._transcribe_google(audio_data)
         except Exception as e:
@@ -120,7 +209,9 @@ class Transcriber:
         logger.info("Transcribed: %s", cleaned)
         return cleaned
 
-    def _transcribe_whisper(self, audio_data: bytes) -> str:
+    def _transcribe_whisper(
+        self, audio_data: bytes, on_partial: Optional[PartialCallback] = None
+    ) -> str:
         """Transcribe using faster-whisper model."""
         # Convert bytes to numpy array (16-bit signed int)
         audio_np = np.frombuffer(audio_data, dtype=np.int16).astype(np.float32) / 32768.0
@@ -141,9 +232,10 @@ class Transcriber:
         assert self.whisper_model is not None
         segments, info = self.whisper_model.transcribe(
             audio_np,
-            language="en",
+            language=whisper_language(self.language),
             beam_size=1,  # Faster with beam_size=1
             vad_filter=True,  # Filter out silence
+            vad_parameters={"threshold": vad_threshold(self.vad_sensitivity)},
             no_speech_threshold=0.6,  # Skip segments likely without speech
             hallucination_silence_threshold=1.0,  # Skip hallucinated silence
             condition_on_previous_text=False,  # Prevent hallucination loops
@@ -153,6 +245,8 @@ class Transcriber:
         parts = []
         for segment in segments:
             parts.append(segment.text)
+            if on_partial:
+                on_partial(self._clean_transcript(" ".join(parts)))
             if len(parts) > 50:  # Safety limit
                 logger.warning("Hit segment limit, stopping transcription")
                 break
@@ -14,6 +14,17 @@ import sounddevice as sd
 
 logger = logging.getLogger(__name__)
 
+# Mean absolute amplitude a chunk needs before it is transcribed, at
+# sensitivity 0.0 and 1.0. The default 0.5 gives the old fixed 100.
+QUIET_THRESHOLD = 180
+LOUD_THRESHOLD = 20
+
+
+def energy_threshold(sensitivity: float) -> float:
+    """Energy gate for a sensitivity in 0.0–1.0; higher lets quieter speech through."""
+    sensitivity = min(max(sensitivity, 0.0), 1.0)
+    return QUIET_THRESHOLD + (LOUD_THRESHOLD - QUIET_THRESHOLD) * sensitivity
+
 
 class WakeWordDetector:
     """Detects wake word using continuous audio monitoring.
@@ -30,12 +41,23 @@ class WakeWordDetector:
         sample_rate: int = 16000,
         chunk_duration: float = 2.0,  # seconds
         device: Optional[int] = None,
+        sensitivity: float = 0.5,
+        should_listen: Optional[Callable[[], bool]] = None,
     ):
         self.wake_words = [w.lower() for w in wake_words]
         self.on_wake = on_wake
         self.sample_rate = sample_rate
         self.chunk_duration = chunk_duration
         self.device = device
+        self.sensitivity = sensitivity
+        # Checked before every chunk; while it returns False the microphone
+        # is left alone (e.g. muted, or a recording is already running).
+        self.should_listen = should_listen
+
+        # Outcomes since start, reported in the state file as
+        # `wake_detections` so the GUI can show whether tuning helped.
+        self.accepted = 0
+        self.false_triggers = 0
 
         self.running = False
         self._thread: Optional[threading.Thread] = None
@@ -45,11 +67,23 @@ class WakeWordDetector:
         """Set the transcriber to use for wake word detection."""
         self._transcriber = transcriber
 
+    def set_sensitivity(self, sensitivity: float):
+        """Apply a new sensitivity (0.0–1.0) from a config reload."""
+        self.sensitivity = min(max(sensitivity, 0.0), 1.0)
+        logger.info("Wake word sensitivity set to %.2f", self.sensitivity)
+
+    def stats(self) -> dict:
+        """Counts in the state file's `wake_detections` shape."""
+        return {"accepted": self.accepted, "false_triggers": self.false_triggers}
+
     def _listen_loop(self):
         """Main listening loop - runs in background thread."""
         logger.info("Wake word detection active")
 
         while self.running:
+            if self.should_listen and not self.should_listen():
+                time.sleep(0.5)
+                continue
             try:
                 # Record a short chunk
                 frames = int(self.sample_rate * self.chunk_duration)
@@ -63,7 +97,7 @@ class WakeWordDetector:
                 sd.wait()
 
                 # Check if audio has enough energy (not silence)
-                if np.abs(audio).mean() < 100:
+                if np.abs(audio).mean() < energy_threshold(self.sensitivity):
                     continue
 
                 # Transcribe and check for wake word
@@ -74,8 +108,12 @@ class WakeWordDetector:
                     for wake_word in self.wake_words:
                         if wake_word in text_lower:
                             logger.info("Wake word detected: '%s'", wake_word)
-                            if self.on_wake:
-                                self.on_wake()
+                            # on_wake returns False when nothing followed
+                            # the wake word, i.e. it was a false trigger.
+                            if self.on_wake and self.on_wake() is False:
+                                self.false_triggers += 1
+                            else:
+                                self.accepted += 1
                             # Brief pause after detection
                             time.sleep(0.5)
                             break
@@ -149,6 +149,13 @@ class TestDefaultConfig:
             "ollama_url",
             "telegram_bot_token",
             "telegram_allowed_users",
+            "discord_bot_token",
+            "discord_allowed_users",
+            "matrix_homeserver",
+            "matrix_user_id",
+            "matrix_access_token",
+            "matrix_allowed_users",
+            "matrix_room_id",
             "tavily_api_key",
             "use_llm_polish",
             "llm_polish_model",
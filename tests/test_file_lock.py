@@ -0,0 +1,38 @@
+"""Tests for synthia.file_lock module."""
+
+import fcntl
+import os
+
+import pytest
+
+from synthia.file_lock import lock_path, locked
+
+
+def test_lock_sits_next_to_the_file():
+    """The lock for synthia-state.json should be synthia-state.lock."""
+    assert lock_path("/run/user/1000/synthia-state.json") == "/run/user/1000/synthia-state.lock"
+
+
+def test_exclusive_lock_is_held_for_the_block(tmp_path):
+    """Another descriptor can't take the lock until the block exits."""
+    state_file = str(tmp_path / "synthia-state.json")
+    with locked(state_file):
+        fd = os.open(lock_path(state_file), os.O_RDWR)
+        try:
+            with pytest.raises(BlockingIOError):
+                fcntl.flock(fd, fcntl.LOCK_SH | fcntl.LOCK_NB)
+        finally:
+            os.close(fd)
+    fd = os.open(lock_path(state_file), os.O_RDWR)
+    try:
+        fcntl.flock(fd, fcntl.LOCK_EX | fcntl.LOCK_NB)
+    finally:
+        os.close(fd)
+
+
+def test_missing_directory_runs_unlocked(tmp_path):
+    """A lock file that can't be created shouldn't stop the write."""
+    ran = False
+    with locked(str(tmp_path / "missing" / "synthia-state.json")):
+        ran = True
+    assert ran
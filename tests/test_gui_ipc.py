@@ -0,0 +1,100 @@
+"""Tests for synthia.gui_ipc module."""
+
+import socket
+import struct
+import threading
+
+import pytest
+
+from synthia.gui_ipc import MAX_MESSAGE_BYTES, GuiLink, encode_frame, read_frame
+
+
+class TestFraming:
+    """Tests for the length-prefixed frame encoding."""
+
+    def test_round_trip(self):
+        """A frame written by encode_frame should read back unchanged."""
+        a, b = socket.socketpair()
+        with a, b:
+            a.sendall(encode_frame({"type": "command", "command": "ping", "args": {}}))
+            a.sendall(encode_frame({"type": "command", "command": "reload_config"}))
+            assert read_frame(b) == {"type": "command", "command": "ping", "args": {}}
+            assert read_frame(b)["command"] == "reload_config"
+
+    def test_eof_returns_none(self):
+        """read_frame should return None once the peer hangs up."""
+        a, b = socket.socketpair()
+        a.close()
+        with b:
+            assert read_frame(b) is None
+
+    def test_oversized_frame_rejected(self):
+        """A length above the limit should raise instead of allocating it."""
+        a, b = socket.socketpair()
+        with a, b:
+            a.sendall(struct.pack(">I", MAX_MESSAGE_BYTES + 1))
+            with pytest.raises(ValueError):
+                read_frame(b)
+
+
+class TestGuiLink:
+    """Tests for the GuiLink connection."""
+
+    def test_send_without_socket_returns_false(self, tmp_path):
+        """send should report failure when the GUI isn't listening."""
+        link = GuiLink(str(tmp_path / "synthia.sock"), lambda command, args: None)
+        assert link.send_state({"status": "ready"}) is False
+
+    def test_state_out_and_command_in(self, tmp_path):
+        """State goes out as a frame and commands from the GUI reach the handler."""
+        path = str(tmp_path / "synthia.sock")
+        server = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        server.bind(path)
+        server.listen(1)
+        received = threading.Event()
+        commands = []
+
+        def on_command(command, args):
+            commands.append((command, args))
+            received.set()
+
+        link = GuiLink(path, on_command)
+        try:
+            assert link.send_state({"status": "ready"})
+            conn, _ = server.accept()
+            with conn:
+                assert read_frame(conn) == {"type": "state", "state": {"status": "ready"}}
+                conn.sendall(encode_frame({"type": "command", "command": "ping", "args": {"n": 1}}))
+                assert received.wait(2)
+            assert commands == [("ping", {"n": 1})]
+        finally:
+            link.close()
+            server.close()
+
+    def test_shutdown_is_not_held_up_by_a_running_command(self, tmp_path):
+        """A slow command runs off the reader thread, so shutdown still gets through."""
+        path = str(tmp_path / "synthia.sock")
+        server = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        server.bind(path)
+        server.listen(1)
+        release = threading.Event()
+        shutdown = threading.Event()
+
+        def on_command(command, args):
+            if command == "recording":
+                release.wait(5)
+            elif command == "shutdown":
+                shutdown.set()
+
+        link = GuiLink(path, on_command)
+        try:
+            assert link.send_state({"status": "ready"})
+            conn, _ = server.accept()
+            with conn:
+                conn.sendall(encode_frame({"type": "command", "command": "recording", "args": {}}))
+                conn.sendall(encode_frame({"type": "command", "command": "shutdown", "args": {}}))
+                assert shutdown.wait(2)
+        finally:
+            release.set()
+            link.close()
+            server.close()
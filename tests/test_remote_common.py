@@ -0,0 +1,190 @@
+"""Tests for synthia.remote.common, shared by every remote-mode bot."""
+
+from __future__ import annotations
+
+import io
+import json
+import os
+
+from synthia.remote import common
+from synthia.remote.common import (
+    ACTIVITY_MARKER,
+    APPROVAL_MARKER,
+    MAX_MESSAGE_LENGTH,
+    STATUS_MARKER,
+    ApprovalGate,
+    ConnectionReporter,
+    forward_to_claude_code,
+    parse_access,
+    parse_approval_timeout,
+    report_activity,
+    sanitize_terminal_input,
+)
+
+
+class TestSanitizeTerminalInput:
+    """Tests for sanitize_terminal_input."""
+
+    def test_strips_control_characters(self):
+        """Escape, bell and null characters are removed, newlines and tabs kept."""
+        text = "ls\x1b -la\x07\x00\nnext\tline\x7f"
+
+        assert sanitize_terminal_input(text) == "ls -la\nnext\tline"
+
+    def test_truncates_long_messages(self):
+        """Messages are cut to MAX_MESSAGE_LENGTH."""
+        assert len(sanitize_terminal_input("a" * (MAX_MESSAGE_LENGTH + 50))) == MAX_MESSAGE_LENGTH
+
+    def test_empty_input(self):
+        """Empty input stays empty."""
+        assert sanitize_terminal_input("") == ""
+
+
+class TestParseAccess:
+    """Tests for parse_access."""
+
+    def test_reads_allowed_users(self):
+        """--allowed-users is split on commas, ignoring blanks."""
+        argv = ["bot.py", "--allowed-users", "123, @me:example.org,,"]
+
+        assert parse_access(argv) == (["123", "@me:example.org"], False)
+
+    def test_allow_all(self):
+        """--allow-all is reported without a list."""
+        assert parse_access(["bot.py", "--allow-all"]) == (None, True)
+
+    def test_no_flags_falls_back_to_config(self):
+        """Without flags the list is None, so the bot reads config.yaml."""
+        assert parse_access(["bot.py"]) == (None, False)
+
+    def test_missing_value(self):
+        """A trailing --allowed-users gives an empty list rather than an error."""
+        assert parse_access(["bot.py", "--allowed-users"]) == ([], False)
+
+
+class TestParseApprovalTimeout:
+    """Tests for parse_approval_timeout."""
+
+    def test_reads_seconds(self):
+        """--approval-timeout gives the number of seconds to wait."""
+        assert parse_approval_timeout(["bot.py", "--approval-timeout", "300"]) == 300.0
+
+    def test_absent_or_unusable_means_ungated(self):
+        """Without a usable positive value nothing waits for approval."""
+        assert parse_approval_timeout(["bot.py"]) is None
+        assert parse_approval_timeout(["bot.py", "--approval-timeout"]) is None
+        assert parse_approval_timeout(["bot.py", "--approval-timeout", "soon"]) is None
+        assert parse_approval_timeout(["bot.py", "--approval-timeout", "0"]) is None
+
+
+class TestApprovalGate:
+    """Tests for ApprovalGate and forward_to_claude_code."""
+
+    @staticmethod
+    def answering(approved: bool, monkeypatch) -> ApprovalGate:
+        """A gate whose GUI answers every request it sees printed."""
+        read_end, write_end = os.pipe()
+        gate = ApprovalGate(5, os.fdopen(read_end))
+
+        def gui(line: str, flush: bool = False) -> None:
+            marker, request = line.split(" ", 1)
+            if marker != APPROVAL_MARKER:
+                return
+            answer = {"id": json.loads(request)["id"], "approved": approved}
+            os.write(write_end, (json.dumps(answer) + "\n").encode())
+
+        monkeypatch.setattr("builtins.print", gui)
+        return gate
+
+    def test_ungated_passes_everything(self):
+        """Without a timeout requests are approved without asking."""
+        assert ApprovalGate(None).request("rm -rf ~") is True
+
+    def test_answers_are_matched_to_requests(self, monkeypatch):
+        """The GUI's answer for a request's ID settles it."""
+        assert self.answering(True, monkeypatch).request("git status") is True
+        assert self.answering(False, monkeypatch).request("rm -rf ~") is False
+
+    def test_times_out_without_answer(self, capsys):
+        """No answer within the timeout gives None, and the request was printed."""
+        gate = ApprovalGate(0.1, io.StringIO(""))
+
+        assert gate.request("rm -rf ~") is None
+        line = capsys.readouterr().out.strip()
+        assert line.startswith(APPROVAL_MARKER)
+        assert json.loads(line.split(" ", 1)[1])["text"] == "rm -rf ~"
+
+    def test_denied_text_is_not_sent(self, monkeypatch):
+        """A denied message never reaches Claude Code."""
+        sent = []
+        monkeypatch.setattr(common, "send_to_claude_code", sent.append)
+        gate = self.answering(False, monkeypatch)
+
+        assert "Denied" in forward_to_claude_code("rm -rf ~", gate)
+        assert sent == []
+
+    def test_approved_text_is_sent(self, monkeypatch):
+        """An approved message is sent, with nothing more to tell the user."""
+        sent = []
+        monkeypatch.setattr(common, "send_to_claude_code", lambda text: sent.append(text) or True)
+        gate = self.answering(True, monkeypatch)
+
+        assert forward_to_claude_code("git status\x07", gate) is None
+        assert sent == ["git status"]
+
+
+class TestReportActivity:
+    """Tests for report_activity and the outcomes forwarding reports."""
+
+    @staticmethod
+    def reported(capsys) -> list[dict]:
+        lines = capsys.readouterr().out.splitlines()
+        reports = [line for line in lines if line.startswith(ACTIVITY_MARKER)]
+        return [json.loads(line.split(" ", 1)[1]) for line in reports]
+
+    def test_prints_one_json_line(self, capsys):
+        """Each exchange is one marker line the GUI can parse."""
+        report_activity("in", "123", "command", "/dev")
+
+        assert self.reported(capsys) == [
+            {"direction": "in", "sender": "123", "kind": "command", "text": "/dev"}
+        ]
+
+    def test_forwarding_reports_its_outcome(self, monkeypatch, capsys):
+        """What became of a forwarded message is logged as sent by Synthia."""
+        monkeypatch.setattr(common, "send_to_claude_code", lambda text: False)
+
+        forward_to_claude_code("git status", ApprovalGate(None))
+
+        [activity] = self.reported(capsys)
+        assert (activity["direction"], activity["kind"]) == ("out", "failed")
+
+
+class TestConnectionReporter:
+    """Tests for ConnectionReporter."""
+
+    def test_prints_marker_only_on_change(self, capsys):
+        """Each change prints one marker line; repeats print nothing."""
+        reporter = ConnectionReporter("Test")
+
+        reporter.report(True)
+        reporter.report(True)
+        reporter.report(False)
+
+        lines = capsys.readouterr().out.splitlines()
+        assert lines == [f"{STATUS_MARKER} connected", f"{STATUS_MARKER} disconnected"]
+
+
+class TestRemoteMode:
+    """Tests for the remote-mode flag file."""
+
+    def test_set_and_clear(self, monkeypatch, tmp_path):
+        """set_remote_mode creates and removes the flag file."""
+        monkeypatch.setattr(common, "REMOTE_MODE_FILE", str(tmp_path / "synthia-remote-mode"))
+
+        common.set_remote_mode(True)
+        assert common.is_remote_mode()
+
+        common.set_remote_mode(False)
+        common.set_remote_mode(False)
+        assert not common.is_remote_mode()
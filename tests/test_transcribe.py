@@ -0,0 +1,31 @@
+"""Tests for the settings helpers in synthia.transcribe."""
+
+import pytest
+
+from synthia.transcribe import vad_threshold, whisper_language
+
+
+class TestVadThreshold:
+    """Tests for vad_threshold."""
+
+    def test_default_matches_faster_whisper(self):
+        """The default sensitivity gives faster-whisper's own threshold."""
+        assert vad_threshold(0.5) == pytest.approx(0.5)
+
+    def test_higher_sensitivity_lowers_threshold(self):
+        """More sensitive means less certain speech is kept."""
+        assert vad_threshold(1.0) < vad_threshold(0.5) < vad_threshold(0.0)
+
+    def test_out_of_range_is_clamped(self):
+        """Values outside 0.0–1.0 behave like the nearest end."""
+        assert vad_threshold(-1.0) == vad_threshold(0.0)
+        assert vad_threshold(2.0) == vad_threshold(1.0)
+
+
+class TestWhisperLanguage:
+    """Tests for whisper_language."""
+
+    def test_strips_region(self):
+        """Whisper takes the language subtag only."""
+        assert whisper_language("en-US") == "en"
+        assert whisper_language("de") == "de"